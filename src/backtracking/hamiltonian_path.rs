@@ -0,0 +1,175 @@
+/*
+    A Hamiltonian path visits every vertex of a graph exactly once. Deciding
+    whether one exists is NP-complete, so the search backtracks: the path is
+    extended with each unvisited neighbor of its last vertex in turn, and the
+    last choice is undone when the path cannot be completed.
+
+    Two prunings cut dead branches early:
+    - the unvisited vertices must all be reachable from the end of the path
+      without going through visited vertices, otherwise some of them can never
+      be added;
+    - an unvisited vertex with at most one unvisited or end-of-path neighbor
+      can only be the last vertex of the path, and there can be only one such
+      vertex.
+
+    Time Complexity: O(n!) in the worst case, so the graph should be small.
+
+    The graph is undirected and given as adjacency lists.
+*/
+
+pub fn hamiltonian_path(adj: &[Vec<usize>]) -> Option<Vec<usize>> {
+    let n = adj.len();
+    if n == 0 {
+        return None;
+    }
+    let mut visited = vec![false; n];
+    let mut path = Vec::with_capacity(n);
+    for start in 0..n {
+        visited[start] = true;
+        path.push(start);
+        if extend(adj, &mut visited, &mut path) {
+            return Some(path);
+        }
+        path.pop();
+        visited[start] = false;
+    }
+    None
+}
+
+fn extend(adj: &[Vec<usize>], visited: &mut [bool], path: &mut Vec<usize>) -> bool {
+    if path.len() == adj.len() {
+        return true;
+    }
+    let last = *path.last().unwrap();
+    if !can_complete(adj, visited, last) {
+        return false;
+    }
+    for &next in &adj[last] {
+        if !visited[next] {
+            visited[next] = true;
+            path.push(next);
+            if extend(adj, visited, path) {
+                return true;
+            }
+            // Backtrack
+            path.pop();
+            visited[next] = false;
+        }
+    }
+    false
+}
+
+/// Checks the necessary conditions for the path ending at `last` to be
+/// completed with the unvisited vertices
+fn can_complete(adj: &[Vec<usize>], visited: &[bool], last: usize) -> bool {
+    // every unvisited vertex is reachable from `last` through unvisited ones
+    let mut reached = vec![false; adj.len()];
+    reached[last] = true;
+    let mut stack = vec![last];
+    let mut reached_count = 0;
+    while let Some(v) = stack.pop() {
+        for &u in &adj[v] {
+            if !visited[u] && !reached[u] {
+                reached[u] = true;
+                reached_count += 1;
+                stack.push(u);
+            }
+        }
+    }
+    let unvisited = visited.iter().filter(|&&v| !v).count();
+    if reached_count < unvisited {
+        return false;
+    }
+
+    // at most one unvisited vertex can be a dead end
+    let dead_ends = (0..adj.len())
+        .filter(|&v| !visited[v])
+        .filter(|&v| adj[v].iter().filter(|&&u| !visited[u] || u == last).count() <= 1)
+        .count();
+    dead_ends <= 1
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::graph_utils::undirected;
+
+    fn assert_hamiltonian(adj: &[Vec<usize>], path: &[usize]) {
+        let mut sorted = path.to_vec();
+        sorted.sort_unstable();
+        assert_eq!(sorted, (0..adj.len()).collect::<Vec<_>>());
+        for w in path.windows(2) {
+            assert!(adj[w[0]].contains(&w[1]));
+        }
+    }
+
+    #[test]
+    fn known_hamiltonian_path() {
+        // a path has to start at 0 or 5, the only vertices of degree 1
+        let adj = undirected(6, &[(0, 1), (1, 2), (1, 3), (2, 3), (2, 4), (3, 4), (4, 5)]);
+        let path = hamiltonian_path(&adj).unwrap();
+        assert_hamiltonian(&adj, &path);
+        assert_eq!(path[0], 0);
+        assert_eq!(path[5], 5);
+    }
+
+    #[test]
+    fn complete_graph() {
+        let n = 7;
+        let adj: Vec<Vec<usize>> = (0..n)
+            .map(|u| (0..n).filter(|&v| v != u).collect())
+            .collect();
+        let path = hamiltonian_path(&adj).unwrap();
+        assert_hamiltonian(&adj, &path);
+    }
+
+    #[test]
+    fn petersen_graph() {
+        // it has a Hamiltonian path but no Hamiltonian cycle
+        let adj = undirected(
+            10,
+            &[
+                (0, 1),
+                (1, 2),
+                (2, 3),
+                (3, 4),
+                (4, 0),
+                (0, 5),
+                (1, 6),
+                (2, 7),
+                (3, 8),
+                (4, 9),
+                (5, 7),
+                (7, 9),
+                (9, 6),
+                (6, 8),
+                (8, 5),
+            ],
+        );
+        let path = hamiltonian_path(&adj).unwrap();
+        assert_hamiltonian(&adj, &path);
+    }
+
+    #[test]
+    fn disconnected_graph() {
+        let adj = undirected(6, &[(0, 1), (1, 2), (2, 0), (3, 4), (4, 5)]);
+        assert_eq!(hamiltonian_path(&adj), None);
+    }
+
+    #[test]
+    fn star_graph() {
+        // the three leaves cannot all be ends of the path
+        let adj = undirected(4, &[(0, 1), (0, 2), (0, 3)]);
+        assert_eq!(hamiltonian_path(&adj), None);
+    }
+
+    #[test]
+    fn trivial_graphs() {
+        assert_eq!(hamiltonian_path(&[vec![]]), Some(vec![0]));
+        assert_eq!(
+            hamiltonian_path(&undirected(2, &[(0, 1)])),
+            Some(vec![0, 1])
+        );
+        assert_eq!(hamiltonian_path(&[]), None);
+    }
+}
@@ -27,6 +27,69 @@ pub fn find_knight_tour(
     tour.find_tour(start_x, start_y)
 }
 
+/// Finds a Knight's Tour of an `n` x `n` board starting from `start`, using
+/// Warnsdorff's heuristic.
+///
+/// The knight always moves first to the square with the fewest onward moves,
+/// which finds a tour without backtracking on most boards. When the heuristic
+/// hits a dead end, the next candidates are tried in the same order.
+///
+/// # Arguments
+///
+/// * `n` - The size of the chessboard.
+/// * `start` - The starting position, as `(row, column)`.
+///
+/// # Returns
+///
+/// The squares in the order the knight visits them, or None if there is no tour.
+pub fn knights_tour(n: usize, start: (usize, usize)) -> Option<Vec<(usize, usize)>> {
+    if start.0 >= n || start.1 >= n {
+        return None;
+    }
+    let mut visited = vec![vec![false; n]; n];
+    let mut path = Vec::with_capacity(n * n);
+    visited[start.0][start.1] = true;
+    path.push(start);
+    if warnsdorff(n, &mut visited, &mut path) {
+        Some(path)
+    } else {
+        None
+    }
+}
+
+/// Returns the unvisited squares a knight can reach from `(x, y)`.
+fn knight_moves(n: usize, visited: &[Vec<bool>], (x, y): (usize, usize)) -> Vec<(usize, usize)> {
+    KnightTour::MOVES
+        .iter()
+        .filter_map(|&(dx, dy)| {
+            let next_x = x.checked_add_signed(dx)?;
+            let next_y = y.checked_add_signed(dy)?;
+            (next_x < n && next_y < n && !visited[next_x][next_y]).then_some((next_x, next_y))
+        })
+        .collect()
+}
+
+/// Extends the tour in `path`, trying the moves by increasing onward degree.
+fn warnsdorff(n: usize, visited: &mut [Vec<bool>], path: &mut Vec<(usize, usize)>) -> bool {
+    if path.len() == n * n {
+        return true;
+    }
+    let mut candidates = knight_moves(n, visited, *path.last().unwrap());
+    // stable sort, so ties are broken by the order of the moves
+    candidates.sort_by_key(|&square| knight_moves(n, visited, square).len());
+    for (x, y) in candidates {
+        visited[x][y] = true;
+        path.push((x, y));
+        if warnsdorff(n, visited, path) {
+            return true;
+        }
+        // Backtrack
+        path.pop();
+        visited[x][y] = false;
+    }
+    false
+}
+
 /// Represents the KnightTour struct which implements the Knight's Tour problem.
 struct KnightTour {
     board: Vec<Vec<usize>>,
@@ -149,6 +212,22 @@ impl KnightTour {
 mod tests {
     use super::*;
 
+    fn assert_valid_tour(n: usize, start: (usize, usize), tour: &[(usize, usize)]) {
+        assert_eq!(tour.len(), n * n);
+        assert_eq!(tour[0], start);
+        let mut seen = vec![vec![false; n]; n];
+        for &(x, y) in tour {
+            assert!(x < n && y < n);
+            assert!(!seen[x][y], "({x}, {y}) is visited twice");
+            seen[x][y] = true;
+        }
+        for step in tour.windows(2) {
+            let dx = step[0].0.abs_diff(step[1].0);
+            let dy = step[0].1.abs_diff(step[1].1);
+            assert!((dx, dy) == (1, 2) || (dx, dy) == (2, 1));
+        }
+    }
+
     macro_rules! test_find_knight_tour {
         ($($name:ident: $tc:expr,)*) => {
         $(
@@ -163,6 +242,40 @@ mod tests {
         )*
         }
     }
+
+    #[test]
+    fn test_knights_tour_5x5_corner() {
+        let tour = knights_tour(5, (0, 0)).unwrap();
+        assert_valid_tour(5, (0, 0), &tour);
+    }
+
+    #[test]
+    fn test_knights_tour_larger_boards() {
+        for (n, start) in [
+            (6, (2, 3)),
+            (8, (0, 0)),
+            (8, (3, 4)),
+            (12, (5, 0)),
+            (30, (0, 0)),
+        ] {
+            let tour = knights_tour(n, start).unwrap();
+            assert_valid_tour(n, start, &tour);
+        }
+    }
+
+    #[test]
+    fn test_knights_tour_no_solution() {
+        assert_eq!(knights_tour(3, (0, 0)), None);
+        assert_eq!(knights_tour(4, (0, 0)), None);
+    }
+
+    #[test]
+    fn test_knights_tour_edge_cases() {
+        assert_eq!(knights_tour(1, (0, 0)), Some(vec![(0, 0)]));
+        assert_eq!(knights_tour(8, (8, 0)), None);
+        assert_eq!(knights_tour(0, (0, 0)), None);
+    }
+
     test_find_knight_tour! {
         test_knight_tour_5x5: (5, 5, 0, 0, Some(vec![
             vec![1, 6, 15, 10, 21],
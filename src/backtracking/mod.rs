@@ -1,4 +1,5 @@
 mod all_combination_of_size_k;
+mod hamiltonian_path;
 mod knight_tour;
 mod n_queens;
 mod parentheses_generator;
@@ -6,7 +7,8 @@ mod permutations;
 mod sudoku;
 
 pub use all_combination_of_size_k::generate_all_combinations;
-pub use knight_tour::find_knight_tour;
+pub use hamiltonian_path::hamiltonian_path;
+pub use knight_tour::{find_knight_tour, knights_tour};
 pub use n_queens::n_queens_solver;
 pub use parentheses_generator::generate_parentheses;
 pub use permutations::permute;
@@ -0,0 +1,123 @@
+//! Adler-32 checksum, as specified in RFC 1950 (zlib).
+//!
+//! Two 16 bits sums are maintained modulo 65521 (the largest prime below
+//! 2^16): `a` is one plus the sum of all the bytes, and `b` is the sum of the
+//! successive values of `a`. The checksum is `b << 16 | a`.
+//! It is faster to compute than CRC-32 but detects fewer errors, especially on
+//! short messages, and provides no protection against deliberate tampering.
+
+use super::Hasher;
+
+const MOD_ADLER: u32 = 65521;
+
+/// Largest number of bytes which can be summed before `b` may overflow a `u32`
+const NMAX: usize = 5552;
+
+/// Streaming Adler-32 computation: feed the data in any number of chunks with
+/// `update`, then read the checksum with `finalize`.
+#[derive(Clone, Copy)]
+pub struct Adler32 {
+    a: u32,
+    b: u32,
+}
+
+impl Default for Adler32 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Adler32 {
+    pub fn new() -> Self {
+        Adler32 { a: 1, b: 0 }
+    }
+
+    /// Adds `data` to the checksummed stream
+    pub fn update(&mut self, data: &[u8]) {
+        // Reducing modulo 65521 only once every `NMAX` bytes is enough to
+        // keep both sums within a `u32`
+        for chunk in data.chunks(NMAX) {
+            for &byte in chunk {
+                self.a += byte as u32;
+                self.b += self.a;
+            }
+            self.a %= MOD_ADLER;
+            self.b %= MOD_ADLER;
+        }
+    }
+
+    /// Returns the checksum of all the data added so far. The instance is
+    /// left untouched, so more data can still be added afterwards.
+    pub fn finalize(&self) -> u32 {
+        (self.b << 16) | self.a
+    }
+}
+
+impl Hasher<4> for Adler32 {
+    fn new_default() -> Self {
+        Self::new()
+    }
+
+    fn update(&mut self, data: &[u8]) {
+        Adler32::update(self, data);
+    }
+
+    fn get_hash(&mut self) -> [u8; 4] {
+        self.finalize().to_be_bytes()
+    }
+}
+
+/// Computes the Adler-32 checksum of `data`
+pub fn adler32(data: &[u8]) -> u32 {
+    let mut adler = Adler32::new();
+    adler.update(data);
+    adler.finalize()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    macro_rules! test_adler32 {
+        ($($name:ident: $test_case:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let (data, expected) = $test_case;
+                    assert_eq!(adler32(data), expected);
+                }
+            )*
+        };
+    }
+
+    test_adler32! {
+        empty: (b"", 0x00000001),
+        single_byte: (b"a", 0x00620062),
+        abc: (b"abc", 0x024D0127),
+        wikipedia: (b"Wikipedia", 0x11E60398),
+        check_value: (b"123456789", 0x091E01DE),
+        quick_brown_fox: (b"The quick brown fox jumps over the lazy dog", 0x5BDC0FDA),
+        many_max_bytes: (&[0xffu8; 10_000], 0xB623EB2B),
+    }
+
+    #[test]
+    fn chunked_updates_match_one_shot() {
+        let data: Vec<u8> = (0..20_000u32).map(|i| (i * 31 % 251) as u8).collect();
+        let expected = adler32(&data);
+        for chunk_size in [1, 2, 7, 64, 1000, NMAX, NMAX + 1, data.len()] {
+            let mut adler = Adler32::new();
+            for chunk in data.chunks(chunk_size) {
+                adler.update(chunk);
+            }
+            assert_eq!(adler.finalize(), expected);
+        }
+    }
+
+    #[test]
+    fn hasher_trait() {
+        let mut adler = <Adler32 as Hasher<4>>::new_default();
+        Hasher::update(&mut adler, b"Wiki");
+        Hasher::update(&mut adler, b"pedia");
+        assert_eq!(adler.get_hash(), [0x11, 0xE6, 0x03, 0x98]);
+    }
+}
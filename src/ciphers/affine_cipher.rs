@@ -0,0 +1,120 @@
+//! Affine Cipher
+//!
+//! The affine cipher is a monoalphabetic substitution cipher generalizing the
+//! Caesar cipher: each letter `x` (mapped to `0..26`) is encrypted to
+//! `E(x) = (a * x + b) mod 26` and decrypted with `D(y) = a⁻¹ * (y - b) mod 26`,
+//! where `a⁻¹` is the modular multiplicative inverse of `a` modulo 26.
+//! The key `a` must therefore be coprime with 26, otherwise several letters
+//! would be mapped to the same one and the message could not be decrypted.
+//!
+//! The Caesar cipher is the special case `a = 1`.
+//! Non-alphabetic characters are left untouched and the case is preserved.
+//!
+//! More information: <https://en.wikipedia.org/wiki/Affine_cipher>
+
+const ALPHABET_LENGTH: u8 = b'z' - b'a' + 1;
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum CipherError {
+    /// The multiplicative key `a` is not coprime with the alphabet length
+    NonInvertibleKey,
+}
+
+/// Returns the inverse of `a` modulo the alphabet length, if it exists.
+fn mod_inverse(a: u8) -> Option<u8> {
+    (1..ALPHABET_LENGTH).find(|&x| (a as u16 * x as u16) % ALPHABET_LENGTH as u16 == 1)
+}
+
+/// Applies `x -> (a * x + b) mod 26` to every ASCII letter of `text`.
+fn transform(text: &str, a: u8, b: u8) -> String {
+    text.chars()
+        .map(|c| {
+            if c.is_ascii_alphabetic() {
+                let first = if c.is_ascii_lowercase() { b'a' } else { b'A' };
+                let x = (c as u8 - first) as u16;
+                let y = (a as u16 * x + b as u16) % ALPHABET_LENGTH as u16;
+                (first + y as u8) as char
+            } else {
+                c
+            }
+        })
+        .collect()
+}
+
+/// Encrypts `text` with the affine cipher `E(x) = (a * x + b) mod 26`.
+///
+/// # Errors
+///
+/// Returns `CipherError::NonInvertibleKey` if `gcd(a, 26) != 1`.
+pub fn affine_encrypt(text: &str, a: u8, b: u8) -> Result<String, CipherError> {
+    let a = a % ALPHABET_LENGTH;
+    mod_inverse(a).ok_or(CipherError::NonInvertibleKey)?;
+    Ok(transform(text, a, b % ALPHABET_LENGTH))
+}
+
+/// Decrypts a `text` encrypted by `affine_encrypt` with the same keys `a` and `b`.
+///
+/// # Errors
+///
+/// Returns `CipherError::NonInvertibleKey` if `gcd(a, 26) != 1`.
+pub fn affine_decrypt(text: &str, a: u8, b: u8) -> Result<String, CipherError> {
+    let a_inv = mod_inverse(a % ALPHABET_LENGTH).ok_or(CipherError::NonInvertibleKey)?;
+    // D(y) = a⁻¹ * (y - b) = a⁻¹ * y + a⁻¹ * (26 - b)
+    let shift =
+        (a_inv as u16 * (ALPHABET_LENGTH - b % ALPHABET_LENGTH) as u16) % ALPHABET_LENGTH as u16;
+    Ok(transform(text, a_inv, shift as u8))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    macro_rules! test_affine_cipher {
+        ($($name:ident: $test_case:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let (text, a, b, expected) = $test_case;
+                    assert_eq!(affine_encrypt(text, a, b).unwrap(), expected);
+                    assert_eq!(affine_decrypt(expected, a, b).unwrap(), text);
+                }
+            )*
+        };
+    }
+
+    test_affine_cipher! {
+        empty_text: ("", 5, 8, ""),
+        wikipedia_example: ("AFFINECIPHER", 5, 8, "IHHWVCSWFRCP"),
+        lowercase: ("affine cipher", 5, 8, "ihhwvc swfrcp"),
+        identity_key: ("Hello, World!", 1, 0, "Hello, World!"),
+        caesar_special_case: ("Hello, World!", 1, 3, "Khoor, Zruog!"),
+        reduced_keys: ("AFFINECIPHER", 31, 34, "IHHWVCSWFRCP"),
+        non_alphabetic_characters: ("12345 !@#$% 攻", 7, 3, "12345 !@#$% 攻"),
+        mixed_content: ("Attack at dawn, 6 AM.", 17, 20, "Uffuci uf tueh, 6 UQ."),
+    }
+
+    #[test]
+    fn round_trip_with_every_valid_key() {
+        let text = "The quick brown fox jumps over the lazy dog.";
+        for a in [1, 3, 5, 7, 9, 11, 15, 17, 19, 21, 23, 25] {
+            for b in 0..ALPHABET_LENGTH {
+                let encrypted = affine_encrypt(text, a, b).unwrap();
+                assert_eq!(affine_decrypt(&encrypted, a, b).unwrap(), text);
+            }
+        }
+    }
+
+    #[test]
+    fn non_invertible_keys_are_rejected() {
+        for a in [0, 2, 4, 13, 26, 52] {
+            assert_eq!(
+                affine_encrypt("rust", a, 1),
+                Err(CipherError::NonInvertibleKey)
+            );
+            assert_eq!(
+                affine_decrypt("rust", a, 1),
+                Err(CipherError::NonInvertibleKey)
+            );
+        }
+    }
+}
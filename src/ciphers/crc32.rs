@@ -0,0 +1,147 @@
+//! CRC-32 checksum (IEEE 802.3), as used by Ethernet, zip, gzip and png.
+//!
+//! The checksum is computed with the reflected polynomial `0xEDB88320` using
+//! a lookup table of 256 entries, so that a whole byte is processed at once.
+//! A cyclic redundancy check detects accidental corruption, but offers no
+//! protection at all against deliberate tampering.
+
+use super::Hasher;
+
+/// Reversed representation of the polynomial `0x04C11DB7`
+const POLYNOMIAL: u32 = 0xEDB88320;
+
+/// `TABLE[b]` is the CRC of the single byte `b`, generated at compile time
+const TABLE: [u32; 256] = {
+    let mut table = [0u32; 256];
+    let mut i = 0;
+    while i < 256 {
+        let mut crc = i as u32;
+        let mut bit = 0;
+        while bit < 8 {
+            crc = if crc & 1 == 1 {
+                (crc >> 1) ^ POLYNOMIAL
+            } else {
+                crc >> 1
+            };
+            bit += 1;
+        }
+        table[i] = crc;
+        i += 1;
+    }
+    table
+};
+
+/// Streaming CRC-32 computation: feed the data in any number of chunks with
+/// `update`, then read the checksum with `finalize`.
+#[derive(Clone, Copy)]
+pub struct Crc32 {
+    state: u32,
+}
+
+impl Default for Crc32 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Crc32 {
+    pub fn new() -> Self {
+        Crc32 { state: !0 }
+    }
+
+    /// Adds `data` to the checksummed stream
+    pub fn update(&mut self, data: &[u8]) {
+        self.state = data.iter().fold(self.state, |crc, &byte| {
+            TABLE[((crc ^ byte as u32) & 0xff) as usize] ^ (crc >> 8)
+        });
+    }
+
+    /// Returns the checksum of all the data added so far. The instance is
+    /// left untouched, so more data can still be added afterwards.
+    pub fn finalize(&self) -> u32 {
+        !self.state
+    }
+}
+
+impl Hasher<4> for Crc32 {
+    fn new_default() -> Self {
+        Self::new()
+    }
+
+    fn update(&mut self, data: &[u8]) {
+        Crc32::update(self, data);
+    }
+
+    fn get_hash(&mut self) -> [u8; 4] {
+        self.finalize().to_be_bytes()
+    }
+}
+
+/// Computes the CRC-32 checksum of `data`
+pub fn crc32(data: &[u8]) -> u32 {
+    let mut crc = Crc32::new();
+    crc.update(data);
+    crc.finalize()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    macro_rules! test_crc32 {
+        ($($name:ident: $test_case:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let (data, expected) = $test_case;
+                    assert_eq!(crc32(data), expected);
+                }
+            )*
+        };
+    }
+
+    test_crc32! {
+        empty: (b"", 0x00000000),
+        check_value: (b"123456789", 0xCBF43926),
+        single_byte: (b"a", 0xE8B7BE43),
+        quick_brown_fox: (b"The quick brown fox jumps over the lazy dog", 0x414FA339),
+        zeros: (&[0u8; 32], 0x190A55AD),
+    }
+
+    #[test]
+    fn table_entries() {
+        assert_eq!(TABLE[0], 0x00000000);
+        assert_eq!(TABLE[1], 0x77073096);
+        assert_eq!(TABLE[255], 0x2D02EF8D);
+    }
+
+    #[test]
+    fn streaming_matches_one_shot() {
+        let data = b"The quick brown fox jumps over the lazy dog";
+        let expected = crc32(data);
+        for chunk_size in 1..=data.len() {
+            let mut crc = Crc32::new();
+            for chunk in data.chunks(chunk_size) {
+                crc.update(chunk);
+            }
+            assert_eq!(crc.finalize(), expected);
+        }
+    }
+
+    #[test]
+    fn finalize_does_not_consume_state() {
+        let mut crc = Crc32::new();
+        crc.update(b"12345");
+        assert_eq!(crc.finalize(), crc32(b"12345"));
+        crc.update(b"6789");
+        assert_eq!(crc.finalize(), 0xCBF43926);
+    }
+
+    #[test]
+    fn hasher_trait() {
+        let mut crc = <Crc32 as Hasher<4>>::new_default();
+        Hasher::update(&mut crc, b"1234");
+        Hasher::update(&mut crc, b"56789");
+        assert_eq!(crc.get_hash(), [0xCB, 0xF4, 0x39, 0x26]);
+    }
+}
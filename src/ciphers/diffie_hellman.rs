@@ -289,6 +289,43 @@ impl DiffieHellman {
     }
 }
 
+/// Computes `base^exp mod modulus` by square-and-multiply, using `u128`
+/// intermediates so that the products never overflow.
+fn mod_pow(base: u64, mut exp: u64, modulus: u64) -> u64 {
+    if modulus == 1 {
+        return 0;
+    }
+    let modulus = modulus as u128;
+    let mut base = base as u128 % modulus;
+    let mut result: u128 = 1;
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = result * base % modulus;
+        }
+        base = base * base % modulus;
+        exp >>= 1;
+    }
+    result as u64
+}
+
+// The two helpers below implement the same exchange as `DiffieHellman` with
+// machine-sized integers, which makes the algorithm easy to follow by hand.
+// They are meant for education only: with a modulus that fits in a `u64`,
+// the discrete logarithm (and thus the private key) can be recovered in a
+// matter of seconds, so they must never be used to protect real data.
+
+/// Computes the public key `g^private mod p` associated to the `private` key,
+/// for the prime modulus `p` and the generator `g` shared by both parties.
+pub fn generate_keypair(p: u64, g: u64, private: u64) -> u64 {
+    mod_pow(g, private, p)
+}
+
+/// Computes the shared secret `their_public^my_private mod p`, which is the
+/// same on both sides of the exchange.
+pub fn shared_secret(their_public: u64, my_private: u64, p: u64) -> u64 {
+    mod_pow(their_public, my_private, p)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -341,4 +378,45 @@ mod tests {
         let bob_shared = bob.generate_shared_key(alice_public.as_str()).unwrap();
         assert_eq!(alice_shared, bob_shared);
     }
+
+    #[test]
+    fn mod_pow_small_values() {
+        assert_eq!(mod_pow(2, 10, 1_000), 24);
+        assert_eq!(mod_pow(3, 0, 7), 1);
+        assert_eq!(mod_pow(0, 5, 7), 0);
+        assert_eq!(mod_pow(5, 3, 1), 0);
+        // would overflow without 128-bit intermediates
+        assert_eq!(mod_pow(u64::MAX - 1, 2, u64::MAX), 1);
+    }
+
+    #[test]
+    fn two_parties_compute_same_secret() {
+        // 23 = 2 * 11 + 1 is a safe prime and 5 generates its multiplicative group
+        let (p, g) = (23, 5);
+        let (alice_private, bob_private) = (6, 15);
+
+        let alice_public = generate_keypair(p, g, alice_private);
+        let bob_public = generate_keypair(p, g, bob_private);
+        assert_eq!(alice_public, 8);
+        assert_eq!(bob_public, 19);
+
+        let alice_secret = shared_secret(bob_public, alice_private, p);
+        let bob_secret = shared_secret(alice_public, bob_private, p);
+        assert_eq!(alice_secret, bob_secret);
+        assert_eq!(alice_secret, 2);
+    }
+
+    #[test]
+    fn two_parties_with_larger_safe_prime() {
+        // 2_147_483_783 = 2 * 1_073_741_891 + 1, both being prime
+        let (p, g) = (2_147_483_783, 2);
+        for (alice_private, bob_private) in [(123_456, 654_321), (1, 2), (2_147_483_000, 42)] {
+            let alice_public = generate_keypair(p, g, alice_private);
+            let bob_public = generate_keypair(p, g, bob_private);
+            assert_eq!(
+                shared_secret(bob_public, alice_private, p),
+                shared_secret(alice_public, bob_private, p)
+            );
+        }
+    }
 }
@@ -1,13 +1,17 @@
+mod adler32;
 mod aes;
+mod affine_cipher;
 mod another_rot13;
 mod baconian_cipher;
 mod base64;
 mod blake2b;
 mod caesar;
 mod chacha;
+mod crc32;
 mod diffie_hellman;
 mod hashing_traits;
 mod kerninghan;
+mod md5;
 mod morse_code;
 mod polybius;
 mod rail_fence;
@@ -20,17 +24,21 @@ mod theoretical_rot13;
 mod transposition;
 mod vigenere;
 mod xor;
+pub use self::adler32::{adler32, Adler32};
 pub use self::aes::{aes_decrypt, aes_encrypt, AesKey};
+pub use self::affine_cipher::{affine_decrypt, affine_encrypt, CipherError};
 pub use self::another_rot13::another_rot13;
 pub use self::baconian_cipher::{baconian_decode, baconian_encode};
 pub use self::base64::{base64_decode, base64_encode};
 pub use self::blake2b::blake2b;
 pub use self::caesar::caesar;
 pub use self::chacha::chacha20;
-pub use self::diffie_hellman::DiffieHellman;
+pub use self::crc32::{crc32, Crc32};
+pub use self::diffie_hellman::{generate_keypair, shared_secret, DiffieHellman};
 pub use self::hashing_traits::Hasher;
 pub use self::hashing_traits::HMAC;
 pub use self::kerninghan::kerninghan;
+pub use self::md5::{md5, md5_hex};
 pub use self::morse_code::{decode, encode};
 pub use self::polybius::{decode_ascii, encode_ascii};
 pub use self::rail_fence::{rail_fence_decrypt, rail_fence_encrypt};
@@ -40,6 +48,9 @@ pub use self::sha256::SHA256;
 pub use self::sha3::{sha3_224, sha3_256, sha3_384, sha3_512};
 pub use self::tea::{tea_decrypt, tea_encrypt};
 pub use self::theoretical_rot13::theoretical_rot13;
-pub use self::transposition::transposition;
+pub use self::transposition::{
+    columnar_transposition_decrypt, columnar_transposition_encrypt, transposition,
+    TRANSPOSITION_FILLER,
+};
 pub use self::vigenere::vigenere;
-pub use self::xor::xor;
+pub use self::xor::{xor, xor_bytes, xor_cipher, xor_crack_single_byte};
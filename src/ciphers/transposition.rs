@@ -184,6 +184,73 @@ fn decrypt(mut msg: String, key_order: Vec<usize>) -> String {
     decrypted_msg
 }
 
+/// Character used to complete the last row of the grid in the padded variant.
+pub const TRANSPOSITION_FILLER: char = 'X';
+
+/// Returns the order in which the columns are read: the indices of the
+/// key's characters sorted alphabetically, duplicate letters keeping their
+/// original left-to-right order.
+fn column_order(key: &str) -> Vec<usize> {
+    let key: Vec<char> = key.to_uppercase().chars().collect();
+    let mut order: Vec<usize> = (0..key.len()).collect();
+    // `sort_by_key` is stable, so equal letters stay ordered by position
+    order.sort_by_key(|&index| key[index]);
+    order
+}
+
+/// Encrypts `text` with the classic (padded) columnar transposition cipher.
+///
+/// The message is written row by row into a grid having one column per
+/// character of `key`, the last row being completed with
+/// `TRANSPOSITION_FILLER`. The columns are then read top to bottom, in the
+/// alphabetical order of the key's characters. Unlike `transposition`, every
+/// character of `text` is kept as is. An empty key leaves `text` unchanged.
+pub fn columnar_transposition_encrypt(text: &str, key: &str) -> String {
+    let order = column_order(key);
+    let columns = order.len();
+    if columns == 0 {
+        return text.to_string();
+    }
+
+    let mut grid: Vec<char> = text.chars().collect();
+    while !grid.len().is_multiple_of(columns) {
+        grid.push(TRANSPOSITION_FILLER);
+    }
+
+    order
+        .into_iter()
+        .flat_map(|column| grid.iter().skip(column).step_by(columns))
+        .collect()
+}
+
+/// Decrypts a `text` encrypted by `columnar_transposition_encrypt` with the same `key`.
+///
+/// The trailing `TRANSPOSITION_FILLER` characters added during encryption are
+/// removed, so a message which originally ended with the filler character
+/// loses it.
+pub fn columnar_transposition_decrypt(text: &str, key: &str) -> String {
+    let order = column_order(key);
+    let columns = order.len();
+    let cipher: Vec<char> = text.chars().collect();
+    if cipher.is_empty() || columns == 0 || !cipher.len().is_multiple_of(columns) {
+        return text.to_string();
+    }
+
+    let rows = cipher.len() / columns;
+    let mut grid = vec![TRANSPOSITION_FILLER; cipher.len()];
+    // the i-th chunk of the ciphertext is the column `order[i]` of the grid
+    for (chunk, &column) in cipher.chunks(rows).zip(order.iter()) {
+        for (row, &c) in chunk.iter().enumerate() {
+            grid[row * columns + column] = c;
+        }
+    }
+
+    grid.into_iter()
+        .collect::<String>()
+        .trim_end_matches(TRANSPOSITION_FILLER)
+        .to_string()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -287,4 +354,66 @@ mod tests {
             "WEAREDISCOVEREDFLEEATONCE"
         );
     }
+
+    #[test]
+    fn padded_encryption() {
+        assert_eq!(
+            columnar_transposition_encrypt("WEAREDISCOVEREDFLEEATONCE", "ZEBRAS"),
+            "EVLNXACDTXESEAXROFOXDEECXWIREE"
+        );
+        assert_eq!(
+            columnar_transposition_encrypt("HELLOWORLD", "KEY"),
+            "EORXHLODLWLX"
+        );
+        // full grid, no padding needed
+        assert_eq!(
+            columnar_transposition_encrypt("WEAREDISCOVERED", "ZEBRA"),
+            "EODASREIERCEWDV"
+        );
+    }
+
+    #[test]
+    fn padded_encryption_with_duplicate_key_letters() {
+        // the two 'L's are read from left to right
+        assert_eq!(
+            columnar_transposition_encrypt("ATTACKATDAWN", "HELLO"),
+            "TANAKWTTXADXCAX"
+        );
+    }
+
+    #[test]
+    fn padded_decryption() {
+        assert_eq!(
+            columnar_transposition_decrypt("EVLNXACDTXESEAXROFOXDEECXWIREE", "ZEBRAS"),
+            "WEAREDISCOVEREDFLEEATONCE"
+        );
+        assert_eq!(
+            columnar_transposition_decrypt("TANAKWTTXADXCAX", "HELLO"),
+            "ATTACKATDAWN"
+        );
+    }
+
+    #[test]
+    fn padded_round_trip() {
+        let messages = [
+            "",
+            "A",
+            "WE ARE DISCOVERED. FLEE AT ONCE.",
+            "The quick brown fox jumps over the lazy dog",
+            "exactly twenty chars",
+        ];
+        for key in ["ZEBRAS", "Archive", "HELLO", "aaaa", "K"] {
+            for msg in messages {
+                let encrypted = columnar_transposition_encrypt(msg, key);
+                assert_eq!(encrypted.chars().count() % key.len(), 0);
+                assert_eq!(columnar_transposition_decrypt(&encrypted, key), msg);
+            }
+        }
+    }
+
+    #[test]
+    fn padded_empty_key() {
+        assert_eq!(columnar_transposition_encrypt("ATTACK", ""), "ATTACK");
+        assert_eq!(columnar_transposition_decrypt("ATTACK", ""), "ATTACK");
+    }
 }
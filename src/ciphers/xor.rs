@@ -6,6 +6,63 @@ pub fn xor(text: &str, key: u8) -> Vec<u8> {
     xor_bytes(text.as_bytes(), key)
 }
 
+/// XORs `data` against `key`, repeating the key as many times as needed.
+/// Since XOR is its own inverse, the same call both encrypts and decrypts.
+///
+/// # Panics
+///
+/// Panics if `key` is empty.
+pub fn xor_cipher(data: &[u8], key: &[u8]) -> Vec<u8> {
+    if key.is_empty() {
+        panic!("Key must not be empty");
+    }
+
+    data.iter()
+        .zip(key.iter().cycle())
+        .map(|(byte, key_byte)| byte ^ key_byte)
+        .collect()
+}
+
+/// Relative frequencies (in percent) of the letters `a` to `z` in English text.
+const ENGLISH_FREQUENCIES: [f64; 26] = [
+    8.2, 1.5, 2.8, 4.3, 12.7, 2.2, 2.0, 6.1, 7.0, 0.15, 0.77, 4.0, 2.4, 6.7, 7.5, 1.9, 0.095, 6.0,
+    6.3, 9.1, 2.8, 0.98, 2.4, 0.15, 2.0, 0.074,
+];
+
+/// Scores how much `text` looks like English: the more frequent the letters
+/// it contains, the higher the score. Spaces are rewarded as the most common
+/// character, while non-printable bytes are penalized.
+fn english_score(text: &[u8]) -> f64 {
+    text.iter()
+        .map(|&byte| match byte {
+            b' ' => 13.0,
+            b'a'..=b'z' => ENGLISH_FREQUENCIES[(byte - b'a') as usize],
+            b'A'..=b'Z' => ENGLISH_FREQUENCIES[(byte - b'A') as usize],
+            b'\n' | b'\r' | b'\t' => 0.0,
+            _ if byte.is_ascii_graphic() => 0.0,
+            _ => -20.0,
+        })
+        .sum()
+}
+
+/// Recovers the key of an English text XORed with a single byte, by trying
+/// all the 256 keys and keeping the one whose plaintext has the best letter
+/// frequency score. Returns the key along with the decrypted text.
+pub fn xor_crack_single_byte(ciphertext: &[u8]) -> (u8, Vec<u8>) {
+    let mut best_key = 0;
+    let mut best_score = f64::NEG_INFINITY;
+
+    for key in 0..=u8::MAX {
+        let score = english_score(&xor_bytes(ciphertext, key));
+        if score > best_score {
+            best_score = score;
+            best_key = key;
+        }
+    }
+
+    (best_key, xor_bytes(ciphertext, best_key))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -47,4 +104,58 @@ mod tests {
         let ciphered_text = xor(test_string, key);
         assert_eq!(test_string.as_bytes(), xor_bytes(&ciphered_text, key));
     }
+
+    #[test]
+    fn test_repeating_key_round_trip() {
+        let test_string = "The quick brown fox jumps over the lazy dog";
+        for key in [
+            &b"k"[..],
+            b"key",
+            b"a much longer key than the text itself, really",
+        ] {
+            let ciphered_text = xor_cipher(test_string.as_bytes(), key);
+            assert_ne!(test_string.as_bytes(), ciphered_text);
+            assert_eq!(test_string.as_bytes(), xor_cipher(&ciphered_text, key));
+        }
+    }
+
+    #[test]
+    fn test_repeating_key_known_vector() {
+        let ciphered_text = xor_cipher(b"Burning 'em", b"ICE");
+        assert_eq!(
+            ciphered_text,
+            [0x0b, 0x36, 0x37, 0x27, 0x2a, 0x2b, 0x2e, 0x63, 0x62, 0x2c, 0x2e]
+        );
+    }
+
+    #[test]
+    fn test_repeating_key_single_byte_matches_xor() {
+        let test_string = "日本語 and ascii";
+        assert_eq!(
+            xor_cipher(test_string.as_bytes(), &[42]),
+            xor(test_string, 42)
+        );
+    }
+
+    #[test]
+    fn test_repeating_key_empty_data() {
+        assert!(xor_cipher(b"", b"key").is_empty());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_repeating_key_empty_key() {
+        xor_cipher(b"data", b"");
+    }
+
+    #[test]
+    fn test_crack_single_byte() {
+        let plain_text = "Cooking MCs like a pound of bacon, and the quick brown fox jumps over it";
+        for key in [0, 1, 42, b'X', 0x7f, 0xff] {
+            let ciphered_text = xor(plain_text, key);
+            let (found_key, decrypted) = xor_crack_single_byte(&ciphered_text);
+            assert_eq!(found_key, key);
+            assert_eq!(decrypted, plain_text.as_bytes());
+        }
+    }
 }
@@ -0,0 +1,143 @@
+// https://en.wikipedia.org/wiki/Burrows%E2%80%93Wheeler_transform
+//
+// The Burrows-Wheeler transform sorts all the rotations of a string and keeps
+// the last character of each of them. Characters followed by similar contexts
+// end up next to each other, so the result has long runs of equal characters
+// which later stages (move-to-front, run-length encoding) compress well.
+//
+// Instead of sorting the n rotations with O(n) comparisons each, they are
+// sorted like a suffix array by prefix doubling: knowing the order of the
+// rotations by their first k characters, the order by their first 2k ones is
+// the order of the pairs (rank of the rotation, rank of the rotation starting
+// k characters later). This takes O(n log² n).
+
+/// Returns the start positions of the rotations of `chars`, in sorted order
+fn sorted_rotations(chars: &[char]) -> Vec<usize> {
+    let n = chars.len();
+    let mut order: Vec<usize> = (0..n).collect();
+    order.sort_by_key(|&i| chars[i]);
+    // rank[i] is the rank of the rotation starting at `i` by its first `k` chars
+    let mut rank = vec![0; n];
+    for w in 1..n {
+        let (prev, cur) = (order[w - 1], order[w]);
+        rank[cur] = rank[prev] + usize::from(chars[prev] != chars[cur]);
+    }
+
+    let mut k = 1;
+    while k < n {
+        let key = |i: usize| (rank[i], rank[(i + k) % n]);
+        order.sort_by_key(|&i| key(i));
+        let mut new_rank = vec![0; n];
+        for w in 1..n {
+            let (prev, cur) = (order[w - 1], order[w]);
+            new_rank[cur] = new_rank[prev] + usize::from(key(prev) != key(cur));
+        }
+        rank = new_rank;
+        // all the ranks are distinct, so the order is final
+        if rank[order[n - 1]] == n - 1 {
+            break;
+        }
+        k *= 2;
+    }
+    order
+}
+
+/// Returns the last characters of the sorted rotations of `s`, and the index
+/// of `s` itself among the sorted rotations (the "primary index"), which is
+/// needed to invert the transform.
+pub fn bwt(s: &str) -> (String, usize) {
+    let chars: Vec<char> = s.chars().collect();
+    let n = chars.len();
+    let order = sorted_rotations(&chars);
+    let transformed = order.iter().map(|&i| chars[(i + n - 1) % n]).collect();
+    // a periodic string has several rotations equal to itself, any of them
+    // can be the primary index
+    let index = order.iter().position(|&i| i == 0).unwrap_or(0);
+    (transformed, index)
+}
+
+/// Rebuilds the string whose transform is `transformed` with primary index
+/// `index`.
+///
+/// Sorting the last column gives the first one. The k-th occurrence of a
+/// character in the last column and the k-th one in the first column are the
+/// same character of the string, which precedes (last column) or starts (first
+/// column) the rotation, so following this mapping from the primary index
+/// reads the string.
+pub fn inverse_bwt(transformed: &str, index: usize) -> String {
+    let last: Vec<char> = transformed.chars().collect();
+    let mut next: Vec<usize> = (0..last.len()).collect();
+    // stable, so that the k-th occurrences are matched
+    next.sort_by_key(|&i| last[i]);
+
+    let mut decoded = String::with_capacity(transformed.len());
+    let mut row = index;
+    for _ in 0..last.len() {
+        row = next[row];
+        decoded.push(last[row]);
+    }
+    decoded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Sorts the explicit rotations
+    fn naive_bwt(s: &str) -> String {
+        let chars: Vec<char> = s.chars().collect();
+        let n = chars.len();
+        let mut rotations: Vec<Vec<char>> = (0..n)
+            .map(|i| chars[i..].iter().chain(&chars[..i]).copied().collect())
+            .collect();
+        rotations.sort();
+        rotations.iter().map(|rotation| rotation[n - 1]).collect()
+    }
+
+    #[test]
+    fn known_transforms() {
+        assert_eq!(bwt("banana"), ("nnbaaa".to_string(), 3));
+        assert_eq!(bwt("abracadabra"), ("rdarcaaaabb".to_string(), 2));
+        assert_eq!(bwt("a"), ("a".to_string(), 0));
+        assert_eq!(bwt(""), (String::new(), 0));
+    }
+
+    #[test]
+    fn matches_naive_rotation_sort() {
+        for s in [
+            "mississippi",
+            "SIX.MIXED.PIXIES.SIFT.SIXTY.PIXIE.DUST.BOXES",
+            "abababab",
+            "aaaaaa",
+            "zyxwvutsrqponm",
+            "the rain in spain falls mainly on the plain",
+            "façade déjà vu",
+        ] {
+            assert_eq!(bwt(s).0, naive_bwt(s), "{s}");
+        }
+    }
+
+    #[test]
+    fn round_trip() {
+        for s in [
+            "",
+            "a",
+            "banana",
+            "mississippi",
+            "aaaaaaaaaa",
+            "abcabcabcabc",
+            "abab",
+            "to be or not to be, that is the question",
+            "TOBEORNOTTOBEORTOBEORNOT",
+            "ünïcödé ünïcödé",
+        ] {
+            let (transformed, index) = bwt(s);
+            assert_eq!(inverse_bwt(&transformed, index), s, "{s}");
+        }
+    }
+
+    #[test]
+    fn groups_repeated_contexts() {
+        assert_eq!(bwt("abcabcabcabcabcabc").0, "ccccccaaaaaabbbbbb");
+    }
+}
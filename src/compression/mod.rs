@@ -1,3 +1,7 @@
+mod bwt;
+mod move_to_front;
 mod run_length_encoding;
 
+pub use self::bwt::{bwt, inverse_bwt};
+pub use self::move_to_front::{mtf_decode, mtf_encode};
 pub use self::run_length_encoding::{run_length_decode, run_length_encode};
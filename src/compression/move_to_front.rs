@@ -0,0 +1,108 @@
+// https://en.wikipedia.org/wiki/Move-to-front_transform
+//
+// Move-to-front encoding replaces each byte with its index in a table of all
+// the 256 byte values, then moves this byte to the front of the table. A byte
+// repeated shortly after its previous occurrence gets a small index, and a run
+// of equal bytes becomes a run of zeros, so that locally repetitive data (such
+// as the output of the Burrows-Wheeler transform) turns into mostly small
+// values that run-length or entropy coding compress well.
+
+fn initial_table() -> Vec<u8> {
+    (0..=u8::MAX).collect()
+}
+
+pub fn mtf_encode(data: &[u8]) -> Vec<u8> {
+    let mut table = initial_table();
+    data.iter()
+        .map(|&byte| {
+            let index = table.iter().position(|&b| b == byte).unwrap();
+            table.remove(index);
+            table.insert(0, byte);
+            index as u8
+        })
+        .collect()
+}
+
+pub fn mtf_decode(encoded: &[u8]) -> Vec<u8> {
+    let mut table = initial_table();
+    encoded
+        .iter()
+        .map(|&index| {
+            let byte = table.remove(index as usize);
+            table.insert(0, byte);
+            byte
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::compression::{bwt, inverse_bwt};
+
+    /// Size of the run-length encoding of `data`, as one (byte, count) pair
+    /// of bytes per run of at most 255 equal bytes
+    fn run_length_size(data: &[u8]) -> usize {
+        let runs: usize = data
+            .chunk_by(|a, b| a == b)
+            .map(|run| run.len().div_ceil(255))
+            .sum();
+        2 * runs
+    }
+
+    #[test]
+    fn known_encoding() {
+        assert_eq!(mtf_encode(b"bananaaa"), vec![98, 98, 110, 1, 1, 1, 0, 0]);
+        assert_eq!(mtf_encode(b""), vec![]);
+    }
+
+    #[test]
+    fn round_trip() {
+        let all_bytes: Vec<u8> = (0..=u8::MAX).rev().collect();
+        for data in [
+            &b""[..],
+            b"a",
+            b"banana",
+            b"aaaaaaaaaaaaaab",
+            b"\x00\xff\x00\xff\x10",
+            &all_bytes,
+        ] {
+            assert_eq!(mtf_decode(&mtf_encode(data)), data);
+        }
+    }
+
+    #[test]
+    fn runs_become_zeros() {
+        let encoded = mtf_encode(b"xxxxyyyyxxxx");
+        assert_eq!(&encoded[1..4], &[0, 0, 0]);
+        assert_eq!(&encoded[5..8], &[0, 0, 0]);
+        // `x` was used just before `y`
+        assert_eq!(encoded[8], 1);
+    }
+
+    #[test]
+    fn bwt_mtf_rle_pipeline() {
+        let text = "the cat sat on the mat, the cat sat on the hat, ".repeat(20);
+        let raw = text.as_bytes();
+
+        let (transformed, index) = bwt(&text);
+        let encoded = mtf_encode(transformed.as_bytes());
+        let pipeline = run_length_size(&encoded);
+
+        // every stage alone does worse than the whole pipeline
+        let alone = [
+            raw.len(),
+            run_length_size(raw),
+            transformed.len(),
+            mtf_encode(raw).len(),
+        ];
+        for size in alone {
+            assert!(pipeline < size, "{pipeline} >= {size}");
+        }
+        assert!(pipeline * 4 < raw.len());
+
+        // and the pipeline is lossless
+        let decoded = String::from_utf8(mtf_decode(&encoded)).unwrap();
+        assert_eq!(inverse_bwt(&decoded, index), text);
+    }
+}
@@ -0,0 +1,234 @@
+/*
+    A bit set stores a set of small non-negative integers as the bits of an
+    array of words: the integer i is in the set if the bit i % 64 of the word
+    i / 64 is set. Each integer of the range takes a single bit, and the set
+    operations work on 64 integers at once.
+
+    The words grow when a larger integer is set, and the trailing zero words
+    are dropped, so that two equal sets have the same words.
+
+    Set, clear and get complexity: O(1) amortized
+    Union, intersection, difference and count complexity: O(n / 64) where n is
+    the largest integer of the sets
+    Iteration complexity: O(n / 64 + k) where k is the number of integers
+*/
+
+const WORD_BITS: usize = u64::BITS as usize;
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct BitSet {
+    // the last word, if any, is not zero
+    words: Vec<u64>,
+}
+
+impl BitSet {
+    pub fn new() -> Self {
+        BitSet { words: vec![] }
+    }
+
+    /// Creates an empty set with room for the integers below `bits` without
+    /// reallocating
+    pub fn with_capacity(bits: usize) -> Self {
+        BitSet {
+            words: Vec::with_capacity(bits.div_ceil(WORD_BITS)),
+        }
+    }
+
+    /// Adds `i` to the set
+    pub fn set(&mut self, i: usize) {
+        let word = i / WORD_BITS;
+        if word >= self.words.len() {
+            self.words.resize(word + 1, 0);
+        }
+        self.words[word] |= 1 << (i % WORD_BITS);
+    }
+
+    /// Removes `i` from the set
+    pub fn clear(&mut self, i: usize) {
+        if let Some(word) = self.words.get_mut(i / WORD_BITS) {
+            *word &= !(1 << (i % WORD_BITS));
+            self.trim();
+        }
+    }
+
+    /// Returns whether `i` is in the set
+    pub fn get(&self, i: usize) -> bool {
+        self.words
+            .get(i / WORD_BITS)
+            .is_some_and(|word| word >> (i % WORD_BITS) & 1 == 1)
+    }
+
+    /// Returns the number of integers in the set
+    pub fn count_ones(&self) -> usize {
+        self.words
+            .iter()
+            .map(|word| word.count_ones() as usize)
+            .sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.is_empty()
+    }
+
+    /// Returns the integers in either set
+    pub fn union(&self, other: &BitSet) -> BitSet {
+        let (longer, shorter) = if self.words.len() >= other.words.len() {
+            (self, other)
+        } else {
+            (other, self)
+        };
+        let mut words = longer.words.clone();
+        for (word, &other) in words.iter_mut().zip(&shorter.words) {
+            *word |= other;
+        }
+        BitSet { words }
+    }
+
+    /// Returns the integers in both sets
+    pub fn intersect(&self, other: &BitSet) -> BitSet {
+        let words = self
+            .words
+            .iter()
+            .zip(&other.words)
+            .map(|(a, b)| a & b)
+            .collect();
+        let mut set = BitSet { words };
+        set.trim();
+        set
+    }
+
+    /// Returns the integers in `self` but not in `other`
+    pub fn difference(&self, other: &BitSet) -> BitSet {
+        let mut words = self.words.clone();
+        for (word, &other) in words.iter_mut().zip(&other.words) {
+            *word &= !other;
+        }
+        let mut set = BitSet { words };
+        set.trim();
+        set
+    }
+
+    /// Iterates over the integers of the set in increasing order
+    pub fn iter(&self) -> impl Iterator<Item = usize> + '_ {
+        self.words.iter().enumerate().flat_map(|(index, &word)| {
+            let mut rest = word;
+            std::iter::from_fn(move || {
+                if rest == 0 {
+                    return None;
+                }
+                let bit = rest.trailing_zeros() as usize;
+                // clear the lowest set bit
+                rest &= rest - 1;
+                Some(index * WORD_BITS + bit)
+            })
+        })
+    }
+
+    fn trim(&mut self) {
+        while self.words.last() == Some(&0) {
+            self.words.pop();
+        }
+    }
+}
+
+impl FromIterator<usize> for BitSet {
+    fn from_iter<I: IntoIterator<Item = usize>>(iter: I) -> Self {
+        let mut set = BitSet::new();
+        for i in iter {
+            set.set(i);
+        }
+        set
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::general::Xorshift64;
+    use std::collections::HashSet;
+
+    /// The integers of `reference`, sorted
+    fn sorted(reference: &HashSet<usize>) -> Vec<usize> {
+        let mut integers: Vec<usize> = reference.iter().copied().collect();
+        integers.sort_unstable();
+        integers
+    }
+
+    #[test]
+    fn set_clear_get() {
+        let mut set = BitSet::new();
+        assert!(set.is_empty());
+        assert!(!set.get(0));
+        for i in [0, 5, 63, 64, 200] {
+            set.set(i);
+        }
+        // setting twice changes nothing
+        set.set(64);
+        assert_eq!(set.count_ones(), 5);
+        assert!(set.get(63) && set.get(64) && set.get(200));
+        assert!(!set.get(1) && !set.get(65) && !set.get(10_000));
+
+        set.clear(200);
+        set.clear(1);
+        set.clear(10_000);
+        assert!(!set.get(200));
+        assert_eq!(set.iter().collect::<Vec<_>>(), vec![0, 5, 63, 64]);
+        // the same set, built another way, is equal
+        assert_eq!(set, [64, 63, 0, 5].into_iter().collect());
+    }
+
+    #[test]
+    fn set_operations_match_hash_sets() {
+        let mut rng = Xorshift64::new(291);
+        for _ in 0..100 {
+            // sets of different ranges
+            let a: HashSet<usize> = (0..rng.gen_range(0..50))
+                .map(|_| rng.gen_range(0..300) as usize)
+                .collect();
+            let b: HashSet<usize> = (0..rng.gen_range(0..50))
+                .map(|_| rng.gen_range(0..100) as usize)
+                .collect();
+            let (x, y): (BitSet, BitSet) =
+                (a.iter().copied().collect(), b.iter().copied().collect());
+
+            let union: HashSet<usize> = a.union(&b).copied().collect();
+            let intersection: HashSet<usize> = a.intersection(&b).copied().collect();
+            let difference: HashSet<usize> = a.difference(&b).copied().collect();
+            assert_eq!(x.union(&y).iter().collect::<Vec<_>>(), sorted(&union));
+            assert_eq!(
+                x.intersect(&y).iter().collect::<Vec<_>>(),
+                sorted(&intersection)
+            );
+            assert_eq!(
+                x.difference(&y).iter().collect::<Vec<_>>(),
+                sorted(&difference)
+            );
+            assert_eq!(y.difference(&x), b.difference(&a).copied().collect());
+            assert_eq!(x.union(&y), y.union(&x));
+            assert_eq!(x.count_ones(), a.len());
+            assert_eq!(x.intersect(&y).count_ones(), intersection.len());
+        }
+    }
+
+    #[test]
+    fn iterates_in_increasing_order() {
+        let set: BitSet = [130, 2, 64, 0, 63, 127, 128].into_iter().collect();
+        assert_eq!(
+            set.iter().collect::<Vec<_>>(),
+            vec![0, 2, 63, 64, 127, 128, 130]
+        );
+        assert_eq!(BitSet::with_capacity(1000).iter().next(), None);
+        let all: BitSet = (0..256).collect();
+        assert!(all.iter().eq(0..256));
+    }
+
+    #[test]
+    fn disjoint_sets() {
+        let evens: BitSet = (0..100).step_by(2).collect();
+        let odds: BitSet = (1..100).step_by(2).collect();
+        assert!(evens.intersect(&odds).is_empty());
+        assert_eq!(evens.difference(&odds), evens);
+        assert_eq!(evens.union(&odds), (0..100).collect());
+        assert!(evens.difference(&evens).is_empty());
+    }
+}
@@ -0,0 +1,316 @@
+// D-ary heap
+// A min-heap stored in an array like the binary heap, where the node `i` has
+// `D` children, `D * i + 1` to `D * i + D`, rather than 2.
+// The tree is only log_D(n) high, so sifting a key up, in `push` and
+// `decrease_key`, compares fewer keys as `D` grows, while sifting a key down,
+// in `pop`, compares the `D` children at each level. Heaps with many more
+// decrease-keys than pops, as in Dijkstra's algorithm on dense graphs, are
+// faster with a larger `D`.
+//
+// `push` returns a handle to the element, to pass to `decrease_key`; the
+// positions of the elements are only kept while they are in the heap.
+//
+// Push and decrease-key complexity: O(log_D(n))
+// Pop complexity: O(D log_D(n))
+//
+// https://en.wikipedia.org/wiki/D-ary_heap
+
+use std::collections::HashMap;
+
+pub struct DaryHeap<T: Ord, const D: usize> {
+    // the heap ordered elements, with their handles
+    items: Vec<(T, usize)>,
+    // the index in `items` of the element of each handle still in the heap
+    positions: HashMap<usize, usize>,
+    next_handle: usize,
+}
+
+impl<T: Ord, const D: usize> Default for DaryHeap<T, D> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Ord, const D: usize> DaryHeap<T, D> {
+    /// Creates an empty heap.
+    ///
+    /// Panics if `D` is less than 2.
+    pub fn new() -> Self {
+        assert!(D >= 2, "the nodes need at least two children");
+        DaryHeap {
+            items: Vec::new(),
+            positions: HashMap::new(),
+            next_handle: 0,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// Inserts `key` and returns its handle
+    pub fn push(&mut self, key: T) -> usize {
+        let handle = self.next_handle;
+        self.next_handle += 1;
+        self.positions.insert(handle, self.items.len());
+        self.items.push((key, handle));
+        self.sift_up(self.items.len() - 1);
+        handle
+    }
+
+    pub fn peek(&self) -> Option<&T> {
+        self.items.first().map(|(key, _)| key)
+    }
+
+    pub fn pop(&mut self) -> Option<T> {
+        if self.is_empty() {
+            return None;
+        }
+        let last = self.items.len() - 1;
+        self.swap(0, last);
+        let (key, handle) = self.items.pop().unwrap();
+        self.positions.remove(&handle);
+        if !self.is_empty() {
+            self.sift_down(0);
+        }
+        Some(key)
+    }
+
+    /// Replaces the key of the element `handle` with the smaller `key`
+    ///
+    /// # Panics
+    ///
+    /// Panics if the element has already been popped or if `key` is greater
+    /// than its current key.
+    pub fn decrease_key(&mut self, handle: usize, key: T) {
+        let index = *self.positions.get(&handle).expect("element already popped");
+        assert!(
+            key <= self.items[index].0,
+            "the new key must not be greater"
+        );
+        self.items[index].0 = key;
+        self.sift_up(index);
+    }
+
+    fn swap(&mut self, i: usize, j: usize) {
+        self.items.swap(i, j);
+        self.positions.insert(self.items[i].1, i);
+        self.positions.insert(self.items[j].1, j);
+    }
+
+    /// Moves the element at `index` up while it is smaller than its parent
+    fn sift_up(&mut self, mut index: usize) {
+        while index > 0 {
+            let parent = (index - 1) / D;
+            if self.items[index].0 >= self.items[parent].0 {
+                break;
+            }
+            self.swap(index, parent);
+            index = parent;
+        }
+    }
+
+    /// Moves the element at `index` down while it is larger than its
+    /// smallest child
+    fn sift_down(&mut self, mut index: usize) {
+        loop {
+            let first = D * index + 1;
+            let children = first..(first + D).min(self.items.len());
+            let Some(smallest) = children.min_by(|&a, &b| self.items[a].0.cmp(&self.items[b].0))
+            else {
+                break;
+            };
+            if self.items[smallest].0 >= self.items[index].0 {
+                break;
+            }
+            self.swap(index, smallest);
+            index = smallest;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::general::Xorshift64;
+    use std::cell::Cell;
+    use std::cmp::Ordering;
+    use std::rc::Rc;
+
+    fn heap_sort<const D: usize>(keys: &[i32]) -> Vec<i32> {
+        let mut heap = DaryHeap::<i32, D>::new();
+        for &key in keys {
+            heap.push(key);
+        }
+        assert_eq!(heap.len(), keys.len());
+        std::iter::from_fn(|| heap.pop()).collect()
+    }
+
+    #[test]
+    fn extraction_order_is_sorted() {
+        let mut rng = Xorshift64::new(294);
+        let keys: Vec<i32> = (0..500).map(|_| rng.gen_range(0..100) as i32).collect();
+        let mut sorted = keys.clone();
+        sorted.sort_unstable();
+        assert_eq!(heap_sort::<2>(&keys), sorted);
+        assert_eq!(heap_sort::<3>(&keys), sorted);
+        assert_eq!(heap_sort::<4>(&keys), sorted);
+        assert_eq!(heap_sort::<4>(&[]), vec![]);
+    }
+
+    #[test]
+    fn interleaved_operations() {
+        let mut heap = DaryHeap::<i32, 3>::new();
+        assert_eq!(heap.pop(), None);
+        heap.push(5);
+        heap.push(8);
+        let nine = heap.push(9);
+        assert_eq!(heap.peek(), Some(&5));
+        heap.decrease_key(nine, 1);
+        assert_eq!(heap.pop(), Some(1));
+        assert_eq!(heap.pop(), Some(5));
+        heap.push(2);
+        assert_eq!(heap.pop(), Some(2));
+        assert_eq!(heap.pop(), Some(8));
+        assert!(heap.is_empty());
+        // the handles stay valid after other elements are popped
+        let mut heap = DaryHeap::<i32, 2>::new();
+        let handles: Vec<usize> = (0..20).map(|key| heap.push(100 + key)).collect();
+        heap.pop();
+        heap.decrease_key(handles[19], 0);
+        assert_eq!(heap.pop(), Some(0));
+        assert_eq!(heap.pop(), Some(101));
+    }
+
+    #[test]
+    fn random_decrease_keys_against_sorting() {
+        let mut rng = Xorshift64::new(2940);
+        let mut heap = DaryHeap::<u64, 4>::new();
+        let mut keys: Vec<u64> = (0..300).map(|_| rng.gen_range(1000..2000)).collect();
+        for &key in &keys {
+            heap.push(key);
+        }
+        for _ in 0..1000 {
+            let handle = rng.gen_range(0..300) as usize;
+            keys[handle] -= rng.gen_range(0..keys[handle] / 2 + 1);
+            heap.decrease_key(handle, keys[handle]);
+        }
+        keys.sort_unstable();
+        assert_eq!(std::iter::from_fn(|| heap.pop()).collect::<Vec<_>>(), keys);
+    }
+
+    /// A key counting the comparisons made on all the keys sharing `counter`
+    #[derive(Clone)]
+    struct Counted {
+        key: u64,
+        counter: Rc<Cell<u64>>,
+    }
+
+    impl PartialEq for Counted {
+        fn eq(&self, other: &Self) -> bool {
+            self.cmp(other) == Ordering::Equal
+        }
+    }
+
+    impl Eq for Counted {}
+
+    impl PartialOrd for Counted {
+        fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+            Some(self.cmp(other))
+        }
+    }
+
+    impl Ord for Counted {
+        fn cmp(&self, other: &Self) -> Ordering {
+            self.counter.set(self.counter.get() + 1);
+            self.key.cmp(&other.key)
+        }
+    }
+
+    /// Returns the comparisons of `D`-ary heaps on a workload with many
+    /// decrease-keys, then on one with many pops
+    fn comparisons<const D: usize>() -> (u64, u64) {
+        let counter = Rc::new(Cell::new(0));
+        let key = |key: u64| Counted {
+            key,
+            counter: Rc::clone(&counter),
+        };
+        let n = 4000;
+        let mut rng = Xorshift64::new(2941);
+        let initial: Vec<u64> = (0..n).map(|_| rng.gen_range(1 << 40..1 << 41)).collect();
+
+        let mut heap = DaryHeap::<Counted, D>::new();
+        let mut keys = initial.clone();
+        for &k in &keys {
+            heap.push(key(k));
+        }
+        for _ in 0..10 * n {
+            let handle = rng.gen_range(0..n as u64) as usize;
+            keys[handle] -= rng.gen_range(0..keys[handle] / 8);
+            heap.decrease_key(handle, key(keys[handle]));
+        }
+        let decrease_heavy = counter.replace(0);
+
+        let mut heap = DaryHeap::<Counted, D>::new();
+        for &k in &initial {
+            heap.push(key(k));
+        }
+        while heap.pop().is_some() {}
+        (decrease_heavy, counter.get())
+    }
+
+    #[test]
+    fn arities_trade_sifting_up_for_sifting_down() {
+        let (decrease_2, pop_2) = comparisons::<2>();
+        let (decrease_3, pop_3) = comparisons::<3>();
+        let (decrease_4, pop_4) = comparisons::<4>();
+        let (decrease_8, pop_8) = comparisons::<8>();
+        // the lower the tree, the cheaper the decrease-keys
+        assert!(decrease_2 > decrease_3, "{decrease_2} {decrease_3}");
+        assert!(decrease_3 > decrease_4, "{decrease_3} {decrease_4}");
+        assert!(decrease_4 > decrease_8, "{decrease_4} {decrease_8}");
+        // but the more children to compare when popping: D log_D(n) is the
+        // lowest for D = 3, and much higher for D = 8
+        assert!(pop_3 < pop_2, "{pop_3} {pop_2}");
+        assert!(pop_3 < pop_4, "{pop_3} {pop_4}");
+        assert!(pop_8 > pop_2, "{pop_8} {pop_2}");
+    }
+
+    #[test]
+    fn popped_positions_are_dropped() {
+        let mut heap = DaryHeap::<u32, 3>::new();
+        for round in 0..1000 {
+            let handles: Vec<usize> = (0..4).map(|key| heap.push(round + key)).collect();
+            heap.decrease_key(handles[3], round);
+            while heap.pop().is_some() {}
+        }
+        assert!(heap.positions.is_empty());
+    }
+
+    #[test]
+    #[should_panic]
+    fn decreasing_a_popped_key_panics() {
+        let mut heap = DaryHeap::<i32, 2>::new();
+        let handle = heap.push(3);
+        heap.pop();
+        heap.decrease_key(handle, 1);
+    }
+
+    #[test]
+    #[should_panic]
+    fn increasing_a_key_panics() {
+        let mut heap = DaryHeap::<i32, 2>::new();
+        let handle = heap.push(3);
+        heap.decrease_key(handle, 4);
+    }
+
+    #[test]
+    #[should_panic]
+    fn unary_heap() {
+        DaryHeap::<i32, 1>::new();
+    }
+}
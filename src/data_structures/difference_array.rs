@@ -0,0 +1,133 @@
+/*
+    A difference array records many range updates of an array, and builds the
+    updated array once they are all done.
+
+    Instead of the values, it stores the differences between consecutive
+    values: adding `val` to the range [l, r] only changes diff[l] by +val and
+    diff[r + 1] by -val. The values are then the prefix sums of the
+    differences.
+
+    Update complexity: O(1)
+    Finalization complexity: O(n)
+*/
+
+pub struct DifferenceArray {
+    // one more slot than the array, so that the updates ending at the last
+    // index need no special case
+    diff: Vec<i64>,
+}
+
+impl DifferenceArray {
+    /// Creates an array of `len` zeros
+    pub fn new(len: usize) -> Self {
+        DifferenceArray {
+            diff: vec![0; len + 1],
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.diff.len() - 1
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Adds `val` to every element of the inclusive range [l, r].
+    ///
+    /// Panics if `l > r` or `r` is out of the array.
+    pub fn range_add(&mut self, l: usize, r: usize, val: i64) {
+        assert!(
+            l <= r && r < self.len(),
+            "invalid range [{l}, {r}] of an array of length {}",
+            self.len()
+        );
+        self.diff[l] += val;
+        self.diff[r + 1] -= val;
+    }
+
+    /// Returns the array with all the updates so far applied
+    pub fn finalize(&self) -> Vec<i64> {
+        self.diff[..self.len()]
+            .iter()
+            .scan(0, |value, &delta| {
+                *value += delta;
+                Some(*value)
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::general::Xorshift64;
+
+    #[test]
+    fn overlapping_updates() {
+        let mut array = DifferenceArray::new(6);
+        array.range_add(0, 2, 5);
+        array.range_add(1, 4, -2);
+        array.range_add(2, 2, 10);
+        array.range_add(3, 5, 1);
+        assert_eq!(array.finalize(), vec![5, 3, 13, -1, -1, 1]);
+    }
+
+    #[test]
+    fn updates_touching_the_ends() {
+        let mut array = DifferenceArray::new(4);
+        array.range_add(3, 3, 7);
+        array.range_add(0, 3, 1);
+        array.range_add(0, 0, -4);
+        assert_eq!(array.finalize(), vec![-3, 1, 1, 8]);
+    }
+
+    #[test]
+    fn against_brute_force() {
+        let mut rng = Xorshift64::new(241);
+        for len in [1, 2, 10, 57] {
+            let mut array = DifferenceArray::new(len);
+            let mut expected = vec![0; len];
+            for _ in 0..200 {
+                let a = rng.gen_range(0..len as u64) as usize;
+                let b = rng.gen_range(0..len as u64) as usize;
+                let (l, r) = (a.min(b), a.max(b));
+                let val = rng.gen_range(0..201) as i64 - 100;
+                array.range_add(l, r, val);
+                for value in &mut expected[l..=r] {
+                    *value += val;
+                }
+            }
+            assert_eq!(array.len(), len);
+            assert_eq!(array.finalize(), expected);
+        }
+    }
+
+    #[test]
+    fn finalize_keeps_the_updates() {
+        let mut array = DifferenceArray::new(3);
+        array.range_add(0, 1, 2);
+        assert_eq!(array.finalize(), vec![2, 2, 0]);
+        array.range_add(1, 2, 3);
+        assert_eq!(array.finalize(), vec![2, 5, 3]);
+    }
+
+    #[test]
+    fn empty_array() {
+        let array = DifferenceArray::new(0);
+        assert!(array.is_empty());
+        assert!(array.finalize().is_empty());
+    }
+
+    #[test]
+    #[should_panic]
+    fn out_of_bounds() {
+        DifferenceArray::new(3).range_add(1, 3, 1);
+    }
+
+    #[test]
+    #[should_panic]
+    fn reversed_range() {
+        DifferenceArray::new(3).range_add(2, 1, 1);
+    }
+}
@@ -0,0 +1,383 @@
+// Fibonacci heap
+// A collection of heap-ordered trees whose roots are kept in a circular doubly
+// linked list. Insertions and merges are lazy and only add trees to the root
+// list, so that the work of joining trees of the same degree is delayed to
+// `extract_min`. `decrease_key` cuts the node from its parent, and a parent
+// losing a second child is cut in turn ("cascading cut"), which keeps the size
+// of a tree exponential in the degree of its root.
+// Amortized costs: O(1) for `insert`, `peek` and `decrease_key`, O(log n) for
+// `extract_min`, making Dijkstra's algorithm run in O(E + V * log V).
+//
+// The nodes are stored in an arena, `insert` returns the index of the new node,
+// which is the handle to pass to `decrease_key`. The nodes of the extracted
+// elements are reused by the next insertions, so that the arena holds no more
+// nodes than the heap ever held elements at once: a handle is only valid until
+// its element is extracted.
+//
+// https://en.wikipedia.org/wiki/Fibonacci_heap
+
+struct Node<T> {
+    // `None` once the node has been extracted
+    key: Option<T>,
+    parent: Option<usize>,
+    // any of the children, which form a circular list
+    child: Option<usize>,
+    left: usize,
+    right: usize,
+    degree: usize,
+    // whether the node lost a child since it became the child of its parent
+    marked: bool,
+}
+
+pub struct FibonacciHeap<T: Ord> {
+    nodes: Vec<Node<T>>,
+    // the nodes of the extracted elements, to reuse
+    free: Vec<usize>,
+    min: Option<usize>,
+    len: usize,
+}
+
+impl<T: Ord> Default for FibonacciHeap<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Ord> FibonacciHeap<T> {
+    pub fn new() -> Self {
+        FibonacciHeap {
+            nodes: Vec::new(),
+            free: Vec::new(),
+            min: None,
+            len: 0,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    fn key(&self, node: usize) -> &T {
+        self.nodes[node].key.as_ref().unwrap()
+    }
+
+    /// Inserts the single node `node` to the right of `anchor` in its list
+    fn insert_after(&mut self, anchor: usize, node: usize) {
+        let right = self.nodes[anchor].right;
+        self.nodes[node].left = anchor;
+        self.nodes[node].right = right;
+        self.nodes[anchor].right = node;
+        self.nodes[right].left = node;
+    }
+
+    /// Removes `node` from its list, leaving it in a list of its own
+    fn unlink(&mut self, node: usize) {
+        let (left, right) = (self.nodes[node].left, self.nodes[node].right);
+        self.nodes[left].right = right;
+        self.nodes[right].left = left;
+        self.nodes[node].left = node;
+        self.nodes[node].right = node;
+    }
+
+    /// Adds the single node `node` to the root list and updates the minimum
+    fn add_root(&mut self, node: usize) {
+        self.nodes[node].parent = None;
+        self.nodes[node].marked = false;
+        match self.min {
+            Some(min) => {
+                self.insert_after(min, node);
+                if self.key(node) < self.key(min) {
+                    self.min = Some(node);
+                }
+            }
+            None => self.min = Some(node),
+        }
+    }
+
+    /// Inserts `key` and returns its handle
+    pub fn insert(&mut self, key: T) -> usize {
+        let handle = self.free.pop().unwrap_or(self.nodes.len());
+        let node = Node {
+            key: Some(key),
+            parent: None,
+            child: None,
+            left: handle,
+            right: handle,
+            degree: 0,
+            marked: false,
+        };
+        if handle == self.nodes.len() {
+            self.nodes.push(node);
+        } else {
+            self.nodes[handle] = node;
+        }
+        self.add_root(handle);
+        self.len += 1;
+        handle
+    }
+
+    pub fn peek(&self) -> Option<&T> {
+        self.min.map(|min| self.key(min))
+    }
+
+    /// Lists the nodes of the circular list containing `start`
+    fn list(&self, start: usize) -> Vec<usize> {
+        let mut nodes = vec![start];
+        let mut current = self.nodes[start].right;
+        while current != start {
+            nodes.push(current);
+            current = self.nodes[current].right;
+        }
+        nodes
+    }
+
+    /// Makes the root `child` a child of the root `parent`
+    fn link(&mut self, child: usize, parent: usize) {
+        self.unlink(child);
+        self.nodes[child].parent = Some(parent);
+        self.nodes[child].marked = false;
+        match self.nodes[parent].child {
+            Some(first) => self.insert_after(first, child),
+            None => self.nodes[parent].child = Some(child),
+        }
+        self.nodes[parent].degree += 1;
+    }
+
+    /// Joins the roots of the same degree until all degrees are distinct
+    fn consolidate(&mut self, start: usize) {
+        let mut by_degree: Vec<Option<usize>> = Vec::new();
+        for root in self.list(start) {
+            let mut x = root;
+            let mut degree = self.nodes[x].degree;
+            while let Some(mut y) = by_degree.get(degree).copied().flatten() {
+                if self.key(y) < self.key(x) {
+                    std::mem::swap(&mut x, &mut y);
+                }
+                self.link(y, x);
+                by_degree[degree] = None;
+                degree += 1;
+            }
+            if by_degree.len() <= degree {
+                by_degree.resize(degree + 1, None);
+            }
+            by_degree[degree] = Some(x);
+        }
+
+        self.min = None;
+        for root in by_degree.into_iter().flatten() {
+            self.unlink(root);
+            self.add_root(root);
+        }
+    }
+
+    pub fn extract_min(&mut self) -> Option<T> {
+        let min = self.min?;
+        if let Some(child) = self.nodes[min].child.take() {
+            for node in self.list(child) {
+                self.unlink(node);
+                self.nodes[node].parent = None;
+                self.nodes[node].marked = false;
+                self.insert_after(min, node);
+            }
+        }
+
+        let next = self.nodes[min].right;
+        self.unlink(min);
+        if next == min {
+            self.min = None;
+        } else {
+            self.consolidate(next);
+        }
+        self.len -= 1;
+        self.free.push(min);
+        self.nodes[min].key.take()
+    }
+
+    /// Moves `node` from the children of `parent` to the root list
+    fn cut(&mut self, node: usize, parent: usize) {
+        if self.nodes[parent].child == Some(node) {
+            let right = self.nodes[node].right;
+            self.nodes[parent].child = if right == node { None } else { Some(right) };
+        }
+        self.unlink(node);
+        self.nodes[parent].degree -= 1;
+        self.add_root(node);
+    }
+
+    /// Replaces the key of the element `handle` with the smaller `key`
+    ///
+    /// # Panics
+    ///
+    /// Panics if the element has already been extracted or if `key` is greater
+    /// than its current key.
+    pub fn decrease_key(&mut self, handle: usize, key: T) {
+        let current = self.nodes[handle]
+            .key
+            .as_mut()
+            .expect("element already extracted");
+        assert!(key <= *current, "the new key must not be greater");
+        *current = key;
+
+        match self.nodes[handle].parent {
+            Some(parent) if self.key(handle) < self.key(parent) => {
+                self.cut(handle, parent);
+                // cascading cut: a node losing a second child is cut as well
+                let mut node = parent;
+                while let Some(parent) = self.nodes[node].parent {
+                    if !self.nodes[node].marked {
+                        self.nodes[node].marked = true;
+                        break;
+                    }
+                    self.cut(node, parent);
+                    node = parent;
+                }
+            }
+            Some(_) => {}
+            None => {
+                if self.key(handle) < self.key(self.min.unwrap()) {
+                    self.min = Some(handle);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::general::Xorshift64;
+
+    fn drain<T: Ord>(heap: &mut FibonacciHeap<T>) -> Vec<T> {
+        std::iter::from_fn(|| heap.extract_min()).collect()
+    }
+
+    #[test]
+    fn empty_heap() {
+        let mut heap: FibonacciHeap<i32> = FibonacciHeap::new();
+        assert!(heap.is_empty());
+        assert_eq!(heap.peek(), None);
+        assert_eq!(heap.extract_min(), None);
+    }
+
+    #[test]
+    fn heap_ordered_extraction() {
+        let mut rng = Xorshift64::new(5);
+        let mut heap = FibonacciHeap::new();
+        let mut values: Vec<u64> = (0..1000).map(|_| rng.gen_range(0..300)).collect();
+        for &value in &values {
+            heap.insert(value);
+        }
+        assert_eq!(heap.len(), 1000);
+        assert_eq!(heap.peek(), values.iter().min());
+        values.sort_unstable();
+        assert_eq!(drain(&mut heap), values);
+    }
+
+    #[test]
+    fn interleaved_insert_and_extract() {
+        let mut heap = FibonacciHeap::new();
+        for value in [7, 2, 9, 4] {
+            heap.insert(value);
+        }
+        assert_eq!(heap.extract_min(), Some(2));
+        heap.insert(1);
+        heap.insert(8);
+        assert_eq!(heap.extract_min(), Some(1));
+        assert_eq!(heap.extract_min(), Some(4));
+        heap.insert(3);
+        assert_eq!(drain(&mut heap), vec![3, 7, 8, 9]);
+    }
+
+    #[test]
+    fn decrease_key_sequence() {
+        let mut heap = FibonacciHeap::new();
+        let handles: Vec<usize> = (0..10).map(|i| heap.insert(100 + i)).collect();
+        // builds trees, so that the following decreases cut nodes
+        assert_eq!(heap.extract_min(), Some(100));
+        heap.decrease_key(handles[9], 50);
+        assert_eq!(heap.peek(), Some(&50));
+        heap.decrease_key(handles[5], 60);
+        heap.decrease_key(handles[7], 101);
+        heap.decrease_key(handles[9], 1);
+        assert_eq!(heap.peek(), Some(&1));
+        assert_eq!(
+            drain(&mut heap),
+            vec![1, 60, 101, 101, 102, 103, 104, 106, 108]
+        );
+    }
+
+    #[test]
+    fn random_operations_against_sorting() {
+        let mut rng = Xorshift64::new(6);
+        let mut heap = FibonacciHeap::new();
+        // the keys of the elements still in the heap, by handle; the number of
+        // the insertion is part of the key so that all the keys are distinct
+        let mut keys: Vec<Option<(u64, usize)>> = Vec::new();
+        let mut max_len = 0;
+        for insertion in 0..5000 {
+            match rng.gen_range(0..4) {
+                0 => {
+                    let expected = (0..keys.len())
+                        .filter(|&h| keys[h].is_some())
+                        .min_by_key(|&h| keys[h]);
+                    assert_eq!(heap.extract_min(), expected.and_then(|h| keys[h]));
+                    if let Some(handle) = expected {
+                        keys[handle] = None;
+                    }
+                }
+                1 if !keys.is_empty() => {
+                    let handle = rng.gen_range(0..keys.len() as u64) as usize;
+                    if let Some((key, _)) = keys[handle] {
+                        let new_key = (key - rng.gen_range(0..key + 1), insertion);
+                        keys[handle] = Some(new_key);
+                        heap.decrease_key(handle, new_key);
+                    }
+                }
+                _ => {
+                    let key = (rng.gen_range(0..10_000), insertion);
+                    let handle = heap.insert(key);
+                    if handle == keys.len() {
+                        keys.push(Some(key));
+                    } else {
+                        // the node of an extracted element
+                        assert_eq!(keys[handle], None);
+                        keys[handle] = Some(key);
+                    }
+                }
+            }
+            assert_eq!(heap.len(), keys.iter().flatten().count());
+            max_len = max_len.max(heap.len());
+        }
+        // with the nodes reused, as many as the elements at the fullest
+        assert_eq!(heap.nodes.len(), max_len);
+        let mut remaining: Vec<(u64, usize)> = keys.into_iter().flatten().collect();
+        remaining.sort_unstable();
+        assert_eq!(drain(&mut heap), remaining);
+    }
+
+    #[test]
+    fn extracted_nodes_are_reused() {
+        let mut heap = FibonacciHeap::new();
+        for round in 0..1000 {
+            for key in 0..5 {
+                heap.insert(round * 10 + key);
+            }
+            for key in 0..5 {
+                assert_eq!(heap.extract_min(), Some(round * 10 + key));
+            }
+        }
+        assert_eq!(heap.nodes.len(), 5);
+    }
+
+    #[test]
+    #[should_panic]
+    fn increasing_a_key_panics() {
+        let mut heap = FibonacciHeap::new();
+        let handle = heap.insert(1);
+        heap.decrease_key(handle, 2);
+    }
+}
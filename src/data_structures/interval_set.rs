@@ -0,0 +1,185 @@
+/*
+    An interval set stores a union of closed intervals [lo, hi] as a sorted list
+    of disjoint intervals.
+
+    When an interval is added, it is merged with all the stored intervals it
+    overlaps or touches (e.g. [1, 3] and [3, 5] become [1, 5]), so the stored
+    intervals never share a point. They are kept in a BTreeMap from their lower
+    to their upper bound, so that the intervals around a point are found by a
+    range query.
+
+    Insertion complexity: O(k log(n)) where k is the number of merged intervals,
+    O(log(n)) amortized
+    Membership complexity: O(log(n))
+*/
+
+use num_traits::Zero;
+use std::collections::BTreeMap;
+use std::ops::{Add, Sub};
+
+#[derive(Clone, Debug, Default)]
+pub struct IntervalSet<T: Ord + Copy> {
+    intervals: BTreeMap<T, T>,
+}
+
+impl<T: Ord + Copy> IntervalSet<T> {
+    pub fn new() -> Self {
+        IntervalSet {
+            intervals: BTreeMap::new(),
+        }
+    }
+
+    /// Adds the closed interval [lo, hi], merging it with the intervals it
+    /// overlaps or touches.
+    ///
+    /// Panics if `lo > hi`.
+    pub fn add(&mut self, lo: T, hi: T) {
+        assert!(lo <= hi, "the interval is reversed");
+        let (mut lo, mut hi) = (lo, hi);
+
+        // the interval starting before `lo` may reach it
+        if let Some((&start, &end)) = self.intervals.range(..=lo).next_back() {
+            if end >= lo {
+                lo = start;
+                hi = hi.max(end);
+            }
+        }
+        // and all the intervals starting in [lo, hi] are swallowed
+        let swallowed: Vec<T> = self.intervals.range(lo..=hi).map(|(&s, _)| s).collect();
+        for start in swallowed {
+            let end = self.intervals.remove(&start).unwrap();
+            hi = hi.max(end);
+        }
+        self.intervals.insert(lo, hi);
+    }
+
+    /// Returns whether `point` is in one of the intervals, bounds included
+    pub fn contains(&self, point: T) -> bool {
+        self.intervals
+            .range(..=point)
+            .next_back()
+            .is_some_and(|(_, &end)| end >= point)
+    }
+
+    /// Returns the number of disjoint intervals
+    pub fn len(&self) -> usize {
+        self.intervals.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+
+    /// Iterates over the disjoint intervals, as `(lo, hi)` pairs, in increasing order
+    pub fn iter(&self) -> impl Iterator<Item = (T, T)> + '_ {
+        self.intervals.iter().map(|(&lo, &hi)| (lo, hi))
+    }
+}
+
+impl<T: Ord + Copy + Zero + Add<Output = T> + Sub<Output = T>> IntervalSet<T> {
+    /// Returns the total length covered by the intervals, `hi - lo` each
+    pub fn total_length(&self) -> T {
+        self.iter()
+            .fold(T::zero(), |total, (lo, hi)| total + (hi - lo))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::general::Xorshift64;
+
+    fn intervals<T: Ord + Copy>(set: &IntervalSet<T>) -> Vec<(T, T)> {
+        set.iter().collect()
+    }
+
+    #[test]
+    fn overlapping_intervals_coalesce() {
+        let mut set = IntervalSet::new();
+        set.add(1, 4);
+        set.add(10, 12);
+        set.add(3, 6);
+        assert_eq!(intervals(&set), vec![(1, 6), (10, 12)]);
+        // bridges the gap and swallows both
+        set.add(5, 11);
+        assert_eq!(intervals(&set), vec![(1, 12)]);
+        // already covered
+        set.add(2, 3);
+        assert_eq!(intervals(&set), vec![(1, 12)]);
+        // covers everything
+        set.add(-5, 20);
+        assert_eq!(intervals(&set), vec![(-5, 20)]);
+    }
+
+    #[test]
+    fn adjacent_intervals_coalesce() {
+        let mut set = IntervalSet::new();
+        set.add(0, 2);
+        set.add(5, 7);
+        set.add(2, 5);
+        assert_eq!(intervals(&set), vec![(0, 7)]);
+        set.add(7, 7);
+        set.add(-3, 0);
+        assert_eq!(intervals(&set), vec![(-3, 7)]);
+        // separated by a gap of 1, they stay apart
+        set.add(8, 9);
+        assert_eq!(intervals(&set), vec![(-3, 7), (8, 9)]);
+        assert_eq!(set.len(), 2);
+    }
+
+    #[test]
+    fn total_length() {
+        let mut set = IntervalSet::new();
+        assert_eq!(set.total_length(), 0);
+        set.add(0, 10);
+        set.add(5, 15);
+        set.add(20, 25);
+        set.add(30, 30);
+        assert_eq!(set.total_length(), 15 + 5);
+    }
+
+    #[test]
+    fn membership_at_boundaries() {
+        let mut set = IntervalSet::new();
+        set.add(1, 3);
+        set.add(6, 8);
+        for point in [1, 2, 3, 6, 7, 8] {
+            assert!(set.contains(point));
+        }
+        for point in [-1, 0, 4, 5, 9] {
+            assert!(!set.contains(point));
+        }
+        assert!(!IntervalSet::new().contains(0));
+    }
+
+    #[test]
+    fn against_brute_force() {
+        // intervals of even integers, so that [2a, 2b] stands for {2a, ..., 2b}
+        // and any odd point between two stored intervals is a gap
+        let mut rng = Xorshift64::new(242);
+        let mut set = IntervalSet::new();
+        let mut covered = [false; 201];
+        for _ in 0..60 {
+            let a = rng.gen_range(0..101) as usize * 2;
+            let b = (a + rng.gen_range(0..11) as usize * 2).min(200);
+            set.add(a, b);
+            covered[a..=b].iter_mut().for_each(|c| *c = true);
+
+            for (point, &expected) in covered.iter().enumerate() {
+                assert_eq!(set.contains(point), expected);
+            }
+            let pairs = intervals(&set);
+            for w in pairs.windows(2) {
+                assert!(w[0].1 < w[1].0);
+            }
+            let length = (1..201).filter(|&p| covered[p - 1] && covered[p]).count();
+            assert_eq!(set.total_length(), length);
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn reversed_interval() {
+        IntervalSet::new().add(3, 2);
+    }
+}
@@ -0,0 +1,309 @@
+use std::cmp::max;
+
+/// An internal node of an `IntervalTree`.
+struct IntervalNode<K: Ord + Copy, V> {
+    lo: K,
+    hi: K,
+    value: V,
+    /// The largest upper bound of the intervals in this subtree.
+    max_hi: K,
+    height: usize,
+    left: Option<Box<IntervalNode<K, V>>>,
+    right: Option<Box<IntervalNode<K, V>>>,
+}
+
+/// A map from closed intervals `[lo, hi]` to values, answering which stored
+/// intervals overlap a given one.
+///
+/// The intervals are kept in an AVL tree ordered by their lower bound, where
+/// each node also stores the largest upper bound of its subtree: a subtree
+/// whose largest upper bound is below the query cannot contain an
+/// overlapping interval and is skipped. Insertions take O(log n) and queries
+/// O(log n + k * log n) for k results.
+///
+/// See <https://en.wikipedia.org/wiki/Interval_tree#Augmented_tree>
+pub struct IntervalTree<K: Ord + Copy, V> {
+    root: Option<Box<IntervalNode<K, V>>>,
+    length: usize,
+}
+
+impl<K: Ord + Copy, V> IntervalTree<K, V> {
+    /// Creates an empty `IntervalTree`.
+    pub fn new() -> Self {
+        IntervalTree {
+            root: None,
+            length: 0,
+        }
+    }
+
+    /// Returns the number of intervals in the tree.
+    pub fn len(&self) -> usize {
+        self.length
+    }
+
+    /// Returns `true` if the tree contains no intervals.
+    pub fn is_empty(&self) -> bool {
+        self.length == 0
+    }
+
+    /// Stores `value` for the interval `[lo, hi]`. The same interval may be
+    /// inserted several times.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `lo > hi`.
+    pub fn insert(&mut self, lo: K, hi: K, value: V) {
+        assert!(lo <= hi, "the lower bound must not exceed the upper bound");
+        self.root = Some(insert(self.root.take(), lo, hi, value));
+        self.length += 1;
+    }
+
+    /// Returns the values of all the intervals overlapping `[lo, hi]`, i.e.
+    /// sharing at least one point with it, ordered by lower bound.
+    pub fn query_overlapping(&self, lo: K, hi: K) -> Vec<&V> {
+        let mut result = Vec::new();
+        query(&self.root, lo, hi, &mut result);
+        result
+    }
+}
+
+impl<K: Ord + Copy, V> Default for IntervalTree<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Recursive helper function for `IntervalTree` insertion.
+fn insert<K: Ord + Copy, V>(
+    tree: Option<Box<IntervalNode<K, V>>>,
+    lo: K,
+    hi: K,
+    value: V,
+) -> Box<IntervalNode<K, V>> {
+    match tree {
+        None => Box::new(IntervalNode {
+            lo,
+            hi,
+            value,
+            max_hi: hi,
+            height: 1,
+            left: None,
+            right: None,
+        }),
+        Some(mut node) => {
+            if lo < node.lo {
+                node.left = Some(insert(node.left.take(), lo, hi, value));
+            } else {
+                node.right = Some(insert(node.right.take(), lo, hi, value));
+            }
+            rebalance(node)
+        }
+    }
+}
+
+/// Recursive helper function for `IntervalTree` queries.
+fn query<'a, K: Ord + Copy, V>(
+    tree: &'a Option<Box<IntervalNode<K, V>>>,
+    lo: K,
+    hi: K,
+    result: &mut Vec<&'a V>,
+) {
+    let Some(node) = tree else {
+        return;
+    };
+    // no interval of this subtree ends at or after `lo`
+    if node.max_hi < lo {
+        return;
+    }
+    query(&node.left, lo, hi, result);
+    // this interval and the ones on its right start after `hi`
+    if node.lo > hi {
+        return;
+    }
+    if lo <= node.hi {
+        result.push(&node.value);
+    }
+    query(&node.right, lo, hi, result);
+}
+
+fn height<K: Ord + Copy, V>(tree: &Option<Box<IntervalNode<K, V>>>) -> usize {
+    tree.as_ref().map_or(0, |node| node.height)
+}
+
+/// Recomputes the `height` and `max_hi` fields from the children.
+fn update<K: Ord + Copy, V>(node: &mut IntervalNode<K, V>) {
+    node.height = 1 + max(height(&node.left), height(&node.right));
+    node.max_hi = node.hi;
+    for child in [&node.left, &node.right].into_iter().flatten() {
+        node.max_hi = max(node.max_hi, child.max_hi);
+    }
+}
+
+fn rotate_left<K: Ord + Copy, V>(mut node: Box<IntervalNode<K, V>>) -> Box<IntervalNode<K, V>> {
+    let mut right = node.right.take().unwrap();
+    node.right = right.left.take();
+    update(&mut node);
+    right.left = Some(node);
+    update(&mut right);
+    right
+}
+
+fn rotate_right<K: Ord + Copy, V>(mut node: Box<IntervalNode<K, V>>) -> Box<IntervalNode<K, V>> {
+    let mut left = node.left.take().unwrap();
+    node.left = left.right.take();
+    update(&mut node);
+    left.right = Some(node);
+    update(&mut left);
+    left
+}
+
+/// Performs the rotations restoring the AVL balance of `node`.
+fn rebalance<K: Ord + Copy, V>(mut node: Box<IntervalNode<K, V>>) -> Box<IntervalNode<K, V>> {
+    update(&mut node);
+    let (left, right) = (height(&node.left), height(&node.right));
+    if left > right + 1 {
+        let child = node.left.take().unwrap();
+        node.left = Some(if height(&child.right) > height(&child.left) {
+            rotate_left(child)
+        } else {
+            child
+        });
+        rotate_right(node)
+    } else if right > left + 1 {
+        let child = node.right.take().unwrap();
+        node.right = Some(if height(&child.left) > height(&child.right) {
+            rotate_right(child)
+        } else {
+            child
+        });
+        rotate_left(node)
+    } else {
+        node
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::general::Xorshift64;
+
+    fn brute_force(intervals: &[(i64, i64)], lo: i64, hi: i64) -> Vec<usize> {
+        (0..intervals.len())
+            .filter(|&i| intervals[i].0 <= hi && lo <= intervals[i].1)
+            .collect()
+    }
+
+    fn sorted(values: Vec<&usize>) -> Vec<usize> {
+        let mut values: Vec<usize> = values.into_iter().copied().collect();
+        values.sort_unstable();
+        values
+    }
+
+    #[test]
+    fn empty_tree() {
+        let tree: IntervalTree<i32, ()> = IntervalTree::new();
+        assert!(tree.is_empty());
+        assert!(tree.query_overlapping(0, 10).is_empty());
+    }
+
+    #[test]
+    fn small_example() {
+        let mut tree = IntervalTree::new();
+        tree.insert(15, 20, "a");
+        tree.insert(10, 30, "b");
+        tree.insert(17, 19, "c");
+        tree.insert(5, 20, "d");
+        tree.insert(12, 15, "e");
+        tree.insert(30, 40, "f");
+        assert_eq!(tree.len(), 6);
+        assert_eq!(tree.query_overlapping(6, 7), vec![&"d"]);
+        assert_eq!(tree.query_overlapping(21, 29), vec![&"b"]);
+        // closed intervals: touching bounds overlap
+        assert_eq!(tree.query_overlapping(30, 30), vec![&"b", &"f"]);
+        assert_eq!(tree.query_overlapping(41, 50), Vec::<&&str>::new());
+        assert_eq!(tree.query_overlapping(14, 16), vec![&"d", &"b", &"e", &"a"]);
+    }
+
+    #[test]
+    fn nested_intervals() {
+        let mut tree = IntervalTree::new();
+        for i in 0..10 {
+            tree.insert(i as i64, 100 - i as i64, i);
+        }
+        // every interval contains [10, 90]
+        assert_eq!(
+            sorted(tree.query_overlapping(50, 50)),
+            (0..10).collect::<Vec<_>>()
+        );
+        assert_eq!(
+            sorted(tree.query_overlapping(95, 200)),
+            vec![0, 1, 2, 3, 4, 5]
+        );
+        assert_eq!(sorted(tree.query_overlapping(-5, 0)), vec![0]);
+        // the query is nested in all the intervals, or contains all of them
+        assert_eq!(tree.query_overlapping(-5, 105).len(), 10);
+    }
+
+    #[test]
+    fn duplicate_intervals() {
+        let mut tree = IntervalTree::new();
+        tree.insert(1, 3, 'x');
+        tree.insert(1, 3, 'y');
+        tree.insert(1, 3, 'x');
+        assert_eq!(tree.len(), 3);
+        assert_eq!(tree.query_overlapping(2, 2).len(), 3);
+    }
+
+    #[test]
+    fn random_against_brute_force() {
+        let mut rng = Xorshift64::new(12);
+        let mut tree = IntervalTree::new();
+        let mut intervals = Vec::new();
+        for i in 0..500usize {
+            let lo = rng.gen_range(0..1000) as i64;
+            let hi = lo + rng.gen_range(0..60) as i64;
+            tree.insert(lo, hi, i);
+            intervals.push((lo, hi));
+
+            if i.is_multiple_of(10) {
+                for _ in 0..20 {
+                    let q_lo = rng.gen_range(0..1100) as i64 - 50;
+                    // point queries and wider ones
+                    let q_hi = q_lo + rng.gen_range(0..3) as i64 * rng.gen_range(0..50) as i64;
+                    assert_eq!(
+                        sorted(tree.query_overlapping(q_lo, q_hi)),
+                        brute_force(&intervals, q_lo, q_hi)
+                    );
+                }
+            }
+        }
+        // the tree stays balanced
+        assert!(height(&tree.root) <= 13);
+    }
+
+    #[test]
+    fn point_queries_against_brute_force() {
+        let mut rng = Xorshift64::new(13);
+        let mut tree = IntervalTree::new();
+        let mut intervals = Vec::new();
+        for i in 0..200 {
+            let lo = rng.gen_range(0..100) as i64;
+            let hi = lo + rng.gen_range(0..10) as i64;
+            tree.insert(lo, hi, i);
+            intervals.push((lo, hi));
+        }
+        for point in -1..=111 {
+            assert_eq!(
+                sorted(tree.query_overlapping(point, point)),
+                brute_force(&intervals, point, point)
+            );
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn reversed_interval() {
+        let mut tree = IntervalTree::new();
+        tree.insert(5, 3, ());
+    }
+}
@@ -0,0 +1,299 @@
+/*
+    A k-d tree is a binary tree of points in k dimensions, which answers nearest
+    neighbor and radius queries without comparing the query with every point.
+
+    Each node splits its points with the median along one axis, cycling through
+    the axes with the depth. A search visits the side of the split containing
+    the query first, and skips the other side when the splitting plane is
+    farther than the best distance found so far.
+
+    The tree is balanced and implicit: the indices of the points are permuted
+    so that the node of a range of indices is at its middle, with its two
+    subtrees on each side.
+
+    Construction complexity: O(n log(n))
+    Nearest neighbor query complexity: O(log(n)) on average for well spread points
+    Radius query complexity: O(n^(1 - 1/k) + m) where m is the number of points found
+
+    Wikipedia: <https://en.wikipedia.org/wiki/K-d_tree>
+*/
+
+pub struct KdTree {
+    points: Vec<Vec<f64>>,
+    // the indices of the points, in the order of the implicit tree
+    order: Vec<usize>,
+    dimensions: usize,
+}
+
+// the state of a radius query, along its recursion
+struct RadiusSearch<'a> {
+    query: &'a [f64],
+    squared_radius: f64,
+    found: &'a mut Vec<usize>,
+    distances: &'a mut usize,
+}
+
+fn squared_distance(a: &[f64], b: &[f64]) -> f64 {
+    a.iter().zip(b).map(|(x, y)| (x - y).powi(2)).sum()
+}
+
+impl KdTree {
+    /// Builds the tree of `points`, which must all have the same dimension.
+    pub fn new(points: &[Vec<f64>]) -> Self {
+        let dimensions = points.first().map_or(0, Vec::len);
+        assert!(
+            points.iter().all(|p| p.len() == dimensions),
+            "the points have different dimensions"
+        );
+        let mut tree = KdTree {
+            points: points.to_vec(),
+            order: (0..points.len()).collect(),
+            dimensions,
+        };
+        tree.build(0, points.len(), 0);
+        tree
+    }
+
+    fn build(&mut self, lo: usize, hi: usize, depth: usize) {
+        if hi - lo <= 1 || self.dimensions == 0 {
+            return;
+        }
+        let axis = depth % self.dimensions;
+        let mid = (lo + hi) / 2;
+        let points = &self.points;
+        self.order[lo..hi].select_nth_unstable_by(mid - lo, |&a, &b| {
+            points[a][axis].total_cmp(&points[b][axis])
+        });
+        self.build(lo, mid, depth + 1);
+        self.build(mid + 1, hi, depth + 1);
+    }
+
+    pub fn len(&self) -> usize {
+        self.points.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.points.is_empty()
+    }
+
+    /// Returns the index of the point nearest to `query`, the lowest index
+    /// among the points at the same distance, or None if the tree is empty.
+    pub fn nearest(&self, query: &[f64]) -> Option<usize> {
+        assert_eq!(
+            query.len(),
+            self.dimensions,
+            "the query has a wrong dimension"
+        );
+        if self.dimensions == 0 {
+            // all the points are the same
+            return (!self.is_empty()).then_some(0);
+        }
+        let mut best = None;
+        self.nearest_in(query, 0, self.len(), 0, &mut best);
+        best.map(|(_, index)| index)
+    }
+
+    fn nearest_in(
+        &self,
+        query: &[f64],
+        lo: usize,
+        hi: usize,
+        depth: usize,
+        best: &mut Option<(f64, usize)>,
+    ) {
+        if lo >= hi {
+            return;
+        }
+        let mid = (lo + hi) / 2;
+        let index = self.order[mid];
+        let point = &self.points[index];
+        let distance = squared_distance(query, point);
+        let closer = match *best {
+            None => true,
+            Some((best_distance, best_index)) => {
+                distance < best_distance || (distance == best_distance && index < best_index)
+            }
+        };
+        if closer {
+            *best = Some((distance, index));
+        }
+
+        let axis = depth % self.dimensions;
+        let offset = query[axis] - point[axis];
+        let (near, far) = if offset < 0.0 {
+            ((lo, mid), (mid + 1, hi))
+        } else {
+            ((mid + 1, hi), (lo, mid))
+        };
+        self.nearest_in(query, near.0, near.1, depth + 1, best);
+        // points on the other side are at least `offset` away; those exactly
+        // as far as the best one are still visited, for their indices
+        if best.is_some_and(|(best_distance, _)| offset * offset <= best_distance) {
+            self.nearest_in(query, far.0, far.1, depth + 1, best);
+        }
+    }
+
+    /// Returns the indices of all the points at distance at most `radius` from
+    /// `query`, in increasing order.
+    pub fn within_radius(&self, query: &[f64], radius: f64) -> Vec<usize> {
+        self.within_radius_counting(query, radius, &mut 0)
+    }
+
+    /// The same as `within_radius`, adding the number of distances computed
+    /// to `distances`, to measure how many points the search skipped.
+    pub(crate) fn within_radius_counting(
+        &self,
+        query: &[f64],
+        radius: f64,
+        distances: &mut usize,
+    ) -> Vec<usize> {
+        assert_eq!(
+            query.len(),
+            self.dimensions,
+            "the query has a wrong dimension"
+        );
+        if self.dimensions == 0 {
+            return (0..self.len()).collect();
+        }
+        let mut found = Vec::new();
+        let mut search = RadiusSearch {
+            query,
+            squared_radius: radius * radius,
+            found: &mut found,
+            distances,
+        };
+        self.within_radius_in(&mut search, 0, self.len(), 0);
+        found.sort_unstable();
+        found
+    }
+
+    fn within_radius_in(&self, search: &mut RadiusSearch, lo: usize, hi: usize, depth: usize) {
+        if lo >= hi {
+            return;
+        }
+        let mid = (lo + hi) / 2;
+        let index = self.order[mid];
+        let point = &self.points[index];
+        *search.distances += 1;
+        if squared_distance(search.query, point) <= search.squared_radius {
+            search.found.push(index);
+        }
+
+        let axis = depth % self.dimensions;
+        let offset = search.query[axis] - point[axis];
+        let reaches_across = offset * offset <= search.squared_radius;
+        // the left subtree is at or below the split, the right one at or above
+        if offset <= 0.0 || reaches_across {
+            self.within_radius_in(search, lo, mid, depth + 1);
+        }
+        if offset >= 0.0 || reaches_across {
+            self.within_radius_in(search, mid + 1, hi, depth + 1);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::general::Xorshift64;
+
+    fn random_points(rng: &mut Xorshift64, n: usize, dimensions: usize) -> Vec<Vec<f64>> {
+        (0..n)
+            .map(|_| (0..dimensions).map(|_| rng.next_f64() * 10.0).collect())
+            .collect()
+    }
+
+    fn brute_force_nearest(points: &[Vec<f64>], query: &[f64]) -> usize {
+        (0..points.len())
+            .min_by(|&a, &b| {
+                squared_distance(query, &points[a])
+                    .total_cmp(&squared_distance(query, &points[b]))
+                    .then(a.cmp(&b))
+            })
+            .unwrap()
+    }
+
+    #[test]
+    fn nearest_against_brute_force() {
+        let mut rng = Xorshift64::new(246);
+        for (n, dimensions) in [(1, 2), (2, 2), (50, 2), (500, 2), (300, 3), (200, 5)] {
+            let points = random_points(&mut rng, n, dimensions);
+            let tree = KdTree::new(&points);
+            assert_eq!(tree.len(), n);
+            for query in random_points(&mut rng, 50, dimensions) {
+                assert_eq!(
+                    tree.nearest(&query),
+                    Some(brute_force_nearest(&points, &query))
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn nearest_ties_go_to_the_lowest_index() {
+        // a grid with every point repeated, queried at points and midpoints
+        let mut points = vec![];
+        for _ in 0..3 {
+            for x in 0..5 {
+                for y in 0..5 {
+                    points.push(vec![x as f64, y as f64]);
+                }
+            }
+        }
+        let tree = KdTree::new(&points);
+        for x in 0..9 {
+            for y in 0..9 {
+                let query = [x as f64 / 2.0, y as f64 / 2.0];
+                assert_eq!(
+                    tree.nearest(&query),
+                    Some(brute_force_nearest(&points, &query))
+                );
+            }
+        }
+        assert_eq!(tree.nearest(&[2.0, 3.0]), Some(13));
+    }
+
+    #[test]
+    fn within_radius_against_brute_force() {
+        let mut rng = Xorshift64::new(2460);
+        for dimensions in [1, 2, 4] {
+            let points = random_points(&mut rng, 400, dimensions);
+            let tree = KdTree::new(&points);
+            for query in random_points(&mut rng, 20, dimensions) {
+                for radius in [0.0, 0.5, 2.0, 20.0] {
+                    let expected: Vec<usize> = (0..points.len())
+                        .filter(|&i| squared_distance(&query, &points[i]) <= radius * radius)
+                        .collect();
+                    assert_eq!(tree.within_radius(&query, radius), expected);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn within_radius_includes_the_boundary() {
+        let points = vec![
+            vec![0.0, 0.0],
+            vec![3.0, 4.0],
+            vec![3.0, 4.0],
+            vec![6.0, 8.0],
+        ];
+        let tree = KdTree::new(&points);
+        assert_eq!(tree.within_radius(&[0.0, 0.0], 5.0), vec![0, 1, 2]);
+        assert_eq!(tree.within_radius(&[3.0, 4.0], 0.0), vec![1, 2]);
+    }
+
+    #[test]
+    fn empty_tree() {
+        let tree = KdTree::new(&[]);
+        assert!(tree.is_empty());
+        assert_eq!(tree.nearest(&[]), None);
+        assert!(tree.within_radius(&[], 1.0).is_empty());
+    }
+
+    #[test]
+    #[should_panic]
+    fn mixed_dimensions() {
+        KdTree::new(&[vec![1.0, 2.0], vec![1.0]]);
+    }
+}
@@ -0,0 +1,315 @@
+/*
+    A least frequently used (LFU) cache holds at most `capacity` entries, and
+    when full, makes room for a new key by evicting the entry read or written
+    the fewest times, the one accessed the longest ago on ties.
+
+    The entries are in a doubly linked list for each access count, the
+    frequency buckets, from the latest to the oldest access. An access
+    moves its entry to the front of the next bucket, and the entry to evict is
+    at the back of the bucket of the smallest count, which is tracked: it only
+    changes to 1 when a key is inserted, or to the next count when its bucket
+    empties. The lists link the indices of the entries in an array, so that
+    no operation searches.
+
+    Get and put complexity: O(1) on average, from the hash maps
+
+    https://en.wikipedia.org/wiki/Least_frequently_used
+*/
+
+use std::collections::HashMap;
+use std::hash::Hash;
+
+struct Entry<K, V> {
+    key: K,
+    value: V,
+    frequency: usize,
+    // the neighbors in the bucket of `frequency`, towards its front and back
+    prev: Option<usize>,
+    next: Option<usize>,
+}
+
+/// The ends of the list of a bucket
+#[derive(Clone, Copy)]
+struct Bucket {
+    front: usize,
+    back: usize,
+}
+
+pub struct LfuCache<K, V> {
+    capacity: usize,
+    entries: Vec<Entry<K, V>>,
+    // the index in `entries` of each key
+    indices: HashMap<K, usize>,
+    buckets: HashMap<usize, Bucket>,
+    min_frequency: usize,
+}
+
+impl<K: Eq + Hash + Clone, V> LfuCache<K, V> {
+    /// Creates an empty cache holding at most `capacity` entries.
+    ///
+    /// Panics if `capacity` is 0.
+    pub fn new(capacity: usize) -> Self {
+        assert!(capacity > 0, "the cache needs room for an entry");
+        LfuCache {
+            capacity,
+            entries: Vec::with_capacity(capacity),
+            indices: HashMap::with_capacity(capacity),
+            buckets: HashMap::new(),
+            min_frequency: 0,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Returns whether the cache holds `key`, without counting an access
+    pub fn contains(&self, key: &K) -> bool {
+        self.indices.contains_key(key)
+    }
+
+    /// Returns the value of `key`, counting an access to it
+    pub fn get(&mut self, key: &K) -> Option<&V> {
+        let index = *self.indices.get(key)?;
+        self.touch(index);
+        Some(&self.entries[index].value)
+    }
+
+    /// Sets the value of `key`, counting an access to it.
+    ///
+    /// Returns the entry evicted to make room for a new key, if any.
+    pub fn put(&mut self, key: K, value: V) -> Option<(K, V)> {
+        if let Some(&index) = self.indices.get(&key) {
+            self.entries[index].value = value;
+            self.touch(index);
+            return None;
+        }
+
+        let entry = Entry {
+            key: key.clone(),
+            value,
+            frequency: 1,
+            prev: None,
+            next: None,
+        };
+        let (index, evicted) = if self.entries.len() < self.capacity {
+            self.entries.push(entry);
+            (self.entries.len() - 1, None)
+        } else {
+            // the new entry takes the place of the evicted one
+            let index = self.buckets[&self.min_frequency].back;
+            self.unlink(index);
+            let evicted = std::mem::replace(&mut self.entries[index], entry);
+            self.indices.remove(&evicted.key);
+            (index, Some((evicted.key, evicted.value)))
+        };
+        self.indices.insert(key, index);
+        self.push_front(index);
+        self.min_frequency = 1;
+        evicted
+    }
+
+    /// Moves the entry at `index` to the front of the next bucket
+    fn touch(&mut self, index: usize) {
+        let frequency = self.entries[index].frequency;
+        self.unlink(index);
+        if self.min_frequency == frequency && !self.buckets.contains_key(&frequency) {
+            self.min_frequency += 1;
+        }
+        self.entries[index].frequency += 1;
+        self.push_front(index);
+    }
+
+    /// Removes the entry at `index` from its bucket, and the bucket if it is
+    /// left empty
+    fn unlink(&mut self, index: usize) {
+        let Entry {
+            frequency,
+            prev,
+            next,
+            ..
+        } = self.entries[index];
+        match prev {
+            Some(prev) => self.entries[prev].next = next,
+            None => match next {
+                Some(next) => self.buckets.get_mut(&frequency).unwrap().front = next,
+                None => {
+                    self.buckets.remove(&frequency);
+                }
+            },
+        }
+        match next {
+            Some(next) => self.entries[next].prev = prev,
+            // the bucket is already gone if the entry was also its front
+            None => {
+                if let Some(bucket) = self.buckets.get_mut(&frequency) {
+                    bucket.back = prev.unwrap();
+                }
+            }
+        }
+    }
+
+    /// Adds the entry at `index` to the front of the bucket of its frequency
+    fn push_front(&mut self, index: usize) {
+        let frequency = self.entries[index].frequency;
+        let front = self.buckets.get(&frequency).map(|bucket| bucket.front);
+        self.entries[index].prev = None;
+        self.entries[index].next = front;
+        match front {
+            Some(front) => {
+                self.entries[front].prev = Some(index);
+                self.buckets.get_mut(&frequency).unwrap().front = index;
+            }
+            None => {
+                self.buckets.insert(
+                    frequency,
+                    Bucket {
+                        front: index,
+                        back: index,
+                    },
+                );
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::general::Xorshift64;
+
+    #[test]
+    fn basic_operations() {
+        let mut cache = LfuCache::new(2);
+        assert!(cache.is_empty());
+        assert_eq!(cache.get(&1), None);
+        assert_eq!(cache.put(1, "one"), None);
+        assert_eq!(cache.put(2, "two"), None);
+        assert_eq!(cache.get(&1), Some(&"one"));
+        // 2 was used once, and 1 twice
+        assert_eq!(cache.put(3, "three"), Some((2, "two")));
+        assert!(!cache.contains(&2));
+        // updating a value counts as an access, and evicts nothing
+        assert_eq!(cache.put(3, "THREE"), None);
+        assert_eq!(cache.get(&3), Some(&"THREE"));
+        assert_eq!(cache.len(), 2);
+        assert_eq!(cache.capacity(), 2);
+    }
+
+    #[test]
+    fn frequently_used_keys_survive() {
+        let mut cache = LfuCache::new(3);
+        cache.put(0, 0);
+        for _ in 0..10 {
+            cache.get(&0);
+        }
+        // a stream of keys used once each, all more recent than 0
+        for key in 1..100 {
+            let evicted = cache.put(key, key);
+            if key > 2 {
+                assert_eq!(evicted, Some((key - 2, key - 2)));
+            }
+        }
+        assert_eq!(cache.get(&0), Some(&0));
+        assert!(cache.contains(&98) && cache.contains(&99));
+    }
+
+    #[test]
+    fn ties_evict_the_least_recently_used() {
+        let mut cache = LfuCache::new(3);
+        cache.put('a', 1);
+        cache.put('b', 2);
+        cache.put('c', 3);
+        // all used twice, 'b' the longest ago
+        cache.get(&'b');
+        cache.get(&'c');
+        cache.get(&'a');
+        assert_eq!(cache.put('d', 4), Some(('b', 2)));
+        // 'd' is the only one used once
+        assert_eq!(cache.put('e', 5), Some(('d', 4)));
+        // 'a' and 'c' are now used less than 'e', and 'c' the longest ago
+        cache.get(&'e');
+        cache.get(&'e');
+        assert_eq!(cache.put('f', 6), Some(('c', 3)));
+    }
+
+    /// Least frequently used, then least recently used, by a linear scan
+    struct NaiveLfuCache {
+        capacity: usize,
+        // key, value, frequency and time of the last access
+        entries: Vec<(u64, u64, usize, usize)>,
+        time: usize,
+    }
+
+    impl NaiveLfuCache {
+        fn get(&mut self, key: u64) -> Option<u64> {
+            self.time += 1;
+            let entry = self.entries.iter_mut().find(|entry| entry.0 == key)?;
+            entry.2 += 1;
+            entry.3 = self.time;
+            Some(entry.1)
+        }
+
+        fn put(&mut self, key: u64, value: u64) -> Option<(u64, u64)> {
+            if self.get(key).is_some() {
+                self.entries
+                    .iter_mut()
+                    .find(|entry| entry.0 == key)
+                    .unwrap()
+                    .1 = value;
+                return None;
+            }
+            let mut evicted = None;
+            if self.entries.len() == self.capacity {
+                let (index, _) = self
+                    .entries
+                    .iter()
+                    .enumerate()
+                    .min_by_key(|(_, entry)| (entry.2, entry.3))
+                    .unwrap();
+                let entry = self.entries.swap_remove(index);
+                evicted = Some((entry.0, entry.1));
+            }
+            self.entries.push((key, value, 1, self.time));
+            evicted
+        }
+    }
+
+    #[test]
+    fn random_operations_against_a_naive_cache() {
+        let mut rng = Xorshift64::new(300);
+        for capacity in [1, 2, 5, 20] {
+            let mut cache = LfuCache::new(capacity);
+            let mut naive = NaiveLfuCache {
+                capacity,
+                entries: vec![],
+                time: 0,
+            };
+            for _ in 0..5000 {
+                // some keys much more common than others
+                let key = rng.gen_range(0..4) * rng.gen_range(0..10);
+                if rng.gen_range(0..2) == 0 {
+                    assert_eq!(cache.get(&key).copied(), naive.get(key));
+                } else {
+                    let value = rng.next_u64();
+                    assert_eq!(cache.put(key, value), naive.put(key, value));
+                }
+                assert_eq!(cache.len(), naive.entries.len());
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn no_capacity() {
+        LfuCache::<u8, u8>::new(0);
+    }
+}
@@ -1,49 +1,81 @@
 mod avl_tree;
 mod b_tree;
 mod binary_search_tree;
+mod bitset;
+mod dary_heap;
+mod difference_array;
 mod fenwick_tree;
+mod fibonacci_heap;
 mod floyds_algorithm;
 pub mod graph;
 mod hash_table;
 mod heap;
 mod infix_to_postfix;
+mod interval_set;
+mod interval_tree;
+mod kd_tree;
 mod lazy_segment_tree;
+mod lfu_cache;
 mod linked_list;
+mod monoid_segment_tree;
+mod pairing_heap;
 mod postfix_evaluation;
+mod prefix_sum_2d;
 mod probabilistic;
 mod queue;
 mod range_minimum_query;
 mod rb_tree;
+mod rope;
 mod segment_tree;
 mod segment_tree_recursive;
+mod splay_tree;
 mod stack_using_singly_linked_list;
+mod tdigest;
 mod treap;
 mod trie;
 mod union_find;
 mod veb_tree;
+mod wavelet_tree;
+mod window_stats;
 
 pub use self::avl_tree::AVLTree;
 pub use self::b_tree::BTree;
 pub use self::binary_search_tree::BinarySearchTree;
+pub use self::bitset::BitSet;
+pub use self::dary_heap::DaryHeap;
+pub use self::difference_array::DifferenceArray;
 pub use self::fenwick_tree::FenwickTree;
+pub use self::fibonacci_heap::FibonacciHeap;
 pub use self::floyds_algorithm::{detect_cycle, has_cycle};
 pub use self::graph::DirectedGraph;
 pub use self::graph::UndirectedGraph;
 pub use self::hash_table::HashTable;
 pub use self::heap::Heap;
 pub use self::infix_to_postfix::infix_to_postfix;
+pub use self::interval_set::IntervalSet;
+pub use self::interval_tree::IntervalTree;
+pub use self::kd_tree::KdTree;
 pub use self::lazy_segment_tree::LazySegmentTree;
+pub use self::lfu_cache::LfuCache;
 pub use self::linked_list::LinkedList;
+pub use self::monoid_segment_tree::SegmentTree as MonoidSegmentTree;
+pub use self::pairing_heap::PairingHeap;
 pub use self::postfix_evaluation::evaluate_postfix;
+pub use self::prefix_sum_2d::PrefixSum2D;
 pub use self::probabilistic::bloom_filter;
 pub use self::probabilistic::count_min_sketch;
 pub use self::queue::Queue;
 pub use self::range_minimum_query::RangeMinimumQuery;
 pub use self::rb_tree::RBTree;
+pub use self::rope::Rope;
 pub use self::segment_tree::SegmentTree;
 pub use self::segment_tree_recursive::SegmentTree as SegmentTreeRecursive;
+pub use self::splay_tree::SplayTree;
 pub use self::stack_using_singly_linked_list::Stack;
+pub use self::tdigest::TDigest;
 pub use self::treap::Treap;
 pub use self::trie::Trie;
 pub use self::union_find::UnionFind;
 pub use self::veb_tree::VebTree;
+pub use self::wavelet_tree::WaveletTree;
+pub use self::window_stats::WindowStats;
@@ -0,0 +1,222 @@
+use std::ops::Range;
+
+/// A segment tree over any monoid: a type `T` with an associative `combine`
+/// operation and an `identity` element such that combining with it changes
+/// nothing. This covers range sums (`0`, `+`), minimums (`T::MAX`, `min`),
+/// maximums, gcds (`0`, `gcd`), products, and even non-commutative
+/// operations like string concatenation or matrix products, as the order of
+/// the values is preserved.
+///
+/// Unlike `SegmentTree`, the values need neither be `Ord` nor `Copy`, and the
+/// combine function may capture its environment.
+///
+/// Both `update` and `query` run in O(log n) calls to `combine`.
+pub struct SegmentTree<T, F>
+where
+    F: Fn(&T, &T) -> T,
+{
+    len: usize,
+    // flat binary tree: the leaves are `tree[len..2 * len]` and the parent of
+    // node `i` is node `i / 2`
+    tree: Vec<T>,
+    identity: T,
+    combine: F,
+}
+
+impl<T: Clone, F: Fn(&T, &T) -> T> SegmentTree<T, F> {
+    /// Builds the tree over `arr` in O(n)
+    pub fn new(arr: &[T], identity: T, combine: F) -> Self {
+        let len = arr.len();
+        let mut tree = vec![identity.clone(); 2 * len];
+        tree[len..].clone_from_slice(arr);
+        for i in (1..len).rev() {
+            tree[i] = combine(&tree[2 * i], &tree[2 * i + 1]);
+        }
+        SegmentTree {
+            len,
+            tree,
+            identity,
+            combine,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Replaces the value at index `idx` with `val`
+    ///
+    /// # Panics
+    ///
+    /// Panics if `idx` is out of bounds.
+    pub fn update(&mut self, idx: usize, val: T) {
+        assert!(idx < self.len, "index out of bounds");
+        let mut idx = idx + self.len;
+        self.tree[idx] = val;
+        idx /= 2;
+        while idx != 0 {
+            self.tree[idx] = (self.combine)(&self.tree[2 * idx], &self.tree[2 * idx + 1]);
+            idx /= 2;
+        }
+    }
+
+    /// Returns the combination of the values in `range` (exclusive), in
+    /// order, or the identity element for an empty range.
+    /// The end of the range is clamped to the length of the array.
+    pub fn query(&self, range: Range<usize>) -> T {
+        let mut l = range.start.min(self.len) + self.len;
+        let mut r = range.end.min(self.len) + self.len;
+        // the left and right parts are accumulated separately to keep the
+        // values in order when `combine` is not commutative
+        let mut left = self.identity.clone();
+        let mut right = self.identity.clone();
+        while l < r {
+            if l % 2 == 1 {
+                left = (self.combine)(&left, &self.tree[l]);
+                l += 1;
+            }
+            if r % 2 == 1 {
+                r -= 1;
+                right = (self.combine)(&self.tree[r], &right);
+            }
+            l /= 2;
+            r /= 2;
+        }
+        (self.combine)(&left, &right)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::general::Xorshift64;
+
+    /// Applies random updates and compares random range queries with a
+    /// brute-force fold over the array
+    fn check_against_brute_force<F>(
+        mut values: Vec<i64>,
+        identity: i64,
+        combine: F,
+        rng: &mut Xorshift64,
+    ) where
+        F: Fn(&i64, &i64) -> i64,
+    {
+        let mut tree = SegmentTree::new(&values, identity, &combine);
+        let n = values.len() as u64;
+        for _ in 0..500 {
+            if rng.gen_range(0..3) == 0 {
+                let idx = rng.gen_range(0..n) as usize;
+                let val = rng.gen_range(0..2001) as i64 - 1000;
+                values[idx] = val;
+                tree.update(idx, val);
+            } else {
+                let a = rng.gen_range(0..n + 1) as usize;
+                let b = rng.gen_range(0..n + 1) as usize;
+                let range = a.min(b)..a.max(b);
+                let expected = values[range.clone()]
+                    .iter()
+                    .fold(identity, |acc, v| combine(&acc, v));
+                assert_eq!(tree.query(range), expected);
+            }
+        }
+    }
+
+    fn random_values(n: usize, rng: &mut Xorshift64) -> Vec<i64> {
+        (0..n)
+            .map(|_| rng.gen_range(0..2001) as i64 - 1000)
+            .collect()
+    }
+
+    #[test]
+    fn range_sum_against_brute_force() {
+        let mut rng = Xorshift64::new(1);
+        for n in [1, 2, 7, 64, 100] {
+            let values = random_values(n, &mut rng);
+            check_against_brute_force(values, 0, |a, b| a + b, &mut rng);
+        }
+    }
+
+    #[test]
+    fn range_min_against_brute_force() {
+        let mut rng = Xorshift64::new(2);
+        for n in [1, 3, 16, 33, 100] {
+            let values = random_values(n, &mut rng);
+            check_against_brute_force(values, i64::MAX, |a, b| *a.min(b), &mut rng);
+        }
+    }
+
+    #[test]
+    fn range_max_and_gcd() {
+        let values = vec![12, 18, 24, 7, 14, 28];
+        let max_tree = SegmentTree::new(&values, i64::MIN, |a: &i64, b: &i64| *a.max(b));
+        assert_eq!(max_tree.query(0..6), 28);
+        assert_eq!(max_tree.query(0..3), 24);
+
+        fn gcd(a: &i64, b: &i64) -> i64 {
+            if *b == 0 {
+                a.abs()
+            } else {
+                gcd(b, &(a % b))
+            }
+        }
+        let gcd_tree = SegmentTree::new(&values, 0, gcd);
+        assert_eq!(gcd_tree.query(0..3), 6);
+        assert_eq!(gcd_tree.query(3..6), 7);
+        assert_eq!(gcd_tree.query(0..6), 1);
+    }
+
+    #[test]
+    fn floats_and_captured_state() {
+        let values = vec![0.5, -1.5, 2.0, 4.0];
+        let tree = SegmentTree::new(&values, f64::NEG_INFINITY, |a: &f64, b: &f64| a.max(*b));
+        assert_eq!(tree.query(0..2), 0.5);
+        assert_eq!(tree.query(1..4), 4.0);
+
+        // the combine function may capture its environment
+        let modulus = 7;
+        let tree = SegmentTree::new(&[5, 4, 6, 3], 0, |a: &u32, b: &u32| (a + b) % modulus);
+        assert_eq!(tree.query(0..4), 4);
+        assert_eq!(tree.query(1..3), 3);
+    }
+
+    #[test]
+    fn non_commutative_operation_keeps_order() {
+        let words: Vec<String> = ["a", "b", "c", "d", "e", "f", "g"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        let mut tree = SegmentTree::new(&words, String::new(), |a: &String, b: &String| {
+            format!("{a}{b}")
+        });
+        for start in 0..=words.len() {
+            for end in start..=words.len() {
+                assert_eq!(tree.query(start..end), words[start..end].concat());
+            }
+        }
+        tree.update(3, "XYZ".to_string());
+        assert_eq!(tree.query(1..6), "bcXYZef");
+    }
+
+    #[test]
+    fn empty_ranges_and_tree() {
+        let tree = SegmentTree::new(&[3, 1, 2], 0, |a: &i32, b: &i32| a + b);
+        assert_eq!(tree.len(), 3);
+        assert_eq!(tree.query(2..2), 0);
+        assert_eq!(tree.query(1..10), 3);
+
+        let empty = SegmentTree::new(&[], 0, |a: &i32, b: &i32| a + b);
+        assert!(empty.is_empty());
+        assert_eq!(empty.query(0..5), 0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn update_out_of_bounds() {
+        let mut tree = SegmentTree::new(&[1, 2], 0, |a: &i32, b: &i32| a + b);
+        tree.update(2, 5);
+    }
+}
@@ -0,0 +1,338 @@
+// Pairing heap
+// A heap-ordered multiway tree: `push`, `merge` and `decrease_key` simply meld
+// two trees by making the root with the larger key a child of the other one,
+// and `pop_min` rebuilds a tree from the children of the root by melding them
+// in pairs from left to right, then from right to left.
+// The amortized cost is O(log n) for `pop_min` and O(1) for `push` and
+// `merge`, while `decrease_key` is o(log n) and very fast in practice.
+//
+// The nodes are stored in an arena, `push` returns the index of the new node,
+// which is the handle to pass to `decrease_key`. The nodes of the popped
+// elements are reused by the next pushes, so that the arena holds no more
+// nodes than the heap ever held elements at once: a handle is only valid until
+// its element is popped.
+//
+// https://en.wikipedia.org/wiki/Pairing_heap
+
+struct Node<T> {
+    // `None` once the node has been popped
+    key: Option<T>,
+    child: Option<usize>,
+    sibling: Option<usize>,
+    // the parent for a first child, the previous sibling otherwise
+    prev: Option<usize>,
+}
+
+pub struct PairingHeap<T: Ord> {
+    nodes: Vec<Node<T>>,
+    // the nodes of the popped elements, to reuse
+    free: Vec<usize>,
+    root: Option<usize>,
+    len: usize,
+}
+
+impl<T: Ord> Default for PairingHeap<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Ord> PairingHeap<T> {
+    pub fn new() -> Self {
+        PairingHeap {
+            nodes: Vec::new(),
+            free: Vec::new(),
+            root: None,
+            len: 0,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    fn key(&self, node: usize) -> &T {
+        self.nodes[node].key.as_ref().unwrap()
+    }
+
+    /// Melds two trees given their roots and returns the new root
+    fn meld(&mut self, a: Option<usize>, b: Option<usize>) -> Option<usize> {
+        let (mut a, mut b) = match (a, b) {
+            (Some(a), Some(b)) => (a, b),
+            (a, None) => return a,
+            (None, b) => return b,
+        };
+        if self.key(b) < self.key(a) {
+            std::mem::swap(&mut a, &mut b);
+        }
+        // `b` becomes the first child of `a`
+        let first_child = self.nodes[a].child;
+        self.nodes[b].sibling = first_child;
+        if let Some(c) = first_child {
+            self.nodes[c].prev = Some(b);
+        }
+        self.nodes[b].prev = Some(a);
+        self.nodes[a].child = Some(b);
+        Some(a)
+    }
+
+    /// Inserts `key` and returns its handle
+    pub fn push(&mut self, key: T) -> usize {
+        let node = Node {
+            key: Some(key),
+            child: None,
+            sibling: None,
+            prev: None,
+        };
+        let handle = match self.free.pop() {
+            Some(handle) => {
+                self.nodes[handle] = node;
+                handle
+            }
+            None => {
+                self.nodes.push(node);
+                self.nodes.len() - 1
+            }
+        };
+        self.root = self.meld(self.root, Some(handle));
+        self.len += 1;
+        handle
+    }
+
+    pub fn peek(&self) -> Option<&T> {
+        self.root.map(|root| self.key(root))
+    }
+
+    pub fn pop_min(&mut self) -> Option<T> {
+        let root = self.root?;
+        let key = self.nodes[root].key.take();
+        self.free.push(root);
+        self.len -= 1;
+
+        let mut children = Vec::new();
+        let mut next = self.nodes[root].child.take();
+        while let Some(child) = next {
+            next = self.nodes[child].sibling.take();
+            self.nodes[child].prev = None;
+            children.push(child);
+        }
+
+        // first pass: meld the children in pairs, from left to right
+        let mut paired = Vec::with_capacity(children.len().div_ceil(2));
+        for pair in children.chunks(2) {
+            let melded = self.meld(Some(pair[0]), pair.get(1).copied());
+            paired.push(melded);
+        }
+        // second pass: meld the pairs into one tree, from right to left
+        self.root = None;
+        for tree in paired.into_iter().rev() {
+            self.root = self.meld(tree, self.root);
+        }
+        key
+    }
+
+    /// Moves all the elements of `other` into this heap. The handles returned
+    /// by `other` are not valid for this heap.
+    pub fn merge(&mut self, other: PairingHeap<T>) {
+        let offset = self.nodes.len();
+        let shift = |index: Option<usize>| index.map(|i| i + offset);
+        self.nodes.extend(other.nodes.into_iter().map(|node| Node {
+            key: node.key,
+            child: shift(node.child),
+            sibling: shift(node.sibling),
+            prev: shift(node.prev),
+        }));
+        self.free.extend(other.free.into_iter().map(|i| i + offset));
+        self.root = self.meld(self.root, shift(other.root));
+        self.len += other.len;
+    }
+
+    /// Replaces the key of the element `handle` with the smaller `key`
+    ///
+    /// # Panics
+    ///
+    /// Panics if the element has already been popped or if `key` is greater
+    /// than its current key.
+    pub fn decrease_key(&mut self, handle: usize, key: T) {
+        let current = self.nodes[handle]
+            .key
+            .as_mut()
+            .expect("element already popped");
+        assert!(key <= *current, "the new key must not be greater");
+        *current = key;
+        if self.root == Some(handle) {
+            return;
+        }
+
+        // cut the subtree of `handle` from its parent, then meld it with the root
+        let prev = self.nodes[handle].prev.take().unwrap();
+        let sibling = self.nodes[handle].sibling.take();
+        if self.nodes[prev].child == Some(handle) {
+            self.nodes[prev].child = sibling;
+        } else {
+            self.nodes[prev].sibling = sibling;
+        }
+        if let Some(s) = sibling {
+            self.nodes[s].prev = Some(prev);
+        }
+        self.root = self.meld(self.root, Some(handle));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::general::Xorshift64;
+
+    fn drain<T: Ord>(heap: &mut PairingHeap<T>) -> Vec<T> {
+        std::iter::from_fn(|| heap.pop_min()).collect()
+    }
+
+    #[test]
+    fn empty_heap() {
+        let mut heap: PairingHeap<i32> = PairingHeap::new();
+        assert!(heap.is_empty());
+        assert_eq!(heap.peek(), None);
+        assert_eq!(heap.pop_min(), None);
+    }
+
+    #[test]
+    fn extraction_order_is_sorted() {
+        let mut rng = Xorshift64::new(3);
+        let mut heap = PairingHeap::new();
+        let mut values: Vec<u64> = (0..1000).map(|_| rng.gen_range(0..500)).collect();
+        for &value in &values {
+            heap.push(value);
+        }
+        assert_eq!(heap.len(), 1000);
+        assert_eq!(heap.peek(), values.iter().min());
+        values.sort_unstable();
+        assert_eq!(drain(&mut heap), values);
+        assert!(heap.is_empty());
+    }
+
+    #[test]
+    fn interleaved_push_and_pop() {
+        let mut heap = PairingHeap::new();
+        for value in [5, 3, 8] {
+            heap.push(value);
+        }
+        assert_eq!(heap.pop_min(), Some(3));
+        heap.push(1);
+        heap.push(6);
+        assert_eq!(heap.pop_min(), Some(1));
+        assert_eq!(heap.pop_min(), Some(5));
+        heap.push(2);
+        assert_eq!(drain(&mut heap), vec![2, 6, 8]);
+    }
+
+    #[test]
+    fn merge_combines_heaps() {
+        let mut a = PairingHeap::new();
+        let mut b = PairingHeap::new();
+        for value in [10, 4, 7, 1] {
+            a.push(value);
+        }
+        for value in [3, 9, 2, 8] {
+            b.push(value);
+        }
+        a.pop_min();
+        b.pop_min();
+        a.merge(b);
+        assert_eq!(a.len(), 6);
+        assert_eq!(a.peek(), Some(&3));
+        assert_eq!(drain(&mut a), vec![3, 4, 7, 8, 9, 10]);
+
+        let mut c = PairingHeap::new();
+        c.merge(PairingHeap::new());
+        assert!(c.is_empty());
+        let mut d = PairingHeap::new();
+        d.push("x");
+        c.merge(d);
+        assert_eq!(c.pop_min(), Some("x"));
+    }
+
+    #[test]
+    fn decrease_key_moves_to_front() {
+        let mut heap = PairingHeap::new();
+        let handles: Vec<usize> = [50, 40, 30, 20, 10].iter().map(|&v| heap.push(v)).collect();
+        // makes the tree deeper than a single level
+        assert_eq!(heap.pop_min(), Some(10));
+        heap.decrease_key(handles[0], 5);
+        assert_eq!(heap.peek(), Some(&5));
+        // not enough to become the minimum
+        heap.decrease_key(handles[1], 35);
+        assert_eq!(heap.peek(), Some(&5));
+        // decreasing the current minimum
+        heap.decrease_key(handles[0], 1);
+        assert_eq!(drain(&mut heap), vec![1, 20, 30, 35]);
+    }
+
+    #[test]
+    fn random_decrease_keys_against_sorting() {
+        let mut rng = Xorshift64::new(4);
+        let mut heap = PairingHeap::new();
+        let mut keys: Vec<u64> = (0..300).map(|_| rng.gen_range(1000..2000)).collect();
+        let handles: Vec<usize> = keys.iter().map(|&key| heap.push(key)).collect();
+        for _ in 0..600 {
+            let i = rng.gen_range(0..300) as usize;
+            keys[i] -= rng.gen_range(0..keys[i] / 2 + 1);
+            heap.decrease_key(handles[i], keys[i]);
+        }
+        keys.sort_unstable();
+        assert_eq!(drain(&mut heap), keys);
+    }
+
+    #[test]
+    fn popped_nodes_are_reused() {
+        let mut rng = Xorshift64::new(219);
+        let mut heap = PairingHeap::new();
+        for _ in 0..10_000 {
+            for _ in 0..rng.gen_range(0..4) {
+                let handle = heap.push(rng.gen_range(100..200));
+                heap.decrease_key(handle, rng.gen_range(0..100));
+            }
+            while heap.len() > 8 {
+                heap.pop_min();
+            }
+        }
+        // never more than 8 elements, and 3 more before the pops
+        assert!(heap.nodes.len() <= 11, "{}", heap.nodes.len());
+
+        // a reused node belongs to the new element
+        let mut heap = PairingHeap::new();
+        let first = heap.push(5);
+        heap.push(7);
+        assert_eq!(heap.pop_min(), Some(5));
+        let second = heap.push(9);
+        assert_eq!(second, first);
+        heap.decrease_key(second, 6);
+        assert_eq!(drain(&mut heap), vec![6, 7]);
+    }
+
+    #[test]
+    fn merge_keeps_the_free_nodes() {
+        let mut a = PairingHeap::new();
+        let mut b = PairingHeap::new();
+        a.push(1);
+        b.push(2);
+        b.push(3);
+        b.pop_min();
+        a.merge(b);
+        a.push(4);
+        assert_eq!(a.nodes.len(), 3);
+        assert_eq!(drain(&mut a), vec![1, 3, 4]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn increasing_a_key_panics() {
+        let mut heap = PairingHeap::new();
+        let handle = heap.push(1);
+        heap.decrease_key(handle, 2);
+    }
+}
@@ -0,0 +1,150 @@
+/*
+    A 2D prefix sum (also known as a summed-area table or an integral image)
+    answers queries of the sum of any rectangular submatrix of a grid.
+
+    sums[r][c] holds the sum of the cells above and to the left of (r, c), i.e.
+    of grid[..r][..c]. The sum of a submatrix is then obtained by
+    inclusion-exclusion of the four prefixes at its corners.
+
+    Precomputation complexity: O(rows * cols)
+    Query complexity: O(1)
+
+    Wikipedia: <https://en.wikipedia.org/wiki/Summed-area_table>
+*/
+
+pub struct PrefixSum2D {
+    // one more row and column than the grid, filled with zeros, so that the
+    // queries touching the top or left border need no special case
+    sums: Vec<Vec<i64>>,
+}
+
+impl PrefixSum2D {
+    /// Precomputes the prefix sums of `grid`, whose rows must all have the
+    /// same length
+    pub fn new(grid: &[Vec<i64>]) -> Self {
+        let rows = grid.len();
+        let cols = grid.first().map_or(0, Vec::len);
+        let mut sums = vec![vec![0; cols + 1]; rows + 1];
+        for (r, row) in grid.iter().enumerate() {
+            assert_eq!(
+                row.len(),
+                cols,
+                "the rows of the grid have different lengths"
+            );
+            for (c, &value) in row.iter().enumerate() {
+                sums[r + 1][c + 1] = value + sums[r][c + 1] + sums[r + 1][c] - sums[r][c];
+            }
+        }
+        PrefixSum2D { sums }
+    }
+
+    pub fn rows(&self) -> usize {
+        self.sums.len() - 1
+    }
+
+    pub fn cols(&self) -> usize {
+        self.sums[0].len() - 1
+    }
+
+    /// Returns the sum of the cells (r, c) with `r1 <= r <= r2` and
+    /// `c1 <= c <= c2`.
+    ///
+    /// Panics if the corners are out of the grid, or if `r1 > r2` or `c1 > c2`.
+    pub fn query(&self, r1: usize, c1: usize, r2: usize, c2: usize) -> i64 {
+        assert!(
+            r1 <= r2 && c1 <= c2 && r2 < self.rows() && c2 < self.cols(),
+            "invalid submatrix ({r1}, {c1}) to ({r2}, {c2})"
+        );
+        self.sums[r2 + 1][c2 + 1] - self.sums[r1][c2 + 1] - self.sums[r2 + 1][c1]
+            + self.sums[r1][c1]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::general::Xorshift64;
+
+    fn brute_force(grid: &[Vec<i64>], r1: usize, c1: usize, r2: usize, c2: usize) -> i64 {
+        grid[r1..=r2]
+            .iter()
+            .map(|row| row[c1..=c2].iter().sum::<i64>())
+            .sum()
+    }
+
+    fn random_grid(rng: &mut Xorshift64, rows: usize, cols: usize) -> Vec<Vec<i64>> {
+        (0..rows)
+            .map(|_| {
+                (0..cols)
+                    .map(|_| rng.gen_range(0..2001) as i64 - 1000)
+                    .collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn small_grid() {
+        let grid = vec![vec![1, 2, 3], vec![4, 5, 6], vec![7, 8, 9]];
+        let prefix = PrefixSum2D::new(&grid);
+        assert_eq!(prefix.query(0, 0, 2, 2), 45);
+        assert_eq!(prefix.query(1, 1, 2, 2), 28);
+        assert_eq!(prefix.query(0, 1, 1, 2), 16);
+        assert_eq!(prefix.query(2, 0, 2, 2), 24);
+        assert_eq!(prefix.query(1, 1, 1, 1), 5);
+    }
+
+    #[test]
+    fn all_queries_on_random_grids() {
+        let mut rng = Xorshift64::new(240);
+        for (rows, cols) in [(1, 1), (1, 7), (6, 1), (5, 8), (9, 9)] {
+            let grid = random_grid(&mut rng, rows, cols);
+            let prefix = PrefixSum2D::new(&grid);
+            assert_eq!((prefix.rows(), prefix.cols()), (rows, cols));
+            // every submatrix, so every single cell, the full grid and
+            // those touching the top and left borders too
+            for r1 in 0..rows {
+                for r2 in r1..rows {
+                    for c1 in 0..cols {
+                        for c2 in c1..cols {
+                            assert_eq!(
+                                prefix.query(r1, c1, r2, c2),
+                                brute_force(&grid, r1, c1, r2, c2)
+                            );
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn border_queries() {
+        let mut rng = Xorshift64::new(7);
+        let grid = random_grid(&mut rng, 20, 30);
+        let prefix = PrefixSum2D::new(&grid);
+        let total: i64 = grid.iter().flatten().sum();
+        assert_eq!(prefix.query(0, 0, 19, 29), total);
+        assert_eq!(prefix.query(0, 0, 0, 0), grid[0][0]);
+        assert_eq!(prefix.query(19, 29, 19, 29), grid[19][29]);
+        assert_eq!(prefix.query(0, 5, 19, 5), brute_force(&grid, 0, 5, 19, 5));
+        assert_eq!(prefix.query(3, 0, 3, 29), grid[3].iter().sum::<i64>());
+    }
+
+    #[test]
+    #[should_panic]
+    fn out_of_grid() {
+        PrefixSum2D::new(&[vec![1, 2], vec![3, 4]]).query(0, 0, 2, 1);
+    }
+
+    #[test]
+    #[should_panic]
+    fn reversed_corners() {
+        PrefixSum2D::new(&[vec![1, 2], vec![3, 4]]).query(1, 0, 0, 1);
+    }
+
+    #[test]
+    #[should_panic]
+    fn ragged_grid() {
+        PrefixSum2D::new(&[vec![1, 2], vec![3]]);
+    }
+}
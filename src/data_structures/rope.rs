@@ -0,0 +1,415 @@
+/*
+    A rope stores a string as a binary tree whose leaves hold its consecutive
+    chunks, and whose inner nodes hold the number of characters below them.
+    Editing the string cuts and joins trees instead of moving its characters,
+    so that inserting into or deleting from a huge text costs no more than a
+    few tree walks.
+
+    The tree is kept balanced as an AVL tree: the heights of the two children
+    of a node differ by at most one. Joining two trees walks down the taller
+    one until the heights match and rebalances the path on the way back, so
+    splits and joins keep the height logarithmic.
+
+    Positions are counted in characters, not bytes.
+
+    Concat, split, insert, delete and char_at complexity: O(log n), plus the
+    length of the inserted text
+*/
+
+use std::fmt;
+use std::ops::Range;
+
+// the maximum number of characters in a leaf, where small leaves are merged
+const LEAF_CHARS: usize = 64;
+
+enum Node {
+    Leaf(String),
+    Branch {
+        left: Box<Node>,
+        right: Box<Node>,
+        // the number of characters in the subtree
+        len: usize,
+        height: usize,
+    },
+}
+
+type Tree = Option<Box<Node>>;
+
+#[derive(Default)]
+pub struct Rope {
+    root: Tree,
+}
+
+impl Rope {
+    pub fn new() -> Self {
+        Rope { root: None }
+    }
+
+    /// Returns the number of characters of the rope
+    pub fn len(&self) -> usize {
+        self.root.as_deref().map_or(0, len)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.root.is_none()
+    }
+
+    /// Appends `other` to the rope
+    pub fn concat(&mut self, other: Rope) {
+        self.root = join(self.root.take(), other.root);
+    }
+
+    /// Splits the rope at the character `at`, keeping the characters before
+    /// it and returning the others.
+    ///
+    /// Panics if `at` is greater than the length of the rope.
+    pub fn split(&mut self, at: usize) -> Rope {
+        assert!(at <= self.len(), "split position out of bounds");
+        let (left, right) = match self.root.take() {
+            Some(root) => split(root, at),
+            None => (None, None),
+        };
+        self.root = left;
+        Rope { root: right }
+    }
+
+    /// Inserts `text` before the character `at`.
+    ///
+    /// Panics if `at` is greater than the length of the rope.
+    pub fn insert(&mut self, at: usize, text: &str) {
+        let rest = self.split(at);
+        self.concat(Rope::from(text));
+        self.concat(rest);
+    }
+
+    /// Removes the characters in `range`.
+    ///
+    /// Panics if the range is decreasing or ends past the end of the rope.
+    pub fn delete(&mut self, range: Range<usize>) {
+        assert!(range.start <= range.end, "decreasing range");
+        let mut rest = self.split(range.start);
+        let tail = rest.split(range.end - range.start);
+        self.concat(tail);
+    }
+
+    /// Returns the character at `index`, or `None` past the end of the rope
+    pub fn char_at(&self, mut index: usize) -> Option<char> {
+        let mut node = self.root.as_deref()?;
+        loop {
+            match node {
+                Node::Leaf(text) => return text.chars().nth(index),
+                Node::Branch { left, right, .. } => {
+                    let left_len = len(left);
+                    if index < left_len {
+                        node = left;
+                    } else {
+                        index -= left_len;
+                        node = right;
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl From<&str> for Rope {
+    /// Builds a balanced rope of the chunks of `text`
+    fn from(text: &str) -> Self {
+        let mut chunks = Vec::new();
+        let mut rest = text;
+        while !rest.is_empty() {
+            let end = rest
+                .char_indices()
+                .nth(LEAF_CHARS)
+                .map_or(rest.len(), |(end, _)| end);
+            chunks.push(rest[..end].to_string());
+            rest = &rest[end..];
+        }
+        let count = chunks.len();
+        Rope {
+            root: build(&mut chunks.into_iter(), count),
+        }
+    }
+}
+
+impl fmt::Display for Rope {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fn write_node(node: &Node, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            match node {
+                Node::Leaf(text) => f.write_str(text),
+                Node::Branch { left, right, .. } => {
+                    write_node(left, f)?;
+                    write_node(right, f)
+                }
+            }
+        }
+        match &self.root {
+            Some(root) => write_node(root, f),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Builds a perfectly balanced tree of the next `count` chunks
+fn build(chunks: &mut impl Iterator<Item = String>, count: usize) -> Tree {
+    match count {
+        0 => None,
+        1 => chunks.next().map(|text| Box::new(Node::Leaf(text))),
+        _ => {
+            let left = build(chunks, count / 2)?;
+            let right = build(chunks, count - count / 2)?;
+            Some(branch(left, right))
+        }
+    }
+}
+
+fn len(node: &Node) -> usize {
+    match node {
+        Node::Leaf(text) => text.chars().count(),
+        Node::Branch { len, .. } => *len,
+    }
+}
+
+fn height(node: &Node) -> usize {
+    match node {
+        Node::Leaf(_) => 0,
+        Node::Branch { height, .. } => *height,
+    }
+}
+
+fn branch(left: Box<Node>, right: Box<Node>) -> Box<Node> {
+    Box::new(Node::Branch {
+        len: len(&left) + len(&right),
+        height: height(&left).max(height(&right)) + 1,
+        left,
+        right,
+    })
+}
+
+/// Returns the children of a branch
+fn children(node: Node) -> (Box<Node>, Box<Node>) {
+    match node {
+        Node::Branch { left, right, .. } => (left, right),
+        Node::Leaf(_) => unreachable!("a leaf is never taller than another node"),
+    }
+}
+
+/// Joins two balanced trees whose heights differ by at most two into a
+/// balanced tree, with a single or a double rotation
+fn balance(left: Box<Node>, right: Box<Node>) -> Box<Node> {
+    if height(&left) > height(&right) + 1 {
+        let (outer, inner) = children(*left);
+        if height(&outer) >= height(&inner) {
+            branch(outer, branch(inner, right))
+        } else {
+            let (inner_left, inner_right) = children(*inner);
+            branch(branch(outer, inner_left), branch(inner_right, right))
+        }
+    } else if height(&right) > height(&left) + 1 {
+        let (inner, outer) = children(*right);
+        if height(&outer) >= height(&inner) {
+            branch(branch(left, inner), outer)
+        } else {
+            let (inner_left, inner_right) = children(*inner);
+            branch(branch(left, inner_left), branch(inner_right, outer))
+        }
+    } else {
+        branch(left, right)
+    }
+}
+
+/// Concatenates two balanced trees, in O(difference of their heights)
+fn join_nodes(left: Box<Node>, right: Box<Node>) -> Box<Node> {
+    if let (Node::Leaf(a), Node::Leaf(b)) = (left.as_ref(), right.as_ref()) {
+        if a.chars().count() + b.chars().count() <= LEAF_CHARS {
+            return Box::new(Node::Leaf(format!("{a}{b}")));
+        }
+    }
+    // the taller tree takes the other one down its inner side until their
+    // heights are close
+    if height(&left) > height(&right) + 1 {
+        let (outer, inner) = children(*left);
+        balance(outer, join_nodes(inner, right))
+    } else if height(&right) > height(&left) + 1 {
+        let (inner, outer) = children(*right);
+        balance(join_nodes(left, inner), outer)
+    } else {
+        branch(left, right)
+    }
+}
+
+fn join(left: Tree, right: Tree) -> Tree {
+    match (left, right) {
+        (Some(left), Some(right)) => Some(join_nodes(left, right)),
+        (left, None) => left,
+        (None, right) => right,
+    }
+}
+
+/// Splits a tree into the trees of its first `at` characters and of the
+/// others
+fn split(node: Box<Node>, at: usize) -> (Tree, Tree) {
+    if at == 0 {
+        return (None, Some(node));
+    }
+    if at >= len(&node) {
+        return (Some(node), None);
+    }
+    match *node {
+        Node::Leaf(mut text) => {
+            let (end, _) = text.char_indices().nth(at).unwrap();
+            let rest = text.split_off(end);
+            (
+                Some(Box::new(Node::Leaf(text))),
+                Some(Box::new(Node::Leaf(rest))),
+            )
+        }
+        Node::Branch { left, right, .. } => {
+            let left_len = len(&left);
+            if at <= left_len {
+                let (before, after) = split(left, at);
+                (before, join(after, Some(right)))
+            } else {
+                let (before, after) = split(right, at - left_len);
+                (join(Some(left), before), after)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::general::Xorshift64;
+
+    /// Checks the lengths, the heights and the balance of the tree, and
+    /// returns its height
+    fn check(node: &Node) -> usize {
+        match node {
+            Node::Leaf(text) => {
+                assert!(!text.is_empty() && text.chars().count() <= LEAF_CHARS);
+                0
+            }
+            Node::Branch {
+                left,
+                right,
+                len: length,
+                height: node_height,
+            } => {
+                let (left_height, right_height) = (check(left), check(right));
+                assert!(left_height.abs_diff(right_height) <= 1, "unbalanced");
+                assert_eq!(*length, len(left) + len(right));
+                assert_eq!(*node_height, left_height.max(right_height) + 1);
+                *node_height
+            }
+        }
+    }
+
+    fn check_rope(rope: &Rope) -> usize {
+        rope.root.as_deref().map_or(0, check)
+    }
+
+    #[test]
+    fn basic_operations() {
+        let mut rope = Rope::from("hello world");
+        assert_eq!(rope.len(), 11);
+        rope.insert(5, ",");
+        rope.insert(12, "!");
+        assert_eq!(rope.to_string(), "hello, world!");
+        assert_eq!(rope.char_at(7), Some('w'));
+        assert_eq!(rope.char_at(13), None);
+
+        let world = rope.split(7);
+        assert_eq!(rope.to_string(), "hello, ");
+        assert_eq!(world.to_string(), "world!");
+        rope.concat(world);
+        rope.delete(0..7);
+        assert_eq!(rope.to_string(), "world!");
+        rope.delete(0..6);
+        assert!(rope.is_empty());
+        assert_eq!(rope.char_at(0), None);
+    }
+
+    #[test]
+    fn positions_count_characters() {
+        let mut rope = Rope::from("naïve café");
+        assert_eq!(rope.len(), 10);
+        assert_eq!(rope.char_at(2), Some('ï'));
+        rope.insert(10, " ☕");
+        rope.delete(2..3);
+        assert_eq!(rope.to_string(), "nave café ☕");
+        assert_eq!(rope.split(9).to_string(), " ☕");
+    }
+
+    #[test]
+    fn random_edits_match_a_string() {
+        let mut rng = Xorshift64::new(299);
+        let alphabet: Vec<char> = "abcdefghij é→".chars().collect();
+        let random_text = |rng: &mut Xorshift64, max_len: u64| -> String {
+            (0..rng.gen_range(0..max_len))
+                .map(|_| alphabet[rng.gen_range(0..alphabet.len() as u64) as usize])
+                .collect()
+        };
+        let initial = random_text(&mut rng, 2000);
+        let mut rope = Rope::from(initial.as_str());
+        let mut reference: Vec<char> = initial.chars().collect();
+
+        for _ in 0..3000 {
+            let at = rng.gen_range(0..reference.len() as u64 + 1) as usize;
+            match rng.gen_range(0..4) {
+                0 => {
+                    let text = random_text(&mut rng, 200);
+                    rope.insert(at, &text);
+                    reference.splice(at..at, text.chars());
+                }
+                1 => {
+                    let end = rng.gen_range(at as u64..reference.len() as u64 + 1) as usize;
+                    rope.delete(at..end);
+                    reference.drain(at..end);
+                }
+                2 => {
+                    // move the tail to the front
+                    let tail = rope.split(at);
+                    let mut moved = tail;
+                    moved.concat(rope);
+                    rope = moved;
+                    reference.rotate_left(at);
+                }
+                _ => assert_eq!(rope.char_at(at), reference.get(at).copied()),
+            }
+            assert_eq!(rope.len(), reference.len());
+            check_rope(&rope);
+        }
+        assert_eq!(rope.to_string(), reference.iter().collect::<String>());
+    }
+
+    #[test]
+    fn stays_balanced() {
+        // appending one character at a time still gives a logarithmic height
+        let mut rope = Rope::new();
+        for i in 0..100_000 {
+            rope.insert(rope.len(), if i % 2 == 0 { "ab" } else { "c" });
+        }
+        assert_eq!(rope.len(), 150_000);
+        // about 2300 leaves
+        assert!(check_rope(&rope) <= 17, "{}", check_rope(&rope));
+
+        // and so does joining ropes of very different lengths
+        let mut rope = Rope::from("x".repeat(100_000).as_str());
+        for _ in 0..1000 {
+            rope.concat(Rope::from("yz"));
+            let mut front = Rope::from("w");
+            front.concat(rope);
+            rope = front;
+        }
+        check_rope(&rope);
+        assert_eq!(rope.char_at(0), Some('w'));
+        assert_eq!(rope.char_at(rope.len() - 1), Some('z'));
+    }
+
+    #[test]
+    #[should_panic]
+    fn split_past_the_end() {
+        Rope::from("abc").split(4);
+    }
+}
@@ -0,0 +1,345 @@
+use std::{cmp::Ordering, iter::FromIterator};
+
+/// An internal node of a `SplayTree`.
+struct SplayNode<T: Ord> {
+    value: T,
+    left: Option<Box<SplayNode<T>>>,
+    right: Option<Box<SplayNode<T>>>,
+}
+
+/// A set based on a splay tree.
+///
+/// A splay tree is a self-adjusting binary search tree: every access moves the
+/// accessed value to the root by rotations, the splay, which also roughly
+/// halves the depth of the nodes on its path. It keeps no balance information,
+/// yet its operations take O(log n) amortized, and the values accessed often
+/// stay near the root, so that skewed access patterns are faster than in a
+/// balanced tree.
+///
+/// As its lookups restructure the tree, `contains` takes `&mut self`.
+///
+/// Wikipedia: <https://en.wikipedia.org/wiki/Splay_tree>
+pub struct SplayTree<T: Ord> {
+    root: Option<Box<SplayNode<T>>>,
+    length: usize,
+}
+
+impl<T: Ord> SplayTree<T> {
+    pub fn new() -> SplayTree<T> {
+        SplayTree {
+            root: None,
+            length: 0,
+        }
+    }
+
+    /// Returns `true` if the tree contains a value, which is then splayed to
+    /// the root.
+    pub fn contains(&mut self, value: &T) -> bool {
+        self.root = splay(self.root.take(), value);
+        self.root.as_ref().is_some_and(|root| root.value == *value)
+    }
+
+    /// Adds a value to the tree, at the root.
+    ///
+    /// Returns `true` if the tree did not yet contain the value.
+    pub fn insert(&mut self, value: T) -> bool {
+        let mut node = Box::new(SplayNode {
+            value,
+            left: None,
+            right: None,
+        });
+        // the root after the splay is the neighbor of the value, and the new
+        // node goes between its subtrees
+        if let Some(mut root) = splay(self.root.take(), &node.value) {
+            match node.value.cmp(&root.value) {
+                Ordering::Equal => {
+                    self.root = Some(root);
+                    return false;
+                }
+                Ordering::Less => {
+                    node.left = root.left.take();
+                    node.right = Some(root);
+                }
+                Ordering::Greater => {
+                    node.right = root.right.take();
+                    node.left = Some(root);
+                }
+            }
+        }
+        self.root = Some(node);
+        self.length += 1;
+        true
+    }
+
+    /// Removes a value from the tree.
+    ///
+    /// Returns `true` if the tree contained the value.
+    pub fn remove(&mut self, value: &T) -> bool {
+        self.root = splay(self.root.take(), value);
+        let Some(mut root) = self.root.take() else {
+            return false;
+        };
+        if root.value != *value {
+            self.root = Some(root);
+            return false;
+        }
+        // splaying the value in the left subtree, where all the values are
+        // smaller, brings its largest value to its root, with no right child
+        self.root = match splay(root.left.take(), value) {
+            Some(mut left) => {
+                left.right = root.right.take();
+                Some(left)
+            }
+            None => root.right.take(),
+        };
+        self.length -= 1;
+        true
+    }
+
+    /// Returns the number of values in the tree.
+    pub fn len(&self) -> usize {
+        self.length
+    }
+
+    /// Returns `true` if the tree contains no values.
+    pub fn is_empty(&self) -> bool {
+        self.length == 0
+    }
+
+    /// Returns an iterator that visits the values in the tree in ascending
+    /// order, without splaying them.
+    pub fn iter(&self) -> Iter<'_, T> {
+        let mut iter = Iter { stack: Vec::new() };
+        iter.push_left_path(&self.root);
+        iter
+    }
+}
+
+/// Splays the node of `value`, or the last node on its search path if
+/// `value` is not in the tree, to the root of `tree`, and returns the new
+/// root.
+///
+/// The splay is top-down: the nodes on the search path are split into the
+/// ones smaller than `value`, which form a left tree, and the larger ones,
+/// which form a right tree, rotating the path when it goes twice in the same
+/// direction. The left and right trees are then hung from the final root.
+fn splay<T: Ord>(tree: Option<Box<SplayNode<T>>>, value: &T) -> Option<Box<SplayNode<T>>> {
+    let mut root = tree?;
+    // the roots of the subtrees cut from the path, in increasing order for the
+    // left tree, and in decreasing order for the right tree
+    let mut smaller: Vec<Box<SplayNode<T>>> = Vec::new();
+    let mut larger: Vec<Box<SplayNode<T>>> = Vec::new();
+    loop {
+        match value.cmp(&root.value) {
+            Ordering::Less => {
+                let Some(mut left) = root.left.take() else {
+                    break;
+                };
+                if *value < left.value {
+                    // zig-zig: rotate right
+                    root.left = left.right.take();
+                    left.right = Some(root);
+                    root = left;
+                    match root.left.take() {
+                        Some(next) => left = next,
+                        None => break,
+                    }
+                }
+                larger.push(root);
+                root = left;
+            }
+            Ordering::Greater => {
+                let Some(mut right) = root.right.take() else {
+                    break;
+                };
+                if *value > right.value {
+                    // zig-zig: rotate left
+                    root.right = right.left.take();
+                    right.left = Some(root);
+                    root = right;
+                    match root.right.take() {
+                        Some(next) => right = next,
+                        None => break,
+                    }
+                }
+                smaller.push(root);
+                root = right;
+            }
+            Ordering::Equal => break,
+        }
+    }
+
+    // each node of the left tree is the right child of the previous one, the
+    // last one taking the left subtree of the root
+    let mut left = root.left.take();
+    while let Some(mut node) = smaller.pop() {
+        node.right = left;
+        left = Some(node);
+    }
+    let mut right = root.right.take();
+    while let Some(mut node) = larger.pop() {
+        node.left = right;
+        right = Some(node);
+    }
+    root.left = left;
+    root.right = right;
+    Some(root)
+}
+
+impl<T: Ord> Default for SplayTree<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Ord> FromIterator<T> for SplayTree<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut tree = SplayTree::new();
+        for value in iter {
+            tree.insert(value);
+        }
+        tree
+    }
+}
+
+impl<T: Ord> Drop for SplayTree<T> {
+    fn drop(&mut self) {
+        // The tree can be a path as deep as its length, which dropping the
+        // nodes recursively would overflow the stack on
+        let mut stack: Vec<Box<SplayNode<T>>> = self.root.take().into_iter().collect();
+        while let Some(mut node) = stack.pop() {
+            stack.extend(node.left.take());
+            stack.extend(node.right.take());
+        }
+    }
+}
+
+/// An iterator over the items of a `SplayTree`.
+///
+/// This struct is created by the `iter` method of `SplayTree`.
+pub struct Iter<'a, T: Ord> {
+    stack: Vec<&'a SplayNode<T>>,
+}
+
+impl<'a, T: Ord> Iter<'a, T> {
+    fn push_left_path(&mut self, mut child: &'a Option<Box<SplayNode<T>>>) {
+        while let Some(node) = child {
+            self.stack.push(node.as_ref());
+            child = &node.left;
+        }
+    }
+}
+
+impl<'a, T: Ord> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        let node = self.stack.pop()?;
+        self.push_left_path(&node.right);
+        Some(&node.value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::general::Xorshift64;
+    use std::collections::BTreeSet;
+
+    /// Returns the depth of `value` in `tree`, 0 at the root, without
+    /// splaying it
+    fn depth<T: Ord>(tree: &SplayTree<T>, value: &T) -> Option<usize> {
+        let mut current = &tree.root;
+        let mut depth = 0;
+        while let Some(node) = current {
+            current = match value.cmp(&node.value) {
+                Ordering::Equal => return Some(depth),
+                Ordering::Less => &node.left,
+                Ordering::Greater => &node.right,
+            };
+            depth += 1;
+        }
+        None
+    }
+
+    #[test]
+    fn basic_operations() {
+        let mut tree = SplayTree::new();
+        assert!(tree.is_empty());
+        assert!(!tree.contains(&1));
+        assert!(!tree.remove(&1));
+        assert!(tree.insert(5));
+        assert!(tree.insert(2));
+        assert!(tree.insert(8));
+        assert!(!tree.insert(5));
+        assert_eq!(tree.len(), 3);
+        assert!(tree.contains(&2));
+        assert!(!tree.contains(&3));
+        assert!(tree.remove(&5));
+        assert!(!tree.remove(&5));
+        assert_eq!(tree.iter().collect::<Vec<_>>(), vec![&2, &8]);
+    }
+
+    #[test]
+    fn random_operations_against_btree_set() {
+        let mut rng = Xorshift64::new(295);
+        let mut tree = SplayTree::new();
+        let mut reference = BTreeSet::new();
+        for _ in 0..5000 {
+            let value = rng.gen_range(0..200);
+            let result = match rng.gen_range(0..3) {
+                0 => (tree.insert(value), reference.insert(value)),
+                1 => (tree.remove(&value), reference.remove(&value)),
+                _ => (tree.contains(&value), reference.contains(&value)),
+            };
+            assert_eq!(result.0, result.1, "{value}");
+            assert_eq!(tree.len(), reference.len());
+        }
+        assert!(tree.iter().eq(reference.iter()));
+    }
+
+    #[test]
+    fn accessed_values_move_to_the_root() {
+        // inserting in order leaves a path
+        let mut tree: SplayTree<u32> = (0..1000).collect();
+        assert_eq!(depth(&tree, &0), Some(999));
+        assert!(tree.contains(&0));
+        assert_eq!(depth(&tree, &0), Some(0));
+        // and the splay roughly halved the depths along it
+        assert!(depth(&tree, &1).unwrap() < 600);
+
+        // repeated accesses keep a value near the root, among other accesses
+        let mut rng = Xorshift64::new(2950);
+        let mut depths = vec![];
+        for _ in 0..200 {
+            tree.contains(&42);
+            for _ in 0..3 {
+                tree.contains(&(rng.gen_range(0..1000) as u32));
+            }
+            depths.push(depth(&tree, &42).unwrap());
+        }
+        let mean = depths.iter().sum::<usize>() as f64 / depths.len() as f64;
+        // where a balanced tree would have it at depth 9 or so
+        assert!(mean < 6.0, "{mean}");
+        assert!(tree.iter().copied().eq(0..1000));
+    }
+
+    #[test]
+    fn remove_everything() {
+        let mut tree: SplayTree<i32> = [3, 1, 4, 1, 5, 9, 2, 6].into_iter().collect();
+        assert_eq!(tree.len(), 7);
+        for value in [9, 1, 5, 2, 6, 3, 4] {
+            assert!(tree.remove(&value));
+        }
+        assert!(tree.is_empty());
+        assert_eq!(tree.iter().next(), None);
+    }
+
+    #[test]
+    fn drop_a_deep_tree() {
+        // sorted inserts build a path of all the values to the left of the root
+        let tree: SplayTree<u32> = (0..1_000_000).collect();
+        assert_eq!(depth(&tree, &0), Some(999_999));
+        drop(tree);
+    }
+}
@@ -0,0 +1,264 @@
+/*
+    A t-digest estimates the quantiles of a stream of numbers in bounded
+    memory, rather than storing and sorting the whole stream.
+
+    It summarizes the stream as sorted centroids, each the mean of a run of
+    neighboring values with its weight, the number of values it stands for.
+    The added values are buffered, and the full buffer is merged into the
+    centroids: a centroid absorbs its right neighbor while the quantiles they
+    cover span less than one unit of the scale function
+    k(q) = δ / (2π) asin(2q - 1), which is steep near 0 and 1. The centroids
+    are thus small at the tails, where the quantiles need more precision, and
+    at most about δ, the compression, are left. A quantile is interpolated
+    between the centroids around its rank.
+
+    This is a simplified version of the merging t-digest, with a single scale
+    function and no tuning of the buffer.
+
+    Add complexity: O(log(δ)) amortized
+    Quantile complexity: O(δ log(δ))
+    Memory: O(δ)
+
+    Paper: <https://arxiv.org/abs/1902.04023>
+*/
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Centroid {
+    mean: f64,
+    weight: f64,
+}
+
+#[derive(Clone, Debug)]
+pub struct TDigest {
+    compression: f64,
+    centroids: Vec<Centroid>,
+    // the values added since the last merge
+    buffer: Vec<f64>,
+    count: u64,
+    min: f64,
+    max: f64,
+}
+
+impl TDigest {
+    /// Creates an empty digest keeping about `compression` centroids: the
+    /// more, the more precise the quantiles. 100 is a common choice.
+    ///
+    /// Panics if `compression` is less than 1.
+    pub fn new(compression: f64) -> Self {
+        assert!(compression >= 1.0, "the compression must be at least 1");
+        TDigest {
+            compression,
+            centroids: vec![],
+            buffer: Vec::with_capacity(Self::buffer_capacity(compression)),
+            count: 0,
+            min: f64::INFINITY,
+            max: f64::NEG_INFINITY,
+        }
+    }
+
+    fn buffer_capacity(compression: f64) -> usize {
+        5 * compression.ceil() as usize
+    }
+
+    /// Adds `value` to the stream.
+    ///
+    /// Panics if `value` is NaN.
+    pub fn add(&mut self, value: f64) {
+        assert!(!value.is_nan(), "cannot add NaN to the digest");
+        self.count += 1;
+        self.min = self.min.min(value);
+        self.max = self.max.max(value);
+        self.buffer.push(value);
+        if self.buffer.len() >= Self::buffer_capacity(self.compression) {
+            self.centroids = self.merged();
+            self.buffer.clear();
+        }
+    }
+
+    /// Returns the number of values added
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+
+    /// Returns the number of centroids and buffered values stored, which is
+    /// at most about 6 times the compression however long the stream is
+    pub fn size(&self) -> usize {
+        self.centroids.len() + self.buffer.len()
+    }
+
+    /// Returns the position k(q) of the quantile `q` on the scale
+    fn scale(&self, q: f64) -> f64 {
+        self.compression / (2.0 * std::f64::consts::PI) * (2.0 * q - 1.0).asin()
+    }
+
+    /// Returns the quantile of position `k` on the scale, the inverse of
+    /// `scale`
+    fn inverse_scale(&self, k: f64) -> f64 {
+        let k = k.min(self.compression / 4.0);
+        ((2.0 * std::f64::consts::PI * k / self.compression).sin() + 1.0) / 2.0
+    }
+
+    /// Returns the centroids with the buffered values merged in
+    fn merged(&self) -> Vec<Centroid> {
+        let mut all = self.centroids.clone();
+        all.extend(
+            self.buffer
+                .iter()
+                .map(|&mean| Centroid { mean, weight: 1.0 }),
+        );
+        all.sort_by(|a, b| a.mean.total_cmp(&b.mean));
+        let total = self.count as f64;
+
+        let mut merged: Vec<Centroid> = Vec::with_capacity(all.len());
+        let mut values = all.into_iter();
+        let Some(mut current) = values.next() else {
+            return merged;
+        };
+        // the weight of the centroids before `current`
+        let mut before = 0.0;
+        let mut limit = self.inverse_scale(self.scale(0.0) + 1.0) * total;
+        for next in values {
+            if before + current.weight + next.weight <= limit {
+                let weight = current.weight + next.weight;
+                current.mean += (next.mean - current.mean) * next.weight / weight;
+                current.weight = weight;
+            } else {
+                before += current.weight;
+                merged.push(current);
+                limit = self.inverse_scale(self.scale(before / total) + 1.0) * total;
+                current = next;
+            }
+        }
+        merged.push(current);
+        merged
+    }
+
+    /// Returns an estimate of the quantile `q` of the stream, the value
+    /// below which a fraction `q` of the values lie: 0.5 for the median. The
+    /// quantiles 0 and 1 are the exact minimum and maximum. An empty digest
+    /// has NaN quantiles.
+    ///
+    /// Panics if `q` is not in [0, 1].
+    pub fn quantile(&self, q: f64) -> f64 {
+        assert!((0.0..=1.0).contains(&q), "the quantile must be in [0, 1]");
+        if self.count == 0 {
+            return f64::NAN;
+        }
+        let centroids = self.merged();
+        let rank = q * self.count as f64;
+
+        // each centroid stands at the middle of the ranks it covers, the
+        // minimum at rank 0 and the maximum at the count
+        let mut previous = (0.0, self.min);
+        let mut cumulative = 0.0;
+        for centroid in &centroids {
+            let middle = cumulative + centroid.weight / 2.0;
+            if rank < middle {
+                return interpolate(previous, (middle, centroid.mean), rank);
+            }
+            previous = (middle, centroid.mean);
+            cumulative += centroid.weight;
+        }
+        interpolate(previous, (cumulative, self.max), rank)
+    }
+}
+
+/// Returns the value at `rank` on the line between the (rank, value) points
+/// `a` and `b`
+fn interpolate(a: (f64, f64), b: (f64, f64), rank: f64) -> f64 {
+    if b.0 <= a.0 {
+        return b.1;
+    }
+    a.1 + (b.1 - a.1) * (rank - a.0) / (b.0 - a.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::general::Xorshift64;
+
+    /// Checks that the quantiles estimated by `digest` have ranks within
+    /// `tolerance` of the right ones in `sorted`, the sorted stream
+    fn check_quantiles(digest: &TDigest, sorted: &[f64], tolerance: f64) {
+        for q in [0.001, 0.01, 0.1, 0.25, 0.5, 0.75, 0.9, 0.99, 0.999] {
+            let estimate = digest.quantile(q);
+            let rank = sorted.partition_point(|&value| value < estimate) as f64;
+            let error = (rank / sorted.len() as f64 - q).abs();
+            assert!(error < tolerance, "q = {q}: {estimate}, off by {error}");
+        }
+        assert_eq!(digest.quantile(0.0), sorted[0]);
+        assert_eq!(digest.quantile(1.0), sorted[sorted.len() - 1]);
+    }
+
+    #[test]
+    fn uniform_stream() {
+        let mut rng = Xorshift64::new(286);
+        let mut digest = TDigest::new(100.0);
+        let mut values: Vec<f64> = (0..100_000).map(|_| rng.next_f64()).collect();
+        for &value in &values {
+            digest.add(value);
+        }
+        values.sort_by(f64::total_cmp);
+        assert_eq!(digest.count(), 100_000);
+        check_quantiles(&digest, &values, 0.005);
+    }
+
+    #[test]
+    fn skewed_stream() {
+        // exponential, with a long right tail
+        let mut rng = Xorshift64::new(2860);
+        let mut digest = TDigest::new(100.0);
+        let mut values: Vec<f64> = (0..100_000).map(|_| -(1.0 - rng.next_f64()).ln()).collect();
+        for &value in &values {
+            digest.add(value);
+        }
+        values.sort_by(f64::total_cmp);
+        check_quantiles(&digest, &values, 0.005);
+        // the median of the exponential distribution is ln(2)
+        assert!((digest.quantile(0.5) - 2f64.ln()).abs() < 0.02);
+    }
+
+    #[test]
+    fn bounded_memory() {
+        let mut rng = Xorshift64::new(2861);
+        let mut digest = TDigest::new(50.0);
+        let mut largest = 0;
+        for i in 1..=500_000 {
+            digest.add(rng.next_gaussian(0.0, 1.0));
+            largest = largest.max(digest.size());
+            if i == 5_000 || i == 500_000 {
+                assert!(digest.merged().len() <= 51, "{}", digest.merged().len());
+            }
+        }
+        assert!(largest <= 6 * 50, "{largest}");
+    }
+
+    #[test]
+    fn small_streams() {
+        let mut digest = TDigest::new(100.0);
+        assert!(digest.quantile(0.5).is_nan());
+        digest.add(3.0);
+        assert_eq!(digest.quantile(0.0), 3.0);
+        assert_eq!(digest.quantile(0.5), 3.0);
+        assert_eq!(digest.quantile(1.0), 3.0);
+        // as many centroids as values: the quantiles are interpolated
+        // between them
+        digest.add(1.0);
+        digest.add(2.0);
+        assert_eq!(digest.quantile(0.5), 2.0);
+        assert_eq!(digest.quantile(0.0), 1.0);
+        assert_eq!(digest.quantile(1.0), 3.0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn quantile_out_of_range() {
+        TDigest::new(100.0).quantile(1.5);
+    }
+
+    #[test]
+    #[should_panic]
+    fn nan_value() {
+        TDigest::new(100.0).add(f64::NAN);
+    }
+}
@@ -1,3 +1,4 @@
+use crate::general::Xorshift64;
 use std::{
     cmp::Ordering,
     iter::FromIterator,
@@ -10,18 +11,31 @@ use std::{
 struct TreapNode<T: Ord> {
     value: T,
     priority: usize,
+    // the number of values in the subtree of this node
+    size: usize,
     left: Option<Box<TreapNode<T>>>,
     right: Option<Box<TreapNode<T>>>,
 }
 
+/// A possibly empty subtree of a `Treap`.
+type Subtree<T> = Option<Box<TreapNode<T>>>;
+
 /// A set based on a Treap (Randomized Binary Search Tree).
 ///
 /// A Treap is a self-balancing binary search tree. It matains a priority value for each node, such
 /// that for every node, its children will have lower priority than itself. So, by just looking at
 /// the priority, it is like a heap, and this is where the name, Treap, comes from, Tree + Heap.
+///
+/// The priorities are drawn from a generator, seeded by the clock in `new`, or by a given seed in
+/// `with_seed` for the same tree on every run.
+///
+/// Each node also records the size of its subtree, which makes the tree an order-statistics tree:
+/// it finds the k-th smallest value, and the rank of a value, in O(log(n)) like the other
+/// operations.
 pub struct Treap<T: Ord> {
     root: Option<Box<TreapNode<T>>>,
     length: usize,
+    rng: Xorshift64,
 }
 
 /// Refers to the left or right subtree of a `Treap`.
@@ -33,9 +47,20 @@ enum Side {
 
 impl<T: Ord> Treap<T> {
     pub fn new() -> Treap<T> {
+        Treap::with_seed(
+            SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap()
+                .subsec_nanos() as u64,
+        )
+    }
+
+    /// Creates an empty tree drawing its priorities from the generator seeded by `seed`.
+    pub fn with_seed(seed: u64) -> Treap<T> {
         Treap {
             root: None,
             length: 0,
+            rng: Xorshift64::new(seed),
         }
     }
 
@@ -56,7 +81,8 @@ impl<T: Ord> Treap<T> {
     ///
     /// Returns `true` if the tree did not yet contain the value.
     pub fn insert(&mut self, value: T) -> bool {
-        let inserted = insert(&mut self.root, value);
+        let priority = self.rng.next_u64() as usize;
+        let inserted = insert(&mut self.root, value, priority);
         if inserted {
             self.length += 1;
         }
@@ -74,6 +100,78 @@ impl<T: Ord> Treap<T> {
         removed
     }
 
+    /// Moves the values greater than or equal to `value` into a new tree, which is returned,
+    /// leaving the smaller ones in this tree.
+    ///
+    /// The split follows a single path of the tree, in O(log(n)).
+    pub fn split(&mut self, value: &T) -> Treap<T> {
+        let (smaller, rest) = split(self.root.take(), value);
+        self.root = smaller;
+        let mut other = Treap::with_seed(self.rng.next_u64());
+        other.root = rest;
+        other.length = size(&other.root);
+        self.length -= other.length;
+        other
+    }
+
+    /// Returns the `k`-th smallest value, from 0, or `None` if the tree has no more than `k`
+    /// values.
+    pub fn kth_smallest(&self, mut k: usize) -> Option<&T> {
+        let mut current = &self.root;
+        while let Some(node) = current {
+            let left = size(&node.left);
+            current = match k.cmp(&left) {
+                Ordering::Equal => return Some(&node.value),
+                Ordering::Less => &node.left,
+                Ordering::Greater => {
+                    k -= left + 1;
+                    &node.right
+                }
+            }
+        }
+        None
+    }
+
+    /// Returns the rank of `value`: the number of smaller values in the tree, whether the tree
+    /// contains `value` or not.
+    pub fn rank_of(&self, value: &T) -> usize {
+        let mut rank = 0;
+        let mut current = &self.root;
+        while let Some(node) = current {
+            current = match value.cmp(&node.value) {
+                Ordering::Equal => return rank + size(&node.left),
+                Ordering::Less => &node.left,
+                Ordering::Greater => {
+                    rank += size(&node.left) + 1;
+                    &node.right
+                }
+            }
+        }
+        rank
+    }
+
+    /// Moves all the values of `other` into this tree, in O(log(n)).
+    ///
+    /// # Panics
+    ///
+    /// Panics if the values of `other` are not all greater than the ones of this tree.
+    pub fn merge(&mut self, other: Treap<T>) {
+        if let (Some(largest), Some(smallest)) = (self.last(), other.iter().next()) {
+            assert!(largest < smallest, "the trees overlap");
+        }
+        self.root = merge(self.root.take(), other.root);
+        self.length += other.length;
+    }
+
+    /// Returns the largest value of the tree.
+    fn last(&self) -> Option<&T> {
+        let mut node = self.root.as_ref()?;
+        while let Some(right) = &node.right {
+            node = right;
+        }
+        Some(&node.value)
+    }
+
     /// Returns the number of values in the tree.
     pub fn len(&self) -> usize {
         self.length
@@ -104,30 +202,29 @@ impl<T: Ord> Treap<T> {
     }
 }
 
-/// Generating random number, should use rand::Rng if possible.
-fn rand() -> usize {
-    SystemTime::now()
-        .duration_since(UNIX_EPOCH)
-        .unwrap()
-        .subsec_nanos() as usize
+/// Returns the number of values in `tree`.
+fn size<T: Ord>(tree: &Subtree<T>) -> usize {
+    tree.as_ref().map_or(0, |node| node.size)
 }
 
 /// Recursive helper function for `Treap` insertion.
-fn insert<T: Ord>(tree: &mut Option<Box<TreapNode<T>>>, value: T) -> bool {
+fn insert<T: Ord>(tree: &mut Option<Box<TreapNode<T>>>, value: T, priority: usize) -> bool {
     if let Some(node) = tree {
         let inserted = match value.cmp(&node.value) {
             Ordering::Equal => false,
-            Ordering::Less => insert(&mut node.left, value),
-            Ordering::Greater => insert(&mut node.right, value),
+            Ordering::Less => insert(&mut node.left, value, priority),
+            Ordering::Greater => insert(&mut node.right, value, priority),
         };
         if inserted {
+            node.update_size();
             node.rebalance();
         }
         inserted
     } else {
         *tree = Some(Box::new(TreapNode {
             value,
-            priority: rand(),
+            priority,
+            size: 1,
             left: None,
             right: None,
         }));
@@ -154,6 +251,7 @@ fn remove<T: Ord>(tree: &mut Option<Box<TreapNode<T>>>, value: &T) -> bool {
                         node.right = Some(right);
                         node.rotate(side);
                         remove(node.child_mut(side), value);
+                        node.update_size();
                         Some(tree.take().unwrap())
                     }
                 };
@@ -161,6 +259,7 @@ fn remove<T: Ord>(tree: &mut Option<Box<TreapNode<T>>>, value: &T) -> bool {
             }
         };
         if removed {
+            node.update_size();
             node.rebalance();
         }
         removed
@@ -169,6 +268,46 @@ fn remove<T: Ord>(tree: &mut Option<Box<TreapNode<T>>>, value: &T) -> bool {
     }
 }
 
+/// Recursive helper function for `Treap` split: returns the trees of the values smaller than
+/// `value`, and of the others.
+fn split<T: Ord>(tree: Subtree<T>, value: &T) -> (Subtree<T>, Subtree<T>) {
+    match tree {
+        None => (None, None),
+        Some(mut node) => {
+            if node.value < *value {
+                let (smaller, rest) = split(node.right.take(), value);
+                node.right = smaller;
+                node.update_size();
+                (Some(node), rest)
+            } else {
+                let (smaller, rest) = split(node.left.take(), value);
+                node.left = rest;
+                node.update_size();
+                (smaller, Some(node))
+            }
+        }
+    }
+}
+
+/// Recursive helper function for `Treap` merge, all the values of `left` being smaller than the
+/// ones of `right`: the root with the highest priority stays the root.
+fn merge<T: Ord>(left: Subtree<T>, right: Subtree<T>) -> Subtree<T> {
+    match (left, right) {
+        (None, tree) | (tree, None) => tree,
+        (Some(mut left), Some(mut right)) => {
+            if left.priority > right.priority {
+                left.right = merge(left.right.take(), Some(right));
+                left.update_size();
+                Some(left)
+            } else {
+                right.left = merge(Some(left), right.left.take());
+                right.update_size();
+                Some(right)
+            }
+        }
+    }
+}
+
 impl<T: Ord> TreapNode<T> {
     /// Returns a reference to the left or right child.
     fn child(&self, side: Side) -> &Option<Box<TreapNode<T>>> {
@@ -202,7 +341,14 @@ impl<T: Ord> TreapNode<T> {
         // Swap root and child nodes in memory
         mem::swap(self, subtree.as_mut());
         // Set old root (subtree) as child of new root (self)
+        subtree.update_size();
         *self.child_mut(side) = Some(subtree);
+        self.update_size();
+    }
+
+    /// Recomputes the size of the subtree from the ones of the children.
+    fn update_size(&mut self) {
+        self.size = 1 + size(&self.left) + size(&self.right);
     }
 
     /// Performs left or right tree rotations to balance this node.
@@ -220,7 +366,9 @@ impl<T: Ord> TreapNode<T> {
 
     #[cfg(test)]
     fn is_valid(&self) -> bool {
-        self.priority >= self.priority(Side::Left) && self.priority >= self.priority(Side::Right)
+        self.priority >= self.priority(Side::Left)
+            && self.priority >= self.priority(Side::Right)
+            && self.size == 1 + size(&self.left) + size(&self.right)
     }
 }
 
@@ -297,6 +445,8 @@ impl<'a, T: Ord> Iterator for Iter<'a, T> {
 #[cfg(test)]
 mod tests {
     use super::Treap;
+    use crate::general::Xorshift64;
+    use std::collections::BTreeSet;
 
     /// Returns `true` if all nodes in the tree are valid.
     fn is_valid<T: Ord>(tree: &Treap<T>) -> bool {
@@ -349,4 +499,113 @@ mod tests {
             assert!(is_valid(&tree));
         }
     }
+
+    #[test]
+    fn random_operations_stay_sorted() {
+        let mut rng = Xorshift64::new(296);
+        let mut tree = Treap::with_seed(2960);
+        let mut reference = BTreeSet::new();
+        for _ in 0..3000 {
+            let value = rng.gen_range(0..300);
+            if rng.gen_range(0..3) == 0 {
+                assert_eq!(tree.remove(&value), reference.remove(&value));
+            } else {
+                assert_eq!(tree.insert(value), reference.insert(value));
+            }
+        }
+        assert_eq!(tree.len(), reference.len());
+        assert!(tree.iter().eq(reference.iter()));
+        assert!(is_valid(&tree));
+    }
+
+    #[test]
+    fn seeded_trees_are_reproducible() {
+        let priorities = |seed| {
+            let mut tree = Treap::with_seed(seed);
+            for x in [5, 3, 9, 1, 4, 8, 2] {
+                tree.insert(x);
+            }
+            tree.node_iter().map(|n| n.priority).collect::<Vec<_>>()
+        };
+        assert_eq!(priorities(296), priorities(296));
+        assert_ne!(priorities(296), priorities(297));
+    }
+
+    #[test]
+    fn split_and_merge_round_trip() {
+        let mut rng = Xorshift64::new(2961);
+        let mut tree = Treap::with_seed(2962);
+        for _ in 0..500 {
+            tree.insert(rng.gen_range(0..1000));
+        }
+        let values: Vec<u64> = tree.iter().copied().collect();
+        for key in [0, 1, 250, 500, 999, 1000, 5000] {
+            let larger = tree.split(&key);
+            assert!(tree.iter().all(|&x| x < key));
+            assert!(larger.iter().all(|&x| x >= key));
+            assert_eq!(tree.len() + larger.len(), values.len());
+            assert_eq!(tree.len(), values.iter().filter(|&&x| x < key).count());
+            assert!(is_valid(&tree) && is_valid(&larger));
+
+            tree.merge(larger);
+            assert!(tree.iter().copied().eq(values.iter().copied()));
+            assert_eq!(tree.len(), values.len());
+            assert!(is_valid(&tree));
+        }
+        // the split trees work on their own
+        let mut larger = tree.split(&500);
+        assert!(larger.insert(42) && tree.contains(&values[0]));
+        assert!(!larger.contains(&values[0]));
+    }
+
+    #[test]
+    #[should_panic]
+    fn merge_overlapping_trees() {
+        let mut tree: Treap<_> = (1..5).collect();
+        tree.merge((4..8).collect());
+    }
+
+    #[test]
+    fn order_statistics() {
+        let mut rng = Xorshift64::new(297);
+        let mut tree = Treap::with_seed(2970);
+        let mut reference = BTreeSet::new();
+        for round in 0..2000 {
+            let value = rng.gen_range(0..500);
+            if rng.gen_range(0..3) == 0 {
+                tree.remove(&value);
+                reference.remove(&value);
+            } else {
+                tree.insert(value);
+                reference.insert(value);
+            }
+            if round % 100 == 0 {
+                assert!(is_valid(&tree));
+                let sorted: Vec<u64> = reference.iter().copied().collect();
+                for (k, value) in sorted.iter().enumerate() {
+                    assert_eq!(tree.kth_smallest(k), Some(value));
+                }
+                assert_eq!(tree.kth_smallest(sorted.len()), None);
+                for value in 0..=500 {
+                    let rank = sorted.iter().filter(|&&x| x < value).count();
+                    assert_eq!(tree.rank_of(&value), rank, "{value}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn order_statistics_after_split() {
+        let mut tree: Treap<_> = (0..100).map(|x| 2 * x).collect();
+        assert_eq!(tree.kth_smallest(10), Some(&20));
+        assert_eq!(tree.rank_of(&21), 11);
+        let larger = tree.split(&50);
+        assert_eq!(tree.len(), 25);
+        assert_eq!(larger.len(), 75);
+        assert_eq!(tree.kth_smallest(24), Some(&48));
+        assert_eq!(tree.kth_smallest(25), None);
+        assert_eq!(larger.kth_smallest(0), Some(&50));
+        assert_eq!(larger.rank_of(&60), 5);
+        assert_eq!(Treap::<i32>::new().kth_smallest(0), None);
+    }
 }
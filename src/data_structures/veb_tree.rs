@@ -15,11 +15,13 @@ impl VebTree {
     /// rounded up to the nearest power of two.
     pub fn new(size: u32) -> VebTree {
         let rounded_size = size.next_power_of_two();
-        let child_size = (size as f64).sqrt().ceil() as u32;
+        let child_size = (rounded_size as f64).sqrt().ceil() as u32;
 
+        // One cluster for each value of `high`, which is at most `child_size`, so that the
+        // summary holds them all.
         let mut cluster = Vec::new();
         if rounded_size > 2 {
-            for _ in 0..rounded_size {
+            for _ in 0..rounded_size.div_ceil(child_size) {
                 cluster.push(VebTree::new(child_size));
             }
         }
@@ -130,11 +132,12 @@ impl VebTree {
             return None;
         }
 
-        if self.size == 2 {
-            // Base case. If pred is 0, and 1 exists in the tree (max is set to 1), the successor
-            // is 1.
-            return if pred == 0 && self.max == 1 {
-                Some(1)
+        if self.size <= 2 {
+            // Base case. The tree has no clusters, and its values are its min and max.
+            return if pred < self.min {
+                Some(self.min)
+            } else if pred < self.max {
+                Some(self.max)
             } else {
                 None
             };
@@ -170,9 +173,11 @@ impl VebTree {
         }
 
         // base case.
-        if self.size == 2 {
-            return if succ == 1 && self.min == 0 {
-                Some(0)
+        if self.size <= 2 {
+            return if succ > self.max {
+                Some(self.max)
+            } else if succ > self.min {
+                Some(self.min)
             } else {
                 None
             };
@@ -236,6 +241,7 @@ impl<'a> Iterator for VebTreeIter<'a> {
 mod test {
     use super::VebTree;
     use rand::{rngs::StdRng, Rng, SeedableRng};
+    use std::collections::BTreeSet;
 
     fn test_veb_tree(size: u32, mut elements: Vec<u32>, exclude: Vec<u32>) {
         // Insert elements
@@ -283,6 +289,19 @@ mod test {
         );
     }
 
+    #[test]
+    fn test_universe_of_one() {
+        for size in [0, 1] {
+            let mut tree = VebTree::new(size);
+            assert_eq!(tree.succ(0), None);
+            tree.insert(0);
+            assert!(tree.search(0));
+            assert_eq!(tree.succ(0), None);
+            assert_eq!(tree.pred(0), None);
+            assert!(tree.iter().eq([0]));
+        }
+    }
+
     #[test]
     fn test_repeat_insert() {
         let mut tree = VebTree::new(16);
@@ -339,4 +358,45 @@ mod test {
         let elements: Vec<u32> = (0..100).map(|_| rng.gen_range(0..255)).collect();
         test_veb_tree(300, elements, Vec::new());
     }
+
+    #[test]
+    fn test_against_btree_set() {
+        let mut rng = StdRng::seed_from_u64(298);
+        for size in [1, 2, 4, 8, 16, 64, 256, 1024, 65536] {
+            for count in [1, 3, size as usize / 8 + 1, size as usize / 2] {
+                let mut tree = VebTree::new(size);
+                let mut reference = BTreeSet::new();
+                // the bounds of the universe, or not
+                if rng.gen_bool(0.5) {
+                    tree.insert(0);
+                    reference.insert(0);
+                }
+                if rng.gen_bool(0.5) {
+                    tree.insert(size - 1);
+                    reference.insert(size - 1);
+                }
+                for _ in 0..count {
+                    let value = rng.gen_range(0..size);
+                    tree.insert(value);
+                    reference.insert(value);
+                }
+                let mut queries: Vec<u32> = (0..200).map(|_| rng.gen_range(0..size)).collect();
+                queries.extend([0, 1, size / 2, size.saturating_sub(2), size - 1]);
+                for query in queries {
+                    assert_eq!(tree.search(query), reference.contains(&query));
+                    assert_eq!(
+                        tree.succ(query),
+                        reference.range(query + 1..).next().copied(),
+                        "succ({query}) in {size}"
+                    );
+                    assert_eq!(
+                        tree.pred(query),
+                        reference.range(..query).next_back().copied(),
+                        "pred({query}) in {size}"
+                    );
+                }
+                assert!(tree.iter().eq(reference.iter().copied()));
+            }
+        }
+    }
 }
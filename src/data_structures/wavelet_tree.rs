@@ -0,0 +1,272 @@
+/*
+    A wavelet tree stores a sequence of integers taken from an alphabet [lo, hi]
+    and answers, in O(log σ) where σ = hi - lo + 1 is the size of the alphabet:
+      - rank(value, pos): how many times `value` occurs in the prefix [0, pos)
+      - select(value, k): the position of the k-th occurrence of `value`
+      - quantile(l, r, k): the k-th smallest value of the range [l, r)
+
+    The root splits the alphabet in two halves: each element goes to the left
+    child if its value is in the lower half, to the right child otherwise,
+    keeping the order of the sequence. Each node only stores, for every prefix
+    of its own subsequence, how many elements went to the left, then its
+    children recursively do the same with their half of the alphabet.
+
+    Space: O(n log σ) integers (a succinct version would use bit vectors)
+
+    Wikipedia: <https://en.wikipedia.org/wiki/Wavelet_Tree>
+*/
+
+struct Node {
+    lo: u32,
+    hi: u32,
+    /// `left_count[i]` is the number of elements among the first `i` of the
+    /// node's subsequence which belong to the left child
+    left_count: Vec<usize>,
+    left: Option<Box<Node>>,
+    right: Option<Box<Node>>,
+}
+
+impl Node {
+    fn build(values: Vec<u32>, lo: u32, hi: u32) -> Node {
+        if lo == hi || values.is_empty() {
+            return Node {
+                lo,
+                hi,
+                left_count: Vec::new(),
+                left: None,
+                right: None,
+            };
+        }
+
+        let mid = lo + (hi - lo) / 2;
+        let mut left_count = Vec::with_capacity(values.len() + 1);
+        left_count.push(0);
+        let (mut left_values, mut right_values) = (Vec::new(), Vec::new());
+        for &value in &values {
+            if value <= mid {
+                left_values.push(value);
+            } else {
+                right_values.push(value);
+            }
+            left_count.push(left_values.len());
+        }
+
+        Node {
+            lo,
+            hi,
+            left_count,
+            left: Some(Box::new(Node::build(left_values, lo, mid))),
+            right: Some(Box::new(Node::build(right_values, mid + 1, hi))),
+        }
+    }
+
+    fn is_leaf(&self) -> bool {
+        self.left.is_none()
+    }
+
+    fn mid(&self) -> u32 {
+        self.lo + (self.hi - self.lo) / 2
+    }
+
+    fn rank(&self, value: u32, pos: usize) -> usize {
+        if self.is_leaf() {
+            return pos;
+        }
+        let to_left = self.left_count[pos];
+        if value <= self.mid() {
+            self.left.as_ref().unwrap().rank(value, to_left)
+        } else {
+            self.right.as_ref().unwrap().rank(value, pos - to_left)
+        }
+    }
+
+    /// Position, in this node's subsequence, of the k-th (0-indexed)
+    /// occurrence of `value`, given that it exists
+    fn select(&self, value: u32, k: usize) -> usize {
+        if self.is_leaf() {
+            return k;
+        }
+        if value <= self.mid() {
+            let child_pos = self.left.as_ref().unwrap().select(value, k);
+            // the first prefix holding `child_pos + 1` left elements
+            self.left_count.partition_point(|&count| count <= child_pos) - 1
+        } else {
+            let child_pos = self.right.as_ref().unwrap().select(value, k);
+            let right_count = |i: usize| i - self.left_count[i];
+            let (mut low, mut high) = (0, self.left_count.len() - 1);
+            while low < high {
+                let middle = (low + high) / 2;
+                if right_count(middle + 1) <= child_pos {
+                    low = middle + 1;
+                } else {
+                    high = middle;
+                }
+            }
+            low
+        }
+    }
+
+    fn quantile(&self, l: usize, r: usize, k: usize) -> u32 {
+        if self.is_leaf() {
+            return self.lo;
+        }
+        let (left_l, left_r) = (self.left_count[l], self.left_count[r]);
+        let in_left = left_r - left_l;
+        if k < in_left {
+            self.left.as_ref().unwrap().quantile(left_l, left_r, k)
+        } else {
+            self.right
+                .as_ref()
+                .unwrap()
+                .quantile(l - left_l, r - left_r, k - in_left)
+        }
+    }
+}
+
+pub struct WaveletTree {
+    len: usize,
+    root: Option<Node>,
+}
+
+impl WaveletTree {
+    /// Builds the tree over `values` in O(n log σ)
+    pub fn new(values: &[u32]) -> Self {
+        let root = match (values.iter().min(), values.iter().max()) {
+            (Some(&lo), Some(&hi)) => Some(Node::build(values.to_vec(), lo, hi)),
+            _ => None,
+        };
+        WaveletTree {
+            len: values.len(),
+            root,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Counts the occurrences of `value` among the first `pos` elements.
+    /// `pos` is clamped to the length of the sequence.
+    pub fn rank(&self, value: u32, pos: usize) -> usize {
+        match &self.root {
+            Some(root) if (root.lo..=root.hi).contains(&value) => {
+                root.rank(value, pos.min(self.len))
+            }
+            _ => 0,
+        }
+    }
+
+    /// Returns the position of the `k`-th (0-indexed) occurrence of `value`,
+    /// or `None` if `value` occurs at most `k` times.
+    pub fn select(&self, value: u32, k: usize) -> Option<usize> {
+        if self.rank(value, self.len) <= k {
+            return None;
+        }
+        self.root.as_ref().map(|root| root.select(value, k))
+    }
+
+    /// Returns the `k`-th (0-indexed) smallest value of the range `[l, r)`,
+    /// or `None` if the range holds at most `k` elements.
+    pub fn quantile(&self, l: usize, r: usize, k: usize) -> Option<u32> {
+        let r = r.min(self.len);
+        if l >= r || k >= r - l {
+            return None;
+        }
+        self.root.as_ref().map(|root| root.quantile(l, r, k))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::general::Xorshift64;
+
+    fn random_sequence(n: usize, alphabet: u64, rng: &mut Xorshift64) -> Vec<u32> {
+        (0..n).map(|_| rng.gen_range(0..alphabet) as u32).collect()
+    }
+
+    #[test]
+    fn small_example() {
+        let values = [3, 1, 4, 1, 5, 9, 2, 6, 5, 3, 5];
+        let tree = WaveletTree::new(&values);
+        assert_eq!(tree.len(), 11);
+        assert_eq!(tree.rank(5, 11), 3);
+        assert_eq!(tree.rank(5, 5), 1);
+        assert_eq!(tree.rank(1, 0), 0);
+        assert_eq!(tree.rank(7, 11), 0);
+        assert_eq!(tree.rank(42, 11), 0);
+        assert_eq!(tree.select(1, 0), Some(1));
+        assert_eq!(tree.select(1, 1), Some(3));
+        assert_eq!(tree.select(1, 2), None);
+        assert_eq!(tree.select(9, 0), Some(5));
+        assert_eq!(tree.select(0, 0), None);
+        // the range [0, 5) sorted is 1 1 3 4 5
+        assert_eq!(tree.quantile(0, 5, 0), Some(1));
+        assert_eq!(tree.quantile(0, 5, 4), Some(5));
+        assert_eq!(tree.quantile(4, 9, 2), Some(5));
+        assert_eq!(tree.quantile(0, 11, 10), Some(9));
+        assert_eq!(tree.quantile(0, 11, 11), None);
+        assert_eq!(tree.quantile(3, 3, 0), None);
+    }
+
+    #[test]
+    fn rank_and_select_against_brute_force() {
+        let mut rng = Xorshift64::new(10);
+        for (n, alphabet) in [(1, 1), (50, 2), (200, 8), (300, 37)] {
+            let values = random_sequence(n, alphabet, &mut rng);
+            let tree = WaveletTree::new(&values);
+            for value in 0..alphabet as u32 + 1 {
+                for pos in 0..=n {
+                    let expected = values[..pos].iter().filter(|&&v| v == value).count();
+                    assert_eq!(tree.rank(value, pos), expected);
+                }
+                let positions: Vec<usize> = (0..n).filter(|&i| values[i] == value).collect();
+                for (k, &pos) in positions.iter().enumerate() {
+                    assert_eq!(tree.select(value, k), Some(pos));
+                }
+                assert_eq!(tree.select(value, positions.len()), None);
+            }
+        }
+    }
+
+    #[test]
+    fn quantile_against_brute_force() {
+        let mut rng = Xorshift64::new(11);
+        for (n, alphabet) in [(1, 1), (40, 3), (120, 16), (150, 100)] {
+            let values: Vec<u32> = random_sequence(n, alphabet, &mut rng)
+                .into_iter()
+                .map(|v| v + 1000)
+                .collect();
+            let tree = WaveletTree::new(&values);
+            for l in 0..n {
+                for r in (l + 1)..=n {
+                    let mut sorted = values[l..r].to_vec();
+                    sorted.sort_unstable();
+                    for (k, &expected) in sorted.iter().enumerate() {
+                        assert_eq!(tree.quantile(l, r, k), Some(expected));
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn positions_beyond_the_end() {
+        let tree = WaveletTree::new(&[2, 2, 0]);
+        assert_eq!(tree.rank(2, 100), 2);
+        assert_eq!(tree.quantile(1, 100, 1), Some(2));
+    }
+
+    #[test]
+    fn empty_tree() {
+        let tree = WaveletTree::new(&[]);
+        assert!(tree.is_empty());
+        assert_eq!(tree.rank(0, 0), 0);
+        assert_eq!(tree.select(0, 0), None);
+        assert_eq!(tree.quantile(0, 0, 0), None);
+    }
+}
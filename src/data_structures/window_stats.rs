@@ -0,0 +1,232 @@
+/*
+    Sliding window statistics keep the mean, variance, minimum and maximum of
+    the last `size` values of a stream, updated as each new value pushes the
+    oldest one out of the window.
+
+    The mean and the sum of the squared deviations from it follow Welford's
+    updates, run backwards to remove the evicted value. The minimum and the
+    maximum are at the front of monotonic deques: the deque of the minimum
+    holds the indices of the values that may still become the minimum, with
+    increasing values, a new value evicting the larger ones at the back since
+    they will leave the window before it. The deque of the maximum is the
+    same with decreasing values.
+
+    Push complexity: O(1) amortized
+    Mean, variance, min and max complexity: O(1)
+    Memory: O(size)
+
+    Wikipedia: <https://en.wikipedia.org/wiki/Algorithms_for_calculating_variance#Welford's_online_algorithm>
+*/
+
+use std::collections::VecDeque;
+
+#[derive(Clone, Debug)]
+pub struct WindowStats {
+    size: usize,
+    // the values of the window, with the index of the first one in the stream
+    values: VecDeque<f64>,
+    first: usize,
+    mean: f64,
+    // the sum of the squared deviations of the values from their mean
+    squared_deviations: f64,
+    // the indices in the stream of the candidate minima and maxima
+    minima: VecDeque<usize>,
+    maxima: VecDeque<usize>,
+}
+
+impl WindowStats {
+    /// Creates statistics over the last `size` values pushed.
+    ///
+    /// Panics if `size` is 0.
+    pub fn new(size: usize) -> Self {
+        assert!(size > 0, "the window must hold at least one value");
+        WindowStats {
+            size,
+            values: VecDeque::with_capacity(size),
+            first: 0,
+            mean: 0.0,
+            squared_deviations: 0.0,
+            minima: VecDeque::new(),
+            maxima: VecDeque::new(),
+        }
+    }
+
+    /// Pushes `x` into the window, evicting the oldest value if it is full.
+    ///
+    /// Panics if `x` is NaN or infinite: the backward update of the mean
+    /// could not remove an infinity, which would leave it NaN.
+    pub fn push(&mut self, x: f64) {
+        assert!(x.is_finite(), "cannot push {x} into the window");
+        if self.values.len() == self.size {
+            self.evict();
+        }
+
+        let index = self.first + self.values.len();
+        self.values.push_back(x);
+        let n = self.values.len() as f64;
+        let delta = x - self.mean;
+        self.mean += delta / n;
+        self.squared_deviations += delta * (x - self.mean);
+
+        while self.minima.back().is_some_and(|&i| self.value(i) >= x) {
+            self.minima.pop_back();
+        }
+        self.minima.push_back(index);
+        while self.maxima.back().is_some_and(|&i| self.value(i) <= x) {
+            self.maxima.pop_back();
+        }
+        self.maxima.push_back(index);
+    }
+
+    /// Removes the oldest value
+    fn evict(&mut self) {
+        let x = self.values.pop_front().unwrap();
+        let n = self.values.len() as f64;
+        if n == 0.0 {
+            self.mean = 0.0;
+            self.squared_deviations = 0.0;
+        } else {
+            let delta = x - self.mean;
+            self.mean -= delta / n;
+            self.squared_deviations = (self.squared_deviations - delta * (x - self.mean)).max(0.0);
+        }
+
+        if self.minima.front() == Some(&self.first) {
+            self.minima.pop_front();
+        }
+        if self.maxima.front() == Some(&self.first) {
+            self.maxima.pop_front();
+        }
+        self.first += 1;
+    }
+
+    /// Returns the value of index `i` in the stream, which is in the window
+    fn value(&self, i: usize) -> f64 {
+        self.values[i - self.first]
+    }
+
+    /// Returns the number of values in the window, at most its size
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Returns the mean of the window, or `None` if it is empty
+    pub fn mean(&self) -> Option<f64> {
+        (!self.is_empty()).then_some(self.mean)
+    }
+
+    /// Returns the variance of the window, normalized by the number of
+    /// values, or `None` if it is empty
+    pub fn variance(&self) -> Option<f64> {
+        (!self.is_empty()).then(|| self.squared_deviations / self.len() as f64)
+    }
+
+    /// Returns the smallest value of the window, or `None` if it is empty
+    pub fn min(&self) -> Option<f64> {
+        self.minima.front().map(|&i| self.value(i))
+    }
+
+    /// Returns the largest value of the window, or `None` if it is empty
+    pub fn max(&self) -> Option<f64> {
+        self.maxima.front().map(|&i| self.value(i))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::general::Xorshift64;
+
+    /// Checks the statistics of `stats` against the ones of `window`,
+    /// recomputed from scratch
+    fn check(stats: &WindowStats, window: &[f64]) {
+        let n = window.len() as f64;
+        let mean = window.iter().sum::<f64>() / n;
+        let variance = window.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / n;
+        assert_eq!(stats.len(), window.len());
+        assert!((stats.mean().unwrap() - mean).abs() < 1e-9, "{window:?}");
+        assert!(
+            (stats.variance().unwrap() - variance).abs() < 1e-9,
+            "{window:?}"
+        );
+        assert_eq!(stats.min(), window.iter().copied().reduce(f64::min));
+        assert_eq!(stats.max(), window.iter().copied().reduce(f64::max));
+    }
+
+    #[test]
+    fn matches_brute_force() {
+        let mut rng = Xorshift64::new(289);
+        for size in [1, 2, 5, 32] {
+            let mut stats = WindowStats::new(size);
+            let mut stream = vec![];
+            for _ in 0..500 {
+                // few distinct values, so that ties are frequent
+                let x = rng.gen_range(0..20) as f64 - 10.0 + rng.next_f64() * 0.5;
+                stats.push(x);
+                stream.push(x);
+                check(&stats, &stream[stream.len().saturating_sub(size)..]);
+            }
+        }
+    }
+
+    #[test]
+    fn fills_then_rolls() {
+        let mut stats = WindowStats::new(3);
+        assert!(stats.is_empty());
+        assert_eq!(stats.mean(), None);
+        assert_eq!(stats.variance(), None);
+        assert_eq!(stats.min(), None);
+        assert_eq!(stats.max(), None);
+
+        for (x, window) in [
+            (4.0, vec![4.0]),
+            (1.0, vec![4.0, 1.0]),
+            (7.0, vec![4.0, 1.0, 7.0]),
+            // 4 leaves
+            (2.0, vec![1.0, 7.0, 2.0]),
+            // then the minimum 1, and the maximum 7
+            (2.0, vec![7.0, 2.0, 2.0]),
+            (3.0, vec![2.0, 2.0, 3.0]),
+        ] {
+            stats.push(x);
+            check(&stats, &window);
+        }
+        assert_eq!(stats.min(), Some(2.0));
+        assert_eq!(stats.max(), Some(3.0));
+    }
+
+    #[test]
+    fn large_offsets() {
+        // the removal of values far from zero keeps the variance precise
+        let mut stats = WindowStats::new(4);
+        for i in 0..10_000 {
+            stats.push(1e9 + (i % 4) as f64);
+        }
+        assert!((stats.variance().unwrap() - 1.25).abs() < 1e-6);
+        assert!((stats.mean().unwrap() - (1e9 + 1.5)).abs() < 1e-6);
+    }
+
+    #[test]
+    #[should_panic]
+    fn empty_window() {
+        WindowStats::new(0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn push_nan() {
+        WindowStats::new(3).push(f64::NAN);
+    }
+
+    #[test]
+    #[should_panic]
+    fn push_infinity() {
+        let mut stats = WindowStats::new(3);
+        stats.push(1.0);
+        stats.push(f64::NEG_INFINITY);
+    }
+}
@@ -0,0 +1,134 @@
+//! Counts the distinct subsequences of a string equal to another one
+
+/// `distinct_subsequences(s, t)` returns the number of ways to delete chars
+/// from `s` so that `t` is left, i.e. the number of distinct sets of positions
+/// of `s` spelling out `t`, or `None` if that number does not fit in a u64.
+///
+/// With `count[i][j]` the answer for the first `i` chars of `s` and the first
+/// `j` chars of `t`, the `i`-th char of `s` is either skipped, or matched with
+/// the `j`-th char of `t` when they are equal:
+/// `count[i][j] = count[i - 1][j] + count[i - 1][j - 1]`. Only the row of
+/// `i - 1` is needed for row `i`, so a single row is updated in place, from
+/// the last column down.
+///
+/// # Complexity
+///    - time complexity: O(n * m),
+///    - space complexity: O(m),
+///
+/// where n and m are the numbers of chars of `s` and `t`.
+pub fn distinct_subsequences(s: &str, t: &str) -> Option<u64> {
+    let target: Vec<char> = t.chars().collect();
+    // count[j] is the number of subsequences of the chars seen so far
+    // spelling out the first j chars of t, None once it overflows: the counts
+    // only grow, so that a count which overflowed never fits again
+    let mut count = vec![Some(0u64); target.len() + 1];
+    count[0] = Some(1);
+
+    for c in s.chars() {
+        for j in (1..=target.len()).rev() {
+            if target[j - 1] == c {
+                count[j] = count[j]
+                    .zip(count[j - 1])
+                    .and_then(|(a, b)| a.checked_add(b));
+            }
+        }
+    }
+    count[target.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::distinct_subsequences;
+
+    /// Counts the subsets of positions of `s` spelling out `t`
+    fn brute_force(s: &[char], t: &[char]) -> u64 {
+        match (s.split_first(), t.first()) {
+            (_, None) => 1,
+            (None, Some(_)) => 0,
+            (Some((first, rest)), Some(wanted)) => {
+                let matched = if first == wanted {
+                    brute_force(rest, &t[1..])
+                } else {
+                    0
+                };
+                matched + brute_force(rest, t)
+            }
+        }
+    }
+
+    #[test]
+    fn test_distinct_subsequences() {
+        assert_eq!(distinct_subsequences("rabbbit", "rabbit"), Some(3));
+        assert_eq!(distinct_subsequences("babgbag", "bag"), Some(5));
+        assert_eq!(distinct_subsequences("aaaa", "aa"), Some(6));
+        assert_eq!(distinct_subsequences("abc", "abc"), Some(1));
+        assert_eq!(distinct_subsequences("abc", "cb"), Some(0));
+    }
+
+    #[test]
+    fn test_empty_strings() {
+        assert_eq!(distinct_subsequences("abc", ""), Some(1));
+        assert_eq!(distinct_subsequences("", ""), Some(1));
+        assert_eq!(distinct_subsequences("", "a"), Some(0));
+    }
+
+    #[test]
+    fn test_target_longer_than_source() {
+        assert_eq!(distinct_subsequences("bag", "bags"), Some(0));
+        assert_eq!(distinct_subsequences("a", "aa"), Some(0));
+    }
+
+    #[test]
+    fn test_multibyte_chars() {
+        assert_eq!(distinct_subsequences("ééé", "éé"), Some(3));
+        assert_eq!(distinct_subsequences("日本日本", "日本"), Some(3));
+    }
+
+    #[test]
+    fn test_against_brute_force() {
+        let cases = [
+            ("abacabadabacaba", "aba"),
+            ("mississippi", "issi"),
+            ("xxyxyyxyx", "xyx"),
+            ("abcabcabc", "cab"),
+        ];
+        for (s, t) in cases {
+            let source: Vec<char> = s.chars().collect();
+            let target: Vec<char> = t.chars().collect();
+            assert_eq!(
+                distinct_subsequences(s, t),
+                Some(brute_force(&source, &target))
+            );
+        }
+    }
+
+    #[test]
+    fn test_large_count() {
+        // every choice of 10 positions out of 40
+        let s = "a".repeat(40);
+        let t = "a".repeat(10);
+        assert_eq!(distinct_subsequences(&s, &t), Some(847_660_528));
+    }
+
+    #[test]
+    fn test_overflow() {
+        // C(100, 50) is about 1e29
+        let s = "a".repeat(100);
+        assert_eq!(distinct_subsequences(&s, &"a".repeat(50)), None);
+        // C(67, 33) is the largest of the C(67, k), and still fits
+        let s = "a".repeat(67);
+        assert_eq!(
+            distinct_subsequences(&s, &"a".repeat(33)),
+            Some(14_226_520_737_620_288_370)
+        );
+        assert_eq!(
+            distinct_subsequences(&"a".repeat(68), &"a".repeat(34)),
+            None
+        );
+        // the counts of the prefixes of t overflow, but no b ends them
+        assert_eq!(
+            distinct_subsequences(&"a".repeat(100), &format!("{}b", "a".repeat(50))),
+            Some(0)
+        );
+    }
+}
@@ -1,53 +1,44 @@
 /// # Egg Dropping Puzzle
-
+///
 /// `egg_drop(eggs, floors)` returns the least number of egg droppings
 ///     required to determine the highest floor from which an egg will not
 ///     break upon dropping
 ///
+/// Instead of searching the best floor to drop from, the dynamic programming
+///     is turned around: `reach[i]` is the largest number of floors that can
+///     be checked with `i` eggs and the number of trials done so far. Dropping
+///     an egg, it either breaks and `i - 1` eggs are left for the floors below,
+///     or it does not and `i` eggs are left for the floors above, so one more
+///     trial gives `reach[i] = reach[i - 1] + 1 + reach[i]`. The answer is the
+///     first number of trials for which `reach[eggs] >= floors`.
+///
+/// Complexity: O(eggs * answer) time, O(eggs) space, where answer <= floors
+///
 /// Assumptions: n > 0
 pub fn egg_drop(eggs: u32, floors: u32) -> u32 {
     assert!(eggs > 0);
 
-    // Explicity handle edge cases (optional)
+    // Explicitly handle edge cases, one egg taking a trial per floor
     if eggs == 1 || floors == 0 || floors == 1 {
         return floors;
     }
 
-    let eggs_index = eggs as usize;
-    let floors_index = floors as usize;
-
-    // Store solutions to subproblems in 2D Vec,
-    // where egg_drops[i][j] represents the solution to the egg dropping
-    // problem with i eggs and j floors
-    let mut egg_drops: Vec<Vec<u32>> = vec![vec![0; floors_index + 1]; eggs_index + 1];
-
-    // Assign solutions for egg_drop(n, 0) = 0, egg_drop(n, 1) = 1
-    for egg_drop in egg_drops.iter_mut().skip(1) {
-        egg_drop[0] = 0;
-        egg_drop[1] = 1;
-    }
-
-    // Assign solutions to egg_drop(1, k) = k
-    for j in 1..=floors_index {
-        egg_drops[1][j] = j as u32;
-    }
-
-    // Complete solutions vector using optimal substructure property
-    for i in 2..=eggs_index {
-        for j in 2..=floors_index {
-            egg_drops[i][j] = std::u32::MAX;
-
-            for k in 1..=j {
-                let res = 1 + std::cmp::max(egg_drops[i - 1][k - 1], egg_drops[i][j - k]);
-
-                if res < egg_drops[i][j] {
-                    egg_drops[i][j] = res;
-                }
-            }
+    // Store the reachable floors with 0..=eggs eggs, for the current number
+    // of trials
+    let eggs = eggs as usize;
+    let mut reach = vec![0u32; eggs + 1];
+    let mut trials = 0;
+
+    while reach[eggs] < floors {
+        trials += 1;
+        // Update from the most eggs down, so that reach[i - 1] still holds
+        // the value for one trial less
+        for i in (1..=eggs).rev() {
+            reach[i] = reach[i].saturating_add(reach[i - 1]).saturating_add(1);
         }
     }
 
-    egg_drops[eggs_index][floors_index]
+    trials
 }
 
 #[cfg(test)]
@@ -88,4 +79,69 @@ mod tests {
     fn large_floors() {
         assert_eq!(egg_drop(2, 100), 14);
     }
+
+    #[test]
+    fn one_egg_is_linear() {
+        for floors in 0..50 {
+            assert_eq!(egg_drop(1, floors), floors);
+        }
+    }
+
+    #[test]
+    fn many_eggs_is_binary_search() {
+        assert_eq!(egg_drop(64, 1000), 10);
+        assert_eq!(egg_drop(15, 1 << 14), 15);
+        // and saturates without overflowing
+        assert_eq!(egg_drop(32, u32::MAX), 32);
+    }
+
+    #[test]
+    fn huge_floor_counts() {
+        assert_eq!(egg_drop(1, u32::MAX), u32::MAX);
+        // the least t with t * (t + 1) / 2 >= u32::MAX
+        assert_eq!(egg_drop(2, u32::MAX), 92682);
+    }
+
+    /// The classic O(eggs * floors^2) dynamic programming over the first floor
+    /// to drop from
+    fn brute_force(eggs: u32, floors: u32) -> u32 {
+        let (eggs, floors) = (eggs as usize, floors as usize);
+        let mut dp = vec![vec![0; floors + 1]; eggs + 1];
+        for (j, cell) in dp[1].iter_mut().enumerate() {
+            *cell = j as u32;
+        }
+        for i in 2..=eggs {
+            for j in 1..=floors {
+                dp[i][j] = (1..=j)
+                    .map(|k| 1 + dp[i - 1][k - 1].max(dp[i][j - k]))
+                    .min()
+                    .unwrap();
+            }
+        }
+        dp[eggs][floors]
+    }
+
+    #[test]
+    fn against_brute_force() {
+        for eggs in 1..5 {
+            for floors in 0..60 {
+                assert_eq!(egg_drop(eggs, floors), brute_force(eggs, floors));
+            }
+        }
+    }
+
+    #[test]
+    fn more_eggs_never_need_more_trials() {
+        for floors in [0, 1, 7, 100, 1000, 123_456] {
+            for eggs in 1..20 {
+                assert!(egg_drop(eggs + 1, floors) <= egg_drop(eggs, floors));
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn no_eggs() {
+        egg_drop(0, 10);
+    }
 }
@@ -1,4 +1,5 @@
 mod coin_change;
+mod distinct_subsequences;
 mod egg_dropping;
 mod fibonacci;
 mod fractional_knapsack;
@@ -12,13 +13,16 @@ mod matrix_chain_multiply;
 mod maximal_square;
 mod maximum_subarray;
 mod minimum_cost_path;
+mod palindrome_partitioning;
 mod rod_cutting;
 mod snail;
 mod subset_generation;
+mod subset_sum;
 mod trapped_rainwater;
 mod word_break;
 
 pub use self::coin_change::coin_change;
+pub use self::distinct_subsequences::distinct_subsequences;
 pub use self::egg_dropping::egg_drop;
 pub use self::fibonacci::classical_fibonacci;
 pub use self::fibonacci::fibonacci;
@@ -39,8 +43,10 @@ pub use self::matrix_chain_multiply::matrix_chain_multiply;
 pub use self::maximal_square::maximal_square;
 pub use self::maximum_subarray::maximum_subarray;
 pub use self::minimum_cost_path::minimum_cost_path;
-pub use self::rod_cutting::rod_cut;
+pub use self::palindrome_partitioning::min_palindrome_cuts;
+pub use self::rod_cutting::{rod_cut, rod_cutting};
 pub use self::snail::snail;
 pub use self::subset_generation::list_subset;
+pub use self::subset_sum::{can_partition, subset_sum, subset_sum_mitm};
 pub use self::trapped_rainwater::trapped_rainwater;
-pub use self::word_break::word_break;
+pub use self::word_break::{word_break, word_break_all};
@@ -0,0 +1,99 @@
+//! Solves the palindrome partitioning problem
+
+/// `min_palindrome_cuts(s)` returns the least number of cuts splitting `s`
+/// into substrings which are all palindromes. The string is split between
+/// chars, not bytes.
+///
+/// `is_palindrome[i][j]` records whether `s[i..=j]` is a palindrome, which is
+/// the case when its ends are equal and `s[i + 1..j]` is one. Then `cuts[j]`,
+/// the least number of cuts of `s[..=j]`, is 0 if it is a palindrome, and
+/// otherwise `cuts[i - 1] + 1` for the best palindromic last piece `s[i..=j]`.
+///
+/// # Complexity
+///    - time complexity: O(n^2),
+///    - space complexity: O(n^2),
+///
+/// where n is the number of chars of `s`.
+pub fn min_palindrome_cuts(s: &str) -> usize {
+    let chars: Vec<char> = s.chars().collect();
+    let n = chars.len();
+    if n == 0 {
+        return 0;
+    }
+
+    let mut is_palindrome = vec![vec![false; n]; n];
+    let mut cuts = vec![0; n];
+    for j in 0..n {
+        let mut best = j;
+        for i in (0..=j).rev() {
+            if chars[i] == chars[j] && (j - i < 2 || is_palindrome[i + 1][j - 1]) {
+                is_palindrome[i][j] = true;
+                best = if i == 0 { 0 } else { best.min(cuts[i - 1] + 1) };
+            }
+        }
+        cuts[j] = best;
+    }
+    cuts[n - 1]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::min_palindrome_cuts;
+
+    /// Tries every way of cutting `chars`
+    fn brute_force(chars: &[char]) -> usize {
+        let is_palindrome = |piece: &[char]| piece.iter().eq(piece.iter().rev());
+        if is_palindrome(chars) {
+            return 0;
+        }
+        (1..chars.len())
+            .filter(|&i| is_palindrome(&chars[..i]))
+            .map(|i| 1 + brute_force(&chars[i..]))
+            .min()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_min_palindrome_cuts() {
+        assert_eq!(min_palindrome_cuts("aab"), 1);
+        assert_eq!(min_palindrome_cuts("banana"), 1);
+        assert_eq!(min_palindrome_cuts("ababbbabbababa"), 3);
+        assert_eq!(min_palindrome_cuts("noonabbad"), 2);
+    }
+
+    #[test]
+    fn test_palindrome_needs_no_cut() {
+        assert_eq!(min_palindrome_cuts("racecar"), 0);
+        assert_eq!(min_palindrome_cuts("abba"), 0);
+        assert_eq!(min_palindrome_cuts("z"), 0);
+        assert_eq!(min_palindrome_cuts(""), 0);
+    }
+
+    #[test]
+    fn test_distinct_chars() {
+        assert_eq!(min_palindrome_cuts("abcdef"), 5);
+        assert_eq!(min_palindrome_cuts("qwertyuiop"), 9);
+    }
+
+    #[test]
+    fn test_multibyte_chars() {
+        // "été" is a palindrome of 3 chars but not of 5 bytes
+        assert_eq!(min_palindrome_cuts("été"), 0);
+        assert_eq!(min_palindrome_cuts("日本日x"), 1);
+        assert_eq!(min_palindrome_cuts("αβγ"), 2);
+    }
+
+    #[test]
+    fn test_against_brute_force() {
+        for s in [
+            "abacdcaba",
+            "aabbcbbaad",
+            "xyzzyxabc",
+            "abcbdbcba",
+            "aaaabaaa",
+        ] {
+            let chars: Vec<char> = s.chars().collect();
+            assert_eq!(min_palindrome_cuts(s), brute_force(&chars), "{s}");
+        }
+    }
+}
@@ -35,9 +35,49 @@ pub fn rod_cut(p: &[usize]) -> usize {
     }
 }
 
+/// `rod_cutting(prices)` returns the maximum revenue from cutting a rod of
+/// length `n` = `prices.len()`, where a piece of length `l` sells for
+/// `prices[l - 1]`, together with the lengths of the pieces achieving it.
+///
+/// A cut table records the best first piece for every length, and the pieces
+/// are recovered by following it from `n` down to 0. The pieces are listed
+/// from the largest to the smallest.
+///
+/// # Complexity
+///    - time complexity: O(n^2),
+///    - space complexity: O(n),
+///
+/// where n is the length of `prices`.
+pub fn rod_cutting(prices: &[u64]) -> (u64, Vec<usize>) {
+    let n = prices.len();
+    // revenue[i] is the best revenue of a rod of length i
+    let mut revenue = vec![0; n + 1];
+    // cut[i] is the first piece of the best cutting of a rod of length i
+    let mut cut = vec![0; n + 1];
+
+    for i in 1..=n {
+        for piece in 1..=i {
+            let candidate = prices[piece - 1] + revenue[i - piece];
+            if candidate > revenue[i] || cut[i] == 0 {
+                revenue[i] = candidate;
+                cut[i] = piece;
+            }
+        }
+    }
+
+    let mut pieces = Vec::new();
+    let mut length = n;
+    while length > 0 {
+        pieces.push(cut[length]);
+        length -= cut[length];
+    }
+    pieces.sort_unstable_by(|a, b| b.cmp(a));
+    (revenue[n], pieces)
+}
+
 #[cfg(test)]
 mod tests {
-    use super::rod_cut;
+    use super::{rod_cut, rod_cutting};
 
     #[test]
     fn test_rod_cut() {
@@ -52,4 +92,50 @@ mod tests {
         assert_eq!(30, rod_cut(&[1, 5, 8, 9, 10, 17, 17, 20, 24, 30]));
         assert_eq!(12, rod_cut(&[1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12]));
     }
+
+    fn check_pieces(prices: &[u64], revenue: u64, pieces: &[usize]) {
+        assert_eq!(pieces.iter().sum::<usize>(), prices.len());
+        assert_eq!(pieces.iter().map(|&l| prices[l - 1]).sum::<u64>(), revenue);
+    }
+
+    #[test]
+    fn test_rod_cutting_textbook() {
+        // the price table of CLRS, chapter 15.1
+        let prices = [1, 5, 8, 9, 10, 17, 17, 20, 24, 30];
+        let expected = [1, 5, 8, 10, 13, 17, 18, 22, 25, 30];
+        for (n, &best) in expected.iter().enumerate() {
+            let (revenue, pieces) = rod_cutting(&prices[..=n]);
+            assert_eq!(revenue, best);
+            check_pieces(&prices[..=n], revenue, &pieces);
+        }
+        assert_eq!(rod_cutting(&prices[..4]), (10, vec![2, 2]));
+        assert_eq!(rod_cutting(&prices), (30, vec![10]));
+    }
+
+    #[test]
+    fn test_rod_cutting_against_rod_cut() {
+        let tables: [&[u64]; 6] = [
+            &[5, 8, 2],
+            &[5, 8, 2, 1, 7],
+            &[0, 0, 0, 0, 0, 87],
+            &[7, 6, 5, 4, 3, 2, 1],
+            &[6, 4, 8, 2, 5, 8, 2, 3, 7, 11],
+            &[1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12],
+        ];
+        for prices in tables {
+            let (revenue, pieces) = rod_cutting(prices);
+            let as_usize: Vec<usize> = prices.iter().map(|&p| p as usize).collect();
+            assert_eq!(revenue as usize, rod_cut(&as_usize));
+            check_pieces(prices, revenue, &pieces);
+        }
+    }
+
+    #[test]
+    fn test_rod_cutting_empty_and_free() {
+        assert_eq!(rod_cutting(&[]), (0, vec![]));
+        // worthless pieces still add up to the whole rod
+        let (revenue, pieces) = rod_cutting(&[0, 0, 0]);
+        assert_eq!(revenue, 0);
+        assert_eq!(pieces.iter().sum::<usize>(), 3);
+    }
 }
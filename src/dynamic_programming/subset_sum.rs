@@ -0,0 +1,219 @@
+//! Solves the subset sum and the partition problems
+
+/// `subset_sum(nums, target)` returns whether some sub-multiset of `nums`
+/// (possibly empty) sums to `target`.
+///
+/// `reachable[s]` records whether a sum of `s` can be made with the numbers
+/// seen so far. Each number is added to every reachable sum, scanning the sums
+/// from the largest down so that a number is used at most once. A target
+/// beyond the sum of all the numbers is unreachable, without building the
+/// table.
+///
+/// # Complexity
+///    - time complexity: O(n * target),
+///    - space complexity: O(target),
+///
+/// where n is the length of `nums`.
+pub fn subset_sum(nums: &[u64], target: u64) -> bool {
+    // None if the total does not fit in a u64, and then exceeds the target
+    let total = nums.iter().try_fold(0u64, |sum, &num| sum.checked_add(num));
+    match total {
+        Some(total) if target > total => return false,
+        Some(total) if target == total => return true,
+        _ => {}
+    }
+
+    let target = target as usize;
+    let mut reachable = vec![false; target + 1];
+    reachable[0] = true;
+
+    for &num in nums {
+        let num = num as usize;
+        if num > target {
+            continue;
+        }
+        for sum in (num..=target).rev() {
+            if reachable[sum - num] {
+                reachable[sum] = true;
+            }
+        }
+        if reachable[target] {
+            return true;
+        }
+    }
+    reachable[target]
+}
+
+/// `subset_sum_mitm(nums, target)` returns whether some sub-multiset of
+/// `nums` (possibly empty) sums to `target`, with a meet in the middle rather
+/// than a table of the sums, for large or negative numbers.
+///
+/// The numbers are split in two halves, and all the subset sums of each half
+/// are enumerated. The sums of the right half are sorted, and a subset of the
+/// whole sums to `target` if one of them complements a sum of the left half.
+/// The sums are computed on 128 bits, so that they do not overflow.
+///
+/// # Complexity
+///    - time complexity: O(2^(n/2) * n),
+///    - space complexity: O(2^(n/2)),
+///
+/// where n is the length of `nums`: about 40 numbers at most, whatever their
+/// magnitude.
+pub fn subset_sum_mitm(nums: &[i64], target: i64) -> bool {
+    let (left, right) = nums.split_at(nums.len() / 2);
+    let mut right_sums = subset_sums(right);
+    right_sums.sort_unstable();
+    subset_sums(left)
+        .into_iter()
+        .any(|sum| right_sums.binary_search(&(target as i128 - sum)).is_ok())
+}
+
+/// Returns the sums of the 2^n subsets of `nums`
+fn subset_sums(nums: &[i64]) -> Vec<i128> {
+    let mut sums = Vec::with_capacity(1 << nums.len());
+    sums.push(0);
+    for &num in nums {
+        // the subsets with `num` are the ones without it, plus `num`
+        for i in 0..sums.len() {
+            sums.push(sums[i] + num as i128);
+        }
+    }
+    sums
+}
+
+/// `can_partition(nums)` returns whether `nums` can be split into two
+/// multisets of equal sum, i.e. whether some subset sums to half the total.
+/// A total which does not fit in a u64 is taken as no partition.
+pub fn can_partition(nums: &[u64]) -> bool {
+    let Some(total) = nums.iter().try_fold(0u64, |sum, &num| sum.checked_add(num)) else {
+        return false;
+    };
+    if total % 2 == 1 {
+        return false;
+    }
+    subset_sum(nums, total / 2)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::general::Xorshift64;
+
+    fn brute_force(nums: &[u64], target: u64) -> bool {
+        (0..1u32 << nums.len()).any(|mask| {
+            let sum: u64 = (0..nums.len())
+                .filter(|&i| mask >> i & 1 == 1)
+                .map(|i| nums[i])
+                .sum();
+            sum == target
+        })
+    }
+
+    #[test]
+    fn test_can_partition() {
+        assert!(can_partition(&[1, 5, 11, 5]));
+        assert!(!can_partition(&[1, 2, 3, 5]));
+        assert!(can_partition(&[3, 1, 1, 2, 2, 1]));
+        // even total, but 100 is more than half of it
+        assert!(!can_partition(&[100, 2, 4]));
+        assert!(!can_partition(&[7]));
+        assert!(can_partition(&[0, 0]));
+    }
+
+    #[test]
+    fn test_subset_sum() {
+        let nums = [3, 34, 4, 12, 5, 2];
+        assert!(subset_sum(&nums, 9));
+        assert!(!subset_sum(&nums, 30));
+        assert!(subset_sum(&nums, 60));
+        assert!(!subset_sum(&nums, 61));
+        // each number is used at most once
+        assert!(!subset_sum(&[5], 10));
+    }
+
+    #[test]
+    fn test_empty_and_zero_target() {
+        assert!(subset_sum(&[], 0));
+        assert!(!subset_sum(&[], 1));
+        assert!(subset_sum(&[4, 7], 0));
+        assert!(can_partition(&[]));
+    }
+
+    #[test]
+    fn test_large_targets() {
+        // beyond the total, without a table of the sums up to the target
+        assert!(!subset_sum(&[1, 2], u64::MAX));
+        assert!(!subset_sum(&[3, 5, 7], 1 << 40));
+        assert!(subset_sum(&[u64::MAX], u64::MAX));
+        assert!(subset_sum(&[1 << 40, 1, 1 << 41], 3 << 40 | 1));
+        // the total overflows
+        assert!(!subset_sum(&[u64::MAX, 2, 3], 4));
+        assert!(!can_partition(&[u64::MAX, u64::MAX]));
+        assert!(!can_partition(&[u64::MAX, 1]));
+    }
+
+    #[test]
+    fn test_against_brute_force() {
+        let nums = [8, 3, 15, 1, 6, 6, 22, 9, 4];
+        for target in 0..=80 {
+            assert_eq!(subset_sum(&nums, target), brute_force(&nums, target));
+        }
+        for len in 0..=nums.len() {
+            let total: u64 = nums[..len].iter().sum();
+            let expected = total.is_multiple_of(2) && brute_force(&nums[..len], total / 2);
+            assert_eq!(can_partition(&nums[..len]), expected);
+        }
+    }
+
+    #[test]
+    fn test_mitm_against_dp() {
+        let nums = [8, 3, 15, 1, 6, 6, 22, 9, 4];
+        let signed: Vec<i64> = nums.iter().map(|&num| num as i64).collect();
+        for target in 0..=80 {
+            assert_eq!(
+                subset_sum_mitm(&signed, target as i64),
+                subset_sum(&nums, target)
+            );
+        }
+        assert!(!subset_sum_mitm(&signed, -1));
+        // halves of lengths 0 and 1
+        assert!(subset_sum_mitm(&[], 0));
+        assert!(!subset_sum_mitm(&[], 5));
+        assert!(subset_sum_mitm(&[5], 5));
+        assert!(!subset_sum_mitm(&[5], 10));
+    }
+
+    #[test]
+    fn test_mitm_negative_numbers() {
+        let nums = [-7, 3, -2, 11, 5, -13, 4];
+        for target in -40..=40 {
+            let expected = (0..1u32 << nums.len()).any(|mask| {
+                let sum: i64 = (0..nums.len())
+                    .filter(|&i| mask >> i & 1 == 1)
+                    .map(|i| nums[i])
+                    .sum();
+                sum == target
+            });
+            assert_eq!(subset_sum_mitm(&nums, target), expected, "{target}");
+        }
+    }
+
+    #[test]
+    fn test_mitm_large_target() {
+        // 40 even numbers near 10^12, far too large for a table of the sums
+        let mut rng = Xorshift64::new(293);
+        let nums: Vec<i64> = (0..40)
+            .map(|_| 2 * rng.gen_range(100_000_000_000..500_000_000_000) as i64)
+            .collect();
+        let target: i64 = nums.iter().step_by(3).sum();
+        assert!(subset_sum_mitm(&nums, target));
+        // an odd sum is out of reach
+        assert!(!subset_sum_mitm(&nums, target + 1));
+        assert!(subset_sum_mitm(&nums, nums.iter().sum()));
+        // the sums do not overflow
+        assert!(subset_sum_mitm(
+            &[i64::MAX, i64::MAX, i64::MIN],
+            i64::MAX - 1
+        ));
+    }
+}
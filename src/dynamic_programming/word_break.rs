@@ -1,5 +1,6 @@
 // Given a string and a list of words, return true if the string can be
-// segmented into a space-separated sequence of one or more words.
+// segmented into a space-separated sequence of one or more words, or list
+// all such segmentations.
 
 // Note that the same word may be reused
 // multiple times in the segmentation.
@@ -10,14 +11,31 @@
 
 use crate::data_structures::Trie;
 
-pub fn word_break(s: &str, word_dict: Vec<&str>) -> bool {
+pub fn word_break(s: &str, word_dict: &[&str]) -> bool {
+    let trie = build_trie(word_dict);
+    let mut memo = vec![None; s.len()];
+    search(&trie, s, 0, &mut memo)
+}
+
+// Returns every segmentation of `s` into words of `word_dict`, as the words
+// joined by spaces. The segmentations are ordered by the lengths of their
+// words, shortest first word first.
+pub fn word_break_all(s: &str, word_dict: &[&str]) -> Vec<String> {
+    if s.is_empty() {
+        // the empty segmentation
+        return vec![String::new()];
+    }
+    let trie = build_trie(word_dict);
+    let mut memo = vec![None; s.len()];
+    search_all(&trie, s, 0, &mut memo).clone()
+}
+
+fn build_trie(word_dict: &[&str]) -> Trie<char, bool> {
     let mut trie = Trie::new();
     for word in word_dict {
         trie.insert(word.chars(), true); // Insert each word with a value `true`
     }
-
-    let mut memo = vec![None; s.len()];
-    search(&trie, s, 0, &mut memo)
+    trie
 }
 
 fn search(trie: &Trie<char, bool>, s: &str, start: usize, memo: &mut Vec<Option<bool>>) -> bool {
@@ -30,7 +48,7 @@ fn search(trie: &Trie<char, bool>, s: &str, start: usize, memo: &mut Vec<Option<
     }
 
     let _node = trie;
-    for end in start + 1..=s.len() {
+    for end in (start + 1..=s.len()).filter(|&end| s.is_char_boundary(end)) {
         // Using trie.get to check if a substring is a word
         if trie.get(s[start..end].chars()).is_some() && search(trie, s, end, memo) {
             memo[start] = Some(true);
@@ -42,42 +60,127 @@ fn search(trie: &Trie<char, bool>, s: &str, start: usize, memo: &mut Vec<Option<
     false
 }
 
+// Memoized segmentations of `s[start..]`, each kept once the suffix is solved
+fn search_all<'a>(
+    trie: &Trie<char, bool>,
+    s: &str,
+    start: usize,
+    memo: &'a mut Vec<Option<Vec<String>>>,
+) -> &'a Vec<String> {
+    if memo[start].is_none() {
+        let mut sentences = Vec::new();
+        for end in (start + 1..=s.len()).filter(|&end| s.is_char_boundary(end)) {
+            let word = &s[start..end];
+            if trie.get(word.chars()).is_none() {
+                continue;
+            }
+            if end == s.len() {
+                sentences.push(word.to_string());
+            } else {
+                for rest in search_all(trie, s, end, memo) {
+                    sentences.push(format!("{word} {rest}"));
+                }
+            }
+        }
+        memo[start] = Some(sentences);
+    }
+    memo[start].as_ref().unwrap()
+}
+
 #[cfg(test)]
 mod tests {
-    use super::word_break;
+    use super::{word_break, word_break_all};
 
     #[test]
     fn typical_cases() {
-        assert!(word_break("applepenapple", vec!["apple", "pen"]));
+        assert!(word_break("applepenapple", &["apple", "pen"]));
         assert!(!word_break(
             "catsandog",
-            vec!["cats", "dog", "sand", "and", "cat"]
+            &["cats", "dog", "sand", "and", "cat"]
         ));
-        assert!(word_break("cars", vec!["car", "ca", "rs"]));
+        assert!(word_break("cars", &["car", "ca", "rs"]));
     }
 
     #[test]
     fn edge_cases() {
-        assert!(!word_break("abc", vec![]));
-        assert!(word_break("a", vec!["a"]));
+        assert!(!word_break("abc", &[]));
+        assert!(word_break("a", &["a"]));
+        assert!(word_break("", &["a"]));
+        assert_eq!(word_break_all("", &["a"]), [""]);
     }
 
     #[test]
     fn repeated_words() {
-        assert!(word_break("aabb", vec!["a", "b"]));
-        assert!(word_break("aaaaaaa", vec!["a", "aa", "aaa"]));
+        assert!(word_break("aabb", &["a", "b"]));
+        assert!(word_break("aaaaaaa", &["a", "aa", "aaa"]));
     }
 
     #[test]
     fn no_solution() {
-        assert!(!word_break("abcdef", vec!["ab", "abc", "cd"]));
-        assert!(!word_break("xyz", vec!["a", "b", "c"]));
+        assert!(!word_break("abcdef", &["ab", "abc", "cd"]));
+        assert!(!word_break("xyz", &["a", "b", "c"]));
     }
 
     #[test]
     fn long_string() {
         let long_string = "a".repeat(100);
-        let words = vec!["a", "aa", "aaa", "aaaa"];
-        assert!(word_break(&long_string, words));
+        let words = ["a", "aa", "aaa", "aaaa"];
+        assert!(word_break(&long_string, &words));
+    }
+
+    #[test]
+    fn leetcode() {
+        assert!(word_break("leetcode", &["leet", "code"]));
+        assert_eq!(word_break_all("leetcode", &["leet", "code"]), ["leet code"]);
+    }
+
+    #[test]
+    fn all_segmentations() {
+        assert_eq!(
+            word_break_all("catsanddog", &["cat", "cats", "and", "sand", "dog"]),
+            ["cat sand dog", "cats and dog"]
+        );
+        assert_eq!(
+            word_break_all(
+                "pineapplepenapple",
+                &["apple", "pen", "applepen", "pine", "pineapple"]
+            ),
+            [
+                "pine apple pen apple",
+                "pine applepen apple",
+                "pineapple pen apple"
+            ]
+        );
+        assert_eq!(
+            word_break_all("aaaa", &["a", "aa"]),
+            ["a a a a", "a a aa", "a aa a", "aa a a", "aa aa"]
+        );
+    }
+
+    #[test]
+    fn all_segmentations_without_solution() {
+        assert!(word_break_all("catsandog", &["cats", "dog", "sand", "and", "cat"]).is_empty());
+        assert!(word_break_all("abc", &[]).is_empty());
+    }
+
+    #[test]
+    fn all_segmentations_match_word_break() {
+        let dict = ["a", "ab", "ba", "bab", "c"];
+        for s in ["abab", "babc", "cc", "abba", "ababcba", "bb", "b"] {
+            assert_eq!(word_break(s, &dict), !word_break_all(s, &dict).is_empty());
+            for sentence in word_break_all(s, &dict) {
+                assert_eq!(sentence.replace(' ', ""), s);
+                assert!(sentence.split(' ').all(|word| dict.contains(&word)));
+            }
+        }
+    }
+
+    #[test]
+    fn multibyte_chars() {
+        assert!(word_break("日本語", &["日", "本語"]));
+        assert_eq!(
+            word_break_all("日本語", &["日本", "日", "本語", "語"]),
+            ["日 本語", "日本 語"]
+        );
     }
 }
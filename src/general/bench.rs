@@ -0,0 +1,113 @@
+use std::fmt;
+use std::time::{Duration, Instant};
+
+/// The wall times of the iterations of a `bench`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BenchResult {
+    pub name: String,
+    pub iters: usize,
+    pub min: Duration,
+    pub max: Duration,
+    pub mean: Duration,
+    pub median: Duration,
+    /// The standard deviation of the time of an iteration
+    pub stddev: Duration,
+}
+
+impl fmt::Display for BenchResult {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}: {} iterations, mean {:?} ± {:?}, median {:?}, min {:?}, max {:?}",
+            self.name, self.iters, self.mean, self.stddev, self.median, self.min, self.max
+        )
+    }
+}
+
+/// Runs `f` `iters` times, timing each run, and returns the statistics of the
+/// times. It is a rough measure, to compare implementations of an algorithm:
+/// unlike a real benchmark harness, it does not warm up, nor keep the
+/// compiler from optimizing away results which `f` does not use (see
+/// `std::hint::black_box`).
+///
+/// # Panics
+///
+/// Panics if `iters` is 0.
+pub fn bench(name: &str, iters: usize, mut f: impl FnMut()) -> BenchResult {
+    assert!(iters > 0, "at least one iteration is needed");
+    let mut times: Vec<Duration> = (0..iters)
+        .map(|_| {
+            let start = Instant::now();
+            f();
+            start.elapsed()
+        })
+        .collect();
+    times.sort_unstable();
+
+    let seconds: Vec<f64> = times.iter().map(Duration::as_secs_f64).collect();
+    let mean = seconds.iter().sum::<f64>() / iters as f64;
+    let variance = seconds.iter().map(|s| (s - mean).powi(2)).sum::<f64>() / iters as f64;
+    let median = if iters % 2 == 1 {
+        times[iters / 2]
+    } else {
+        (times[iters / 2 - 1] + times[iters / 2]) / 2
+    };
+    BenchResult {
+        name: name.to_string(),
+        iters,
+        min: times[0],
+        max: times[iters - 1],
+        // within the bounds, despite rounding errors
+        mean: Duration::from_secs_f64(mean).clamp(times[0], times[iters - 1]),
+        median,
+        stddev: Duration::from_secs_f64(variance.sqrt()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::hint::black_box;
+
+    fn assert_consistent(result: &BenchResult) {
+        assert!(result.min <= result.median && result.median <= result.max);
+        assert!(result.min <= result.mean && result.mean <= result.max);
+        assert!(result.stddev <= result.max - result.min);
+    }
+
+    #[test]
+    fn runs_every_iteration() {
+        let mut calls = 0;
+        let result = bench("sum", 25, || {
+            calls += 1;
+            black_box((0..1000u64).sum::<u64>());
+        });
+        assert_eq!(calls, 25);
+        assert_eq!(result.iters, 25);
+        assert_eq!(result.name, "sum");
+        assert_consistent(&result);
+    }
+
+    #[test]
+    fn measures_the_time() {
+        let result = bench("sleep", 4, || std::thread::sleep(Duration::from_millis(2)));
+        assert!(result.min >= Duration::from_millis(2));
+        assert_consistent(&result);
+        assert!(result.to_string().starts_with("sleep: 4 iterations, mean "));
+    }
+
+    #[test]
+    fn single_iteration() {
+        let result = bench("once", 1, || {});
+        assert_eq!(result.min, result.max);
+        assert_eq!(result.median, result.min);
+        assert_eq!(result.mean, result.min);
+        assert_eq!(result.stddev, Duration::ZERO);
+    }
+
+    #[test]
+    #[should_panic]
+    fn no_iteration() {
+        bench("never", 0, || {});
+    }
+}
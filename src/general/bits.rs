@@ -0,0 +1,154 @@
+//! # Bit manipulation
+//!
+//! Constant time tricks on the bits of a `u64`, without loops over its 64
+//! bits. The standard library has most of them as methods, compiled to
+//! single instructions where the processor has them; these show how they
+//! work.
+//!
+//! Wikipedia: <https://en.wikipedia.org/wiki/Bit_manipulation>
+
+/// Returns the number of ones in `n`, its population count: the bits are
+/// summed in pairs, then in nibbles, then bytes, each step adding
+/// neighboring fields in parallel, and the multiplication sums the bytes
+/// into the top one.
+pub fn count_set_bits(n: u64) -> u32 {
+    let pairs = n - ((n >> 1) & 0x5555_5555_5555_5555);
+    let nibbles = (pairs & 0x3333_3333_3333_3333) + ((pairs >> 2) & 0x3333_3333_3333_3333);
+    let bytes = (nibbles + (nibbles >> 4)) & 0x0f0f_0f0f_0f0f_0f0f;
+    (bytes.wrapping_mul(0x0101_0101_0101_0101) >> 56) as u32
+}
+
+/// Returns whether `n` is a power of two: clearing its lowest set bit with
+/// `n & (n - 1)` leaves 0. 0 is not a power of two.
+pub fn is_power_of_two(n: u64) -> bool {
+    n != 0 && n & (n - 1) == 0
+}
+
+/// Returns the smallest power of two greater than or equal to `n`, by
+/// spreading the highest set bit of `n - 1` to all the bits below it: 1 for
+/// 0 and 1, and `None` past 2^63.
+pub fn next_power_of_two(n: u64) -> Option<u64> {
+    if n <= 1 {
+        return Some(1);
+    }
+    let mut spread = n - 1;
+    for shift in [1, 2, 4, 8, 16, 32] {
+        spread |= spread >> shift;
+    }
+    spread.checked_add(1)
+}
+
+/// Returns the index, from 0 for the least significant, of the lowest set
+/// bit of `n`, which `n & -n` isolates, or `None` for 0
+pub fn lowest_set_bit(n: u64) -> Option<u32> {
+    if n == 0 {
+        return None;
+    }
+    let lowest = n & n.wrapping_neg();
+    // the index of the single set bit is the number of ones below it
+    Some(count_set_bits(lowest - 1))
+}
+
+/// Returns `n` with its bits in reverse order, by swapping its halves, then
+/// the halves of each half, and so on down to single bits
+pub fn reverse_bits(n: u64) -> u64 {
+    let mut n = n.rotate_left(32);
+    for (shift, mask) in [
+        (16, 0x0000_ffff_0000_ffff),
+        (8, 0x00ff_00ff_00ff_00ff),
+        (4, 0x0f0f_0f0f_0f0f_0f0f),
+        (2, 0x3333_3333_3333_3333),
+        (1, 0x5555_5555_5555_5555),
+    ] {
+        n = ((n >> shift) & mask) | ((n & mask) << shift);
+    }
+    n
+}
+
+/// Returns the `n`-th reflected binary Gray code, in which consecutive codes
+/// differ by a single bit
+pub fn gray_code(n: u64) -> u64 {
+    n ^ (n >> 1)
+}
+
+/// Returns the `n` whose Gray code is `code`: each bit of `n` is the xor of
+/// the bits of `code` above and at it, computed as a prefix xor over
+/// doubling shifts
+pub fn inverse_gray_code(code: u64) -> u64 {
+    let mut n = code;
+    for shift in [1, 2, 4, 8, 16, 32] {
+        n ^= n >> shift;
+    }
+    n
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::general::Xorshift64;
+
+    /// Edge cases, then random numbers of every bit length
+    fn test_numbers() -> Vec<u64> {
+        let mut rng = Xorshift64::new(292);
+        let mut numbers = vec![0, 1, 2, 3, u64::MAX, u64::MAX - 1, 1 << 63, (1 << 63) + 1];
+        numbers.extend((0..64).map(|bits| rng.next_u64() >> bits));
+        numbers
+    }
+
+    #[test]
+    fn hand_computed_values() {
+        assert_eq!(count_set_bits(0b1011_0001), 4);
+        assert!(is_power_of_two(1024) && !is_power_of_two(1023));
+        assert_eq!(next_power_of_two(100), Some(128));
+        assert_eq!(next_power_of_two(128), Some(128));
+        assert_eq!(lowest_set_bit(0b1011_0000), Some(4));
+        assert_eq!(reverse_bits(1), 1 << 63);
+        assert_eq!(reverse_bits(0b1101), 0b1011 << 60);
+        // 0, 1, 11, 10, 110, 111, 101, 100
+        let codes: Vec<u64> = (0..8).map(gray_code).collect();
+        assert_eq!(codes, vec![0, 1, 3, 2, 6, 7, 5, 4]);
+    }
+
+    #[test]
+    fn agree_with_the_standard_library() {
+        for n in test_numbers() {
+            assert_eq!(count_set_bits(n), n.count_ones(), "{n}");
+            assert_eq!(is_power_of_two(n), n.is_power_of_two(), "{n}");
+            assert_eq!(next_power_of_two(n), n.checked_next_power_of_two(), "{n}");
+            assert_eq!(
+                lowest_set_bit(n),
+                (n != 0).then(|| n.trailing_zeros()),
+                "{n}"
+            );
+            assert_eq!(reverse_bits(n), n.reverse_bits(), "{n}");
+        }
+    }
+
+    #[test]
+    fn edges() {
+        assert_eq!(count_set_bits(u64::MAX), 64);
+        assert!(!is_power_of_two(0));
+        assert!(!is_power_of_two(u64::MAX));
+        assert_eq!(next_power_of_two(0), Some(1));
+        assert_eq!(next_power_of_two(1 << 63), Some(1 << 63));
+        assert_eq!(next_power_of_two((1 << 63) + 1), None);
+        assert_eq!(next_power_of_two(u64::MAX), None);
+        assert_eq!(lowest_set_bit(0), None);
+        assert_eq!(lowest_set_bit(u64::MAX), Some(0));
+        assert_eq!(lowest_set_bit(1 << 63), Some(63));
+        assert_eq!(reverse_bits(u64::MAX), u64::MAX);
+        assert_eq!(gray_code(u64::MAX), 1 << 63);
+        assert_eq!(inverse_gray_code(1 << 63), u64::MAX);
+    }
+
+    #[test]
+    fn gray_codes() {
+        for n in test_numbers() {
+            assert_eq!(inverse_gray_code(gray_code(n)), n, "{n}");
+            // the next code flips a single bit
+            if n < u64::MAX {
+                assert_eq!(count_set_bits(gray_code(n) ^ gray_code(n + 1)), 1);
+            }
+        }
+    }
+}
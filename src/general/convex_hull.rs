@@ -1,20 +1,32 @@
-use std::cmp::Ordering::Equal;
+use std::cmp::Ordering::{Equal, Greater, Less};
 
-fn sort_by_min_angle(pts: &[(f64, f64)], min: &(f64, f64)) -> Vec<(f64, f64)> {
-    let mut points: Vec<(f64, f64, (f64, f64))> = pts
-        .iter()
-        .map(|x| {
-            (
-                (x.1 - min.1).atan2(x.0 - min.0),
-                // angle
-                (x.1 - min.1).hypot(x.0 - min.0),
-                // distance (we want the closest to be first)
-                *x,
-            )
-        })
-        .collect();
+// the points without their duplicates
+fn distinct(pts: &[(f64, f64)]) -> Vec<(f64, f64)> {
+    let mut points = pts.to_vec();
     points.sort_by(|a, b| a.partial_cmp(b).unwrap_or(Equal));
-    points.into_iter().map(|x| x.2).collect()
+    points.dedup();
+    points
+}
+
+fn sort_by_min_angle(pts: &[(f64, f64)], min: &(f64, f64)) -> Vec<(f64, f64)> {
+    let mut points = pts.to_vec();
+    // all the points are above min or on its right, so that the sign of the
+    // vector product orders them by angle
+    points.sort_by(|a, b| {
+        match calc_z_coord_vector_product(min, a, b)
+            .partial_cmp(&0.)
+            .unwrap_or(Equal)
+        {
+            Greater => Less,
+            Less => Greater,
+            // distance (we want the closest to be first)
+            Equal => (a.1 - min.1)
+                .hypot(a.0 - min.0)
+                .partial_cmp(&(b.1 - min.1).hypot(b.0 - min.0))
+                .unwrap_or(Equal),
+        }
+    });
+    points
 }
 
 // calculates the z coordinate of the vector product of vectors ab and ac
@@ -25,6 +37,7 @@ fn calc_z_coord_vector_product(a: &(f64, f64), b: &(f64, f64), c: &(f64, f64)) -
 /*
     If three points are aligned and are part of the convex hull then the three are kept.
     If one doesn't want to keep those points, it is easy to iterate the answer and remove them.
+    Duplicate points are only given once.
 
     The first point is the one with the lowest y-coordinate and the lowest x-coordinate.
     Points are then given counter-clockwise, and the closest one is given first if needed.
@@ -34,6 +47,7 @@ pub fn convex_hull_graham(pts: &[(f64, f64)]) -> Vec<(f64, f64)> {
         return vec![];
     }
 
+    let pts = distinct(pts);
     let mut stack: Vec<(f64, f64)> = vec![];
     let min = pts
         .iter()
@@ -45,12 +59,23 @@ pub fn convex_hull_graham(pts: &[(f64, f64)]) -> Vec<(f64, f64)> {
             }
         })
         .unwrap();
-    let points = sort_by_min_angle(pts, min);
+    let mut points = sort_by_min_angle(&pts, min);
 
     if points.len() <= 3 {
         return points;
     }
 
+    // the points aligned with min on the last edge of the hull come back to
+    // it, the farthest first, unless all the points are aligned
+    let last = points[points.len() - 1];
+    let mut start = points.len() - 1;
+    while start > 0 && calc_z_coord_vector_product(min, &last, &points[start - 1]) == 0. {
+        start -= 1;
+    }
+    if start > 0 {
+        points[start..].reverse();
+    }
+
     for point in points {
         while stack.len() > 1
             && calc_z_coord_vector_product(&stack[stack.len() - 2], &stack[stack.len() - 1], &point)
@@ -64,6 +89,96 @@ pub fn convex_hull_graham(pts: &[(f64, f64)]) -> Vec<(f64, f64)> {
     stack
 }
 
+/*
+    QuickHull, a divide and conquer alternative to the Graham scan.
+
+    The leftmost and rightmost points are on the hull and split the other points
+    in two sides of the line joining them. On each side, the point farthest from
+    the line is on the hull too, and the points inside the triangle it forms
+    with the line are dropped; the search goes on recursively with the points
+    outside each of the two new edges, or on them.
+
+    The hull is the same as the one of `convex_hull_graham`: the points aligned
+    on its edges are kept, the duplicate points are given once, and the points
+    are given counter-clockwise from the one with the lowest y-coordinate and
+    the lowest x-coordinate.
+
+    Time complexity: O(n log(n)) on average, O(n^2) in the worst case.
+*/
+pub fn quickhull(pts: &[(f64, f64)]) -> Vec<(f64, f64)> {
+    let pts = distinct(pts);
+    let by_x = |a: &&(f64, f64), b: &&(f64, f64)| {
+        a.0.partial_cmp(&b.0)
+            .unwrap_or(Equal)
+            .then(a.1.partial_cmp(&b.1).unwrap_or(Equal))
+    };
+    let (Some(&left), Some(&right)) = (pts.iter().min_by(by_x), pts.iter().max_by(by_x)) else {
+        return vec![];
+    };
+    let by_y = |a: &(f64, f64), b: &(f64, f64)| {
+        a.1.partial_cmp(&b.1)
+            .unwrap_or(Equal)
+            .then(a.0.partial_cmp(&b.0).unwrap_or(Equal))
+    };
+
+    if pts
+        .iter()
+        .all(|p| calc_z_coord_vector_product(&left, &right, p) == 0.)
+    {
+        // all the points are on a segment, given from its lowest end
+        let start = *pts.iter().min_by(|a, b| by_y(a, b)).unwrap();
+        return sort_by_min_angle(&pts, &start);
+    }
+
+    let side = |a: &(f64, f64), b: &(f64, f64)| -> Vec<(f64, f64)> {
+        pts.iter()
+            .copied()
+            .filter(|p| p != a && p != b && calc_z_coord_vector_product(a, b, p) <= 0.)
+            .collect()
+    };
+
+    // counter-clockwise: the lower part of the hull from left to right, then
+    // the upper part back from right to left
+    let mut hull = vec![left];
+    hull_side(&left, &right, &side(&left, &right), &mut hull);
+    hull.push(right);
+    hull_side(&right, &left, &side(&right, &left), &mut hull);
+
+    let start = (0..hull.len())
+        .min_by(|&i, &j| by_y(&hull[i], &hull[j]))
+        .unwrap();
+    hull.rotate_left(start);
+    hull
+}
+
+// pushes the points of the hull strictly between a and b, given the points
+// on the right of the directed line from a to b or strictly between them on it
+fn hull_side(a: &(f64, f64), b: &(f64, f64), pts: &[(f64, f64)], hull: &mut Vec<(f64, f64)>) {
+    let farthest = pts
+        .iter()
+        .min_by(|p, q| {
+            calc_z_coord_vector_product(a, b, p)
+                .partial_cmp(&calc_z_coord_vector_product(a, b, q))
+                .unwrap_or(Equal)
+        })
+        .copied()
+        .filter(|p| calc_z_coord_vector_product(a, b, p) < 0.);
+    let Some(farthest) = farthest else {
+        // a to b is an edge of the hull, along which the points are aligned
+        hull.extend(sort_by_min_angle(pts, a));
+        return;
+    };
+    let outside = |from: &(f64, f64), to: &(f64, f64)| -> Vec<(f64, f64)> {
+        pts.iter()
+            .copied()
+            .filter(|p| p != from && p != to && calc_z_coord_vector_product(from, to, p) <= 0.)
+            .collect()
+    };
+    hull_side(a, &farthest, &outside(a, &farthest), hull);
+    hull.push(farthest);
+    hull_side(&farthest, b, &outside(&farthest, b), hull);
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -171,4 +286,139 @@ mod tests {
 
         assert_eq!(convex_hull_graham(&list), ans);
     }
+
+    #[test]
+    fn quickhull_small_inputs() {
+        assert_eq!(quickhull(&[]), vec![]);
+        assert_eq!(quickhull(&[(1., 2.)]), vec![(1., 2.)]);
+        assert_eq!(quickhull(&[(1., 2.), (1., 2.)]), vec![(1., 2.)]);
+        assert_eq!(quickhull(&[(3., 1.), (0., 0.)]), vec![(0., 0.), (3., 1.)]);
+        assert_eq!(
+            quickhull(&[(2., 2.), (1., 2.), (0., 0.)]),
+            vec![(0., 0.), (2., 2.), (1., 2.)]
+        );
+    }
+
+    #[test]
+    fn quickhull_same_as_graham() {
+        let list = vec![
+            (4.4, 14.),
+            (6.7, 15.25),
+            (6.9, 12.8),
+            (2.1, 11.1),
+            (9.5, 14.9),
+            (13.2, 11.9),
+            (10.3, 12.3),
+            (6.8, 9.5),
+            (3.3, 7.7),
+            (0.6, 5.1),
+            (5.3, 2.4),
+            (8.45, 4.7),
+            (11.5, 9.6),
+            (13.8, 7.3),
+            (12.9, 3.1),
+            (11., 1.1),
+        ];
+        assert_eq!(quickhull(&list), convex_hull_graham(&list));
+    }
+
+    #[test]
+    fn quickhull_random_points_same_as_graham() {
+        let mut rng = crate::general::Xorshift64::new(243);
+        for n in [4, 10, 50, 300] {
+            let pts: Vec<(f64, f64)> = (0..n)
+                .map(|_| (rng.next_f64() * 100. - 50., rng.next_f64() * 100. - 50.))
+                .collect();
+            assert_eq!(quickhull(&pts), convex_hull_graham(&pts));
+        }
+    }
+
+    #[test]
+    fn collinear_and_duplicate_points() {
+        // a square with points along its edges, its corners repeated and
+        // points inside
+        let mut pts = vec![];
+        for i in 0..=4 {
+            for j in 0..=4 {
+                pts.push((i as f64, j as f64));
+            }
+        }
+        pts.extend([(0., 0.), (4., 4.), (4., 0.), (2., 2.)]);
+        let boundary = vec![
+            (0., 0.),
+            (1., 0.),
+            (2., 0.),
+            (3., 0.),
+            (4., 0.),
+            (4., 1.),
+            (4., 2.),
+            (4., 3.),
+            (4., 4.),
+            (3., 4.),
+            (2., 4.),
+            (1., 4.),
+            (0., 4.),
+            (0., 3.),
+            (0., 2.),
+            (0., 1.),
+        ];
+        assert_eq!(convex_hull_graham(&pts), boundary);
+        assert_eq!(quickhull(&pts), boundary);
+
+        // all the points on a line, from its lowest end
+        let line: Vec<(f64, f64)> = (0..6).map(|i| (5. - i as f64, 2. * i as f64)).collect();
+        let mut reversed = line.clone();
+        reversed.reverse();
+        reversed.push((2., 6.));
+        assert_eq!(convex_hull_graham(&reversed), line);
+        assert_eq!(quickhull(&reversed), line);
+        assert_eq!(convex_hull_graham(&[(3., 3.); 5]), vec![(3., 3.)]);
+        assert_eq!(quickhull(&[(3., 3.); 5]), vec![(3., 3.)]);
+    }
+
+    /// Checks that `hull` is counter-clockwise around `pts`, and made of all
+    /// the points on its edges
+    fn assert_boundary(pts: &[(f64, f64)], hull: &[(f64, f64)]) {
+        let n = hull.len();
+        let on_edge = |p: &(f64, f64)| {
+            (0..n).any(|i| {
+                let (a, b) = (hull[i], hull[(i + 1) % n]);
+                calc_z_coord_vector_product(&a, &b, p) == 0.
+                    && a.0.min(b.0) <= p.0
+                    && p.0 <= a.0.max(b.0)
+                    && a.1.min(b.1) <= p.1
+                    && p.1 <= a.1.max(b.1)
+            })
+        };
+        let collinear = pts
+            .iter()
+            .all(|p| calc_z_coord_vector_product(&hull[0], &hull[n - 1], p) == 0.);
+        if !collinear {
+            for i in 0..n {
+                let (a, b) = (hull[i], hull[(i + 1) % n]);
+                assert!(pts
+                    .iter()
+                    .all(|p| calc_z_coord_vector_product(&a, &b, p) >= 0.));
+            }
+        }
+        let expected: Vec<(f64, f64)> = distinct(pts).into_iter().filter(on_edge).collect();
+        assert_eq!(hull.len(), expected.len(), "{hull:?}");
+        assert_eq!(distinct(hull), expected);
+    }
+
+    #[test]
+    fn quickhull_same_as_graham_on_grid_points() {
+        // few distinct coordinates, for many aligned and duplicate points
+        let mut rng = crate::general::Xorshift64::new(2430);
+        for n in [3, 5, 10, 30, 100] {
+            for _ in 0..50 {
+                let pts: Vec<(f64, f64)> = (0..n)
+                    .map(|_| (rng.gen_range(0..5) as f64, rng.gen_range(0..5) as f64))
+                    .collect();
+                let hull = convex_hull_graham(&pts);
+                assert_boundary(&pts, &hull);
+                assert_eq!(quickhull(&pts), hull, "{pts:?}");
+            }
+        }
+    }
 }
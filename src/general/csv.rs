@@ -0,0 +1,151 @@
+//! Loading numeric datasets from comma-separated values, one point per line,
+//! into the `Vec<Vec<f64>>` that k-means and the classifiers take:
+//!
+//! ```text
+//! height,weight
+//! 1.62,55.0
+//! 1.80,78.5
+//! ```
+//!
+//! Only numbers are read: there are no quoted fields. The fields are trimmed,
+//! and the blank lines skipped.
+//!
+//! Wikipedia: <https://en.wikipedia.org/wiki/Comma-separated_values>
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum CsvError {
+    /// A header was expected, but the text has no line
+    MissingHeader,
+    /// The field in the `column` (from 1) of the `line` (from 1) is not a
+    /// finite number
+    InvalidNumber { line: usize, column: usize },
+    /// The `line` (from 1) has `found` fields, where the previous lines had
+    /// `expected`
+    RaggedRow {
+        line: usize,
+        expected: usize,
+        found: usize,
+    },
+}
+
+/// Parses the comma-separated numbers of `text`, skipping its first
+/// non-blank line if `has_header`, into one row per line. All the rows must
+/// have as many fields as the first one.
+pub fn read_numeric_csv(text: &str, has_header: bool) -> Result<Vec<Vec<f64>>, CsvError> {
+    let mut lines = text
+        .lines()
+        .enumerate()
+        .map(|(index, line)| (index + 1, line))
+        .filter(|(_, line)| !line.trim().is_empty());
+    if has_header && lines.next().is_none() {
+        return Err(CsvError::MissingHeader);
+    }
+
+    let mut rows: Vec<Vec<f64>> = vec![];
+    for (line, text) in lines {
+        let row = text
+            .split(',')
+            .enumerate()
+            .map(|(index, field)| {
+                // `str::parse` also reads "nan", "inf" and "infinity"
+                field
+                    .trim()
+                    .parse()
+                    .ok()
+                    .filter(|number: &f64| number.is_finite())
+                    .ok_or(CsvError::InvalidNumber {
+                        line,
+                        column: index + 1,
+                    })
+            })
+            .collect::<Result<Vec<f64>, _>>()?;
+        if let Some(first) = rows.first() {
+            if row.len() != first.len() {
+                return Err(CsvError::RaggedRow {
+                    line,
+                    expected: first.len(),
+                    found: row.len(),
+                });
+            }
+        }
+        rows.push(row);
+    }
+    Ok(rows)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::general::kmeans_f64;
+
+    #[test]
+    fn small_dataset() {
+        let text = "1.0,2.0\n-3.5, 4e2\n0,  -0.25\n";
+        assert_eq!(
+            read_numeric_csv(text, false),
+            Ok(vec![vec![1.0, 2.0], vec![-3.5, 400.0], vec![0.0, -0.25]])
+        );
+        assert_eq!(read_numeric_csv("", false), Ok(vec![]));
+        assert_eq!(read_numeric_csv("7", false), Ok(vec![vec![7.0]]));
+    }
+
+    #[test]
+    fn header_row() {
+        let text = "x,y\n\n1,2\n3,4\n\n";
+        assert_eq!(
+            read_numeric_csv(text, true),
+            Ok(vec![vec![1.0, 2.0], vec![3.0, 4.0]])
+        );
+        // which is not a number
+        assert_eq!(
+            read_numeric_csv(text, false),
+            Err(CsvError::InvalidNumber { line: 1, column: 1 })
+        );
+        assert_eq!(read_numeric_csv("x,y\n", true), Ok(vec![]));
+        assert_eq!(read_numeric_csv("\n", true), Err(CsvError::MissingHeader));
+    }
+
+    #[test]
+    fn rejects_bad_input() {
+        assert_eq!(
+            read_numeric_csv("1,2\n3,4\n5\n", false),
+            Err(CsvError::RaggedRow {
+                line: 3,
+                expected: 2,
+                found: 1
+            })
+        );
+        assert_eq!(
+            read_numeric_csv("a,b\n1,2\n3,4,5\n", true),
+            Err(CsvError::RaggedRow {
+                line: 3,
+                expected: 2,
+                found: 3
+            })
+        );
+        assert_eq!(
+            read_numeric_csv("1,2\n3,four\n", false),
+            Err(CsvError::InvalidNumber { line: 2, column: 2 })
+        );
+        // not numbers, although Rust parses them
+        for field in ["nan", "NaN", "inf", "-infinity", "1e999"] {
+            assert_eq!(
+                read_numeric_csv(&format!("1,2\n3,{field}\n"), false),
+                Err(CsvError::InvalidNumber { line: 2, column: 2 }),
+                "{field}"
+            );
+        }
+        // an empty field
+        assert_eq!(
+            read_numeric_csv("1,,2\n", false),
+            Err(CsvError::InvalidNumber { line: 1, column: 2 })
+        );
+    }
+
+    #[test]
+    fn feeds_kmeans() {
+        let text = "x,y\n0.0,0.1\n0.2,0.0\n9.8,10.0\n10.1,9.9\n";
+        let xs = read_numeric_csv(text, true).unwrap();
+        assert_eq!(kmeans_f64(&xs, 2, None), Some(vec![0, 0, 1, 1]));
+    }
+}
@@ -0,0 +1,157 @@
+/*
+Memoization table for top-down dynamic programming over two indices
+
+A recursive solution stores the answer of each subproblem `(i, j)` the first
+time it is computed, and later calls read it back instead of recursing again,
+without keeping a separate table of "visited" flags by hand:
+
+    fn solve(table: &mut DpTable2D<u64>, i: usize, j: usize) -> u64 {
+        table.get_or_compute(i, j, |table| {
+            // ... recursive calls to solve(table, ..) ...
+        })
+    }
+ */
+
+#[derive(Clone, Debug)]
+pub struct DpTable2D<T> {
+    rows: usize,
+    cols: usize,
+    cells: Vec<Option<T>>,
+}
+
+impl<T: Clone> DpTable2D<T> {
+    /// Creates a table for the subproblems `(i, j)` with `i < rows` and
+    /// `j < cols`, none of them computed yet
+    pub fn new(rows: usize, cols: usize) -> Self {
+        DpTable2D {
+            rows,
+            cols,
+            cells: vec![None; rows * cols],
+        }
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    /// Returns the memoized value of `(i, j)`, if it was computed
+    pub fn get(&self, i: usize, j: usize) -> Option<&T> {
+        self.cells[self.index(i, j)].as_ref()
+    }
+
+    /// Returns the value of `(i, j)`, calling `compute` to get it the first
+    /// time. `compute` is given the table, so that it can look up or compute
+    /// the subproblems it depends on.
+    ///
+    /// Panics if `(i, j)` is out of the table.
+    pub fn get_or_compute(
+        &mut self,
+        i: usize,
+        j: usize,
+        compute: impl FnOnce(&mut Self) -> T,
+    ) -> T {
+        let index = self.index(i, j);
+        if let Some(value) = &self.cells[index] {
+            return value.clone();
+        }
+        let value = compute(self);
+        self.cells[index] = Some(value.clone());
+        value
+    }
+
+    fn index(&self, i: usize, j: usize) -> usize {
+        assert!(
+            i < self.rows && j < self.cols,
+            "({i}, {j}) is out of a {}x{} table",
+            self.rows,
+            self.cols
+        );
+        i * self.cols + j
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dynamic_programming::longest_common_subsequence;
+
+    /// Length of the longest common subsequence of `a[i..]` and `b[j..]`
+    fn lcs(table: &mut DpTable2D<usize>, a: &[char], b: &[char], i: usize, j: usize) -> usize {
+        if i == a.len() || j == b.len() {
+            return 0;
+        }
+        table.get_or_compute(i, j, |table| {
+            if a[i] == b[j] {
+                1 + lcs(table, a, b, i + 1, j + 1)
+            } else {
+                lcs(table, a, b, i + 1, j).max(lcs(table, a, b, i, j + 1))
+            }
+        })
+    }
+
+    fn lcs_top_down(a: &str, b: &str) -> usize {
+        let a: Vec<char> = a.chars().collect();
+        let b: Vec<char> = b.chars().collect();
+        let mut table = DpTable2D::new(a.len(), b.len());
+        lcs(&mut table, &a, &b, 0, 0)
+    }
+
+    #[test]
+    fn lcs_matches_bottom_up() {
+        let pairs = [
+            ("abcdgh", "aedfhr"),
+            ("AGGTAB", "GXTXAYB"),
+            ("abc", "abc"),
+            ("abc", "xyz"),
+            ("", "abc"),
+            ("the quick brown fox", "jumps over the lazy dog"),
+            ("你好世界", "世界你好"),
+        ];
+        for (a, b) in pairs {
+            let expected = longest_common_subsequence(a, b).chars().count();
+            assert_eq!(lcs_top_down(a, b), expected, "{a} / {b}");
+        }
+    }
+
+    #[test]
+    fn computes_each_cell_once() {
+        // counts the calls to `compute`, and memoizes the binomial coefficients
+        fn binomial(table: &mut DpTable2D<u64>, calls: &mut usize, n: usize, k: usize) -> u64 {
+            if k == 0 || k == n {
+                return 1;
+            }
+            table.get_or_compute(n, k, |table| {
+                *calls += 1;
+                binomial(table, calls, n - 1, k - 1) + binomial(table, calls, n - 1, k)
+            })
+        }
+        let mut table = DpTable2D::new(31, 31);
+        let mut calls = 0;
+        assert_eq!(binomial(&mut table, &mut calls, 30, 15), 155_117_520);
+        // one call per cell 0 < k < n with n - k <= 15 and k <= 15
+        assert_eq!(calls, 15 * 15);
+    }
+
+    #[test]
+    fn get_and_dimensions() {
+        let mut table = DpTable2D::new(2, 3);
+        assert_eq!((table.rows(), table.cols()), (2, 3));
+        assert_eq!(table.get(1, 2), None);
+        assert_eq!(table.get_or_compute(1, 2, |_| 7), 7);
+        // already computed, the closure is not called again
+        assert_eq!(table.get_or_compute(1, 2, |_| unreachable!()), 7);
+        assert_eq!(table.get(1, 2), Some(&7));
+        assert_eq!(table.get(0, 2), None);
+    }
+
+    #[test]
+    #[should_panic]
+    fn out_of_bounds() {
+        let mut table = DpTable2D::new(2, 3);
+        table.get_or_compute(0, 3, |_| 0);
+    }
+}
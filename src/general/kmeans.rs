@@ -4,7 +4,10 @@ macro_rules! impl_kmeans {
     ($kind: ident) => {
         // Since we can't overload methods in rust, we have to use namespace
         pub mod $kind {
-            use std::$kind::INFINITY;
+            use super::{JsonReader, KMeansParseError};
+            use crate::general::Xorshift64;
+            use crate::machine_learning::cluster_variances;
+            use crate::math::Metric;
             /// computes sum of squared deviation between two identically sized vectors
             /// `x`, and `y`.
             fn distance(x: &[$kind], y: &[$kind]) -> $kind {
@@ -14,28 +17,45 @@ macro_rules! impl_kmeans {
             }
 
             /// Returns a vector containing the indices z<sub>i</sub> in {0, ..., K-1} of
-            /// the centroid nearest to each datum.
-            fn nearest_centroids(xs: &[Vec<$kind>], centroids: &[Vec<$kind>]) -> Vec<usize> {
+            /// the centroid nearest to each datum, under `metric` if given, and
+            /// else under the squared Euclidean `distance` in `$kind`.
+            fn nearest_centroids(
+                xs: &[Vec<$kind>],
+                centroids: &[Vec<$kind>],
+                metric: Option<Metric>,
+            ) -> Vec<usize> {
                 xs.iter()
-                    .map(|xi| {
-                        // Find the argmin by folding using a tuple containing the argmin
-                        // and the minimum distance.
-                        let (argmin, _) = centroids.iter().enumerate().fold(
-                            (0_usize, INFINITY),
-                            |(min_ix, min_dist), (ix, ci)| {
-                                let dist = distance(xi, ci);
-                                if dist < min_dist {
-                                    (ix, dist)
-                                } else {
-                                    (min_ix, min_dist)
-                                }
-                            },
-                        );
-                        argmin
+                    .map(|xi| match metric {
+                        None => argmin(
+                            centroids.iter().map(|ci| distance(xi, ci)),
+                            <$kind>::INFINITY,
+                        ),
+                        Some(metric) => argmin(
+                            centroids.iter().map(|ci| metric.distance(xi, ci)),
+                            f64::INFINITY,
+                        ),
                     })
                     .collect()
             }
 
+            /// Returns the index of the smallest of the `distances`, the first
+            /// one on ties, or 0 if none is below `infinity`.
+            fn argmin<D: PartialOrd>(distances: impl Iterator<Item = D>, infinity: D) -> usize {
+                // Find the argmin by folding using a tuple containing the argmin
+                // and the minimum distance.
+                let (argmin, _) = distances.enumerate().fold(
+                    (0_usize, infinity),
+                    |(min_ix, min_dist), (ix, dist)| {
+                        if dist < min_dist {
+                            (ix, dist)
+                        } else {
+                            (min_ix, min_dist)
+                        }
+                    },
+                );
+                argmin
+            }
+
             /// Recompute the centroids given the current clustering
             fn recompute_centroids(
                 xs: &[Vec<$kind>],
@@ -63,31 +83,255 @@ macro_rules! impl_kmeans {
                     .collect()
             }
 
+            /// Returns the sum of squared distances of the data to their centroids
+            fn inertia(xs: &[Vec<$kind>], clustering: &[usize], centroids: &[Vec<$kind>]) -> $kind {
+                xs.iter()
+                    .zip(clustering.iter())
+                    .map(|(xi, &zi)| distance(xi, &centroids[zi]))
+                    .sum()
+            }
+
             /// Assign the N D-dimensional data, `xs`, to `k` clusters using
             /// K-Means clustering, with optional iteration limitation `max_iter`
             pub fn kmeans(
                 xs: &Vec<Vec<$kind>>,
                 k: usize,
                 max_iter: Option<i32>,
+            ) -> Option<Vec<usize>> {
+                kmeans_with_options(xs, k, max_iter, None, None, None)
+            }
+
+            /// The same as `kmeans`, assigning each datum to the centroid nearest
+            /// to it under `metric` rather than the Euclidean distance. The
+            /// centroids are still the means of their clusters, which only
+            /// minimize the squared Euclidean distances: under other metrics,
+            /// the iterations may not settle, so `max_iter` should be given.
+            pub fn kmeans_with_metric(
+                xs: &Vec<Vec<$kind>>,
+                k: usize,
+                max_iter: Option<i32>,
+                metric: Metric,
+            ) -> Option<Vec<usize>> {
+                kmeans_with_options(xs, k, max_iter, Some(metric), None, None)
+            }
+
+            /// A fitted k-means model: the centroids of the clusters, and the
+            /// cluster of each datum
+            #[derive(Clone, Debug, PartialEq)]
+            pub struct KMeansResult {
+                pub centroids: Vec<Vec<$kind>>,
+                pub labels: Vec<usize>,
+            }
+
+            /// The same as `kmeans`, also returning the centroids of the
+            /// clusters found. The centroid of an empty cluster is NaN.
+            pub fn kmeans_fit(
+                xs: &Vec<Vec<$kind>>,
+                k: usize,
+                max_iter: Option<i32>,
+            ) -> Option<KMeansResult> {
+                let labels = kmeans(xs, k, max_iter)?;
+                Some(KMeansResult {
+                    centroids: recompute_centroids(xs, &labels, k),
+                    labels,
+                })
+            }
+
+            /// The same as `kmeans_fit`, starting from `k` distinct data drawn at
+            /// random with `seed` as the centroids, rather than evenly spaced
+            /// ones. K-means only finds a local optimum, which depends on its
+            /// start: fitting with several seeds and keeping the best model
+            /// often gets closer to the global one.
+            pub fn kmeans_fit_seeded(
+                xs: &Vec<Vec<$kind>>,
+                k: usize,
+                max_iter: Option<i32>,
+                seed: u64,
+            ) -> Option<KMeansResult> {
+                let labels = kmeans_with_options(xs, k, max_iter, None, None, Some(seed))?;
+                Some(KMeansResult {
+                    centroids: recompute_centroids(xs, &labels, k),
+                    labels,
+                })
+            }
+
+            /// Fits k-means to `xs` `n_restarts` times, from seeds drawn with
+            /// `seed`, and returns the labels and centroids of the fit with the
+            /// lowest inertia (sum of squared distances of the data to their
+            /// centroids), along with it. A single start may settle in a poor
+            /// local optimum, such as two centroids sharing a cluster while
+            /// another centroid covers two; more restarts make it less likely.
+            /// Returns None, as `kmeans`, if `k` is 0 or larger than the number
+            /// of data.
+            ///
+            /// # Panics
+            ///
+            /// Panics if `n_restarts` is 0.
+            pub fn kmeans_best_of(
+                xs: &Vec<Vec<$kind>>,
+                k: usize,
+                max_iter: Option<i32>,
+                n_restarts: usize,
+                seed: u64,
+            ) -> Option<(Vec<usize>, Vec<Vec<$kind>>, $kind)> {
+                assert!(n_restarts > 0, "k-means needs at least one start");
+                let mut seeds = Xorshift64::new(seed);
+                let mut best: Option<(Vec<usize>, Vec<Vec<$kind>>, $kind)> = None;
+                for _ in 0..n_restarts {
+                    let result = kmeans_fit_seeded(xs, k, max_iter, seeds.next_u64())?;
+                    let inertia = inertia(xs, &result.labels, &result.centroids);
+                    if best
+                        .as_ref()
+                        .map_or(true, |(_, _, lowest)| inertia < *lowest)
+                    {
+                        best = Some((result.labels, result.centroids, inertia));
+                    }
+                }
+                best
+            }
+
+            impl KMeansResult {
+                /// Returns the variance of each cluster of `xs`, the data the
+                /// model was fitted on: the mean squared distance of its data
+                /// to its centroid, 0.0 for an empty cluster. See
+                /// `machine_learning::cluster_variances`.
+                pub fn cluster_variances(&self, xs: &[Vec<$kind>]) -> Vec<f64> {
+                    cluster_variances(xs, &self.labels, &self.centroids)
+                }
+
+                /// Returns the model as the JSON object
+                ///
+                /// `{"centroids":[[1.5,-2],[0.25,3]],"labels":[0,1,1]}`
+                ///
+                /// which `from_json` reads back exactly: the numbers are
+                /// written with as many digits as it takes, the NaN of an
+                /// empty cluster as `null`, and the infinities as `1e999` and
+                /// `-1e999`, which JSON numbers can be but no float is.
+                pub fn to_json(&self) -> String {
+                    let number = |x: &$kind| {
+                        if x.is_nan() {
+                            "null".to_string()
+                        } else if x.is_infinite() {
+                            if *x > 0.0 { "1e999" } else { "-1e999" }.to_string()
+                        } else {
+                            x.to_string()
+                        }
+                    };
+                    let centroids: Vec<String> = self
+                        .centroids
+                        .iter()
+                        .map(|c| {
+                            format!("[{}]", c.iter().map(number).collect::<Vec<_>>().join(","))
+                        })
+                        .collect();
+                    let labels: Vec<String> = self.labels.iter().map(usize::to_string).collect();
+                    format!(
+                        "{{\"centroids\":[{}],\"labels\":[{}]}}",
+                        centroids.join(","),
+                        labels.join(",")
+                    )
+                }
+
+                /// Reads a model written by `to_json`. The whitespace between
+                /// the tokens, and the order of the two fields, are free.
+                pub fn from_json(text: &str) -> Result<Self, KMeansParseError> {
+                    let mut reader = JsonReader::new(text);
+                    let (mut centroids, mut labels) = (None, None);
+                    reader.expect(b'{')?;
+                    loop {
+                        let field = reader.position();
+                        match reader.key()? {
+                            "centroids" if centroids.is_none() => {
+                                reader.expect(b':')?;
+                                centroids = Some(reader.array(|r| {
+                                    r.array(|r| {
+                                        r.value(|token| match token {
+                                            "null" => Some(<$kind>::NAN),
+                                            _ => token.parse().ok(),
+                                        })
+                                    })
+                                })?);
+                            }
+                            "labels" if labels.is_none() => {
+                                reader.expect(b':')?;
+                                labels =
+                                    Some(reader.array(|r| r.value(|token| token.parse().ok()))?);
+                            }
+                            _ => return Err(KMeansParseError::Syntax { position: field }),
+                        }
+                        if !reader.eat(b',') {
+                            break;
+                        }
+                    }
+                    reader.expect(b'}')?;
+                    reader.end()?;
+
+                    let centroids: Vec<Vec<$kind>> =
+                        centroids.ok_or(KMeansParseError::MissingField("centroids"))?;
+                    let labels: Vec<usize> =
+                        labels.ok_or(KMeansParseError::MissingField("labels"))?;
+                    if let Some(&label) = labels.iter().find(|&&label| label >= centroids.len()) {
+                        return Err(KMeansParseError::InvalidLabel { label });
+                    }
+                    Ok(KMeansResult { centroids, labels })
+                }
+            }
+
+            /// The same as `kmeans`, calling `on_iter` after each iteration with
+            /// its number, from 0, and the inertia (sum of squared distances of
+            /// the data to their centroids) of the new clustering, which never
+            /// increases. It can log the progress, or count the iterations.
+            pub fn kmeans_with_callback(
+                xs: &Vec<Vec<$kind>>,
+                k: usize,
+                max_iter: Option<i32>,
+                on_iter: Option<&mut dyn FnMut(usize, $kind)>,
+            ) -> Option<Vec<usize>> {
+                kmeans_with_options(xs, k, max_iter, None, on_iter, None)
+            }
+
+            fn kmeans_with_options(
+                xs: &Vec<Vec<$kind>>,
+                k: usize,
+                max_iter: Option<i32>,
+                metric: Option<Metric>,
+                mut on_iter: Option<&mut dyn FnMut(usize, $kind)>,
+                seed: Option<u64>,
             ) -> Option<Vec<usize>> {
                 if xs.len() < k || k == 0 {
                     return None;
                 }
 
-                // Rather than pulling in a dependency to randomly select the staring
-                // points for the centroids, we're going to deterministically choose them by
-                // selecting evenly spaced points in `xs`
-                let n_per_cluster: usize = xs.len() / k;
-                let centroids: Vec<Vec<$kind>> =
-                    (0..k).map(|j| xs[j * n_per_cluster].clone()).collect();
+                let centroids: Vec<Vec<$kind>> = match seed {
+                    // `k` distinct data, drawn with the seeded generator
+                    Some(seed) => {
+                        let mut indices: Vec<usize> = (0..xs.len()).collect();
+                        Xorshift64::new(seed).shuffle(&mut indices);
+                        indices[..k].iter().map(|&i| xs[i].clone()).collect()
+                    }
+                    // Rather than pulling in a dependency to randomly select the staring
+                    // points for the centroids, we're going to deterministically choose them by
+                    // selecting evenly spaced points in `xs`
+                    None => {
+                        let n_per_cluster: usize = xs.len() / k;
+                        (0..k).map(|j| xs[j * n_per_cluster].clone()).collect()
+                    }
+                };
 
-                let mut clustering = nearest_centroids(&xs, &centroids);
+                let mut clustering = nearest_centroids(&xs, &centroids, metric);
 
                 let mut count_iter: i32 = 0;
 
                 while max_iter == None || count_iter < max_iter.unwrap() {
                     let centroids = recompute_centroids(&xs, &clustering, k);
-                    let new_clustering = nearest_centroids(&xs, &centroids);
+                    let new_clustering = nearest_centroids(&xs, &centroids, metric);
+
+                    if let Some(on_iter) = on_iter.as_mut() {
+                        on_iter(
+                            count_iter as usize,
+                            inertia(&xs, &new_clustering, &centroids),
+                        );
+                    }
 
                     // loop until the clustering doesn't change after the new centroids are computed
                     if new_clustering
@@ -110,15 +354,172 @@ macro_rules! impl_kmeans {
     };
 }
 
+/// Why `KMeansResult::from_json` rejected a text
+#[derive(Debug, PartialEq, Eq)]
+pub enum KMeansParseError {
+    /// The text does not have the format of `to_json` from the byte `position`
+    Syntax { position: usize },
+    /// The object has no such field
+    MissingField(&'static str),
+    /// A label is not the index of a centroid
+    InvalidLabel { label: usize },
+}
+
+/// Reads the tokens of the little JSON `KMeansResult::to_json` writes
+struct JsonReader<'a> {
+    text: &'a str,
+    position: usize,
+}
+
+impl<'a> JsonReader<'a> {
+    fn new(text: &'a str) -> Self {
+        JsonReader { text, position: 0 }
+    }
+
+    /// Skips the whitespace, and returns the position of the next token
+    fn position(&mut self) -> usize {
+        let rest = &self.text[self.position..];
+        self.position += rest.len() - rest.trim_start().len();
+        self.position
+    }
+
+    fn error(&mut self) -> KMeansParseError {
+        KMeansParseError::Syntax {
+            position: self.position(),
+        }
+    }
+
+    /// Consumes `byte` if it comes next
+    fn eat(&mut self, byte: u8) -> bool {
+        let position = self.position();
+        let found = self.text.as_bytes().get(position) == Some(&byte);
+        if found {
+            self.position += 1;
+        }
+        found
+    }
+
+    fn expect(&mut self, byte: u8) -> Result<(), KMeansParseError> {
+        if self.eat(byte) {
+            Ok(())
+        } else {
+            Err(self.error())
+        }
+    }
+
+    /// Reads a string without escapes
+    fn key(&mut self) -> Result<&'a str, KMeansParseError> {
+        self.expect(b'"')?;
+        let length = self.text[self.position..]
+            .find('"')
+            .ok_or_else(|| self.error())?;
+        let key = &self.text[self.position..self.position + length];
+        self.position += length + 1;
+        Ok(key)
+    }
+
+    /// Reads a number or `null`, converted by `parse`
+    fn value<T>(&mut self, parse: impl Fn(&str) -> Option<T>) -> Result<T, KMeansParseError> {
+        let start = self.position();
+        let length = self.text[start..]
+            .find(|c: char| !(c.is_ascii_alphanumeric() || "+-.".contains(c)))
+            .unwrap_or(self.text.len() - start);
+        let token = &self.text[start..start + length];
+        // `str::parse` also takes tokens like `NaN`, `inf` or `+1`
+        if token != "null" && !is_json_number(token) {
+            return Err(self.error());
+        }
+        let value = parse(token).ok_or_else(|| self.error())?;
+        self.position += length;
+        Ok(value)
+    }
+
+    /// Reads an array, its items being read by `item`
+    fn array<T>(
+        &mut self,
+        mut item: impl FnMut(&mut Self) -> Result<T, KMeansParseError>,
+    ) -> Result<Vec<T>, KMeansParseError> {
+        self.expect(b'[')?;
+        let mut items = vec![];
+        if self.eat(b']') {
+            return Ok(items);
+        }
+        loop {
+            items.push(item(self)?);
+            if !self.eat(b',') {
+                self.expect(b']')?;
+                return Ok(items);
+            }
+        }
+    }
+
+    /// Checks that only whitespace is left
+    fn end(&mut self) -> Result<(), KMeansParseError> {
+        if self.position() == self.text.len() {
+            Ok(())
+        } else {
+            Err(self.error())
+        }
+    }
+}
+
+/// Returns whether `token` is a number in the JSON grammar: an optional
+/// minus sign, an integer part without leading zeros, then an optional
+/// fraction and exponent
+fn is_json_number(token: &str) -> bool {
+    fn digits(bytes: &[u8]) -> usize {
+        bytes.iter().take_while(|b| b.is_ascii_digit()).count()
+    }
+    let mut bytes = token.strip_prefix('-').unwrap_or(token).as_bytes();
+    let integer = digits(bytes);
+    if integer == 0 || (integer > 1 && bytes[0] == b'0') {
+        return false;
+    }
+    bytes = &bytes[integer..];
+    if let Some(rest) = bytes.strip_prefix(b".") {
+        let fraction = digits(rest);
+        if fraction == 0 {
+            return false;
+        }
+        bytes = &rest[fraction..];
+    }
+    if let Some(rest) = bytes.strip_prefix(b"e").or(bytes.strip_prefix(b"E")) {
+        let rest = rest
+            .strip_prefix(b"+")
+            .or(rest.strip_prefix(b"-"))
+            .unwrap_or(rest);
+        let exponent = digits(rest);
+        if exponent == 0 {
+            return false;
+        }
+        bytes = &rest[exponent..];
+    }
+    bytes.is_empty()
+}
+
 // generate code for kmeans for f32 and f64 data
 impl_kmeans!(f64);
 impl_kmeans!(f32);
 
 #[cfg(test)]
 mod test {
-    use self::super::f64::kmeans;
-    use crate::machine_learning::k_means;
-    use rand::random;
+    use self::super::f64::{
+        kmeans, kmeans_best_of, kmeans_fit, kmeans_fit_seeded, kmeans_with_callback,
+        kmeans_with_metric, KMeansResult,
+    };
+    use self::super::{is_json_number, KMeansParseError};
+    use crate::general::{lcg_f64, make_blobs};
+    use crate::machine_learning::{canonicalize_labels, k_means};
+    use crate::math::Metric;
+
+    /// Returns `n` points uniform in [0, 100)², drawn from the LCG seeded by
+    /// `seed`
+    fn uniform_points(n: usize, seed: u64) -> Vec<(f64, f64)> {
+        let mut state = seed;
+        (0..n)
+            .map(|_| (lcg_f64(&mut state) * 100.0, lcg_f64(&mut state) * 100.0))
+            .collect()
+    }
 
     #[test]
     fn easy_univariate_clustering() {
@@ -206,23 +607,278 @@ mod test {
     /// **dimension** and **type** compare to `machine_learning::k_means`.
     #[test]
     fn compare_two_impl_of_k_means() {
-        let mut xs: Vec<Vec<f64>> = vec![];
-        let mut data_points: Vec<(f64, f64)> = vec![];
-        let n_points: usize = 1000;
+        let data_points = uniform_points(1000, 273);
+        let xs: Vec<Vec<f64>> = data_points.iter().map(|&(x, y)| vec![x, y]).collect();
+        let max_iter = 100;
 
-        for _ in 0..n_points {
-            let x: f64 = random::<f64>() * 100.0;
-            let y: f64 = random::<f64>() * 100.0;
+        assert_eq!(
+            kmeans(&xs, 10, Some(max_iter)),
+            k_means(data_points, 10, max_iter)
+        );
+    }
+
+    #[test]
+    fn the_test_points_are_reproducible() {
+        assert_eq!(uniform_points(100, 2730), uniform_points(100, 2730));
+        assert_ne!(uniform_points(100, 2730), uniform_points(100, 2731));
+    }
 
-            xs.push(vec![x, y]);
-            data_points.push((x, y));
+    #[test]
+    fn other_metrics() {
+        let (xs, _) = make_blobs(300, &[vec![-5.0, 0.0], vec![5.0, 0.0]], 1.0, 274);
+        let expected = kmeans(&xs, 2, None);
+        assert_eq!(
+            kmeans_with_metric(&xs, 2, None, Metric::SquaredEuclidean),
+            expected
+        );
+        // the Euclidean distance orders the centroids as its square
+        assert_eq!(
+            kmeans_with_metric(&xs, 2, None, Metric::Euclidean),
+            expected
+        );
+        // and the blobs are far enough apart for any metric
+        for metric in [Metric::Manhattan, Metric::Chebyshev, Metric::Minkowski(3.0)] {
+            assert_eq!(kmeans_with_metric(&xs, 2, Some(100), metric), expected);
         }
+        // the cosine distance only sees the directions
+        let xs: Vec<Vec<f64>> = vec![
+            vec![1.0, 0.1],
+            vec![10.0, 0.5],
+            vec![0.1, 1.0],
+            vec![0.5, 10.0],
+        ];
+        assert_eq!(
+            kmeans_with_metric(&xs, 2, Some(100), Metric::Cosine),
+            Some(vec![0, 0, 1, 1])
+        );
+    }
 
-        let max_iter = 100;
+    #[test]
+    fn no_metric_compares_the_common_coordinates() {
+        // without a metric, the data are compared on their common
+        // coordinates, in their own type
+        let xs: Vec<Vec<f64>> = vec![
+            vec![0.0, 0.0, 5.0],
+            vec![0.1, 0.2],
+            vec![10.0, 10.0],
+            vec![10.2, 10.1],
+        ];
+        assert_eq!(kmeans(&xs, 2, None), Some(vec![0, 0, 1, 1]));
+        let xs: Vec<Vec<f32>> = xs
+            .iter()
+            .map(|x| x.iter().map(|&v| v as f32).collect())
+            .collect();
+        assert_eq!(super::f32::kmeans(&xs, 2, None), Some(vec![0, 0, 1, 1]));
+    }
+
+    #[test]
+    fn the_inertia_never_increases() {
+        let centers: Vec<Vec<f64>> = (0..6)
+            .map(|i| vec![(i % 3) as f64 * 4.0, (i / 3) as f64 * 4.0])
+            .collect();
+        let (xs, _) = make_blobs(600, &centers, 1.5, 259);
+        let mut inertias = vec![];
+        let mut record = |iteration: usize, inertia: f64| {
+            assert_eq!(iteration, inertias.len());
+            inertias.push(inertia);
+        };
+        let clustering = kmeans_with_callback(&xs, 6, None, Some(&mut record));
+        assert!(inertias.len() > 1);
+        for pair in inertias.windows(2) {
+            assert!(pair[1] <= pair[0] + 1e-9, "{inertias:?}");
+        }
+        // the callback does not change the result
+        assert_eq!(clustering, kmeans(&xs, 6, None));
+        assert_eq!(clustering, kmeans_with_callback(&xs, 6, None, None));
+    }
+
+    #[test]
+    fn the_callback_can_count_iterations() {
+        let (xs, _) = make_blobs(300, &[vec![0.0, 0.0], vec![1.0, 1.0]], 1.0, 2590);
+        let mut iterations = 0;
+        kmeans_with_callback(&xs, 4, Some(3), Some(&mut |_, _| iterations += 1));
+        assert_eq!(iterations, 3);
+        // no iteration runs for invalid inputs
+        kmeans_with_callback(&xs, 0, None, Some(&mut |_, _| iterations += 1));
+        assert_eq!(iterations, 3);
+    }
 
+    #[test]
+    fn seeded_fits() {
+        let centers = vec![vec![0.0, 0.0], vec![8.0, 0.0], vec![4.0, 8.0]];
+        let (xs, truth) = make_blobs(300, &centers, 1.0, 284);
+        let result = kmeans_fit_seeded(&xs, 3, None, 7).unwrap();
+        // the same seed gives the same model
+        assert_eq!(kmeans_fit_seeded(&xs, 3, None, 7), Some(result.clone()));
         assert_eq!(
-            kmeans(&xs, 10, Some(max_iter)),
-            k_means(data_points, 10, max_iter)
+            canonicalize_labels(&result.labels),
+            canonicalize_labels(&truth)
+        );
+        assert_eq!(kmeans_fit_seeded(&xs, 301, None, 7), None);
+        assert_eq!(kmeans_fit_seeded(&xs, 0, None, 7), None);
+    }
+
+    #[test]
+    fn best_of_restarts() {
+        // a grid of 6 tight blobs, where a bad start leaves two centroids in
+        // one blob and one centroid between two others
+        let centers: Vec<Vec<f64>> = (0..6)
+            .map(|i| vec![(i % 3) as f64 * 10.0, (i / 3) as f64 * 10.0])
+            .collect();
+        let (xs, truth) = make_blobs(300, &centers, 0.5, 285);
+        let inertia = |result: &KMeansResult| -> f64 {
+            xs.iter()
+                .zip(&result.labels)
+                .map(|(x, &label)| Metric::SquaredEuclidean.distance(x, &result.centroids[label]))
+                .sum()
+        };
+        let single = kmeans_fit_seeded(&xs, 6, None, 0).unwrap();
+        assert_ne!(
+            canonicalize_labels(&single.labels),
+            canonicalize_labels(&truth)
+        );
+
+        let (labels, centroids, best) = kmeans_best_of(&xs, 6, None, 10, 285).unwrap();
+        assert_eq!(canonicalize_labels(&labels), canonicalize_labels(&truth));
+        assert!(
+            10.0 * best < inertia(&single),
+            "{best} {}",
+            inertia(&single)
         );
+        assert!((best - inertia(&KMeansResult { centroids, labels })).abs() < 1e-9);
+        // its first start alone does no better
+        let (_, _, one) = kmeans_best_of(&xs, 6, None, 1, 285).unwrap();
+        assert!(one >= best);
+    }
+
+    #[test]
+    #[should_panic]
+    fn best_of_no_restart() {
+        kmeans_best_of(&vec![vec![1.0], vec![2.0]], 1, None, 0, 285);
+    }
+
+    #[test]
+    fn best_of_invalid_number_of_clusters() {
+        let xs = vec![vec![1.0], vec![2.0]];
+        assert_eq!(kmeans_best_of(&xs, 3, None, 5, 285), None);
+        assert_eq!(kmeans_best_of(&xs, 0, None, 5, 285), None);
+        assert_eq!(kmeans_best_of(&vec![], 1, None, 5, 285), None);
+    }
+
+    #[test]
+    fn json_round_trip() {
+        let centers = vec![vec![0.1, -3.7, 2.0], vec![5.5, 1.0 / 3.0, -1e-9]];
+        let (xs, _) = make_blobs(200, &centers, 0.7, 279);
+        let result = kmeans_fit(&xs, 2, None).unwrap();
+        assert_eq!(Some(result.labels.clone()), kmeans(&xs, 2, None));
+        let json = result.to_json();
+        // bit for bit
+        assert_eq!(KMeansResult::from_json(&json), Ok(result));
+        // in f32 too
+        let xs: Vec<Vec<f32>> = xs
+            .iter()
+            .map(|x| x.iter().map(|&v| v as f32).collect())
+            .collect();
+        let result = super::f32::kmeans_fit(&xs, 3, None).unwrap();
+        assert_eq!(
+            super::f32::KMeansResult::from_json(&result.to_json()),
+            Ok(result)
+        );
+    }
+
+    #[test]
+    fn json_round_trip_of_infinities() {
+        let result = KMeansResult {
+            centroids: vec![vec![f64::INFINITY, -1.0], vec![f64::NEG_INFINITY, 2.5]],
+            labels: vec![1, 0],
+        };
+        let json = result.to_json();
+        assert_eq!(
+            json,
+            r#"{"centroids":[[1e999,-1],[-1e999,2.5]],"labels":[1,0]}"#
+        );
+        assert_eq!(KMeansResult::from_json(&json), Ok(result));
+        let result = super::f32::KMeansResult {
+            centroids: vec![vec![f32::NEG_INFINITY, f32::INFINITY]],
+            labels: vec![0],
+        };
+        assert_eq!(
+            super::f32::KMeansResult::from_json(&result.to_json()),
+            Ok(result)
+        );
+    }
+
+    #[test]
+    fn json_format() {
+        let result = KMeansResult {
+            centroids: vec![vec![1.5, -2.0], vec![0.25, 3e-7]],
+            labels: vec![0, 1, 1],
+        };
+        let json = result.to_json();
+        assert_eq!(
+            json,
+            r#"{"centroids":[[1.5,-2],[0.25,0.0000003]],"labels":[0,1,1]}"#
+        );
+        // spaced out, and the other way around
+        let spaced = r#" { "labels" : [ 0 , 1, 1 ],
+            "centroids": [[1.5, -2.0], [0.25, 3e-7]] } "#;
+        assert_eq!(KMeansResult::from_json(spaced), Ok(result));
+        // an empty cluster
+        let result = KMeansResult::from_json(r#"{"centroids":[[null],[1]],"labels":[1]}"#).unwrap();
+        assert!(result.centroids[0][0].is_nan());
+        assert!(result.to_json().contains("[null]"));
+        let empty = KMeansResult::from_json(r#"{"centroids":[],"labels":[]}"#).unwrap();
+        assert!(empty.centroids.is_empty() && empty.labels.is_empty());
+    }
+
+    #[test]
+    fn json_numbers() {
+        for number in ["0", "-0", "7", "-12.5", "0.25", "3e-7", "1E+300", "10.5e2"] {
+            assert!(is_json_number(number), "{number}");
+        }
+        for token in [
+            "", "-", "NaN", "inf", "+3", "00", "1.", ".5", "1e", "1e+", "0x1", "1f",
+        ] {
+            assert!(!is_json_number(token), "{token}");
+        }
+    }
+
+    #[test]
+    fn json_errors() {
+        assert_eq!(
+            KMeansResult::from_json(r#"{"centroids":[[1]]}"#),
+            Err(KMeansParseError::MissingField("labels"))
+        );
+        assert_eq!(
+            KMeansResult::from_json(r#"{"centroids":[[1]],"labels":[1]}"#),
+            Err(KMeansParseError::InvalidLabel { label: 1 })
+        );
+        for (text, position) in [
+            ("", 0),
+            ("[]", 0),
+            (r#"{"centroids":[[1,]],"labels":[]}"#, 17),
+            (r#"{"centroids":[[x]],"labels":[]}"#, 15),
+            (r#"{"labels":[-1],"centroids":[]}"#, 11),
+            (r#"{"labels":[],"labels":[]}"#, 13),
+            (r#"{"labels":[],"other":[]}"#, 13),
+            (r#"{"labels":[],"centroids":[]} x"#, 29),
+            (r#"{"labels":[]"#, 12),
+            // not JSON numbers, although Rust parses them
+            (r#"{"centroids":[[NaN]],"labels":[]}"#, 15),
+            (r#"{"centroids":[[inf]],"labels":[]}"#, 15),
+            (r#"{"centroids":[[-infinity]],"labels":[]}"#, 15),
+            (r#"{"centroids":[[+1]],"labels":[]}"#, 15),
+            (r#"{"centroids":[[1.]],"labels":[]}"#, 15),
+            (r#"{"centroids":[[.5]],"labels":[]}"#, 15),
+            (r#"{"centroids":[[01]],"labels":[]}"#, 15),
+            (r#"{"centroids":[[1e]],"labels":[]}"#, 15),
+            (r#"{"labels":[+0],"centroids":[]}"#, 11),
+        ] {
+            assert_eq!(
+                KMeansResult::from_json(text),
+                Err(KMeansParseError::Syntax { position }),
+                "{text}"
+            );
+        }
     }
 }
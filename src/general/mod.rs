@@ -1,4 +1,8 @@
+mod bench;
+mod bits;
 mod convex_hull;
+mod csv;
+mod dp_table;
 mod fisher_yates_shuffle;
 mod genetic;
 mod hanoi;
@@ -6,20 +10,46 @@ mod huffman_encoding;
 mod kadane_algorithm;
 mod kmeans;
 mod mex;
+mod mos_algorithm;
+mod parallel;
 mod permutations;
+mod prng;
+mod reservoir_sampling;
+mod synthetic_data;
 mod two_sum;
 
-pub use self::convex_hull::convex_hull_graham;
+pub use self::bench::{bench, BenchResult};
+pub use self::bits::{
+    count_set_bits, gray_code, inverse_gray_code, is_power_of_two, lowest_set_bit,
+    next_power_of_two, reverse_bits,
+};
+pub use self::convex_hull::{convex_hull_graham, quickhull};
+pub use self::csv::{read_numeric_csv, CsvError};
+pub use self::dp_table::DpTable2D;
 pub use self::fisher_yates_shuffle::fisher_yates_shuffle;
 pub use self::genetic::GeneticAlgorithm;
 pub use self::hanoi::hanoi;
 pub use self::huffman_encoding::{HuffmanDictionary, HuffmanEncoding};
 pub use self::kadane_algorithm::max_sub_array;
-pub use self::kmeans::f32::kmeans as kmeans_f32;
-pub use self::kmeans::f64::kmeans as kmeans_f64;
+pub use self::kmeans::f32::{
+    kmeans as kmeans_f32, kmeans_best_of as kmeans_f32_best_of, kmeans_fit as kmeans_f32_fit,
+    kmeans_fit_seeded as kmeans_f32_fit_seeded, kmeans_with_callback as kmeans_f32_with_callback,
+    kmeans_with_metric as kmeans_f32_with_metric, KMeansResult as KMeansResultF32,
+};
+pub use self::kmeans::f64::{
+    kmeans as kmeans_f64, kmeans_best_of as kmeans_f64_best_of, kmeans_fit as kmeans_f64_fit,
+    kmeans_fit_seeded as kmeans_f64_fit_seeded, kmeans_with_callback as kmeans_f64_with_callback,
+    kmeans_with_metric as kmeans_f64_with_metric, KMeansResult as KMeansResultF64,
+};
+pub use self::kmeans::KMeansParseError;
 pub use self::mex::mex_using_set;
 pub use self::mex::mex_using_sort;
+pub use self::mos_algorithm::mos_algorithm;
+pub use self::parallel::parallel_map;
 pub use self::permutations::{
     heap_permute, permute, permute_unique, steinhaus_johnson_trotter_permute,
 };
+pub use self::prng::{lcg_f64, Xorshift64};
+pub use self::reservoir_sampling::{approximate_median, reservoir_sample};
+pub use self::synthetic_data::make_blobs;
 pub use self::two_sum::two_sum;
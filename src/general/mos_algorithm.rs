@@ -0,0 +1,154 @@
+use std::collections::HashMap;
+
+/*
+    Mo's algorithm answers offline range queries for which the answer of a range
+    can be updated cheaply when the range grows or shrinks by one element.
+
+    The array is cut into blocks of size ~√n and the queries are sorted by the
+    block of their left end, then by their right end (in alternating directions
+    for consecutive blocks, which avoids rewinding the right end). Moving the
+    current range from one query to the next then adds or removes O((n + q)√n)
+    elements in total instead of O(nq).
+
+    Here the queries ask for the number of distinct values in a range, keeping
+    the frequency of every value of the current range in a map.
+*/
+
+/// Frequencies of the values in the current range
+struct Window {
+    frequency: HashMap<u32, usize>,
+    distinct: u64,
+}
+
+impl Window {
+    fn add(&mut self, value: u32) {
+        let count = self.frequency.entry(value).or_insert(0);
+        if *count == 0 {
+            self.distinct += 1;
+        }
+        *count += 1;
+    }
+
+    fn remove(&mut self, value: u32) {
+        let count = self.frequency.get_mut(&value).unwrap();
+        *count -= 1;
+        if *count == 0 {
+            self.distinct -= 1;
+        }
+    }
+}
+
+/// Returns, for each inclusive range `(l, r)` of `queries`, the number of
+/// distinct values among `arr[l..=r]`, in the order of the queries.
+///
+/// # Panics
+///
+/// Panics if a query has `l > r` or `r >= arr.len()`.
+pub fn mos_algorithm(arr: &[u32], queries: &[(usize, usize)]) -> Vec<u64> {
+    for &(l, r) in queries {
+        assert!(l <= r && r < arr.len(), "invalid query ({l}, {r})");
+    }
+
+    let block_size = ((arr.len() as f64).sqrt() as usize).max(1);
+    let mut order: Vec<usize> = (0..queries.len()).collect();
+    order.sort_by_key(|&i| {
+        let (l, r) = queries[i];
+        let block = l / block_size;
+        let r_key = if block.is_multiple_of(2) {
+            r
+        } else {
+            usize::MAX - r
+        };
+        (block, r_key)
+    });
+
+    let mut window = Window {
+        frequency: HashMap::new(),
+        distinct: 0,
+    };
+    // the current range is [cur_l, cur_r), empty at first
+    let (mut cur_l, mut cur_r) = (0, 0);
+    let mut answers = vec![0; queries.len()];
+    for i in order {
+        let (l, r) = queries[i];
+        // grow before shrinking, so that the range never becomes negative
+        while cur_l > l {
+            cur_l -= 1;
+            window.add(arr[cur_l]);
+        }
+        while cur_r <= r {
+            window.add(arr[cur_r]);
+            cur_r += 1;
+        }
+        while cur_l < l {
+            window.remove(arr[cur_l]);
+            cur_l += 1;
+        }
+        while cur_r > r + 1 {
+            cur_r -= 1;
+            window.remove(arr[cur_r]);
+        }
+        answers[i] = window.distinct;
+    }
+    answers
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::general::Xorshift64;
+    use std::collections::HashSet;
+
+    fn brute_force(arr: &[u32], queries: &[(usize, usize)]) -> Vec<u64> {
+        queries
+            .iter()
+            .map(|&(l, r)| arr[l..=r].iter().collect::<HashSet<_>>().len() as u64)
+            .collect()
+    }
+
+    #[test]
+    fn small_example() {
+        let arr = [1, 1, 2, 1, 3, 4, 5, 2, 8];
+        let queries = [(0, 4), (1, 3), (2, 4), (0, 8), (7, 8)];
+        assert_eq!(mos_algorithm(&arr, &queries), vec![3, 2, 3, 6, 2]);
+    }
+
+    #[test]
+    fn random_ranges_against_brute_force() {
+        let mut rng = Xorshift64::new(42);
+        for (n, alphabet) in [(1, 1), (10, 3), (100, 10), (500, 60), (1000, 1000)] {
+            let arr: Vec<u32> = (0..n).map(|_| rng.gen_range(0..alphabet) as u32).collect();
+            let queries: Vec<(usize, usize)> = (0..300)
+                .map(|_| {
+                    let a = rng.gen_range(0..n as u64) as usize;
+                    let b = rng.gen_range(0..n as u64) as usize;
+                    (a.min(b), a.max(b))
+                })
+                .collect();
+            assert_eq!(mos_algorithm(&arr, &queries), brute_force(&arr, &queries));
+        }
+    }
+
+    #[test]
+    fn single_elements_and_full_range() {
+        let arr = [4, 7, 4, 4, 9, 7, 1];
+        let mut queries: Vec<(usize, usize)> = (0..arr.len()).map(|i| (i, i)).collect();
+        queries.push((0, arr.len() - 1));
+        queries.push((0, arr.len() - 1));
+        let mut expected = vec![1; arr.len()];
+        expected.extend([4, 4]);
+        assert_eq!(mos_algorithm(&arr, &queries), expected);
+    }
+
+    #[test]
+    fn no_queries() {
+        assert!(mos_algorithm(&[1, 2, 3], &[]).is_empty());
+        assert!(mos_algorithm(&[], &[]).is_empty());
+    }
+
+    #[test]
+    #[should_panic]
+    fn out_of_bounds_query() {
+        mos_algorithm(&[1, 2, 3], &[(1, 3)]);
+    }
+}
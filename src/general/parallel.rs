@@ -0,0 +1,111 @@
+use std::thread;
+
+/// Applies `f` to all the `items` on `n_threads` threads, and returns the
+/// results in the order of the items.
+///
+/// The items are split into `n_threads` contiguous chunks of nearly equal
+/// sizes, each one mapped by its own scoped thread, so that `f` may borrow
+/// from the caller. It spawns fresh threads at each call, without any thread
+/// pool or external dependency, which pays off when `f` is expensive enough.
+///
+/// # Panics
+///
+/// Panics if `n_threads` is 0, or if `f` panics.
+pub fn parallel_map<T: Send, R: Send>(
+    items: Vec<T>,
+    n_threads: usize,
+    f: impl Fn(T) -> R + Sync,
+) -> Vec<R> {
+    assert!(n_threads > 0, "at least one thread is needed");
+    let chunk_size = items.len().div_ceil(n_threads).max(1);
+    let mut chunks = Vec::with_capacity(n_threads);
+    let mut items = items.into_iter();
+    loop {
+        let chunk: Vec<T> = items.by_ref().take(chunk_size).collect();
+        if chunk.is_empty() {
+            break;
+        }
+        chunks.push(chunk);
+    }
+
+    let f = &f;
+    thread::scope(|scope| {
+        let handles: Vec<_> = chunks
+            .into_iter()
+            .map(|chunk| scope.spawn(move || chunk.into_iter().map(f).collect::<Vec<R>>()))
+            .collect();
+        handles
+            .into_iter()
+            .flat_map(|handle| handle.join().unwrap())
+            .collect()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    fn collatz_steps(mut n: u64) -> u32 {
+        let mut steps = 0;
+        while n != 1 {
+            n = if n.is_multiple_of(2) {
+                n / 2
+            } else {
+                3 * n + 1
+            };
+            steps += 1;
+        }
+        steps
+    }
+
+    #[test]
+    fn matches_sequential_map() {
+        let items: Vec<u64> = (1..=1000).collect();
+        let expected: Vec<u32> = items.iter().map(|&n| collatz_steps(n)).collect();
+        for n_threads in [1, 2, 3, 7, 16] {
+            assert_eq!(
+                parallel_map(items.clone(), n_threads, collatz_steps),
+                expected
+            );
+        }
+    }
+
+    #[test]
+    fn preserves_order() {
+        let items: Vec<usize> = (0..100).collect();
+        let result = parallel_map(items, 4, |i| (i, thread::current().id()));
+        assert_eq!(
+            result.iter().map(|&(i, _)| i).collect::<Vec<_>>(),
+            (0..100).collect::<Vec<_>>()
+        );
+        // on 4 threads, other than the calling one
+        let threads: HashSet<_> = result.iter().map(|&(_, id)| id).collect();
+        assert_eq!(threads.len(), 4);
+        assert!(!threads.contains(&thread::current().id()));
+    }
+
+    #[test]
+    fn borrows_from_the_caller() {
+        let words = ["zero", "one", "two", "three"];
+        let lengths = parallel_map(vec![3, 1, 2, 0], 2, |i: usize| words[i].len());
+        assert_eq!(lengths, vec![5, 3, 3, 4]);
+        // and owned values are moved to the threads
+        let strings = vec![String::from("a"), String::from("bc")];
+        assert_eq!(parallel_map(strings, 2, |s| s + "!"), vec!["a!", "bc!"]);
+    }
+
+    #[test]
+    fn single_thread_and_empty_input() {
+        assert_eq!(parallel_map(vec![1, 2, 3], 1, |x| x * 10), vec![10, 20, 30]);
+        assert_eq!(parallel_map(Vec::<i32>::new(), 4, |x| x), Vec::<i32>::new());
+        // more threads than items
+        assert_eq!(parallel_map(vec![1, 2], 8, |x| -x), vec![-1, -2]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn no_thread() {
+        parallel_map(vec![1], 0, |x| x);
+    }
+}
@@ -0,0 +1,272 @@
+/*
+Xorshift64 pseudo-random number generator
+https://en.wikipedia.org/wiki/Xorshift
+
+A tiny, fast and explicitly seeded generator: the same seed always produces the
+same sequence, which makes it handy for reproducible tests and for algorithms
+which only need "good enough" randomness (see also `math::PCG32`).
+It is _NOT_ cryptographically secure.
+ */
+use std::ops::Range;
+
+/// Used in place of a zero seed, for which xorshift would only produce zeros
+const DEFAULT_SEED: u64 = 0x9E3779B97F4A7C15;
+
+#[derive(Clone, Debug)]
+pub struct Xorshift64 {
+    state: u64,
+    /// Second standard normal sample produced by the last Box-Muller transform
+    spare_gaussian: Option<f64>,
+}
+
+impl Xorshift64 {
+    /// Creates a generator from `seed`. A seed of 0 is replaced by a fixed
+    /// non-zero constant.
+    pub fn new(seed: u64) -> Self {
+        Xorshift64 {
+            state: if seed == 0 { DEFAULT_SEED } else { seed },
+            spare_gaussian: None,
+        }
+    }
+
+    /// Returns the next 64 random bits
+    pub fn next_u64(&mut self) -> u64 {
+        let mut x = self.state;
+        x ^= x << 13;
+        x ^= x >> 7;
+        x ^= x << 17;
+        self.state = x;
+        x
+    }
+
+    /// Returns a float uniformly distributed in [0, 1)
+    pub fn next_f64(&mut self) -> f64 {
+        // keep the 53 most significant bits, the precision of a f64 mantissa
+        (self.next_u64() >> 11) as f64 * (1.0 / (1u64 << 53) as f64)
+    }
+
+    /// Returns an integer uniformly distributed in `range`.
+    /// Rejection sampling is used, so that no value is more likely than another.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `range` is empty.
+    pub fn gen_range(&mut self, range: Range<u64>) -> u64 {
+        assert!(range.start < range.end, "cannot sample an empty range");
+        let span = range.end - range.start;
+        // the largest multiple of `span` (minus one) representable in a u64
+        let zone = u64::MAX - (u64::MAX - span + 1) % span;
+        loop {
+            let x = self.next_u64();
+            if x <= zone {
+                return range.start + x % span;
+            }
+        }
+    }
+
+    /// Returns a sample of the normal distribution of mean `mean` and
+    /// standard deviation `std`.
+    ///
+    /// The Box-Muller transform turns two uniform samples into two independent
+    /// standard normal ones, so every other call is answered from a cache.
+    /// See <https://en.wikipedia.org/wiki/Box%E2%80%93Muller_transform>
+    pub fn next_gaussian(&mut self, mean: f64, std: f64) -> f64 {
+        let z = match self.spare_gaussian.take() {
+            Some(z) => z,
+            None => {
+                // 1 - u lies in (0, 1], so that its logarithm is finite
+                let u1 = 1.0 - self.next_f64();
+                let u2 = self.next_f64();
+                let radius = (-2.0 * u1.ln()).sqrt();
+                let angle = 2.0 * std::f64::consts::PI * u2;
+                self.spare_gaussian = Some(radius * angle.sin());
+                radius * angle.cos()
+            }
+        };
+        mean + std * z
+    }
+
+    /// Shuffles `slice` in place, every permutation being equally likely,
+    /// with the Fisher-Yates algorithm.
+    /// See <https://en.wikipedia.org/wiki/Fisher%E2%80%93Yates_shuffle>
+    pub fn shuffle<T>(&mut self, slice: &mut [T]) {
+        for i in (1..slice.len()).rev() {
+            let j = self.gen_range(0..i as u64 + 1) as usize;
+            slice.swap(i, j);
+        }
+    }
+}
+
+/// Advances the linear congruential generator of state `state`, and returns
+/// a float uniformly distributed in [0, 1).
+///
+/// The state is updated as `state = a * state + c` modulo 2^64, with the
+/// constants of Knuth's MMIX, and its 53 most significant bits give the
+/// float, the low bits of such a generator being poor. Any state is a valid
+/// seed, so a test can hold a `u64` and draw reproducible inputs from it
+/// without building a generator. It is weaker than `Xorshift64`.
+/// See <https://en.wikipedia.org/wiki/Linear_congruential_generator>
+pub fn lcg_f64(state: &mut u64) -> f64 {
+    *state = state
+        .wrapping_mul(6364136223846793005)
+        .wrapping_add(1442695040888963407);
+    (*state >> 11) as f64 * (1.0 / (1u64 << 53) as f64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn deterministic_under_fixed_seed() {
+        let mut a = Xorshift64::new(42);
+        let mut b = Xorshift64::new(42);
+        for _ in 0..1000 {
+            assert_eq!(a.next_u64(), b.next_u64());
+        }
+        let mut c = Xorshift64::new(43);
+        assert_ne!(Xorshift64::new(42).next_u64(), c.next_u64());
+    }
+
+    #[test]
+    fn known_sequence() {
+        let mut rng = Xorshift64::new(1);
+        assert_eq!(rng.next_u64(), 1082269761);
+        assert_eq!(rng.next_u64(), 1152992998833853505);
+    }
+
+    #[test]
+    fn lcg_known_sequence() {
+        let mut state = 0;
+        assert_eq!(lcg_f64(&mut state), 0.07820865487829387);
+        assert_eq!(state, 1442695040888963407);
+        assert_eq!(lcg_f64(&mut state), 0.10169876029679303);
+    }
+
+    #[test]
+    fn lcg_is_deterministic_and_uniform() {
+        let (mut a, mut b) = (273, 273);
+        let n = 100_000;
+        let mut sum = 0.0;
+        for _ in 0..n {
+            let x = lcg_f64(&mut a);
+            assert_eq!(x, lcg_f64(&mut b));
+            assert!((0.0..1.0).contains(&x));
+            sum += x;
+        }
+        assert!((sum / n as f64 - 0.5).abs() < 0.01);
+    }
+
+    #[test]
+    fn zero_seed_is_usable() {
+        let mut rng = Xorshift64::new(0);
+        assert!((0..100).any(|_| rng.next_u64() != 0));
+    }
+
+    #[test]
+    fn next_f64_is_uniform_in_unit_interval() {
+        let mut rng = Xorshift64::new(314159);
+        let n = 100_000;
+        let mut sum = 0.0;
+        for _ in 0..n {
+            let x = rng.next_f64();
+            assert!((0.0..1.0).contains(&x));
+            sum += x;
+        }
+        let mean = sum / n as f64;
+        assert!((mean - 0.5).abs() < 0.01, "mean was {mean}");
+    }
+
+    #[test]
+    fn gen_range_stays_in_bounds_and_covers_range() {
+        let mut rng = Xorshift64::new(7);
+        let mut counts = [0usize; 6];
+        for _ in 0..60_000 {
+            let x = rng.gen_range(10..16);
+            assert!((10..16).contains(&x));
+            counts[(x - 10) as usize] += 1;
+        }
+        // each value is expected about 10_000 times
+        assert!(counts.iter().all(|&c| (9_000..11_000).contains(&c)));
+        assert_eq!(rng.gen_range(5..6), 5);
+        assert!(rng.gen_range(0..u64::MAX) < u64::MAX);
+    }
+
+    #[test]
+    #[should_panic]
+    fn gen_range_empty() {
+        Xorshift64::new(1).gen_range(3..3);
+    }
+
+    #[test]
+    fn gaussian_matches_requested_parameters() {
+        let mut rng = Xorshift64::new(2718);
+        let n = 100_000;
+        let (mean, std) = (3.0, 2.0);
+        let samples: Vec<f64> = (0..n).map(|_| rng.next_gaussian(mean, std)).collect();
+        let empirical_mean = samples.iter().sum::<f64>() / n as f64;
+        let empirical_variance = samples
+            .iter()
+            .map(|x| (x - empirical_mean).powi(2))
+            .sum::<f64>()
+            / (n - 1) as f64;
+        assert!(
+            (empirical_mean - mean).abs() < 0.03,
+            "mean was {empirical_mean}"
+        );
+        assert!(
+            (empirical_variance.sqrt() - std).abs() < 0.03,
+            "std was {}",
+            empirical_variance.sqrt()
+        );
+        // about 68.3% of the samples lie within one standard deviation
+        let within_one_std = samples.iter().filter(|x| (*x - mean).abs() < std).count();
+        assert!((0.67..0.69).contains(&(within_one_std as f64 / n as f64)));
+    }
+
+    #[test]
+    fn gaussian_is_deterministic_and_uses_both_samples() {
+        let mut a = Xorshift64::new(5);
+        let mut b = Xorshift64::new(5);
+        let first: Vec<f64> = (0..10).map(|_| a.next_gaussian(0.0, 1.0)).collect();
+        let second: Vec<f64> = (0..10).map(|_| b.next_gaussian(0.0, 1.0)).collect();
+        assert_eq!(first, second);
+        // two gaussian samples only consume two uniform draws
+        let mut c = Xorshift64::new(5);
+        c.next_gaussian(0.0, 1.0);
+        c.next_gaussian(0.0, 1.0);
+        let mut d = Xorshift64::new(5);
+        d.next_u64();
+        d.next_u64();
+        assert_eq!(c.next_u64(), d.next_u64());
+    }
+
+    #[test]
+    fn gaussian_with_zero_std_is_constant() {
+        let mut rng = Xorshift64::new(11);
+        assert!((0..100).all(|_| rng.next_gaussian(-1.5, 0.0) == -1.5));
+    }
+
+    #[test]
+    fn shuffle_is_a_uniform_permutation() {
+        let mut rng = Xorshift64::new(11);
+        // the 6 orders of 3 elements are about equally frequent
+        let mut counts = std::collections::HashMap::new();
+        for _ in 0..60_000 {
+            let mut items = [0, 1, 2];
+            rng.shuffle(&mut items);
+            *counts.entry(items).or_insert(0) += 1;
+        }
+        assert_eq!(counts.len(), 6);
+        for &count in counts.values() {
+            assert!((9_000..11_000).contains(&count), "{counts:?}");
+        }
+
+        let mut items: Vec<u32> = (0..100).collect();
+        rng.shuffle(&mut items);
+        let mut sorted = items.clone();
+        sorted.sort_unstable();
+        assert_eq!(sorted, (0..100).collect::<Vec<_>>());
+        rng.shuffle(&mut [] as &mut [u32]);
+    }
+}
@@ -0,0 +1,106 @@
+//! # Reservoir sampling
+//!
+//! Draws a uniform sample of `k` items from a stream in a single pass,
+//! without knowing its length in advance nor storing more than the `k` items
+//! (Algorithm R): the first `k` items fill the reservoir, then the `i`-th
+//! item, from 1, replaces a random item of the reservoir with probability
+//! `k / i`. Every item of the stream ends up in the sample with the same
+//! probability.
+//!
+//! Wikipedia: <https://en.wikipedia.org/wiki/Reservoir_sampling>
+
+use crate::general::Xorshift64;
+use crate::math::median;
+
+/// Returns a uniform sample of `k` items of `iter`, drawn with the generator
+/// seeded by `seed`, or all of them if it has fewer. The sample keeps the
+/// items in no particular order.
+///
+/// Panics if `k` is 0.
+pub fn reservoir_sample<T>(iter: impl Iterator<Item = T>, k: usize, seed: u64) -> Vec<T> {
+    assert!(k > 0, "the sample needs room for one item");
+    let mut rng = Xorshift64::new(seed);
+    let mut reservoir = Vec::with_capacity(k);
+    for (i, item) in iter.enumerate() {
+        if i < k {
+            reservoir.push(item);
+        } else {
+            let j = rng.gen_range(0..i as u64 + 1) as usize;
+            if j < k {
+                reservoir[j] = item;
+            }
+        }
+    }
+    reservoir
+}
+
+/// Estimates the median of a stream in a single pass and O(`sample_size`)
+/// memory: the exact median of a reservoir sample of `sample_size` values,
+/// drawn with `seed`. The median of an empty stream is NaN.
+///
+/// Panics if `sample_size` is 0, or if a sampled value is NaN.
+pub fn approximate_median(iter: impl Iterator<Item = f64>, sample_size: usize, seed: u64) -> f64 {
+    median(reservoir_sample(iter, sample_size, seed)).unwrap_or(f64::NAN)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn short_streams_are_kept_whole() {
+        assert_eq!(reservoir_sample(1..=3, 5, 287), vec![1, 2, 3]);
+        assert_eq!(reservoir_sample(1..=5, 5, 287), vec![1, 2, 3, 4, 5]);
+        assert!(reservoir_sample(std::iter::empty::<u8>(), 5, 287).is_empty());
+    }
+
+    #[test]
+    fn every_item_is_equally_likely() {
+        // each of the 10 items is in a sample of 3 with probability 0.3
+        let mut counts = [0u32; 10];
+        let mut seeds = Xorshift64::new(287);
+        for _ in 0..10_000 {
+            let sample = reservoir_sample(0..10, 3, seeds.next_u64());
+            assert_eq!(sample.len(), 3);
+            for item in sample {
+                counts[item] += 1;
+            }
+        }
+        for count in counts {
+            assert!((2_800..3_200).contains(&count), "{counts:?}");
+        }
+    }
+
+    #[test]
+    fn close_to_the_true_median() {
+        let mut rng = Xorshift64::new(287);
+        let stream: Vec<f64> = (0..200_000).map(|_| rng.next_gaussian(5.0, 2.0)).collect();
+        let truth = median(stream.clone()).unwrap();
+        let estimate = approximate_median(stream.iter().copied(), 2_000, 2870);
+        // the sample median has a standard deviation of about
+        // 1.25 * 2 / sqrt(2000) = 0.056
+        assert!((estimate - truth).abs() < 0.2, "{estimate} vs {truth}");
+        // the same seed draws the same sample
+        assert_eq!(
+            approximate_median(stream.iter().copied(), 2_000, 2870),
+            estimate
+        );
+        assert_ne!(
+            approximate_median(stream.iter().copied(), 2_000, 2871),
+            estimate
+        );
+    }
+
+    #[test]
+    fn small_medians() {
+        assert_eq!(approximate_median([3.0, 1.0, 2.0].into_iter(), 10, 1), 2.0);
+        assert_eq!(approximate_median([4.0, 1.0].into_iter(), 10, 1), 2.5);
+        assert!(approximate_median(std::iter::empty(), 10, 1).is_nan());
+    }
+
+    #[test]
+    #[should_panic]
+    fn empty_sample() {
+        reservoir_sample(0..10, 0, 287);
+    }
+}
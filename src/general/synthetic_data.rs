@@ -0,0 +1,119 @@
+use super::Xorshift64;
+
+/// Generates `n_samples` points drawn from isotropic gaussian blobs, along
+/// with the index of the blob each point was drawn from.
+///
+/// The samples are split as evenly as possible between the `centers` (the
+/// first `n_samples % centers.len()` blobs get one more point), and every
+/// coordinate is drawn from a normal distribution of standard deviation `std`
+/// around its center. The points are returned grouped by blob, in the order
+/// of `centers`. The same `seed` always yields the same data, which gives a
+/// reproducible ground truth to test clustering algorithms against.
+///
+/// # Panics
+///
+/// Panics if `centers` is empty while `n_samples` is not zero.
+pub fn make_blobs(
+    n_samples: usize,
+    centers: &[Vec<f64>],
+    std: f64,
+    seed: u64,
+) -> (Vec<Vec<f64>>, Vec<usize>) {
+    if n_samples == 0 {
+        return (Vec::new(), Vec::new());
+    }
+    assert!(!centers.is_empty(), "at least one center is needed");
+
+    let mut rng = Xorshift64::new(seed);
+    let mut points = Vec::with_capacity(n_samples);
+    let mut labels = Vec::with_capacity(n_samples);
+
+    let per_center = n_samples / centers.len();
+    let remainder = n_samples % centers.len();
+    for (label, center) in centers.iter().enumerate() {
+        let count = per_center + usize::from(label < remainder);
+        for _ in 0..count {
+            points.push(
+                center
+                    .iter()
+                    .map(|&c| rng.next_gaussian(c, std))
+                    .collect::<Vec<f64>>(),
+            );
+            labels.push(label);
+        }
+    }
+
+    (points, labels)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::general::kmeans_f64;
+    use crate::machine_learning::canonicalize_labels;
+
+    #[test]
+    fn shapes_and_label_counts() {
+        let centers = vec![
+            vec![0.0, 0.0, 0.0],
+            vec![5.0, 5.0, 5.0],
+            vec![-5.0, 5.0, 0.0],
+        ];
+        let (points, labels) = make_blobs(100, &centers, 1.0, 42);
+        assert_eq!(points.len(), 100);
+        assert_eq!(labels.len(), 100);
+        assert!(points.iter().all(|p| p.len() == 3));
+        let counts: Vec<usize> = (0..3)
+            .map(|k| labels.iter().filter(|&&l| l == k).count())
+            .collect();
+        assert_eq!(counts, vec![34, 33, 33]);
+    }
+
+    #[test]
+    fn points_are_centered_around_their_center() {
+        let centers = vec![vec![-10.0, 2.0], vec![10.0, -2.0]];
+        let (points, labels) = make_blobs(10_000, &centers, 0.5, 7);
+        for (label, center) in centers.iter().enumerate() {
+            let members: Vec<&Vec<f64>> = points
+                .iter()
+                .zip(labels.iter())
+                .filter(|(_, &l)| l == label)
+                .map(|(p, _)| p)
+                .collect();
+            for (dim, &c) in center.iter().enumerate() {
+                let mean = members.iter().map(|p| p[dim]).sum::<f64>() / members.len() as f64;
+                assert!((mean - c).abs() < 0.05);
+            }
+        }
+    }
+
+    #[test]
+    fn deterministic_under_fixed_seed() {
+        let centers = vec![vec![1.0], vec![2.0]];
+        assert_eq!(
+            make_blobs(50, &centers, 1.0, 3),
+            make_blobs(50, &centers, 1.0, 3)
+        );
+        assert_ne!(
+            make_blobs(50, &centers, 1.0, 3).0,
+            make_blobs(50, &centers, 1.0, 4).0
+        );
+    }
+
+    #[test]
+    fn empty_output() {
+        assert_eq!(make_blobs(0, &[], 1.0, 1), (vec![], vec![]));
+    }
+
+    #[test]
+    fn kmeans_recovers_two_blobs_up_to_permutation() {
+        let centers = vec![vec![-5.0, -5.0], vec![5.0, 5.0]];
+        let (points, labels) = make_blobs(200, &centers, 1.0, 1234);
+        let clustering = kmeans_f64(&points, 2, None).unwrap();
+
+        assert_eq!(
+            canonicalize_labels(&clustering),
+            canonicalize_labels(&labels)
+        );
+    }
+}
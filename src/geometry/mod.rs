@@ -1,15 +1,21 @@
 mod closest_points;
 mod graham_scan;
 mod jarvis_scan;
+mod nearest_site;
 mod point;
+mod polygon;
 mod polygon_points;
 mod ramer_douglas_peucker;
+mod rotating_calipers;
 mod segment;
 
 pub use self::closest_points::closest_points;
 pub use self::graham_scan::graham_scan;
 pub use self::jarvis_scan::jarvis_march;
+pub use self::nearest_site::{nearest_site, NearestSiteIndex};
 pub use self::point::Point;
+pub use self::polygon::{polygon_area, polygon_centroid};
 pub use self::polygon_points::lattice_points;
 pub use self::ramer_douglas_peucker::ramer_douglas_peucker;
+pub use self::rotating_calipers::point_set_diameter;
 pub use self::segment::Segment;
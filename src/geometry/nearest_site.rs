@@ -0,0 +1,132 @@
+use crate::data_structures::KdTree;
+
+/// Returns the index of the site nearest to `query`, i.e. the site whose
+/// Voronoi cell contains `query`, without building the Voronoi diagram. When
+/// several sites are at the same distance, the lowest index is returned.
+///
+/// The sites are scanned in O(n). To answer many queries against the same
+/// sites, build a `NearestSiteIndex` of them once.
+///
+/// Panics if there are no sites.
+pub fn nearest_site(sites: &[(f64, f64)], query: (f64, f64)) -> usize {
+    assert!(!sites.is_empty(), "there are no sites");
+    let distance = |(x, y): (f64, f64)| (x - query.0).powi(2) + (y - query.1).powi(2);
+    let mut nearest = 0;
+    for (i, &site) in sites.iter().enumerate().skip(1) {
+        if distance(site) < distance(sites[nearest]) {
+            nearest = i;
+        }
+    }
+    nearest
+}
+
+/// The sites put in a k-d tree, built once in O(n log(n)), to find the one
+/// nearest to each query in O(log(n)) on average.
+pub struct NearestSiteIndex {
+    tree: KdTree,
+}
+
+impl NearestSiteIndex {
+    /// Panics if there are no sites.
+    pub fn new(sites: &[(f64, f64)]) -> Self {
+        assert!(!sites.is_empty(), "there are no sites");
+        let points: Vec<Vec<f64>> = sites.iter().map(|&(x, y)| vec![x, y]).collect();
+        NearestSiteIndex {
+            tree: KdTree::new(&points),
+        }
+    }
+
+    /// Returns the index of the site nearest to `query`, the lowest index
+    /// among the sites at the same distance, like `nearest_site`.
+    pub fn nearest_site(&self, query: (f64, f64)) -> usize {
+        self.tree.nearest(&[query.0, query.1]).unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::general::Xorshift64;
+
+    /// Checks the index against the scan of the sites on every query
+    fn assert_same_sites(sites: &[(f64, f64)], queries: &[(f64, f64)]) {
+        let index = NearestSiteIndex::new(sites);
+        for &query in queries {
+            assert_eq!(index.nearest_site(query), nearest_site(sites, query));
+        }
+    }
+
+    #[test]
+    fn random_sites_and_queries() {
+        let mut rng = Xorshift64::new(246);
+        for n in [1, 3, 10, 100, 1000] {
+            let sites: Vec<(f64, f64)> = (0..n)
+                .map(|_| (rng.next_f64() * 100.0, rng.next_f64() * 100.0))
+                .collect();
+            let queries: Vec<(f64, f64)> = (0..30)
+                .map(|_| (rng.next_f64() * 120.0 - 10.0, rng.next_f64() * 120.0 - 10.0))
+                .collect();
+            assert_same_sites(&sites, &queries);
+        }
+    }
+
+    #[test]
+    fn sites_own_themselves() {
+        let sites = [(0.0, 0.0), (5.0, 1.0), (-3.0, 4.0), (2.0, -7.0)];
+        let index = NearestSiteIndex::new(&sites);
+        for (i, &site) in sites.iter().enumerate() {
+            assert_eq!(nearest_site(&sites, site), i);
+            assert_eq!(index.nearest_site(site), i);
+        }
+    }
+
+    #[test]
+    fn ties_go_to_the_lowest_index() {
+        // the query is on the boundary of the cells of the four sites
+        let sites = [
+            (1.0, 1.0),
+            (-1.0, 1.0),
+            (1.0, -1.0),
+            (-1.0, -1.0),
+            (3.0, 3.0),
+        ];
+        assert_eq!(nearest_site(&sites, (0.0, 0.0)), 0);
+        assert_eq!(nearest_site(&sites[1..], (0.0, 0.0)), 0);
+        // on the boundary between (-1, 1) and (-1, -1) only
+        assert_eq!(nearest_site(&sites, (-2.0, 0.0)), 1);
+        // duplicate sites
+        let sites = [(4.0, 4.0), (2.0, 2.0), (2.0, 2.0), (0.0, 0.0)];
+        assert_eq!(nearest_site(&sites, (2.5, 2.0)), 1);
+        assert_eq!(nearest_site(&sites, (1.0, 1.0)), 1);
+        assert_same_sites(&sites, &[(2.5, 2.0), (1.0, 1.0), (3.0, 3.0)]);
+    }
+
+    #[test]
+    fn ties_on_a_grid() {
+        let mut sites = vec![];
+        for x in 0..6 {
+            for y in 0..6 {
+                sites.push((x as f64, y as f64));
+            }
+        }
+        let mut queries = vec![];
+        for x in 0..11 {
+            for y in 0..11 {
+                queries.push((x as f64 / 2.0, y as f64 / 2.0));
+            }
+        }
+        assert_same_sites(&sites, &queries);
+    }
+
+    #[test]
+    #[should_panic]
+    fn no_sites() {
+        nearest_site(&[], (0.0, 0.0));
+    }
+
+    #[test]
+    #[should_panic]
+    fn no_sites_to_index() {
+        NearestSiteIndex::new(&[]);
+    }
+}
@@ -0,0 +1,156 @@
+// Area and centroid of a simple polygon, given by its vertices in order
+// (clockwise or counter-clockwise), with the shoelace formula:
+// https://en.wikipedia.org/wiki/Shoelace_formula
+// https://en.wikipedia.org/wiki/Centroid#Of_a_polygon
+
+// Twice the signed area: positive for counter-clockwise vertices
+fn doubled_signed_area(vertices: &[(f64, f64)]) -> f64 {
+    edges(vertices)
+        .map(|((x0, y0), (x1, y1))| x0 * y1 - x1 * y0)
+        .sum()
+}
+
+// The edges of the polygon, closing it from the last vertex to the first one
+fn edges(vertices: &[(f64, f64)]) -> impl Iterator<Item = ((f64, f64), (f64, f64))> + '_ {
+    vertices
+        .iter()
+        .copied()
+        .zip(vertices.iter().copied().cycle().skip(1))
+}
+
+/// Returns the area of the polygon, 0 if it has less than three vertices
+pub fn polygon_area(vertices: &[(f64, f64)]) -> f64 {
+    doubled_signed_area(vertices).abs() / 2.0
+}
+
+/// Returns the centroid (center of mass) of the surface of the polygon.
+///
+/// A polygon of zero area has no such centroid, the average of its vertices is
+/// returned instead. The area counts as zero when it is within the rounding
+/// errors of the cross products summed to compute it, which scale with the
+/// polygon rather than with an absolute threshold.
+///
+/// Panics if there are no vertices.
+pub fn polygon_centroid(vertices: &[(f64, f64)]) -> (f64, f64) {
+    assert!(!vertices.is_empty(), "the polygon has no vertices");
+    // relative to the first vertex, so that the cross products are as small,
+    // and as precise, as the polygon allows, wherever it lies
+    let (ox, oy) = vertices[0];
+    let shifted: Vec<(f64, f64)> = vertices.iter().map(|&(x, y)| (x - ox, y - oy)).collect();
+
+    let (mut doubled_area, mut magnitude) = (0.0, 0.0);
+    let (mut x, mut y) = (0.0, 0.0);
+    for ((x0, y0), (x1, y1)) in edges(&shifted) {
+        let cross = x0 * y1 - x1 * y0;
+        doubled_area += cross;
+        magnitude += cross.abs();
+        x += (x0 + x1) * cross;
+        y += (y0 + y1) * cross;
+    }
+    if doubled_area.abs() <= 16.0 * f64::EPSILON * magnitude {
+        let n = vertices.len() as f64;
+        let (x, y) = vertices
+            .iter()
+            .fold((0.0, 0.0), |(x, y), &(vx, vy)| (x + vx, y + vy));
+        return (x / n, y / n);
+    }
+    // the sign of the area cancels out with the one of the sums
+    (ox + x / (3.0 * doubled_area), oy + y / (3.0 * doubled_area))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_point_eq(actual: (f64, f64), expected: (f64, f64)) {
+        assert!(
+            (actual.0 - expected.0).abs() < 1e-9 && (actual.1 - expected.1).abs() < 1e-9,
+            "{actual:?} != {expected:?}"
+        );
+    }
+
+    #[test]
+    fn unit_square() {
+        let square = [(0.0, 0.0), (1.0, 0.0), (1.0, 1.0), (0.0, 1.0)];
+        assert_eq!(polygon_area(&square), 1.0);
+        assert_point_eq(polygon_centroid(&square), (0.5, 0.5));
+    }
+
+    #[test]
+    fn both_orientations() {
+        let counter_clockwise = [(1.0, 1.0), (5.0, 1.0), (5.0, 3.0), (1.0, 3.0)];
+        let mut clockwise = counter_clockwise;
+        clockwise.reverse();
+        for rectangle in [counter_clockwise, clockwise] {
+            assert_eq!(polygon_area(&rectangle), 8.0);
+            assert_point_eq(polygon_centroid(&rectangle), (3.0, 2.0));
+        }
+    }
+
+    #[test]
+    fn triangle() {
+        // base 6 and height 4
+        let triangle = [(0.0, 0.0), (6.0, 0.0), (2.0, 4.0)];
+        assert_eq!(polygon_area(&triangle), 12.0);
+        // the average of the vertices, for a triangle
+        assert_point_eq(polygon_centroid(&triangle), (8.0 / 3.0, 4.0 / 3.0));
+    }
+
+    #[test]
+    fn concave_polygon() {
+        // an L shape: a 2x2 square at the origin, and a 1x1 square on its right
+        let l_shape = [
+            (0.0, 0.0),
+            (3.0, 0.0),
+            (3.0, 1.0),
+            (2.0, 1.0),
+            (2.0, 2.0),
+            (0.0, 2.0),
+        ];
+        assert_eq!(polygon_area(&l_shape), 5.0);
+        // weighted average of the centroids (1, 1) and (2.5, 0.5) of the squares
+        assert_point_eq(polygon_centroid(&l_shape), (6.5 / 5.0, 4.5 / 5.0));
+    }
+
+    #[test]
+    fn degenerate_polygons() {
+        let segment = [(0.0, 0.0), (2.0, 2.0), (4.0, 4.0)];
+        assert_eq!(polygon_area(&segment), 0.0);
+        assert_point_eq(polygon_centroid(&segment), (2.0, 2.0));
+        assert_eq!(polygon_area(&[(1.0, 2.0)]), 0.0);
+        assert_point_eq(polygon_centroid(&[(1.0, 2.0)]), (1.0, 2.0));
+        assert_eq!(polygon_area(&[]), 0.0);
+    }
+
+    #[test]
+    fn tolerance_scales_with_the_polygon() {
+        // a valid triangle, whose doubled area of 8e-18 is below f64::EPSILON
+        let tiny = [(0.0, 0.0), (4e-9, 0.0), (0.0, 2e-9)];
+        let centroid = polygon_centroid(&tiny);
+        assert!((centroid.0 - 4e-9 / 3.0).abs() < 1e-21, "{centroid:?}");
+        assert!((centroid.1 - 2e-9 / 3.0).abs() < 1e-21, "{centroid:?}");
+
+        // collinear points far away, which leave rounding errors much larger
+        // than f64::EPSILON in the area
+        let segment = [(1e9, 1e9), (1e9 + 0.1, 1e9 + 0.3), (1e9 + 0.3, 1e9 + 0.9)];
+        let expected = (1e9 + 0.4 / 3.0, 1e9 + 0.4);
+        assert_point_eq(polygon_centroid(&segment), expected);
+        let far = [(3e8, 1e8), (6e8, 2e8), (9e8, 3e8), (1.2e9, 4e8)];
+        assert_point_eq(polygon_centroid(&far), (7.5e8, 2.5e8));
+
+        // and a unit square that far keeps its centroid
+        let square = [
+            (1e8, 1e8),
+            (1e8 + 1.0, 1e8),
+            (1e8 + 1.0, 1e8 + 1.0),
+            (1e8, 1e8 + 1.0),
+        ];
+        assert_point_eq(polygon_centroid(&square), (1e8 + 0.5, 1e8 + 0.5));
+    }
+
+    #[test]
+    #[should_panic]
+    fn centroid_without_vertices() {
+        polygon_centroid(&[]);
+    }
+}
@@ -0,0 +1,138 @@
+use crate::general::quickhull;
+
+fn distance(a: (f64, f64), b: (f64, f64)) -> f64 {
+    (a.0 - b.0).hypot(a.1 - b.1)
+}
+
+// Twice the area of the triangle abc, positive if it is counter-clockwise
+fn doubled_area(a: (f64, f64), b: (f64, f64), c: (f64, f64)) -> f64 {
+    (b.0 - a.0) * (c.1 - a.1) - (c.0 - a.0) * (b.1 - a.1)
+}
+
+/// Returns the largest distance between two of the points, or 0 if there are
+/// less than two points.
+///
+/// The two farthest points are vertices of the convex hull, and they are
+/// antipodal: there are two parallel lines through them with the whole hull in
+/// between. The rotating calipers walk the hull edge by edge, and for each
+/// edge advance the vertex farthest from it, which only moves forward around
+/// the hull. Each antipodal pair is met on the way, in O(h) for a hull of h
+/// vertices, so the whole is O(n log(n)) to build the hull.
+///
+/// See <https://en.wikipedia.org/wiki/Rotating_calipers>
+pub fn point_set_diameter(points: &[(f64, f64)]) -> f64 {
+    let boundary = quickhull(points);
+    // the calipers only stop on vertices, not on the points along the edges
+    let m = boundary.len();
+    let hull: Vec<(f64, f64)> = (0..m)
+        .filter(|&i| {
+            doubled_area(
+                boundary[(i + m - 1) % m],
+                boundary[i],
+                boundary[(i + 1) % m],
+            ) != 0.0
+        })
+        .map(|i| boundary[i])
+        .collect();
+    let n = hull.len();
+    if n < 3 {
+        // the points are on a segment, from one end to the other
+        return match (boundary.first(), boundary.last()) {
+            (Some(&a), Some(&b)) => distance(a, b),
+            _ => 0.0,
+        };
+    }
+
+    let mut diameter: f64 = 0.0;
+    let mut j = 1;
+    for i in 0..n {
+        let (a, b) = (hull[i], hull[(i + 1) % n]);
+        // the farthest vertex from the edge ab is where the area stops growing
+        while doubled_area(a, b, hull[(j + 1) % n]) > doubled_area(a, b, hull[j]) {
+            j = (j + 1) % n;
+        }
+        diameter = diameter.max(distance(a, hull[j])).max(distance(b, hull[j]));
+    }
+    diameter
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::general::Xorshift64;
+
+    fn brute_force(points: &[(f64, f64)]) -> f64 {
+        let mut diameter: f64 = 0.0;
+        for (i, &a) in points.iter().enumerate() {
+            for &b in &points[i + 1..] {
+                diameter = diameter.max(distance(a, b));
+            }
+        }
+        diameter
+    }
+
+    #[test]
+    fn square() {
+        let square = [(0.0, 0.0), (3.0, 0.0), (3.0, 3.0), (0.0, 3.0), (1.0, 2.0)];
+        assert!((point_set_diameter(&square) - 18f64.sqrt()).abs() < 1e-12);
+    }
+
+    #[test]
+    fn random_points() {
+        let mut rng = Xorshift64::new(245);
+        for n in [3, 5, 20, 100, 500] {
+            for _ in 0..10 {
+                let points: Vec<(f64, f64)> = (0..n)
+                    .map(|_| (rng.next_f64() * 20.0 - 10.0, rng.next_gaussian(0.0, 5.0)))
+                    .collect();
+                assert!((point_set_diameter(&points) - brute_force(&points)).abs() < 1e-9);
+            }
+        }
+    }
+
+    #[test]
+    fn points_on_a_circle() {
+        // many vertices on the hull, all pairs of opposite ones are farthest
+        let points: Vec<(f64, f64)> = (0..64)
+            .map(|i| {
+                let angle = i as f64 * std::f64::consts::TAU / 64.0;
+                (2.0 * angle.cos(), 2.0 * angle.sin())
+            })
+            .collect();
+        assert!((point_set_diameter(&points) - 4.0).abs() < 1e-9);
+        assert!((point_set_diameter(&points) - brute_force(&points)).abs() < 1e-12);
+    }
+
+    #[test]
+    fn collinear_points() {
+        let points = [(2.0, 1.0), (0.0, 0.0), (6.0, 3.0), (4.0, 2.0), (-2.0, -1.0)];
+        assert!((point_set_diameter(&points) - 80f64.sqrt()).abs() < 1e-12);
+        assert_eq!(point_set_diameter(&points), brute_force(&points));
+    }
+
+    #[test]
+    fn points_along_the_edges() {
+        // a rectangle with points on its edges, which the hull keeps
+        let mut points = vec![];
+        for i in 0..=6 {
+            points.extend([(i as f64, 0.0), (i as f64, 2.0)]);
+        }
+        points.extend([(0.0, 1.0), (6.0, 1.0), (3.0, 1.0)]);
+        assert_eq!(point_set_diameter(&points), 40f64.sqrt());
+        let mut rng = Xorshift64::new(2450);
+        for _ in 0..50 {
+            let points: Vec<(f64, f64)> = (0..30)
+                .map(|_| (rng.gen_range(0..6) as f64, rng.gen_range(0..4) as f64))
+                .collect();
+            assert_eq!(point_set_diameter(&points), brute_force(&points));
+        }
+    }
+
+    #[test]
+    fn degenerate_sets() {
+        assert_eq!(point_set_diameter(&[]), 0.0);
+        assert_eq!(point_set_diameter(&[(1.0, 1.0)]), 0.0);
+        assert_eq!(point_set_diameter(&[(1.0, 1.0); 3]), 0.0);
+        assert_eq!(point_set_diameter(&[(0.0, 0.0), (3.0, 4.0)]), 5.0);
+    }
+}
@@ -0,0 +1,166 @@
+use std::collections::VecDeque;
+
+/// Computes the betweenness centrality of every node of the unweighted graph
+/// `adj`, with Brandes' algorithm in O(V * E).
+///
+/// The betweenness of `v` is the sum, over all the ordered pairs of distinct
+/// nodes `(s, t)` other than `v`, of the fraction of the shortest paths from
+/// `s` to `t` which go through `v`. The graph is directed: for an undirected
+/// graph, where every edge is listed in both directions, every unordered pair
+/// is counted twice, so the scores are twice the usual undirected ones.
+///
+/// A breadth-first search from each source `s` counts the shortest paths
+/// `sigma[v]` from `s` to every `v`. Then, in the reverse order of the
+/// search, the dependency of `s` on `v`, `delta[v] = sum of sigma[v] /
+/// sigma[w] * (1 + delta[w])` over the successors `w` of `v` on shortest
+/// paths, is accumulated into the score of `v`.
+///
+/// See <https://en.wikipedia.org/wiki/Betweenness_centrality>
+pub fn betweenness_centrality(adj: &[Vec<usize>]) -> Vec<f64> {
+    let n = adj.len();
+    let mut centrality = vec![0.0; n];
+
+    for source in 0..n {
+        let mut order = Vec::with_capacity(n);
+        let mut predecessors: Vec<Vec<usize>> = vec![Vec::new(); n];
+        let mut sigma = vec![0.0; n];
+        let mut distance: Vec<Option<usize>> = vec![None; n];
+        sigma[source] = 1.0;
+        distance[source] = Some(0);
+
+        let mut queue = VecDeque::from([source]);
+        while let Some(v) = queue.pop_front() {
+            order.push(v);
+            let next_distance = distance[v].unwrap() + 1;
+            for &w in &adj[v] {
+                if distance[w].is_none() {
+                    distance[w] = Some(next_distance);
+                    queue.push_back(w);
+                }
+                if distance[w] == Some(next_distance) {
+                    sigma[w] += sigma[v];
+                    predecessors[w].push(v);
+                }
+            }
+        }
+
+        let mut delta = vec![0.0; n];
+        for &w in order.iter().rev() {
+            for &v in &predecessors[w] {
+                delta[v] += sigma[v] / sigma[w] * (1.0 + delta[w]);
+            }
+            if w != source {
+                centrality[w] += delta[w];
+            }
+        }
+    }
+    centrality
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::graph_utils::undirected;
+
+    /// All-pairs shortest path counts with a BFS per source, then the
+    /// definition of betweenness applied directly
+    fn brute_force(adj: &[Vec<usize>]) -> Vec<f64> {
+        let n = adj.len();
+        let mut dist = vec![vec![usize::MAX; n]; n];
+        let mut count = vec![vec![0.0; n]; n];
+        for s in 0..n {
+            dist[s][s] = 0;
+            count[s][s] = 1.0;
+            let mut queue = VecDeque::from([s]);
+            while let Some(v) = queue.pop_front() {
+                for &w in &adj[v] {
+                    if dist[s][w] == usize::MAX {
+                        dist[s][w] = dist[s][v] + 1;
+                        queue.push_back(w);
+                    }
+                    if dist[s][w] == dist[s][v] + 1 {
+                        count[s][w] += count[s][v];
+                    }
+                }
+            }
+        }
+
+        let mut centrality = vec![0.0; n];
+        for (v, score) in centrality.iter_mut().enumerate() {
+            for s in (0..n).filter(|&s| s != v) {
+                for t in (0..n).filter(|&t| t != v && t != s) {
+                    let through_v = dist[s][v] != usize::MAX
+                        && dist[v][t] != usize::MAX
+                        && dist[s][v] + dist[v][t] == dist[s][t];
+                    if through_v {
+                        *score += count[s][v] * count[v][t] / count[s][t];
+                    }
+                }
+            }
+        }
+        centrality
+    }
+
+    fn assert_close(a: &[f64], b: &[f64]) {
+        assert_eq!(a.len(), b.len());
+        for (x, y) in a.iter().zip(b) {
+            assert!((x - y).abs() < 1e-9, "{a:?} != {b:?}");
+        }
+    }
+
+    #[test]
+    fn star_graph() {
+        let adj = undirected(6, &[(0, 1), (0, 2), (0, 3), (0, 4), (0, 5)]);
+        let scores = betweenness_centrality(&adj);
+        // every ordered pair of leaves goes through the center
+        assert_close(&scores, &[20.0, 0.0, 0.0, 0.0, 0.0, 0.0]);
+    }
+
+    #[test]
+    fn path_graph() {
+        let adj = undirected(5, &[(0, 1), (1, 2), (2, 3), (3, 4)]);
+        let scores = betweenness_centrality(&adj);
+        // node i separates i * (4 - i) unordered pairs, each counted twice
+        assert_close(&scores, &[0.0, 6.0, 8.0, 6.0, 0.0]);
+        assert!(scores[2] > scores[1] && scores[1] > scores[0]);
+    }
+
+    #[test]
+    fn split_shortest_paths() {
+        // a square: the two paths from 0 to 2 go through 1 and 3
+        let adj = undirected(4, &[(0, 1), (1, 2), (2, 3), (3, 0)]);
+        assert_close(&betweenness_centrality(&adj), &[1.0, 1.0, 1.0, 1.0]);
+    }
+
+    #[test]
+    fn against_brute_force() {
+        let graphs = [
+            undirected(
+                7,
+                &[
+                    (0, 1),
+                    (0, 2),
+                    (1, 2),
+                    (1, 3),
+                    (2, 4),
+                    (3, 4),
+                    (4, 5),
+                    (5, 6),
+                    (3, 6),
+                ],
+            ),
+            // directed, with an unreachable node
+            vec![vec![1, 2], vec![3], vec![3], vec![4, 0], vec![], vec![0]],
+            undirected(5, &[(0, 1), (2, 3)]),
+        ];
+        for adj in graphs {
+            assert_close(&betweenness_centrality(&adj), &brute_force(&adj));
+        }
+    }
+
+    #[test]
+    fn empty_graph() {
+        assert!(betweenness_centrality(&[]).is_empty());
+        assert_eq!(betweenness_centrality(&[vec![]]), vec![0.0]);
+    }
+}
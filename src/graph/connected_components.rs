@@ -0,0 +1,87 @@
+use std::collections::VecDeque;
+
+/// Labels the connected components of the undirected graph `adj`.
+///
+/// Returns the number of components and, for every node, the id of its
+/// component. The ids are numbered from 0 in the order of the smallest node of
+/// each component. An edge may be listed in one direction only, as the
+/// components of a directed graph are taken as those of its undirected version
+/// (its "weakly connected" components).
+///
+/// Each component is flooded with a breadth-first search, in O(V + E).
+pub fn connected_components(adj: &[Vec<usize>]) -> (usize, Vec<usize>) {
+    let n = adj.len();
+    let mut neighbors: Vec<Vec<usize>> = adj.to_vec();
+    for (u, targets) in adj.iter().enumerate() {
+        for &v in targets {
+            neighbors[v].push(u);
+        }
+    }
+
+    let mut component: Vec<Option<usize>> = vec![None; n];
+    let mut count = 0;
+    for start in 0..n {
+        if component[start].is_some() {
+            continue;
+        }
+        component[start] = Some(count);
+        let mut queue = VecDeque::from([start]);
+        while let Some(u) = queue.pop_front() {
+            for &v in &neighbors[u] {
+                if component[v].is_none() {
+                    component[v] = Some(count);
+                    queue.push_back(v);
+                }
+            }
+        }
+        count += 1;
+    }
+    (count, component.into_iter().map(Option::unwrap).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::graph_utils::undirected;
+
+    #[test]
+    fn three_components() {
+        let adj = undirected(8, &[(0, 3), (3, 6), (1, 4), (4, 7), (7, 1), (2, 5)]);
+        let (count, labels) = connected_components(&adj);
+        assert_eq!(count, 3);
+        assert_eq!(labels, vec![0, 1, 2, 0, 1, 2, 0, 1]);
+        for u in 0..8 {
+            for v in 0..8 {
+                assert_eq!(labels[u] == labels[v], u % 3 == v % 3);
+            }
+        }
+    }
+
+    #[test]
+    fn isolated_nodes() {
+        let (count, labels) = connected_components(&[vec![], vec![], vec![]]);
+        assert_eq!(count, 3);
+        assert_eq!(labels, vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn complete_graph() {
+        let n = 6;
+        let adj: Vec<Vec<usize>> = (0..n)
+            .map(|u| (0..n).filter(|&v| v != u).collect())
+            .collect();
+        assert_eq!(connected_components(&adj), (1, vec![0; n]));
+    }
+
+    #[test]
+    fn edges_listed_in_one_direction() {
+        // 2 -> 0 and 1 -> 0 connect all three nodes
+        let adj = vec![vec![], vec![0], vec![0], vec![4], vec![]];
+        assert_eq!(connected_components(&adj), (2, vec![0, 0, 0, 1, 1]));
+    }
+
+    #[test]
+    fn empty_graph() {
+        assert_eq!(connected_components(&[]), (0, vec![]));
+    }
+}
@@ -1,6 +1,8 @@
 use std::collections::BTreeMap;
 use std::ops::Add;
 
+use crate::data_structures::FibonacciHeap;
+
 type Graph<V, E> = BTreeMap<V, BTreeMap<V, E>>;
 
 // performs Dijsktra's algorithm on the given graph from the given start
@@ -47,10 +49,63 @@ pub fn dijkstra<V: Ord + Copy, E: Ord + Copy + Add<Output = E>>(
     ans
 }
 
+// same as `dijkstra`, but the vertices to visit are kept in a Fibonacci heap ordered by
+// their current distance, whose keys are decreased when a shorter path is found
+//
+// Time: O(E + V * logV). Each edge may decrease a key in amortized O(1), and each vertex is
+// extracted once from the heap in amortized O(logV).
+// Space: O(V). The heap holds up to V vertices.
+pub fn dijkstra_fibonacci_heap<V: Ord + Copy, E: Ord + Copy + Add<Output = E>>(
+    graph: &Graph<V, E>,
+    start: V,
+) -> BTreeMap<V, Option<(V, E)>> {
+    let mut ans = BTreeMap::new();
+    let mut prio = FibonacciHeap::new();
+    // handle in `prio` of each vertex that has been pushed
+    let mut handles = BTreeMap::new();
+
+    // start is the special case that doesn't have a predecessor
+    ans.insert(start, None);
+
+    for (new, weight) in &graph[&start] {
+        if *new != start {
+            ans.insert(*new, Some((start, *weight)));
+            handles.insert(*new, prio.insert((*weight, *new)));
+        }
+    }
+
+    while let Some((path_weight, vertex)) = prio.extract_min() {
+        for (next, weight) in &graph[&vertex] {
+            let new_weight = path_weight + *weight;
+            match ans.get(next) {
+                // if ans[next] is a lower dist than the alternative one, we do nothing
+                // (this is always the case once next has been extracted)
+                Some(Some((_, dist_next))) if new_weight >= *dist_next => {}
+                // if ans[next] is None then next is start and so the distance won't be changed
+                Some(None) => {}
+                // the new path is shorter, either new was not in ans or it was farther
+                _ => {
+                    ans.insert(*next, Some((vertex, new_weight)));
+                    match handles.get(next) {
+                        Some(&handle) => prio.decrease_key(handle, (new_weight, *next)),
+                        None => {
+                            handles.insert(*next, prio.insert((new_weight, *next)));
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    ans
+}
+
 #[cfg(test)]
 mod tests {
-    use super::{dijkstra, Graph};
-    use std::collections::BTreeMap;
+    use super::{dijkstra, dijkstra_fibonacci_heap, Graph};
+    use crate::general::Xorshift64;
+    use std::cmp::Reverse;
+    use std::collections::{BTreeMap, BinaryHeap};
 
     fn add_edge<V: Ord + Copy, E: Ord>(graph: &mut Graph<V, E>, v1: V, v2: V, c: E) {
         graph.entry(v1).or_default().insert(v2, c);
@@ -152,4 +207,72 @@ mod tests {
         dists_e.insert('b', Some(('c', 39)));
         assert_eq!(dijkstra(&graph, 'e'), dists_e);
     }
+
+    /// Distances from `start` computed with a binary heap (lazy deletion)
+    fn binary_heap_distances(graph: &Graph<usize, u64>, start: usize) -> BTreeMap<usize, u64> {
+        let mut dist = BTreeMap::new();
+        let mut heap = BinaryHeap::new();
+        heap.push(Reverse((0, start)));
+        while let Some(Reverse((d, vertex))) = heap.pop() {
+            if dist.contains_key(&vertex) {
+                continue;
+            }
+            dist.insert(vertex, d);
+            for (next, weight) in &graph[&vertex] {
+                if !dist.contains_key(next) {
+                    heap.push(Reverse((d + weight, *next)));
+                }
+            }
+        }
+        dist
+    }
+
+    #[test]
+    fn fibonacci_heap_graph_1() {
+        let mut graph = BTreeMap::new();
+        add_edge(&mut graph, 'a', 'c', 12);
+        add_edge(&mut graph, 'a', 'd', 60);
+        add_edge(&mut graph, 'b', 'a', 10);
+        add_edge(&mut graph, 'c', 'b', 20);
+        add_edge(&mut graph, 'c', 'd', 32);
+        add_edge(&mut graph, 'e', 'a', 7);
+        for start in ['a', 'b', 'c', 'd', 'e'] {
+            assert_eq!(
+                dijkstra_fibonacci_heap(&graph, start),
+                dijkstra(&graph, start)
+            );
+        }
+    }
+
+    #[test]
+    fn fibonacci_heap_against_binary_heap() {
+        let mut rng = Xorshift64::new(8);
+        for n in [1, 5, 50, 300] {
+            let mut graph = BTreeMap::new();
+            for v in 0..n {
+                graph.insert(v, BTreeMap::new());
+            }
+            for _ in 0..4 * n {
+                let u = rng.gen_range(0..n as u64) as usize;
+                let v = rng.gen_range(0..n as u64) as usize;
+                add_edge(&mut graph, u, v, rng.gen_range(1..100));
+            }
+            for start in [0, n / 2, n - 1] {
+                let result = dijkstra_fibonacci_heap(&graph, start);
+                let expected = binary_heap_distances(&graph, start);
+                assert_eq!(result.len(), expected.len());
+                for (vertex, dist) in expected {
+                    match result[&vertex] {
+                        None => assert_eq!(dist, 0),
+                        Some((pred, d)) => {
+                            assert_eq!(d, dist);
+                            // the predecessor is on a shortest path
+                            let pred_dist = result[&pred].map_or(0, |(_, d)| d);
+                            assert_eq!(pred_dist + graph[&pred][&vertex], d);
+                        }
+                    }
+                }
+            }
+        }
+    }
 }
@@ -0,0 +1,128 @@
+//! Export of graphs to the DOT language of Graphviz, to draw them, for
+//! instance with `dot -Tsvg graph.dot -o graph.svg`.
+//!
+//! Wikipedia: <https://en.wikipedia.org/wiki/DOT_(graph_description_language)>
+
+use std::collections::HashSet;
+use std::fmt::Write;
+
+/// Returns `label` as a DOT quoted string
+fn quote(label: &str) -> String {
+    let mut quoted = String::from("\"");
+    for c in label.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            _ => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+/// Returns the DOT description of the graph of adjacency lists `adj`, its
+/// nodes labelled by their indices. See `to_dot_with_labels`.
+pub fn to_dot(adj: &[Vec<usize>], directed: bool) -> String {
+    let labels: Vec<String> = (0..adj.len()).map(|node| node.to_string()).collect();
+    to_dot_with_labels(adj, directed, &labels)
+}
+
+/// Returns the DOT description of the graph of adjacency lists `adj`, the
+/// node `i` being labelled by `labels[i]`, escaped as needed.
+///
+/// A directed graph is a `digraph` with an edge `u -> v` for each `v` in
+/// `adj[u]`. An undirected graph is a `graph` with an edge `u -- v` for each
+/// pair of neighbors, written once whether `adj` lists it on both sides or
+/// only one.
+///
+/// Panics if there is not a label per node, or if a neighbor is not a node.
+pub fn to_dot_with_labels<S: AsRef<str>>(
+    adj: &[Vec<usize>],
+    directed: bool,
+    labels: &[S],
+) -> String {
+    assert_eq!(labels.len(), adj.len(), "every node needs a label");
+    let (kind, arrow) = if directed {
+        ("digraph", "->")
+    } else {
+        ("graph", "--")
+    };
+    let mut dot = format!("{kind} {{\n");
+    for (node, label) in labels.iter().enumerate() {
+        writeln!(dot, "    {node} [label={}];", quote(label.as_ref())).unwrap();
+    }
+    let mut written = HashSet::new();
+    for (u, neighbors) in adj.iter().enumerate() {
+        for &v in neighbors {
+            assert!(v < adj.len(), "the neighbor {v} of {u} is not a node");
+            if directed || written.insert((u.min(v), u.max(v))) {
+                writeln!(dot, "    {u} {arrow} {v};").unwrap();
+            }
+        }
+    }
+    dot.push_str("}\n");
+    dot
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn edge_lines(dot: &str) -> Vec<&str> {
+        dot.lines()
+            .map(str::trim)
+            .filter(|line| line.contains("->") || line.contains("--"))
+            .collect()
+    }
+
+    #[test]
+    fn directed_graph() {
+        let adj = vec![vec![1, 2], vec![2], vec![0]];
+        let dot = to_dot(&adj, true);
+        assert!(dot.starts_with("digraph {\n"));
+        assert!(dot.ends_with("}\n"));
+        assert!(dot.contains("    1 [label=\"1\"];\n"));
+        assert_eq!(
+            edge_lines(&dot),
+            vec!["0 -> 1;", "0 -> 2;", "1 -> 2;", "2 -> 0;"]
+        );
+    }
+
+    #[test]
+    fn undirected_graph_has_no_duplicate_edges() {
+        // a triangle listed on both sides, and a pendant listed on one side
+        let adj = vec![vec![1, 2], vec![0, 2], vec![0, 1, 3], vec![]];
+        let dot = to_dot(&adj, false);
+        assert!(dot.starts_with("graph {\n"));
+        assert!(!dot.contains("->"));
+        assert_eq!(
+            edge_lines(&dot),
+            vec!["0 -- 1;", "0 -- 2;", "1 -- 2;", "2 -- 3;"]
+        );
+        // the same lists, directed, keep both directions
+        assert_eq!(edge_lines(&to_dot(&adj, true)).len(), 7);
+    }
+
+    #[test]
+    fn labels_are_escaped() {
+        let adj = vec![vec![1], vec![]];
+        let dot = to_dot_with_labels(&adj, true, &["say \"hi\"", "a\\b\nc"]);
+        assert!(dot.contains("    0 [label=\"say \\\"hi\\\"\"];\n"));
+        assert!(dot.contains("    1 [label=\"a\\\\b\\nc\"];\n"));
+        assert_eq!(edge_lines(&dot), vec!["0 -> 1;"]);
+    }
+
+    #[test]
+    fn empty_graph() {
+        assert_eq!(to_dot(&[], false), "graph {\n}\n");
+        // a self-loop
+        assert_eq!(edge_lines(&to_dot(&[vec![0]], false)), vec!["0 -- 0;"]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn neighbor_out_of_range() {
+        to_dot(&[vec![1]], true);
+    }
+}
@@ -0,0 +1,134 @@
+//! Reading weighted graphs from text, one edge per line:
+//!
+//! ```text
+//! # a comment
+//! 4
+//! 0 1 5
+//! 1 2 -3
+//! 2 3 7
+//! ```
+//!
+//! The first line holds the number of nodes `n`, and each following line an
+//! edge `u v w` from `u` to `v` of weight `w`, with `u` and `v` in `0..n`.
+//! The blank lines, and those starting with `#`, are skipped.
+
+/// An edge `(u, v, w)` from `u` to `v` of weight `w`
+pub type WeightedEdge = (usize, usize, i64);
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum ParseError {
+    /// The text has no line giving the number of nodes
+    MissingNodeCount,
+    /// The number of nodes, on the `line` (from 1), is not an integer
+    InvalidNodeCount { line: usize },
+    /// The edge on the `line` does not have three integers
+    MalformedEdge { line: usize },
+    /// The edge on the `line` has an end which is not a node
+    NodeOutOfRange { line: usize, node: usize },
+}
+
+/// Parses `text` as a number of nodes followed by weighted edges, in the
+/// format above, returning the number of nodes and the edges `(u, v, w)` in
+/// their order.
+pub fn parse_edge_list(text: &str) -> Result<(usize, Vec<WeightedEdge>), ParseError> {
+    let mut lines = text
+        .lines()
+        .enumerate()
+        .map(|(index, line)| (index + 1, line.trim()))
+        .filter(|(_, line)| !line.is_empty() && !line.starts_with('#'));
+
+    let (line, count) = lines.next().ok_or(ParseError::MissingNodeCount)?;
+    let nodes: usize = count
+        .parse()
+        .map_err(|_| ParseError::InvalidNodeCount { line })?;
+
+    let mut edges = vec![];
+    for (line, text) in lines {
+        let fields: Vec<&str> = text.split_whitespace().collect();
+        let parsed = match fields[..] {
+            [u, v, w] => (u.parse(), v.parse(), w.parse()),
+            _ => return Err(ParseError::MalformedEdge { line }),
+        };
+        let (u, v, w) = match parsed {
+            (Ok(u), Ok(v), Ok(w)) => (u, v, w),
+            _ => return Err(ParseError::MalformedEdge { line }),
+        };
+        if let Some(&node) = [u, v].iter().find(|&&node| node >= nodes) {
+            return Err(ParseError::NodeOutOfRange { line, node });
+        }
+        edges.push((u, v, w));
+    }
+    Ok((nodes, edges))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::dijkstra;
+    use std::collections::BTreeMap;
+
+    #[test]
+    fn parses_a_graph() {
+        let text = "4\n0 1 5\n1 2 -3\n2 3 7\n0 3 100\n";
+        assert_eq!(
+            parse_edge_list(text),
+            Ok((4, vec![(0, 1, 5), (1, 2, -3), (2, 3, 7), (0, 3, 100)]))
+        );
+        // no edges at all
+        assert_eq!(parse_edge_list("3"), Ok((3, vec![])));
+    }
+
+    #[test]
+    fn skips_comments_and_blank_lines() {
+        let text = "# a triangle\n\n3\n  0 1 1\n# the heavy edge\n1\t2   4\n\n2 0 2\n";
+        assert_eq!(
+            parse_edge_list(text),
+            Ok((3, vec![(0, 1, 1), (1, 2, 4), (2, 0, 2)]))
+        );
+    }
+
+    #[test]
+    fn feeds_the_shortest_paths() {
+        let (nodes, edges) = parse_edge_list("3\n0 1 4\n1 2 1\n0 2 7\n").unwrap();
+        let mut graph: BTreeMap<usize, BTreeMap<usize, i64>> =
+            (0..nodes).map(|node| (node, BTreeMap::new())).collect();
+        for (u, v, w) in edges {
+            graph.get_mut(&u).unwrap().insert(v, w);
+        }
+        let paths = dijkstra(&graph, 0);
+        assert_eq!(paths[&2], Some((1, 5)));
+    }
+
+    #[test]
+    fn rejects_malformed_lines() {
+        assert_eq!(parse_edge_list(""), Err(ParseError::MissingNodeCount));
+        assert_eq!(
+            parse_edge_list("# only comments\n"),
+            Err(ParseError::MissingNodeCount)
+        );
+        assert_eq!(
+            parse_edge_list("# nodes\nfour\n0 1 2"),
+            Err(ParseError::InvalidNodeCount { line: 2 })
+        );
+        assert_eq!(
+            parse_edge_list("-1\n"),
+            Err(ParseError::InvalidNodeCount { line: 1 })
+        );
+        for (bad, line) in [
+            ("3\n0 1\n", 2),
+            ("3\n0 1 2 3\n", 2),
+            ("3\n0 1 2\n\n1 x 2\n", 4),
+            ("3\n0 1 2.5\n", 2),
+            ("3\n-1 1 2\n", 2),
+        ] {
+            assert_eq!(
+                parse_edge_list(bad),
+                Err(ParseError::MalformedEdge { line })
+            );
+        }
+        assert_eq!(
+            parse_edge_list("3\n0 1 1\n1 3 1\n"),
+            Err(ParseError::NodeOutOfRange { line: 3, node: 3 })
+        );
+    }
+}
@@ -131,9 +131,82 @@ impl EulerianPath {
     }
 }
 
+/// Finds an Eulerian path, i.e. a walk using every edge exactly once, in the
+/// undirected graph `adj`, where every edge `{u, v}` is listed both in `adj[u]`
+/// and in `adj[v]` (a self-loop is thus listed twice in `adj[u]`).
+///
+/// Such a path exists if the edges are connected and either every node has an
+/// even degree, in which case the path is a circuit ending where it starts,
+/// or exactly two nodes have an odd degree, in which case the path goes from
+/// one to the other. As with `EulerianPath`, a graph without edges has no
+/// Eulerian path.
+///
+/// The path is built with Hierholzer's algorithm in O(V + E): follow unused
+/// edges until getting stuck, which can only happen at the end of the path,
+/// then backtrack and splice in the cycles which start from the nodes having
+/// unused edges left.
+///
+/// # Returns
+///
+/// The nodes of the path in order, or `None` if no Eulerian path exists.
+pub fn eulerian_path(adj: &[Vec<usize>]) -> Option<Vec<usize>> {
+    let n = adj.len();
+    // incident[u] holds the (neighbor, edge id) pairs of the edges at `u`
+    let mut incident: Vec<Vec<(usize, usize)>> = vec![Vec::new(); n];
+    let mut edge_count = 0;
+    for (u, neighbors) in adj.iter().enumerate() {
+        let mut self_loops = 0;
+        for &v in neighbors {
+            if u < v || (u == v && self_loops % 2 == 0) {
+                incident[u].push((v, edge_count));
+                incident[v].push((u, edge_count));
+                edge_count += 1;
+            }
+            if u == v {
+                self_loops += 1;
+            }
+        }
+    }
+    if edge_count == 0 {
+        return None;
+    }
+
+    let odd: Vec<usize> = (0..n).filter(|&u| incident[u].len() % 2 == 1).collect();
+    let start = match odd.len() {
+        0 => (0..n).find(|&u| !incident[u].is_empty()).unwrap(),
+        2 => odd[0],
+        _ => return None,
+    };
+
+    let mut used = vec![false; edge_count];
+    let mut next_edge = vec![0; n];
+    let mut stack = vec![start];
+    let mut path = Vec::with_capacity(edge_count + 1);
+    while let Some(&u) = stack.last() {
+        while next_edge[u] < incident[u].len() && used[incident[u][next_edge[u]].1] {
+            next_edge[u] += 1;
+        }
+        match incident[u].get(next_edge[u]) {
+            Some(&(v, edge)) => {
+                used[edge] = true;
+                stack.push(v);
+            }
+            None => path.push(stack.pop().unwrap()),
+        }
+    }
+
+    // some edges are not connected to the others
+    if path.len() != edge_count + 1 {
+        return None;
+    }
+    path.reverse();
+    Some(path)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::graph::graph_utils::undirected;
 
     /// Creates an empty graph with `n` nodes.
     fn create_empty_graph(n: usize) -> Vec<Vec<usize>> {
@@ -190,4 +263,64 @@ mod tests {
             vec![0, 1, 4, 1, 2, 1, 3]
         );
     }
+
+    /// Checks that `path` uses every edge of `edges` exactly once
+    fn assert_uses_every_edge(path: &[usize], edges: &[(usize, usize)]) {
+        let normalize = |u: usize, v: usize| (u.min(v), u.max(v));
+        let mut expected: Vec<(usize, usize)> =
+            edges.iter().map(|&(u, v)| normalize(u, v)).collect();
+        let mut walked: Vec<(usize, usize)> =
+            path.windows(2).map(|w| normalize(w[0], w[1])).collect();
+        expected.sort_unstable();
+        walked.sort_unstable();
+        assert_eq!(walked, expected);
+    }
+
+    #[test]
+    fn undirected_circuit() {
+        // two triangles sharing node 2: every degree is even
+        let edges = [(0, 1), (1, 2), (2, 0), (2, 3), (3, 4), (4, 2)];
+        let path = eulerian_path(&undirected(5, &edges)).unwrap();
+        assert_eq!(path.first(), path.last());
+        assert_uses_every_edge(&path, &edges);
+    }
+
+    #[test]
+    fn undirected_path_without_circuit() {
+        // the "house" drawn without lifting the pen: 0 and 1 have odd degrees
+        let edges = [
+            (0, 1),
+            (0, 2),
+            (1, 3),
+            (2, 3),
+            (2, 4),
+            (3, 4),
+            (0, 3),
+            (1, 2),
+        ];
+        let path = eulerian_path(&undirected(5, &edges)).unwrap();
+        assert_eq!(path.len(), edges.len() + 1);
+        let ends = (path[0].min(path[8]), path[0].max(path[8]));
+        assert_eq!(ends, (0, 1));
+        assert_uses_every_edge(&path, &edges);
+    }
+
+    #[test]
+    fn undirected_multi_edges_and_self_loops() {
+        let edges = [(0, 1), (0, 1), (1, 1), (1, 2)];
+        let path = eulerian_path(&undirected(3, &edges)).unwrap();
+        assert_uses_every_edge(&path, &edges);
+    }
+
+    #[test]
+    fn undirected_without_eulerian_path() {
+        // K4: four nodes of odd degree
+        let edges = [(0, 1), (0, 2), (0, 3), (1, 2), (1, 3), (2, 3)];
+        assert_eq!(eulerian_path(&undirected(4, &edges)), None);
+        // even degrees, but two separate cycles
+        let edges = [(0, 1), (1, 2), (2, 0), (3, 4), (4, 5), (5, 3)];
+        assert_eq!(eulerian_path(&undirected(6, &edges)), None);
+        // no edges
+        assert_eq!(eulerian_path(&undirected(3, &[])), None);
+    }
 }
@@ -0,0 +1,25 @@
+//! Graph builders shared by the tests of the graph algorithms
+
+/// Returns the adjacency lists of the undirected graph on the nodes `0..n`
+/// with the `edges`, each edge being listed at both its ends
+pub(crate) fn undirected(n: usize, edges: &[(usize, usize)]) -> Vec<Vec<usize>> {
+    let mut adj = vec![vec![]; n];
+    for &(u, v) in edges {
+        adj[u].push(v);
+        adj[v].push(u);
+    }
+    adj
+}
+
+/// Returns the matrix of the Euclidean distances between the `points`
+pub(crate) fn euclidean_distances(points: &[(f64, f64)]) -> Vec<Vec<f64>> {
+    points
+        .iter()
+        .map(|a| {
+            points
+                .iter()
+                .map(|b| (a.0 - b.0).hypot(a.1 - b.1))
+                .collect()
+        })
+        .collect()
+}
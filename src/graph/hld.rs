@@ -0,0 +1,248 @@
+/*
+Path queries on a tree with heavy-light decomposition:
+The decomposition gives consecutive positions to the vertices of each heavy
+path, so a segment tree over these positions can combine the values of any
+part of a heavy path in O(lg(n)). As any path between two vertices crosses
+O(lg(n)) heavy paths, a path query (e.g. the sum or the maximum of the values
+on the path) takes O(lg(n)^2), and updating the value of a vertex O(lg(n)).
+
+As with `HeavyLightDecomposition`, the vertices are numbered from 1 to n.
+*/
+
+use super::HeavyLightDecomposition;
+use crate::data_structures::MonoidSegmentTree;
+
+pub struct HldPathQuery<T, F>
+where
+    F: Fn(&T, &T) -> T + Clone,
+{
+    hld: HeavyLightDecomposition,
+    parent: Vec<usize>,
+    depth: Vec<usize>,
+    // values of the vertices, indexed by `position - 1`
+    tree: MonoidSegmentTree<T, F>,
+    identity: T,
+    combine: F,
+}
+
+impl<T: Clone, F: Fn(&T, &T) -> T + Clone> HldPathQuery<T, F> {
+    /// Decomposes the tree rooted at `root`, where `values[v]` is the value of
+    /// vertex `v` (`values[0]` is ignored). `combine` must be associative and
+    /// commutative, `identity` being its neutral element, e.g. `0` and `+` for
+    /// path sums or `T::MIN` and `max` for path maximums.
+    pub fn new(adj: &[Vec<usize>], root: usize, values: &[T], identity: T, combine: F) -> Self {
+        let num_vertices = adj.len() - 1;
+        let mut hld = HeavyLightDecomposition::new(num_vertices);
+        hld.decompose(root, adj);
+
+        let mut parent = vec![0; num_vertices + 1];
+        let mut depth = vec![0; num_vertices + 1];
+        let mut stack = vec![root];
+        while let Some(v) = stack.pop() {
+            for &u in adj[v].iter() {
+                if u != parent[v] {
+                    parent[u] = v;
+                    depth[u] = depth[v] + 1;
+                    stack.push(u);
+                }
+            }
+        }
+
+        let mut ordered = vec![identity.clone(); num_vertices];
+        for v in 1..=num_vertices {
+            ordered[hld.position[v] - 1] = values[v].clone();
+        }
+        let tree = MonoidSegmentTree::new(&ordered, identity.clone(), combine.clone());
+
+        HldPathQuery {
+            hld,
+            parent,
+            depth,
+            tree,
+            identity,
+            combine,
+        }
+    }
+
+    /// Combines the values of all the vertices on the path from `u` to `v`,
+    /// both included
+    pub fn path_query(&self, mut u: usize, mut v: usize) -> T {
+        let head = &self.hld.head;
+        let position = &self.hld.position;
+        let mut result = self.identity.clone();
+        while head[u] != head[v] {
+            // climb from the vertex whose heavy path starts deeper
+            if self.depth[head[u]] < self.depth[head[v]] {
+                std::mem::swap(&mut u, &mut v);
+            }
+            let part = self.tree.query(position[head[u]] - 1..position[u]);
+            result = (self.combine)(&result, &part);
+            u = self.parent[head[u]];
+        }
+        // `u` and `v` are now on the same heavy path
+        let (low, high) = if position[u] < position[v] {
+            (u, v)
+        } else {
+            (v, u)
+        };
+        let part = self.tree.query(position[low] - 1..position[high]);
+        (self.combine)(&result, &part)
+    }
+
+    /// Sets the value of vertex `node` to `value`
+    pub fn update(&mut self, node: usize, value: T) {
+        self.tree.update(self.hld.position[node] - 1, value);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::general::Xorshift64;
+
+    //           1
+    //         / | \
+    //        2  3  4
+    //       / \     \
+    //      5   6     7
+    //     /   / \     \
+    //    8   9  10    11
+    //                   \
+    //                   12
+    fn example_tree() -> Vec<Vec<usize>> {
+        let edges = [
+            (1, 2),
+            (1, 3),
+            (1, 4),
+            (2, 5),
+            (2, 6),
+            (4, 7),
+            (5, 8),
+            (6, 9),
+            (6, 10),
+            (7, 11),
+            (11, 12),
+        ];
+        let mut adj = vec![vec![]; 13];
+        for (u, v) in edges {
+            adj[u].push(v);
+            adj[v].push(u);
+        }
+        adj
+    }
+
+    /// Collects the values on the path from `u` to `v` by climbing from the
+    /// deeper vertex until both meet at their lowest common ancestor
+    fn naive_path_values(
+        adj: &[Vec<usize>],
+        root: usize,
+        values: &[i64],
+        u: usize,
+        v: usize,
+    ) -> Vec<i64> {
+        let n = adj.len();
+        let mut parent = vec![0; n];
+        let mut depth = vec![0; n];
+        let mut stack = vec![root];
+        while let Some(x) = stack.pop() {
+            for &y in &adj[x] {
+                if y != parent[x] {
+                    parent[y] = x;
+                    depth[y] = depth[x] + 1;
+                    stack.push(y);
+                }
+            }
+        }
+        let (mut u, mut v) = (u, v);
+        let mut path = Vec::new();
+        while u != v {
+            if depth[u] < depth[v] {
+                std::mem::swap(&mut u, &mut v);
+            }
+            path.push(values[u]);
+            u = parent[u];
+        }
+        path.push(values[u]);
+        path
+    }
+
+    #[test]
+    fn path_sum_and_max_on_known_tree() {
+        let adj = example_tree();
+        let values: Vec<i64> = vec![0, 5, -3, 8, 1, 7, 2, 4, -6, 9, 3, 10, -1];
+        let sums = HldPathQuery::new(&adj, 1, &values, 0, |a: &i64, b: &i64| a + b);
+        let maxs = HldPathQuery::new(&adj, 1, &values, i64::MIN, |a: &i64, b: &i64| *a.max(b));
+
+        // 8 - 5 - 2 - 6 - 10, summing to -6 + 7 - 3 + 2 + 3
+        assert_eq!(sums.path_query(8, 10), 3);
+        assert_eq!(maxs.path_query(8, 10), 7);
+        // 9 - 6 - 2 - 1 - 4 - 7 - 11 - 12
+        assert_eq!(sums.path_query(9, 12), 9 + 2 - 3 + 5 + 1 + 4 + 10 - 1);
+        assert_eq!(maxs.path_query(12, 9), 10);
+        // a vertex with itself
+        assert_eq!(sums.path_query(3, 3), 8);
+        assert_eq!(maxs.path_query(8, 8), -6);
+
+        for u in 1..=12 {
+            for v in 1..=12 {
+                let path = naive_path_values(&adj, 1, &values, u, v);
+                assert_eq!(sums.path_query(u, v), path.iter().sum::<i64>());
+                assert_eq!(maxs.path_query(u, v), *path.iter().max().unwrap());
+            }
+        }
+    }
+
+    #[test]
+    fn ancestor_queries() {
+        let adj = example_tree();
+        let values: Vec<i64> = (0..13).collect();
+        let sums = HldPathQuery::new(&adj, 1, &values, 0, |a: &i64, b: &i64| a + b);
+        // 1 is an ancestor of every vertex: the path is a path to the root
+        assert_eq!(sums.path_query(1, 12), 1 + 4 + 7 + 11 + 12);
+        assert_eq!(sums.path_query(12, 1), 1 + 4 + 7 + 11 + 12);
+        assert_eq!(sums.path_query(2, 9), 2 + 6 + 9);
+        assert_eq!(sums.path_query(10, 6), 10 + 6);
+    }
+
+    #[test]
+    fn updates_against_naive_walk() {
+        let mut rng = Xorshift64::new(7);
+        let n = 300;
+        // random tree where the parent of `i` is a previous vertex
+        let mut adj = vec![vec![]; n + 1];
+        for i in 2..=n {
+            let par = rng.gen_range(1..i as u64) as usize;
+            adj[par].push(i);
+            adj[i].push(par);
+        }
+        let mut values: Vec<i64> = (0..=n).map(|_| rng.gen_range(0..100) as i64).collect();
+        let root = 5;
+        let mut sums = HldPathQuery::new(&adj, root, &values, 0, |a: &i64, b: &i64| a + b);
+        let mut maxs =
+            HldPathQuery::new(&adj, root, &values, i64::MIN, |a: &i64, b: &i64| *a.max(b));
+
+        for _ in 0..500 {
+            let u = rng.gen_range(1..n as u64 + 1) as usize;
+            if rng.gen_range(0..3) == 0 {
+                let value = rng.gen_range(0..1000) as i64 - 500;
+                values[u] = value;
+                sums.update(u, value);
+                maxs.update(u, value);
+            } else {
+                let v = rng.gen_range(1..n as u64 + 1) as usize;
+                let path = naive_path_values(&adj, root, &values, u, v);
+                assert_eq!(sums.path_query(u, v), path.iter().sum::<i64>());
+                assert_eq!(maxs.path_query(u, v), *path.iter().max().unwrap());
+            }
+        }
+    }
+
+    #[test]
+    fn single_vertex() {
+        let adj = vec![vec![], vec![]];
+        let mut sums = HldPathQuery::new(&adj, 1, &[0, 42], 0, |a: &i32, b: &i32| a + b);
+        assert_eq!(sums.path_query(1, 1), 42);
+        sums.update(1, -1);
+        assert_eq!(sums.path_query(1, 1), -1);
+    }
+}
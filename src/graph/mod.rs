@@ -1,8 +1,10 @@
 mod astar;
 mod bellman_ford;
+mod betweenness_centrality;
 mod bipartite_matching;
 mod breadth_first_search;
 mod centroid_decomposition;
+mod connected_components;
 mod decremental_connectivity;
 mod depth_first_search;
 mod depth_first_search_tic_tac_toe;
@@ -10,46 +12,62 @@ mod detect_cycle;
 mod dijkstra;
 mod dinic_maxflow;
 mod disjoint_set_union;
+mod dot;
+mod edge_list;
 mod eulerian_path;
 mod floyd_warshall;
 mod ford_fulkerson;
 mod graph_enumeration;
+#[cfg(test)]
+pub(crate) mod graph_utils;
 mod heavy_light_decomposition;
+mod hld;
 mod kosaraju;
 mod lee_breadth_first_search;
 mod lowest_common_ancestor;
 mod minimum_spanning_tree;
+mod pagerank;
 mod prim;
 mod prufer_code;
 mod strongly_connected_components;
 mod tarjans_ssc;
 mod topological_sort;
+mod tsp_nearest_neighbor;
+mod two_opt;
 mod two_satisfiability;
 
 pub use self::astar::astar;
 pub use self::bellman_ford::bellman_ford;
+pub use self::betweenness_centrality::betweenness_centrality;
 pub use self::bipartite_matching::BipartiteMatching;
 pub use self::breadth_first_search::breadth_first_search;
 pub use self::centroid_decomposition::CentroidDecomposition;
+pub use self::connected_components::connected_components;
 pub use self::decremental_connectivity::DecrementalConnectivity;
 pub use self::depth_first_search::depth_first_search;
 pub use self::depth_first_search_tic_tac_toe::minimax;
 pub use self::detect_cycle::DetectCycle;
-pub use self::dijkstra::dijkstra;
+pub use self::dijkstra::{dijkstra, dijkstra_fibonacci_heap};
 pub use self::dinic_maxflow::DinicMaxFlow;
 pub use self::disjoint_set_union::DisjointSetUnion;
-pub use self::eulerian_path::EulerianPath;
+pub use self::dot::{to_dot, to_dot_with_labels};
+pub use self::edge_list::{parse_edge_list, ParseError, WeightedEdge};
+pub use self::eulerian_path::{eulerian_path, EulerianPath};
 pub use self::floyd_warshall::floyd_warshall;
 pub use self::ford_fulkerson::ford_fulkerson;
 pub use self::graph_enumeration::enumerate_graph;
 pub use self::heavy_light_decomposition::HeavyLightDecomposition;
+pub use self::hld::HldPathQuery;
 pub use self::kosaraju::kosaraju;
 pub use self::lee_breadth_first_search::lee;
 pub use self::lowest_common_ancestor::{LowestCommonAncestorOffline, LowestCommonAncestorOnline};
 pub use self::minimum_spanning_tree::kruskal;
+pub use self::pagerank::pagerank;
 pub use self::prim::{prim, prim_with_start};
 pub use self::prufer_code::{prufer_decode, prufer_encode};
 pub use self::strongly_connected_components::StronglyConnectedComponents;
 pub use self::tarjans_ssc::tarjan_scc;
 pub use self::topological_sort::topological_sort;
+pub use self::tsp_nearest_neighbor::tsp_nearest_neighbor;
+pub use self::two_opt::{tour_length, two_opt};
 pub use self::two_satisfiability::solve_two_satisfiability;
@@ -0,0 +1,126 @@
+use super::tour_length;
+
+/// Builds a tour of all the cities with the nearest neighbor heuristic for the
+/// travelling salesman problem, and returns it with its length.
+///
+/// Starting from `start`, the salesman always goes to the nearest city not
+/// visited yet (the one of lowest index on ties), and finally back to `start`.
+/// The tour lists each city once, beginning with `start`, and its length
+/// includes the way back. It takes O(n^2), and gives a reasonable starting
+/// tour for local searches such as `two_opt`, though it can be far from
+/// optimal.
+///
+/// See <https://en.wikipedia.org/wiki/Nearest_neighbour_algorithm>
+pub fn tsp_nearest_neighbor(dist: &[Vec<f64>], start: usize) -> (Vec<usize>, f64) {
+    let n = dist.len();
+    assert!(start < n, "the start is not a city");
+    let mut visited = vec![false; n];
+    let mut tour = Vec::with_capacity(n);
+    let mut current = start;
+    visited[start] = true;
+    tour.push(start);
+
+    while tour.len() < n {
+        let next = (0..n)
+            .filter(|&city| !visited[city])
+            .min_by(|&a, &b| dist[current][a].total_cmp(&dist[current][b]))
+            .unwrap();
+        visited[next] = true;
+        tour.push(next);
+        current = next;
+    }
+
+    let length = tour_length(&tour, dist);
+    (tour, length)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::general::Xorshift64;
+    use crate::graph::graph_utils::euclidean_distances;
+    use crate::graph::two_opt;
+
+    /// The length of the shortest tour, trying every permutation
+    fn optimum(dist: &[Vec<f64>]) -> f64 {
+        fn extend(dist: &[Vec<f64>], path: &mut Vec<usize>, length: f64, best: &mut f64) {
+            let n = dist.len();
+            let last = *path.last().unwrap();
+            if path.len() == n {
+                *best = best.min(length + dist[last][path[0]]);
+                return;
+            }
+            for city in 0..n {
+                if !path.contains(&city) {
+                    path.push(city);
+                    extend(dist, path, length + dist[last][city], best);
+                    path.pop();
+                }
+            }
+        }
+        let mut best = f64::INFINITY;
+        extend(dist, &mut vec![0], 0.0, &mut best);
+        best
+    }
+
+    fn assert_valid_tour(tour: &[usize], n: usize, start: usize) {
+        assert_eq!(tour[0], start);
+        let mut sorted = tour.to_vec();
+        sorted.sort_unstable();
+        assert_eq!(sorted, (0..n).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn tiny_instance() {
+        let dist = vec![
+            vec![0.0, 2.0, 9.0, 10.0],
+            vec![2.0, 0.0, 6.0, 4.0],
+            vec![9.0, 6.0, 0.0, 3.0],
+            vec![10.0, 4.0, 3.0, 0.0],
+        ];
+        let (tour, length) = tsp_nearest_neighbor(&dist, 0);
+        assert_eq!(tour, vec![0, 1, 3, 2]);
+        // 2 + 4 + 3, and 9 to come back
+        assert_eq!(length, 18.0);
+        // which is the optimum here
+        assert_eq!(optimum(&dist), 18.0);
+    }
+
+    #[test]
+    fn greedy_is_not_optimal() {
+        // on a line, the greedy walk zigzags across the start
+        let points = [(0.0, 0.0), (1.0, 0.0), (-1.25, 0.0), (3.5, 0.0)];
+        let dist = euclidean_distances(&points);
+        let (tour, length) = tsp_nearest_neighbor(&dist, 0);
+        assert_eq!(tour, vec![0, 1, 2, 3]);
+        assert_eq!(length, 1.0 + 2.25 + 4.75 + 3.5);
+        // while the best tour goes to one end and back
+        assert_eq!(optimum(&dist), 2.0 * 4.75);
+    }
+
+    #[test]
+    fn random_instances() {
+        let mut rng = Xorshift64::new(248);
+        for n in [1, 2, 5, 8] {
+            let points: Vec<(f64, f64)> = (0..n)
+                .map(|_| (rng.next_f64() * 10.0, rng.next_f64() * 10.0))
+                .collect();
+            let dist = euclidean_distances(&points);
+            let best = optimum(&dist);
+            for start in 0..n {
+                let (mut tour, length) = tsp_nearest_neighbor(&dist, start);
+                assert_valid_tour(&tour, n, start);
+                assert!(length >= best - 1e-9);
+                assert!((tour_length(&tour, &dist) - length).abs() < 1e-9);
+                // 2-opt can only improve on it
+                assert!(two_opt(&mut tour, &dist) <= length + 1e-9);
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn start_out_of_range() {
+        tsp_nearest_neighbor(&[vec![0.0]], 1);
+    }
+}
@@ -0,0 +1,141 @@
+/// Returns the length of the closed tour visiting the cities of `tour` in
+/// order and coming back to the first one, with `dist[a][b]` the distance
+/// from city `a` to city `b`.
+pub fn tour_length(tour: &[usize], dist: &[Vec<f64>]) -> f64 {
+    tour.iter()
+        .zip(tour.iter().cycle().skip(1))
+        .map(|(&a, &b)| dist[a][b])
+        .sum()
+}
+
+/// Improves the closed `tour` in place with the 2-opt local search, and
+/// returns its new length. The distances must be symmetric.
+///
+/// A 2-opt move removes two edges `(a, b)` and `(c, d)` of the tour and
+/// reconnects it as `(a, c)` and `(b, d)`, which reverses the path from `b` to
+/// `c`. While some move shortens the tour, it is applied. At the end, the tour
+/// has no improving move left, in particular no two crossing edges in the
+/// Euclidean case. Each pass over the moves is O(n^2).
+///
+/// See <https://en.wikipedia.org/wiki/2-opt>
+pub fn two_opt(tour: &mut [usize], dist: &[Vec<f64>]) -> f64 {
+    let n = tour.len();
+    // ignores the improvements which could only be rounding errors
+    const EPSILON: f64 = 1e-12;
+    let mut improved = true;
+    while improved {
+        improved = false;
+        for i in 0..n.saturating_sub(2) {
+            // the edges (i, i + 1) and (j, j + 1) must not share a city
+            let last = if i == 0 { n - 1 } else { n };
+            for j in i + 2..last {
+                let (a, b) = (tour[i], tour[i + 1]);
+                let (c, d) = (tour[j], tour[(j + 1) % n]);
+                let delta = dist[a][c] + dist[b][d] - dist[a][b] - dist[c][d];
+                if delta < -EPSILON {
+                    tour[i + 1..=j].reverse();
+                    improved = true;
+                }
+            }
+        }
+    }
+    tour_length(tour, dist)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::general::Xorshift64;
+    use crate::graph::graph_utils::euclidean_distances;
+
+    fn is_permutation(tour: &[usize], n: usize) -> bool {
+        let mut sorted = tour.to_vec();
+        sorted.sort_unstable();
+        sorted == (0..n).collect::<Vec<_>>()
+    }
+
+    fn segments_cross(p: (f64, f64), q: (f64, f64), r: (f64, f64), s: (f64, f64)) -> bool {
+        let orientation = |a: (f64, f64), b: (f64, f64), c: (f64, f64)| {
+            (b.0 - a.0) * (c.1 - a.1) - (b.1 - a.1) * (c.0 - a.0)
+        };
+        orientation(p, q, r) * orientation(p, q, s) < 0.0
+            && orientation(r, s, p) * orientation(r, s, q) < 0.0
+    }
+
+    #[test]
+    fn removes_a_crossing() {
+        // the corners of a square, visited along its two diagonals
+        let points = [(0.0, 0.0), (1.0, 0.0), (1.0, 1.0), (0.0, 1.0)];
+        let dist = euclidean_distances(&points);
+        let mut tour = vec![0, 2, 1, 3];
+        assert!((tour_length(&tour, &dist) - (2.0 + 2.0 * 2f64.sqrt())).abs() < 1e-12);
+        let length = two_opt(&mut tour, &dist);
+        assert!((length - 4.0).abs() < 1e-12);
+        assert_eq!(tour, vec![0, 1, 2, 3]);
+    }
+
+    #[test]
+    fn never_increases_the_length() {
+        let mut rng = Xorshift64::new(247);
+        for n in [3, 5, 10, 40, 100] {
+            let points: Vec<(f64, f64)> = (0..n)
+                .map(|_| (rng.next_f64() * 100.0, rng.next_f64() * 100.0))
+                .collect();
+            let dist = euclidean_distances(&points);
+            let mut tour: Vec<usize> = (0..n).collect();
+            for i in (1..n).rev() {
+                tour.swap(i, rng.gen_range(0..i as u64 + 1) as usize);
+            }
+            let before = tour_length(&tour, &dist);
+            let after = two_opt(&mut tour, &dist);
+            assert!(after <= before + 1e-9);
+            assert!((after - tour_length(&tour, &dist)).abs() < 1e-9);
+            assert!(is_permutation(&tour, n));
+
+            // a 2-optimal Euclidean tour has no crossing edges
+            for i in 0..n {
+                for j in i + 2..n {
+                    let (a, b) = (points[tour[i]], points[tour[(i + 1) % n]]);
+                    let (c, d) = (points[tour[j]], points[tour[(j + 1) % n]]);
+                    assert!(!segments_cross(a, b, c, d));
+                }
+            }
+            // and running it again changes nothing
+            let mut again = tour.clone();
+            two_opt(&mut again, &dist);
+            assert_eq!(again, tour);
+        }
+    }
+
+    #[test]
+    fn points_on_a_circle() {
+        // the optimal tour goes around the circle
+        let n = 12;
+        let points: Vec<(f64, f64)> = (0..n)
+            .map(|i| {
+                let angle = (i * 5 % n) as f64 * std::f64::consts::TAU / n as f64;
+                (angle.cos(), angle.sin())
+            })
+            .collect();
+        let dist = euclidean_distances(&points);
+        let mut tour: Vec<usize> = (0..n).collect();
+        let length = two_opt(&mut tour, &dist);
+        let side = 2.0 * (std::f64::consts::PI / n as f64).sin();
+        assert!((length - n as f64 * side).abs() < 1e-9);
+    }
+
+    #[test]
+    fn tiny_tours() {
+        let dist = vec![
+            vec![0.0, 2.0, 3.0],
+            vec![2.0, 0.0, 4.0],
+            vec![3.0, 4.0, 0.0],
+        ];
+        let mut tour = vec![2, 0, 1];
+        assert_eq!(two_opt(&mut tour, &dist), 9.0);
+        assert_eq!(tour, vec![2, 0, 1]);
+        assert_eq!(two_opt(&mut [1, 0], &dist), 4.0);
+        assert_eq!(two_opt(&mut [1], &dist), 0.0);
+        assert_eq!(two_opt(&mut [], &dist), 0.0);
+    }
+}
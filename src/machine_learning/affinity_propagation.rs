@@ -0,0 +1,237 @@
+//! # Affinity propagation
+//!
+//! Affinity propagation clusters points by electing some of them as
+//! exemplars, the other points joining their most similar exemplar. It takes
+//! the similarities `s(i, k)` of the points (often minus their squared
+//! distances), and the number of clusters comes out of the preferences
+//! `s(k, k)`: the higher the preference of a point, the more likely it is to
+//! become an exemplar. A common choice is the median of the similarities.
+//!
+//! Two kinds of messages are exchanged between the points until the
+//! exemplars settle:
+//!   - the responsibility `r(i, k)`, how well suited `k` is to be the
+//!     exemplar of `i`, compared with the other candidates:
+//!     `r(i, k) = s(i, k) - max_{k' != k} (a(i, k') + s(i, k'))`
+//!   - the availability `a(i, k)`, how appropriate it would be for `i` to
+//!     choose `k`, given the support of the other points for `k`:
+//!     `a(i, k) = min(0, r(k, k) + Σ_{i' ∉ {i, k}} max(0, r(i', k)))` and
+//!     `a(k, k) = Σ_{i' != k} max(0, r(i', k))`
+//!
+//! Each update is damped with the previous value to avoid oscillations, and
+//! the points `k` with `a(k, k) + r(k, k) > 0` are the exemplars. Each
+//! iteration is O(n^2).
+//!
+//! ## Resources:
+//!   - [https://en.wikipedia.org/wiki/Affinity_propagation]
+
+/// The number of iterations without any change of the exemplars after which
+/// they are deemed settled
+const CONVERGENCE_ITERATIONS: usize = 15;
+
+fn exemplars(responsibility: &[Vec<f64>], availability: &[Vec<f64>]) -> Vec<usize> {
+    (0..responsibility.len())
+        .filter(|&k| responsibility[k][k] + availability[k][k] > 0.0)
+        .collect()
+}
+
+/// Clusters the points with affinity propagation, given their `similarity`
+/// matrix, with the preferences on its diagonal. The `damping` factor, in
+/// [0.5, 1), weighs the previous messages in each update; it runs for at most
+/// `max_iter` iterations.
+///
+/// Returns the index of the exemplar of each point, an exemplar being its own
+/// exemplar. Should no point qualify as an exemplar, the likeliest candidate
+/// is the exemplar of all the points.
+pub fn affinity_propagation(similarity: &[Vec<f64>], damping: f64, max_iter: usize) -> Vec<usize> {
+    let n = similarity.len();
+    assert!(
+        similarity.iter().all(|row| row.len() == n),
+        "the similarity matrix is not square"
+    );
+    assert!(
+        (0.5..1.0).contains(&damping),
+        "the damping must be in [0.5, 1)"
+    );
+    if n == 0 {
+        return vec![];
+    }
+    let mut responsibility = vec![vec![0.0; n]; n];
+    let mut availability = vec![vec![0.0; n]; n];
+    let mut current = vec![];
+    let mut unchanged = 0;
+
+    for _ in 0..max_iter {
+        for i in 0..n {
+            // the two largest a(i, k) + s(i, k), to exclude k from the maximum
+            let (mut first, mut second) = ((f64::NEG_INFINITY, 0), f64::NEG_INFINITY);
+            for k in 0..n {
+                let value = availability[i][k] + similarity[i][k];
+                if value > first.0 {
+                    second = first.0;
+                    first = (value, k);
+                } else if value > second {
+                    second = value;
+                }
+            }
+            for k in 0..n {
+                let best_other = if k == first.1 { second } else { first.0 };
+                let update = similarity[i][k] - best_other;
+                responsibility[i][k] = damping * responsibility[i][k] + (1.0 - damping) * update;
+            }
+        }
+
+        for k in 0..n {
+            let support: f64 = (0..n)
+                .filter(|&i| i != k)
+                .map(|i| responsibility[i][k].max(0.0))
+                .sum();
+            for i in 0..n {
+                let update = if i == k {
+                    support
+                } else {
+                    (responsibility[k][k] + support - responsibility[i][k].max(0.0)).min(0.0)
+                };
+                availability[i][k] = damping * availability[i][k] + (1.0 - damping) * update;
+            }
+        }
+
+        let found = exemplars(&responsibility, &availability);
+        if !found.is_empty() && found == current {
+            unchanged += 1;
+            if unchanged >= CONVERGENCE_ITERATIONS {
+                break;
+            }
+        } else {
+            unchanged = 0;
+            current = found;
+        }
+    }
+
+    let mut found = exemplars(&responsibility, &availability);
+    if found.is_empty() {
+        let likeliest = (0..n)
+            .max_by(|&a, &b| {
+                (responsibility[a][a] + availability[a][a])
+                    .total_cmp(&(responsibility[b][b] + availability[b][b]))
+            })
+            .unwrap();
+        found.push(likeliest);
+    }
+    (0..n)
+        .map(|i| {
+            if found.contains(&i) {
+                i
+            } else {
+                *found
+                    .iter()
+                    .max_by(|&&a, &&b| similarity[i][a].total_cmp(&similarity[i][b]))
+                    .unwrap()
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::general::make_blobs;
+
+    /// Minus the squared distances, and the given preference on the diagonal
+    fn similarities(xs: &[Vec<f64>], preference: f64) -> Vec<Vec<f64>> {
+        (0..xs.len())
+            .map(|i| {
+                (0..xs.len())
+                    .map(|k| {
+                        if i == k {
+                            preference
+                        } else {
+                            -xs[i]
+                                .iter()
+                                .zip(&xs[k])
+                                .map(|(a, b)| (a - b).powi(2))
+                                .sum::<f64>()
+                        }
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    fn median_similarity(similarity: &[Vec<f64>]) -> f64 {
+        let mut values: Vec<f64> = (0..similarity.len())
+            .flat_map(|i| {
+                (0..similarity.len())
+                    .filter(move |&k| k != i)
+                    .map(move |k| (i, k))
+            })
+            .map(|(i, k)| similarity[i][k])
+            .collect();
+        values.sort_by(f64::total_cmp);
+        values[values.len() / 2]
+    }
+
+    fn distinct(exemplars: &[usize]) -> Vec<usize> {
+        let mut distinct = exemplars.to_vec();
+        distinct.sort_unstable();
+        distinct.dedup();
+        distinct
+    }
+
+    #[test]
+    fn easy_bivariate_clustering() {
+        let xs: Vec<Vec<f64>> = vec![
+            vec![-1.1, 0.2],
+            vec![-1.2, 0.3],
+            vec![-1.3, 0.1],
+            vec![-1.4, 0.4],
+            vec![1.1, -1.1],
+            vec![1.2, -1.0],
+            vec![1.3, -1.2],
+            vec![1.4, -1.3],
+        ];
+        let preference = median_similarity(&similarities(&xs, 0.0));
+        let exemplars = affinity_propagation(&similarities(&xs, preference), 0.5, 200);
+        let found = distinct(&exemplars);
+        assert_eq!(found.len(), 2);
+        // one exemplar in each cluster, which all its points choose
+        assert!(found[0] < 4 && found[1] >= 4);
+        assert_eq!(exemplars, [vec![found[0]; 4], vec![found[1]; 4]].concat());
+        for &k in &found {
+            assert_eq!(exemplars[k], k);
+        }
+    }
+
+    #[test]
+    fn preference_sets_the_number_of_clusters() {
+        let centers = vec![vec![0.0, 0.0], vec![6.0, 0.0], vec![0.0, 6.0]];
+        let (xs, truth) = make_blobs(60, &centers, 0.5, 258);
+        // a low preference elects one exemplar per blob
+        let exemplars = affinity_propagation(&similarities(&xs, -50.0), 0.7, 500);
+        let found = distinct(&exemplars);
+        assert_eq!(found.len(), 3);
+        for i in 0..xs.len() {
+            assert_eq!(truth[exemplars[i]], truth[i]);
+        }
+        // a high one makes more points exemplars
+        let exemplars = affinity_propagation(&similarities(&xs, -0.1), 0.7, 500);
+        assert!(distinct(&exemplars).len() > 3);
+        // and the highest one makes every point its own exemplar
+        let separated = vec![vec![0.0], vec![10.0], vec![20.0]];
+        assert_eq!(
+            affinity_propagation(&similarities(&separated, 0.0), 0.5, 100),
+            vec![0, 1, 2]
+        );
+    }
+
+    #[test]
+    fn tiny_inputs() {
+        assert!(affinity_propagation(&[], 0.5, 10).is_empty());
+        assert_eq!(affinity_propagation(&[vec![0.0]], 0.5, 10), vec![0]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn damping_out_of_range() {
+        affinity_propagation(&[vec![0.0]], 1.0, 10);
+    }
+}
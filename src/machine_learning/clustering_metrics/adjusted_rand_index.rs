@@ -0,0 +1,96 @@
+use super::contingency_table;
+
+/// Number of unordered pairs which can be formed out of `n` elements
+fn pairs(n: usize) -> f64 {
+    (n * n.saturating_sub(1)) as f64 / 2.0
+}
+
+/// Computes the adjusted Rand index between two clusterings of the same points.
+///
+/// The Rand index is the fraction of pairs of points on which both
+/// clusterings agree (grouped together in both, or separated in both). The
+/// adjusted index corrects it for chance: it is 1.0 for identical partitions,
+/// whatever the actual label values, and close to 0.0 (possibly negative) for
+/// independent ones.
+///
+/// If both partitions are trivial in the same way (a single cluster, or only
+/// singletons), the index is defined as 1.0.
+///
+/// See <https://en.wikipedia.org/wiki/Rand_index#Adjusted_Rand_index>
+///
+/// # Panics
+///
+/// Panics if the two labelings do not have the same length.
+pub fn adjusted_rand_index(true_labels: &[usize], pred_labels: &[usize]) -> f64 {
+    let table = contingency_table(true_labels, pred_labels);
+
+    let index: f64 = table.iter().flatten().map(|&n_ij| pairs(n_ij)).sum();
+    let sum_rows: f64 = table.iter().map(|row| pairs(row.iter().sum())).sum();
+    let sum_columns: f64 = (0..table.first().map_or(0, |row| row.len()))
+        .map(|j| pairs(table.iter().map(|row| row[j]).sum()))
+        .sum();
+
+    let expected_index = sum_rows * sum_columns / pairs(true_labels.len()).max(1.0);
+    let max_index = (sum_rows + sum_columns) / 2.0;
+    if max_index == expected_index {
+        return 1.0;
+    }
+    (index - expected_index) / (max_index - expected_index)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::general::Xorshift64;
+
+    #[test]
+    fn identical_clusterings() {
+        let labels = [0, 0, 1, 1, 2, 2, 2];
+        assert_eq!(adjusted_rand_index(&labels, &labels), 1.0);
+    }
+
+    #[test]
+    fn permuted_labels_score_one() {
+        let true_labels = [0, 0, 0, 1, 1, 2, 2, 2, 2];
+        let pred_labels = [2, 2, 2, 0, 0, 1, 1, 1, 1];
+        assert!((adjusted_rand_index(&true_labels, &pred_labels) - 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn known_value() {
+        // value given by scikit-learn's `adjusted_rand_score`
+        let ari = adjusted_rand_index(&[0, 0, 1, 1], &[0, 0, 1, 2]);
+        assert!((ari - 0.571_428_571_428_571_4).abs() < 1e-12);
+    }
+
+    #[test]
+    fn completely_split_clusters() {
+        // every pair grouped together by one labeling is separated by the other
+        let ari = adjusted_rand_index(&[0, 0, 0, 0], &[0, 1, 2, 3]);
+        assert_eq!(ari, 0.0);
+    }
+
+    #[test]
+    fn symmetric() {
+        let a = [0, 0, 1, 1, 1, 2, 0, 2];
+        let b = [1, 0, 1, 1, 2, 2, 0, 0];
+        assert_eq!(adjusted_rand_index(&a, &b), adjusted_rand_index(&b, &a));
+    }
+
+    #[test]
+    fn random_labelings_score_near_zero() {
+        let mut rng = Xorshift64::new(42);
+        let n = 10_000;
+        let a: Vec<usize> = (0..n).map(|_| rng.gen_range(0..5) as usize).collect();
+        let b: Vec<usize> = (0..n).map(|_| rng.gen_range(0..5) as usize).collect();
+        assert!(adjusted_rand_index(&a, &b).abs() < 0.01);
+    }
+
+    #[test]
+    fn trivial_clusterings() {
+        assert_eq!(adjusted_rand_index(&[], &[]), 1.0);
+        assert_eq!(adjusted_rand_index(&[0], &[3]), 1.0);
+        assert_eq!(adjusted_rand_index(&[1, 1, 1], &[0, 0, 0]), 1.0);
+        assert_eq!(adjusted_rand_index(&[0, 1, 2], &[2, 0, 1]), 1.0);
+    }
+}
@@ -0,0 +1,81 @@
+use crate::math::Metric;
+
+/// Computes the variance of each cluster: the mean squared Euclidean
+/// distance of its points to its centroid, `centroids[c]` for the points of
+/// label `c` in `clustering`.
+///
+/// It tells the tight clusters from the loose ones, which may gather several
+/// groups, or be noise. An empty cluster has a variance of 0.0.
+///
+/// # Panics
+///
+/// Panics if `xs` and `clustering` do not have the same length, or if a label
+/// is not the index of a centroid.
+pub fn cluster_variances<T: Copy + Into<f64>>(
+    xs: &[Vec<T>],
+    clustering: &[usize],
+    centroids: &[Vec<T>],
+) -> Vec<f64> {
+    assert_eq!(xs.len(), clustering.len(), "every point needs a label");
+    let mut sums = vec![0.0; centroids.len()];
+    let mut sizes = vec![0usize; centroids.len()];
+    for (x, &label) in xs.iter().zip(clustering) {
+        assert!(label < centroids.len(), "the label {label} has no centroid");
+        sums[label] += Metric::SquaredEuclidean.distance(x, &centroids[label]);
+        sizes[label] += 1;
+    }
+    sums.iter()
+        .zip(&sizes)
+        .map(|(&sum, &size)| if size == 0 { 0.0 } else { sum / size as f64 })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::general::{kmeans_f64_fit, make_blobs};
+
+    #[test]
+    fn known_variances() {
+        let xs = vec![
+            vec![0.0, 0.0],
+            vec![2.0, 0.0],
+            vec![10.0, 10.0],
+            vec![10.0, 14.0],
+            vec![10.0, 12.0],
+        ];
+        let clustering = [0, 0, 1, 1, 1];
+        let centroids = vec![vec![1.0, 0.0], vec![10.0, 12.0], vec![-5.0, -5.0]];
+        // (1 + 1) / 2, (4 + 4 + 0) / 3, and an empty cluster
+        assert_eq!(
+            cluster_variances(&xs, &clustering, &centroids),
+            vec![1.0, 8.0 / 3.0, 0.0]
+        );
+    }
+
+    #[test]
+    fn tight_and_loose_clusters() {
+        let centers = vec![vec![0.0, 0.0], vec![20.0, 0.0], vec![0.0, 20.0]];
+        let (xs, _) = make_blobs(300, &centers, 0.5, 280);
+        let result = kmeans_f64_fit(&xs, 3, None).unwrap();
+        let variances = cluster_variances(&xs, &result.labels, &result.centroids);
+        // 0.5² in each of the two dimensions
+        for variance in &variances {
+            assert!((variance - 0.5).abs() < 0.15, "{variances:?}");
+        }
+        assert_eq!(result.cluster_variances(&xs), variances);
+
+        // overlapping blobs, split in three anyway
+        let (xs, _) = make_blobs(300, &centers, 8.0, 2800);
+        let result = kmeans_f64_fit(&xs, 3, None).unwrap();
+        for variance in cluster_variances(&xs, &result.labels, &result.centroids) {
+            assert!(variance > 10.0, "{variance}");
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn label_without_centroid() {
+        cluster_variances(&[vec![1.0]], &[1], &[vec![0.0]]);
+    }
+}
@@ -0,0 +1,45 @@
+/// Builds the contingency table of two labelings of the same points: the
+/// entry `(i, j)` counts the points labeled `i` in `true_labels` and `j` in
+/// `pred_labels`. Labels are expected to be small integers, as the table
+/// has one row (respectively column) per label up to the largest one.
+///
+/// # Panics
+///
+/// Panics if the two labelings do not have the same length.
+pub fn contingency_table(true_labels: &[usize], pred_labels: &[usize]) -> Vec<Vec<usize>> {
+    assert_eq!(
+        true_labels.len(),
+        pred_labels.len(),
+        "labelings must have the same length"
+    );
+
+    let rows = true_labels.iter().max().map_or(0, |&max| max + 1);
+    let columns = pred_labels.iter().max().map_or(0, |&max| max + 1);
+    let mut table = vec![vec![0; columns]; rows];
+    for (&t, &p) in true_labels.iter().zip(pred_labels.iter()) {
+        table[t][p] += 1;
+    }
+    table
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn counts_pairs_of_labels() {
+        let table = contingency_table(&[0, 0, 1, 1, 2], &[1, 1, 0, 1, 0]);
+        assert_eq!(table, vec![vec![0, 2], vec![1, 1], vec![1, 0]]);
+    }
+
+    #[test]
+    fn empty_labelings() {
+        assert!(contingency_table(&[], &[]).is_empty());
+    }
+
+    #[test]
+    #[should_panic]
+    fn length_mismatch() {
+        contingency_table(&[0, 1], &[0]);
+    }
+}
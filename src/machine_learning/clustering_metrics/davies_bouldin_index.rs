@@ -0,0 +1,125 @@
+use crate::math::Metric;
+
+/// Computes the Davies-Bouldin index of a clustering of `xs`, the label of
+/// each point in `clustering` being the index of its centroid in `centroids`:
+///
+/// `DB = 1/k Σ_i max_{j != i} (s_i + s_j) / d(c_i, c_j)`
+///
+/// with `s_i` the scatter of the cluster `i`, the mean Euclidean distance of
+/// its points to its centroid `c_i`, and `d` the Euclidean distance. Each
+/// cluster is compared with the one it is the most alike, and the lower the
+/// index, the tighter and further apart the clusters. The empty clusters do
+/// not count, and with less than two clusters, the index is 0.0.
+///
+/// See <https://en.wikipedia.org/wiki/Davies%E2%80%93Bouldin_index>
+///
+/// # Panics
+///
+/// Panics if `xs` and `clustering` do not have the same length, or if a label
+/// is not the index of a centroid.
+pub fn davies_bouldin_index<T: Copy + Into<f64>>(
+    xs: &[Vec<T>],
+    clustering: &[usize],
+    centroids: &[Vec<T>],
+) -> f64 {
+    assert_eq!(xs.len(), clustering.len(), "every point needs a label");
+    let mut sums = vec![0.0; centroids.len()];
+    let mut sizes = vec![0usize; centroids.len()];
+    for (x, &label) in xs.iter().zip(clustering) {
+        assert!(label < centroids.len(), "the label {label} has no centroid");
+        sums[label] += Metric::Euclidean.distance(x, &centroids[label]);
+        sizes[label] += 1;
+    }
+    let clusters: Vec<usize> = (0..centroids.len()).filter(|&c| sizes[c] > 0).collect();
+    if clusters.len() < 2 {
+        return 0.0;
+    }
+    let scatter = |c: usize| sums[c] / sizes[c] as f64;
+
+    let total: f64 = clusters
+        .iter()
+        .map(|&i| {
+            clusters
+                .iter()
+                .filter(|&&j| j != i)
+                .map(|&j| {
+                    (scatter(i) + scatter(j))
+                        / Metric::Euclidean.distance(&centroids[i], &centroids[j])
+                })
+                .fold(0.0, f64::max)
+        })
+        .sum();
+    total / clusters.len() as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::general::make_blobs;
+
+    /// The means of the clusters
+    fn centroids(xs: &[Vec<f64>], clustering: &[usize], k: usize) -> Vec<Vec<f64>> {
+        (0..k)
+            .map(|c| {
+                let members: Vec<&Vec<f64>> = xs
+                    .iter()
+                    .zip(clustering)
+                    .filter(|&(_, &label)| label == c)
+                    .map(|(x, _)| x)
+                    .collect();
+                (0..xs[0].len())
+                    .map(|d| members.iter().map(|x| x[d]).sum::<f64>() / members.len() as f64)
+                    .collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn known_index() {
+        // two pairs of points 2 apart, with centroids 10 apart
+        let xs = vec![
+            vec![-1.0, 0.0],
+            vec![1.0, 0.0],
+            vec![9.0, 0.0],
+            vec![11.0, 0.0],
+        ];
+        let clustering = [0, 0, 1, 1];
+        let centroids = vec![vec![0.0, 0.0], vec![10.0, 0.0]];
+        // (1 + 1) / 10 for both
+        assert!((davies_bouldin_index(&xs, &clustering, &centroids) - 0.2).abs() < 1e-12);
+    }
+
+    #[test]
+    fn separated_clusters_score_low() {
+        let centers = vec![vec![0.0, 0.0], vec![15.0, 0.0], vec![0.0, 15.0]];
+        let (xs, truth) = make_blobs(300, &centers, 0.5, 281);
+        let index = davies_bouldin_index(&xs, &truth, &centroids(&xs, &truth, 3));
+        assert!(index < 0.15, "{index}");
+
+        // merging two distinct clusters makes a loose one
+        let merged: Vec<usize> = truth.iter().map(|&label| label.min(1)).collect();
+        let merged_index = davies_bouldin_index(&xs, &merged, &centroids(&xs, &merged, 2));
+        assert!(merged_index > 2.0 * index, "{merged_index} vs {index}");
+    }
+
+    #[test]
+    fn single_cluster() {
+        let xs = vec![vec![1.0], vec![2.0], vec![4.0]];
+        assert_eq!(
+            davies_bouldin_index(&xs, &[0, 0, 0], &[vec![7.0 / 3.0]]),
+            0.0
+        );
+        // the empty clusters do not count
+        assert_eq!(
+            davies_bouldin_index(&xs, &[1, 1, 1], &[vec![0.0], vec![7.0 / 3.0]]),
+            0.0
+        );
+        assert_eq!(davies_bouldin_index::<f64>(&[], &[], &[]), 0.0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn missing_labels() {
+        davies_bouldin_index(&[vec![1.0], vec![2.0]], &[0], &[vec![1.5]]);
+    }
+}
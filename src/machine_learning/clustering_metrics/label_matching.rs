@@ -0,0 +1,231 @@
+use super::contingency_table;
+use std::collections::HashMap;
+
+/// Solves the assignment problem on the square `cost` matrix with the
+/// Hungarian algorithm in O(n³), returning for each row the column assigned
+/// to it, such that the total cost is minimal.
+///
+/// See <https://en.wikipedia.org/wiki/Hungarian_algorithm>
+fn hungarian(cost: &[Vec<i64>]) -> Vec<usize> {
+    let n = cost.len();
+    // Potentials of the rows (`u`) and columns (`v`), and the row matched to
+    // each column (`matched`). Index 0 is a sentinel, so indices are 1-based.
+    let mut u = vec![0i64; n + 1];
+    let mut v = vec![0i64; n + 1];
+    let mut matched = vec![0usize; n + 1];
+    let mut way = vec![0usize; n + 1];
+
+    for row in 1..=n {
+        // find an augmenting path from `row`, Dijkstra-like on reduced costs
+        matched[0] = row;
+        let mut column = 0;
+        let mut min_to = vec![i64::MAX; n + 1];
+        let mut used = vec![false; n + 1];
+        loop {
+            used[column] = true;
+            let current_row = matched[column];
+            let mut delta = i64::MAX;
+            let mut next_column = 0;
+            for j in 1..=n {
+                if !used[j] {
+                    let reduced = cost[current_row - 1][j - 1] - u[current_row] - v[j];
+                    if reduced < min_to[j] {
+                        min_to[j] = reduced;
+                        way[j] = column;
+                    }
+                    if min_to[j] < delta {
+                        delta = min_to[j];
+                        next_column = j;
+                    }
+                }
+            }
+            for j in 0..=n {
+                if used[j] {
+                    u[matched[j]] += delta;
+                    v[j] -= delta;
+                } else {
+                    min_to[j] -= delta;
+                }
+            }
+            column = next_column;
+            if matched[column] == 0 {
+                break;
+            }
+        }
+        // flip the augmenting path
+        while column != 0 {
+            let previous = way[column];
+            matched[column] = matched[previous];
+            column = previous;
+        }
+    }
+
+    let mut assignment = vec![0; n];
+    for j in 1..=n {
+        if matched[j] != 0 {
+            assignment[matched[j] - 1] = j - 1;
+        }
+    }
+    assignment
+}
+
+/// Finds the one-to-one relabeling of the predicted clusters which best matches
+/// the true clusters, i.e. which maximizes the number of points whose label is
+/// the same in both labelings once `pred_labels` is relabeled.
+///
+/// The result maps each predicted label to a true label: `mapping[pred]`.
+/// When the prediction has more clusters than the ground truth, the predicted
+/// clusters which cannot be matched are mapped to unused labels beyond the
+/// true ones, so that the mapping stays a permutation.
+///
+/// # Panics
+///
+/// Panics if the two labelings do not have the same length.
+pub fn best_label_permutation(true_labels: &[usize], pred_labels: &[usize]) -> Vec<usize> {
+    let table = contingency_table(true_labels, pred_labels);
+    let n_pred = pred_labels.iter().max().map_or(0, |&max| max + 1);
+    let size = table.len().max(n_pred);
+
+    // maximizing the overlap is minimizing its opposite
+    let cost: Vec<Vec<i64>> = (0..size)
+        .map(|pred| {
+            (0..size)
+                .map(|truth| {
+                    let overlap = table.get(truth).and_then(|row| row.get(pred));
+                    -(overlap.copied().unwrap_or(0) as i64)
+                })
+                .collect()
+        })
+        .collect();
+
+    let mut mapping = hungarian(&cost);
+    mapping.truncate(n_pred);
+    mapping
+}
+
+/// Relabels the clusters of `labels` in their order of first appearance: the
+/// cluster of the first point becomes 0, the next new cluster 1, and so on.
+///
+/// The points sharing a label still share one, and only they do, so two
+/// labelings of the same partition, such as those of two k-means
+/// implementations seeded differently, canonicalize to the same labels.
+pub fn canonicalize_labels(labels: &[usize]) -> Vec<usize> {
+    let mut renamed: HashMap<usize, usize> = HashMap::new();
+    labels
+        .iter()
+        .map(|&label| {
+            let next = renamed.len();
+            *renamed.entry(label).or_insert(next)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn relabel(labels: &[usize], mapping: &[usize]) -> Vec<usize> {
+        labels.iter().map(|&label| mapping[label]).collect()
+    }
+
+    #[test]
+    fn hungarian_known_assignment() {
+        let cost = vec![vec![4, 1, 3], vec![2, 0, 5], vec![3, 2, 2]];
+        // the optimum is 1 + 2 + 2 = 5
+        assert_eq!(hungarian(&cost), vec![1, 0, 2]);
+    }
+
+    #[test]
+    fn hungarian_matches_brute_force() {
+        let cost = vec![
+            vec![7, 53, 183, 439],
+            vec![497, 383, 563, 79],
+            vec![627, 343, 773, 959],
+            vec![447, 283, 463, 29],
+        ];
+        let assignment = hungarian(&cost);
+        let total: i64 = assignment
+            .iter()
+            .enumerate()
+            .map(|(i, &j)| cost[i][j])
+            .sum();
+        let best = crate::general::permute(&[0, 1, 2, 3])
+            .iter()
+            .map(|p| p.iter().enumerate().map(|(i, &j)| cost[i][j]).sum::<i64>())
+            .min()
+            .unwrap();
+        assert_eq!(total, best);
+    }
+
+    #[test]
+    fn recovers_permutation() {
+        let true_labels = [0, 0, 1, 1, 1, 2, 2];
+        let pred_labels = [2, 2, 0, 0, 0, 1, 1];
+        let mapping = best_label_permutation(&true_labels, &pred_labels);
+        assert_eq!(mapping, vec![1, 2, 0]);
+        assert_eq!(relabel(&pred_labels, &mapping), true_labels);
+    }
+
+    #[test]
+    fn maximizes_agreement_despite_errors() {
+        let true_labels = [0, 0, 0, 0, 1, 1, 1, 1];
+        let pred_labels = [1, 1, 1, 0, 0, 0, 0, 1];
+        let mapping = best_label_permutation(&true_labels, &pred_labels);
+        assert_eq!(mapping, vec![1, 0]);
+        let agreement = relabel(&pred_labels, &mapping)
+            .iter()
+            .zip(true_labels.iter())
+            .filter(|(a, b)| a == b)
+            .count();
+        assert_eq!(agreement, 6);
+    }
+
+    #[test]
+    fn more_predicted_clusters_than_true_ones() {
+        let true_labels = [0, 0, 0, 1, 1, 1];
+        let pred_labels = [2, 2, 1, 0, 0, 0];
+        let mapping = best_label_permutation(&true_labels, &pred_labels);
+        assert_eq!(mapping[2], 0);
+        assert_eq!(mapping[0], 1);
+        assert_eq!(mapping[1], 2);
+    }
+
+    #[test]
+    fn fewer_predicted_clusters_than_true_ones() {
+        let true_labels = [0, 0, 1, 1, 2, 2];
+        let pred_labels = [0, 0, 0, 0, 1, 1];
+        let mapping = best_label_permutation(&true_labels, &pred_labels);
+        assert_eq!(mapping.len(), 2);
+        assert_eq!(mapping[1], 2);
+        assert!(mapping[0] == 0 || mapping[0] == 1);
+    }
+
+    #[test]
+    fn empty_labelings() {
+        assert!(best_label_permutation(&[], &[]).is_empty());
+    }
+
+    #[test]
+    fn canonical_labels() {
+        assert_eq!(canonicalize_labels(&[2, 2, 0, 1]), vec![0, 0, 1, 2]);
+        assert_eq!(canonicalize_labels(&[0, 1, 2]), vec![0, 1, 2]);
+        assert_eq!(canonicalize_labels(&[7, 3, 7, 9, 3]), vec![0, 1, 0, 2, 1]);
+        assert!(canonicalize_labels(&[]).is_empty());
+    }
+
+    #[test]
+    fn canonical_labels_keep_the_partition() {
+        let labels = [4, 1, 1, 8, 4, 0, 8, 8, 2];
+        let canonical = canonicalize_labels(&labels);
+        for i in 0..labels.len() {
+            for j in 0..labels.len() {
+                assert_eq!(labels[i] == labels[j], canonical[i] == canonical[j]);
+            }
+        }
+        // any relabeling of the partition gives the same labels
+        let relabeled: Vec<usize> = labels.iter().map(|&l| (l * 5 + 3) % 11).collect();
+        assert_eq!(canonicalize_labels(&relabeled), canonical);
+        // and canonical labels are their own canonical form
+        assert_eq!(canonicalize_labels(&canonical), canonical);
+    }
+}
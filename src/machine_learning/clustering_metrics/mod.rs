@@ -0,0 +1,15 @@
+mod adjusted_rand_index;
+mod calinski_harabasz;
+mod cluster_variances;
+mod contingency_table;
+mod davies_bouldin_index;
+mod label_matching;
+mod normalized_mutual_info;
+
+pub use self::adjusted_rand_index::adjusted_rand_index;
+pub use self::calinski_harabasz::calinski_harabasz;
+pub use self::cluster_variances::cluster_variances;
+pub use self::contingency_table::contingency_table;
+pub use self::davies_bouldin_index::davies_bouldin_index;
+pub use self::label_matching::{best_label_permutation, canonicalize_labels};
+pub use self::normalized_mutual_info::normalized_mutual_info;
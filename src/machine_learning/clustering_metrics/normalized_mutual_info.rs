@@ -0,0 +1,115 @@
+use super::contingency_table;
+
+/// Shannon entropy (in nats) of a distribution given by its counts
+fn entropy(counts: impl Iterator<Item = usize>, total: f64) -> f64 {
+    counts
+        .filter(|&count| count > 0)
+        .map(|count| {
+            let p = count as f64 / total;
+            -p * p.ln()
+        })
+        .sum()
+}
+
+/// Computes the normalized mutual information between two clusterings of
+/// the same points.
+///
+/// The mutual information `I(U, V)` of the two labelings is computed from
+/// their contingency table, then normalized by the arithmetic mean of their
+/// entropies: `NMI = I(U, V) / ((H(U) + H(V)) / 2)`. The score lies in
+/// [0, 1], is 1.0 for identical partitions (whatever the actual label
+/// values), close to 0.0 for independent ones, and symmetric.
+///
+/// When both labelings put every point in a single cluster, both entropies
+/// are zero and the partitions are identical, so the score is defined as 1.0.
+///
+/// See <https://en.wikipedia.org/wiki/Adjusted_mutual_information>
+///
+/// # Panics
+///
+/// Panics if the two labelings do not have the same length.
+pub fn normalized_mutual_info(true_labels: &[usize], pred_labels: &[usize]) -> f64 {
+    let table = contingency_table(true_labels, pred_labels);
+    let total = true_labels.len() as f64;
+
+    let row_sums: Vec<usize> = table.iter().map(|row| row.iter().sum()).collect();
+    let column_sums: Vec<usize> = (0..table.first().map_or(0, |row| row.len()))
+        .map(|j| table.iter().map(|row| row[j]).sum())
+        .collect();
+
+    let h_true = entropy(row_sums.iter().copied(), total);
+    let h_pred = entropy(column_sums.iter().copied(), total);
+    if h_true == 0.0 && h_pred == 0.0 {
+        return 1.0;
+    }
+
+    let mut mutual_info = 0.0;
+    for (i, row) in table.iter().enumerate() {
+        for (j, &n_ij) in row.iter().enumerate() {
+            if n_ij > 0 {
+                let n_ij = n_ij as f64;
+                let ratio = total * n_ij / (row_sums[i] as f64 * column_sums[j] as f64);
+                mutual_info += n_ij / total * ratio.ln();
+            }
+        }
+    }
+
+    // rounding errors may let the value slightly escape [0, 1]
+    (mutual_info / ((h_true + h_pred) / 2.0)).clamp(0.0, 1.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::general::Xorshift64;
+
+    #[test]
+    fn identical_labelings() {
+        let labels = [0, 0, 1, 1, 2, 2, 2, 3];
+        assert!((normalized_mutual_info(&labels, &labels) - 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn permuted_labels_score_one() {
+        let true_labels = [0, 0, 0, 1, 1, 2, 2, 2, 2];
+        let pred_labels = [1, 1, 1, 2, 2, 0, 0, 0, 0];
+        assert!((normalized_mutual_info(&true_labels, &pred_labels) - 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn known_value() {
+        // value given by scikit-learn's `normalized_mutual_info_score`
+        let nmi = normalized_mutual_info(&[0, 0, 1, 1], &[0, 0, 1, 2]);
+        assert!((nmi - 0.8).abs() < 1e-12);
+    }
+
+    #[test]
+    fn independent_labelings_score_near_zero() {
+        // every true cluster is spread evenly over the predicted ones
+        let nmi = normalized_mutual_info(&[0, 0, 1, 1], &[0, 1, 0, 1]);
+        assert!(nmi.abs() < 1e-12);
+
+        let mut rng = Xorshift64::new(17);
+        let n = 10_000;
+        let a: Vec<usize> = (0..n).map(|_| rng.gen_range(0..4) as usize).collect();
+        let b: Vec<usize> = (0..n).map(|_| rng.gen_range(0..4) as usize).collect();
+        assert!(normalized_mutual_info(&a, &b) < 0.01);
+    }
+
+    #[test]
+    fn symmetric() {
+        let a = [0, 0, 1, 1, 1, 2, 0, 2, 3];
+        let b = [1, 0, 1, 1, 2, 2, 0, 0, 0];
+        let nmi = normalized_mutual_info(&a, &b);
+        assert!((nmi - normalized_mutual_info(&b, &a)).abs() < 1e-12);
+        assert!(nmi > 0.0 && nmi < 1.0);
+    }
+
+    #[test]
+    fn zero_entropy_edge_cases() {
+        assert_eq!(normalized_mutual_info(&[0, 0, 0], &[2, 2, 2]), 1.0);
+        assert_eq!(normalized_mutual_info(&[], &[]), 1.0);
+        // a single cluster carries no information about a real partition
+        assert_eq!(normalized_mutual_info(&[0, 0, 0, 0], &[0, 0, 1, 1]), 0.0);
+    }
+}
@@ -0,0 +1,69 @@
+//! # Cross-entropy
+//!
+//! The softmax, `math::softmax_f64`, turns the scores (logits) of the classes
+//! output by a classifier into a probability distribution, and the
+//! cross-entropy of those probabilities against the true class `t`,
+//! `-ln(p_t)`, is the loss minimized to train the classifier.
+
+/// Returns the cross-entropy `-ln(probs[target_class])` of the predicted
+/// probabilities `probs` when the true class is `target_class`. It is 0 when
+/// the target has probability 1, and infinite when it has probability 0.
+pub fn cross_entropy(probs: &[f64], target_class: usize) -> f64 {
+    assert!(
+        target_class < probs.len(),
+        "the target class has no probability"
+    );
+    -probs[target_class].ln()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::softmax_f64;
+
+    #[test]
+    fn cross_entropy_values() {
+        assert_eq!(cross_entropy(&[0.0, 1.0, 0.0], 1), 0.0);
+        assert!((cross_entropy(&[0.5, 0.25, 0.25], 2) - 4f64.ln()).abs() < 1e-12);
+        assert_eq!(cross_entropy(&[1.0, 0.0], 1), f64::INFINITY);
+        // the same as the general cross-entropy against a one-hot distribution
+        let probs = [0.1, 0.7, 0.1, 0.05, 0.05];
+        let one_hot = [0.0, 1.0, 0.0, 0.0, 0.0];
+        assert!(
+            (cross_entropy(&probs, 1) - crate::math::cross_entropy_loss(&one_hot, &probs)).abs()
+                < 1e-12
+        );
+    }
+
+    #[test]
+    fn cross_entropy_is_minimal_for_a_certain_target() {
+        let target = 2;
+        let mut previous = f64::INFINITY;
+        // the loss decreases as the probability of the target grows to 1
+        for p in [0.01, 0.1, 0.3, 0.5, 0.9, 0.99, 1.0] {
+            let rest = (1.0 - p) / 2.0;
+            let loss = cross_entropy(&[rest, rest, p], target);
+            assert!(loss >= 0.0);
+            assert!(loss < previous);
+            previous = loss;
+        }
+        assert_eq!(previous, 0.0);
+        for logits in [[0.0, 0.0, 0.0], [5.0, -1.0, 0.0], [0.0, 0.0, 10.0]] {
+            assert!(cross_entropy(&softmax_f64(&logits), target) > 0.0);
+        }
+    }
+
+    #[test]
+    fn from_large_logits() {
+        // e^1000 overflows, but not the softmax
+        let probs = softmax_f64(&[1000.0, 990.0]);
+        assert!(cross_entropy(&probs, 0) < 1e-4);
+        assert!((cross_entropy(&probs, 1) - 10.0).abs() < 1e-3);
+    }
+
+    #[test]
+    #[should_panic]
+    fn target_out_of_range() {
+        cross_entropy(&[0.5, 0.5], 2);
+    }
+}
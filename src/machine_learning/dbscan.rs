@@ -0,0 +1,232 @@
+//! # DBSCAN
+//!
+//! Density-based spatial clustering of applications with noise groups the
+//! points lying in dense regions, without knowing the number of clusters.
+//!
+//! A point is a core point if at least `min_pts` points (itself included) are
+//! within distance `eps` of it. The clusters are the sets of points reachable
+//! from a core point through a chain of core points, each within `eps` of the
+//! previous one; the points reached which are not core points themselves are
+//! border points. The points reachable from no core point are noise.
+//!
+//! Searching the neighbors of every point takes O(n^2) distance computations
+//! with a linear scan, which `dbscan_kdtree` replaces with radius queries on a
+//! k-d tree, far faster for many points in few dimensions. Both return the
+//! same labels. The linear scan can also measure the distances with any
+//! `Metric`, with `dbscan_with_metric`, where the k-d tree is Euclidean.
+//!
+//! ## Resources:
+//!   - [https://en.wikipedia.org/wiki/DBSCAN]
+
+use crate::data_structures::KdTree;
+use crate::math::Metric;
+
+/// Labels the `n` points, given the sorted indices of the points within
+/// `eps` of each point
+fn expand_clusters(
+    n: usize,
+    min_pts: usize,
+    mut neighbors: impl FnMut(usize) -> Vec<usize>,
+) -> Vec<Option<usize>> {
+    let mut labels = vec![None; n];
+    let mut visited = vec![false; n];
+    let mut clusters = 0;
+
+    for point in 0..n {
+        if visited[point] {
+            continue;
+        }
+        visited[point] = true;
+        let mut queue = neighbors(point);
+        if queue.len() < min_pts {
+            // noise, unless a cluster reaches it later on
+            continue;
+        }
+        let cluster = clusters;
+        clusters += 1;
+        labels[point] = Some(cluster);
+        while let Some(other) = queue.pop() {
+            if labels[other].is_none() {
+                labels[other] = Some(cluster);
+            }
+            if !visited[other] {
+                visited[other] = true;
+                let reached = neighbors(other);
+                if reached.len() >= min_pts {
+                    queue.extend(reached);
+                }
+            }
+        }
+    }
+    labels
+}
+
+fn dbscan_counting(
+    xs: &[Vec<f64>],
+    eps: f64,
+    min_pts: usize,
+    metric: Metric,
+    distances: &mut usize,
+) -> Vec<Option<usize>> {
+    expand_clusters(xs.len(), min_pts, |point| {
+        *distances += xs.len();
+        (0..xs.len())
+            .filter(|&other| metric.distance(&xs[point], &xs[other]) <= eps)
+            .collect()
+    })
+}
+
+fn dbscan_kdtree_counting(
+    xs: &[Vec<f64>],
+    eps: f64,
+    min_pts: usize,
+    distances: &mut usize,
+) -> Vec<Option<usize>> {
+    let tree = KdTree::new(xs);
+    expand_clusters(xs.len(), min_pts, |point| {
+        tree.within_radius_counting(&xs[point], eps, distances)
+    })
+}
+
+/// Clusters the points `xs` with DBSCAN, scanning all the points to find the
+/// neighbors of each one.
+///
+/// Returns the cluster of each point, numbered from 0 in the order they are
+/// found, or None for noise. A border point within `eps` of several clusters
+/// joins the first one to reach it.
+pub fn dbscan(xs: &[Vec<f64>], eps: f64, min_pts: usize) -> Vec<Option<usize>> {
+    dbscan_counting(xs, eps, min_pts, Metric::Euclidean, &mut 0)
+}
+
+/// Clusters the points `xs` with DBSCAN as `dbscan`, the neighbors of a point
+/// being the points within `eps` of it under `metric`.
+pub fn dbscan_with_metric(
+    xs: &[Vec<f64>],
+    eps: f64,
+    min_pts: usize,
+    metric: Metric,
+) -> Vec<Option<usize>> {
+    dbscan_counting(xs, eps, min_pts, metric, &mut 0)
+}
+
+/// Clusters the points `xs` with DBSCAN as `dbscan`, finding the neighbors of
+/// each point with a k-d tree. The points must all have the same dimension.
+pub fn dbscan_kdtree(xs: &[Vec<f64>], eps: f64, min_pts: usize) -> Vec<Option<usize>> {
+    dbscan_kdtree_counting(xs, eps, min_pts, &mut 0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::general::make_blobs;
+    use crate::general::Xorshift64;
+
+    #[test]
+    fn two_clusters_and_noise() {
+        let xs = vec![
+            vec![0.0, 0.0],
+            vec![0.0, 1.0],
+            vec![1.0, 0.0],
+            vec![10.0, 10.0],
+            vec![10.0, 11.0],
+            vec![11.0, 10.0],
+            vec![11.0, 11.0],
+            // within eps of a core point only: a border point
+            vec![12.0, 11.0],
+            vec![5.0, 5.0],
+        ];
+        let expected = vec![
+            Some(0),
+            Some(0),
+            Some(0),
+            Some(1),
+            Some(1),
+            Some(1),
+            Some(1),
+            Some(1),
+            None,
+        ];
+        assert_eq!(dbscan(&xs, 1.0, 3), expected);
+        assert_eq!(dbscan_kdtree(&xs, 1.0, 3), expected);
+        // with larger neighborhoods, only (11, 11) is a core point, and the
+        // points of the first cluster become noise
+        let mut expected = vec![None; 9];
+        expected[4..8].fill(Some(0));
+        assert_eq!(dbscan(&xs, 1.0, 4), expected);
+        assert_eq!(dbscan_kdtree(&xs, 1.0, 4), expected);
+    }
+
+    #[test]
+    fn blobs_are_found() {
+        let centers = vec![vec![0.0, 0.0], vec![8.0, 0.0], vec![0.0, 8.0]];
+        let (xs, truth) = make_blobs(150, &centers, 0.5, 256);
+        let labels = dbscan_kdtree(&xs, 1.0, 5);
+        // each blob is a single cluster, some outliers aside
+        let noise = labels.iter().filter(|l| l.is_none()).count();
+        assert!(noise < 5);
+        for i in 0..xs.len() {
+            for j in 0..xs.len() {
+                if let (Some(a), Some(b)) = (labels[i], labels[j]) {
+                    assert_eq!(a == b, truth[i] == truth[j]);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn kdtree_gives_identical_labels() {
+        let mut rng = Xorshift64::new(2560);
+        for (n, dimensions) in [(0, 2), (1, 2), (50, 1), (300, 2), (300, 3)] {
+            let xs: Vec<Vec<f64>> = (0..n)
+                .map(|_| (0..dimensions).map(|_| rng.next_f64() * 10.0).collect())
+                .collect();
+            for (eps, min_pts) in [(0.3, 3), (0.8, 4), (1.5, 10), (20.0, 1)] {
+                assert_eq!(dbscan_kdtree(&xs, eps, min_pts), dbscan(&xs, eps, min_pts));
+            }
+        }
+    }
+
+    #[test]
+    fn metrics_shape_the_neighborhoods() {
+        // a diagonal, whose steps are 1 for Chebyshev, sqrt(2) for Euclid and
+        // 2 for Manhattan
+        let xs: Vec<Vec<f64>> = (0..5).map(|i| vec![i as f64, i as f64]).collect();
+        let eps = 1.5;
+        assert_eq!(
+            dbscan_with_metric(&xs, eps, 3, Metric::Euclidean),
+            dbscan(&xs, eps, 3)
+        );
+        assert_eq!(
+            dbscan_with_metric(&xs, eps, 3, Metric::Chebyshev),
+            vec![Some(0); 5]
+        );
+        assert_eq!(
+            dbscan_with_metric(&xs, eps, 3, Metric::Euclidean),
+            vec![Some(0); 5]
+        );
+        assert_eq!(
+            dbscan_with_metric(&xs, eps, 3, Metric::Manhattan),
+            vec![None; 5]
+        );
+        // the cosine distance groups the points by direction
+        let rays: Vec<Vec<f64>> = [1.0, 2.0, 3.0, 4.0]
+            .iter()
+            .flat_map(|&r| [vec![r, 0.1], vec![0.1, r]])
+            .collect();
+        let labels = dbscan_with_metric(&rays, 0.01, 3, Metric::Cosine);
+        assert_eq!(labels, [Some(0), Some(1)].repeat(4));
+    }
+
+    #[test]
+    fn kdtree_computes_far_fewer_distances() {
+        let centers: Vec<Vec<f64>> = (0..10)
+            .map(|i| vec![(i % 5) as f64 * 20.0, (i / 5) as f64 * 20.0])
+            .collect();
+        let (xs, _) = make_blobs(2000, &centers, 1.0, 2561);
+        let (mut naive, mut with_tree) = (0, 0);
+        let labels = dbscan_counting(&xs, 0.5, 5, Metric::Euclidean, &mut naive);
+        assert_eq!(dbscan_kdtree_counting(&xs, 0.5, 5, &mut with_tree), labels);
+        assert_eq!(naive, xs.len() * xs.len());
+        assert!(with_tree * 20 < naive, "{with_tree} vs {naive}");
+    }
+}
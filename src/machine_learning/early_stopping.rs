@@ -0,0 +1,144 @@
+//! # Early stopping
+//!
+//! An iterative fit often reaches a point where more iterations barely
+//! improve it, or where the error on held out data starts to grow as the
+//! model overfits. Early stopping monitors such a metric, lower being better,
+//! and stops the fit once it has not improved for a while.
+//!
+//! ## Resources:
+//!   - [https://en.wikipedia.org/wiki/Early_stopping]
+
+/// Tells when to stop a fit whose monitored metric has not improved on its
+/// best value by more than `min_delta` for `patience` updates in a row.
+///
+/// To monitor a metric for which higher is better, update it with its
+/// opposite.
+#[derive(Clone, Debug, PartialEq)]
+pub struct EarlyStopper {
+    patience: usize,
+    min_delta: f64,
+    best: Option<f64>,
+    // the updates since the last improvement
+    waiting: usize,
+}
+
+impl EarlyStopper {
+    /// Creates a stopper waiting `patience` updates for an improvement of
+    /// more than `min_delta`.
+    ///
+    /// Panics if `patience` is 0, or if `min_delta` is negative.
+    pub fn new(patience: usize, min_delta: f64) -> Self {
+        assert!(patience > 0, "the patience must be at least one update");
+        assert!(
+            min_delta >= 0.0,
+            "the minimal improvement must not be negative"
+        );
+        EarlyStopper {
+            patience,
+            min_delta,
+            best: None,
+            waiting: 0,
+        }
+    }
+
+    /// Records the new `value` of the metric, and returns true if the fit
+    /// should stop: the `patience` last values have not improved on the best
+    /// one before them by more than `min_delta`. The first value never stops
+    /// the fit, unless it is NaN: a NaN, as a diverging fit gives, never
+    /// improves the metric.
+    pub fn update(&mut self, value: f64) -> bool {
+        match self.best {
+            _ if value.is_nan() => self.waiting += 1,
+            Some(best) if value >= best - self.min_delta => self.waiting += 1,
+            _ => {
+                self.best = Some(value);
+                self.waiting = 0;
+            }
+        }
+        self.waiting >= self.patience
+    }
+
+    /// Returns the best value so far, which improved on the previous best by
+    /// more than `min_delta`
+    pub fn best(&self) -> Option<f64> {
+        self.best
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Returns the number of updates after which `stopper` stops on `values`
+    fn stopping_step(stopper: &mut EarlyStopper, values: &[f64]) -> Option<usize> {
+        values
+            .iter()
+            .position(|&value| stopper.update(value))
+            .map(|i| i + 1)
+    }
+
+    #[test]
+    fn stops_on_a_plateau() {
+        let mut stopper = EarlyStopper::new(3, 0.1);
+        let values = [10.0, 5.0, 3.0, 2.95, 2.94, 2.96, 2.93, 1.0];
+        // the 3 values after 3.0 do not improve on it by more than 0.1
+        assert_eq!(stopping_step(&mut stopper, &values), Some(6));
+        assert_eq!(stopper.best(), Some(3.0));
+    }
+
+    #[test]
+    fn improvements_reset_the_patience() {
+        let mut stopper = EarlyStopper::new(2, 0.0);
+        let values = [4.0, 4.0, 3.0, 3.5, 2.0, 2.0, 2.5, 0.0];
+        // 4, 4 waits once, 3 improves, 3.5 waits, 2 improves, then 2, 2.5
+        assert_eq!(stopping_step(&mut stopper, &values), Some(7));
+        assert_eq!(stopper.best(), Some(2.0));
+
+        // a metric which keeps on improving never stops
+        let mut stopper = EarlyStopper::new(1, 0.01);
+        let decreasing: Vec<f64> = (0..100).map(|i| 100.0 - i as f64).collect();
+        assert_eq!(stopping_step(&mut stopper, &decreasing), None);
+    }
+
+    #[test]
+    fn least_patience() {
+        // stops at the first update without improvement
+        let mut stopper = EarlyStopper::new(1, 0.0);
+        assert_eq!(stopper.best(), None);
+        assert_eq!(stopping_step(&mut stopper, &[3.0, 2.0, 2.0]), Some(3));
+    }
+
+    #[test]
+    fn nan_does_not_improve() {
+        let mut stopper = EarlyStopper::new(3, 0.0);
+        let values = [
+            5.0,
+            4.0,
+            f64::NAN,
+            f64::NAN,
+            3.9,
+            f64::NAN,
+            f64::NAN,
+            f64::NAN,
+        ];
+        // 3.9 improves on 4 and resets the patience, then the fit diverges
+        assert_eq!(stopping_step(&mut stopper, &values), Some(8));
+        assert_eq!(stopper.best(), Some(3.9));
+
+        let mut stopper = EarlyStopper::new(2, 0.0);
+        assert_eq!(stopping_step(&mut stopper, &[f64::NAN, f64::NAN]), Some(2));
+        assert_eq!(stopper.best(), None);
+    }
+
+    #[test]
+    #[should_panic]
+    fn no_patience() {
+        EarlyStopper::new(0, 0.1);
+    }
+
+    #[test]
+    #[should_panic]
+    fn negative_delta() {
+        EarlyStopper::new(3, -1.0);
+    }
+}
@@ -0,0 +1,100 @@
+use crate::general::kmeans_f64_fit_seeded;
+
+/// Scores a clustering from the data, the labels and the centroids
+pub type ClusteringMetric = fn(&[Vec<f64>], &[usize], &[Vec<f64>]) -> f64;
+
+/// Fits k-means to `xs` for every number of clusters in `k_values` and every
+/// seed in `seeds`, and returns the `(k, seed)` of the best clustering by
+/// `metric`, along with its score.
+///
+/// The higher the score of `metric`, the better, as for `calinski_harabasz`;
+/// a metric for which lower is better, like `davies_bouldin_index`, can be
+/// negated in a closure
+/// `|xs, labels, centroids| -davies_bouldin_index(xs, labels, centroids)`.
+/// On a tie, the first configuration tried wins, in the order of `k_values`,
+/// then of `seeds`. The configurations scoring NaN or an infinity are
+/// skipped.
+///
+/// # Panics
+///
+/// Panics if no configuration can be fitted: `k_values` or `seeds` are empty,
+/// every `k` is 0 or larger than the number of points, or no score is finite.
+pub fn grid_search_kmeans(
+    xs: &Vec<Vec<f64>>,
+    k_values: &[usize],
+    seeds: &[u64],
+    metric: ClusteringMetric,
+) -> (usize, u64, f64) {
+    let mut best: Option<(usize, u64, f64)> = None;
+    for &k in k_values {
+        for &seed in seeds {
+            let Some(result) = kmeans_f64_fit_seeded(xs, k, None, seed) else {
+                continue;
+            };
+            let score = metric(xs, &result.labels, &result.centroids);
+            // a NaN would never be beaten
+            if !score.is_finite() {
+                continue;
+            }
+            if best.is_none_or(|(_, _, best_score)| score > best_score) {
+                best = Some((k, seed, score));
+            }
+        }
+    }
+    best.expect("no configuration could be fitted")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::general::make_blobs;
+    use crate::machine_learning::{calinski_harabasz, davies_bouldin_index};
+
+    #[test]
+    fn two_blobs() {
+        let (xs, _) = make_blobs(200, &[vec![-6.0, 0.0], vec![6.0, 0.0]], 1.0, 284);
+        let seeds = [1, 2, 3];
+        let (k, seed, score) = grid_search_kmeans(&xs, &[2, 3, 4, 5], &seeds, calinski_harabasz);
+        assert_eq!(k, 2);
+        assert!(seeds.contains(&seed));
+        assert!(score > 0.0);
+
+        let (k, _, score) = grid_search_kmeans(&xs, &[5, 4, 3, 2], &seeds, |xs, labels, c| {
+            -davies_bouldin_index(xs, labels, c)
+        });
+        assert_eq!(k, 2);
+        assert!(score < 0.0);
+    }
+
+    #[test]
+    fn skips_invalid_k() {
+        let xs = vec![vec![0.0], vec![0.5], vec![10.0], vec![10.5]];
+        let (k, _, _) = grid_search_kmeans(&xs, &[0, 2, 9], &[5], calinski_harabasz);
+        assert_eq!(k, 2);
+    }
+
+    #[test]
+    fn skips_non_finite_scores() {
+        let xs = vec![vec![0.0], vec![0.5], vec![10.0], vec![10.5]];
+        // a NaN for the first k tried, then the fewer clusters the better
+        let metric: ClusteringMetric = |_, _, centroids| match centroids.len() {
+            2 => f64::NAN,
+            k => -(k as f64),
+        };
+        let (k, _, score) = grid_search_kmeans(&xs, &[2, 4, 3], &[1, 2], metric);
+        assert_eq!((k, score), (3, -3.0));
+    }
+
+    #[test]
+    #[should_panic]
+    fn no_finite_score() {
+        let xs = vec![vec![0.0], vec![0.5], vec![10.0]];
+        grid_search_kmeans(&xs, &[1, 2], &[1], |_, _, _| f64::INFINITY);
+    }
+
+    #[test]
+    #[should_panic]
+    fn nothing_to_fit() {
+        grid_search_kmeans(&vec![vec![1.0]], &[2, 3], &[1], calinski_harabasz);
+    }
+}
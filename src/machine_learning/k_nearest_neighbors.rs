@@ -0,0 +1,128 @@
+//! # k-nearest neighbors
+//!
+//! The k-nearest neighbors classifier needs no training: a query takes the
+//! label most common among the `k` labelled points nearest to it, under the
+//! metric chosen. A small `k` follows the data closely, and is sensitive to
+//! noise; a large `k` smooths the boundaries between the classes. Each query
+//! scans the whole data, in O(n log n).
+//!
+//! ## Resources:
+//!   - [https://en.wikipedia.org/wiki/K-nearest_neighbors_algorithm]
+
+use crate::math::Metric;
+use std::collections::HashMap;
+
+/// Returns the label of `query` by a majority vote of its `k` nearest points
+/// in `xs` under `metric`, labelled by `labels`, or None if there are no
+/// points or `k` is 0. A tie goes to the label of the nearest point among the
+/// tied labels, and `k` larger than the data counts all the points.
+///
+/// Panics if `xs` and `labels` have different lengths.
+pub fn k_nearest_neighbors(
+    xs: &[Vec<f64>],
+    labels: &[usize],
+    query: &[f64],
+    k: usize,
+    metric: Metric,
+) -> Option<usize> {
+    assert_eq!(xs.len(), labels.len(), "every point needs a label");
+    let mut order: Vec<(f64, usize)> = xs
+        .iter()
+        .map(|x| metric.distance(x, query))
+        .zip(labels.iter().copied())
+        .collect();
+    order.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+    let mut votes: HashMap<usize, usize> = HashMap::new();
+    for &(_, label) in order.iter().take(k) {
+        *votes.entry(label).or_default() += 1;
+    }
+    let most = votes.values().copied().max()?;
+    // the nearest of the labels with the most votes
+    order
+        .iter()
+        .take(k)
+        .map(|&(_, label)| label)
+        .find(|label| votes[label] == most)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::general::make_blobs;
+
+    #[test]
+    fn small_example() {
+        let xs = vec![
+            vec![0.0, 0.0],
+            vec![0.0, 1.0],
+            vec![1.0, 0.0],
+            vec![5.0, 5.0],
+            vec![5.0, 6.0],
+        ];
+        let labels = [0, 0, 0, 1, 1];
+        let classify =
+            |query: &[f64], k| k_nearest_neighbors(&xs, &labels, query, k, Metric::Euclidean);
+        assert_eq!(classify(&[0.2, 0.2], 3), Some(0));
+        assert_eq!(classify(&[4.0, 4.5], 1), Some(1));
+        assert_eq!(classify(&[4.0, 4.5], 2), Some(1));
+        // three near points outvote two nearer ones
+        assert_eq!(classify(&[4.0, 4.5], 5), Some(0));
+        // a tie goes to the nearest label
+        assert_eq!(classify(&[3.0, 3.0], 4), Some(1));
+        assert_eq!(classify(&[3.0, 3.0], 100), Some(0));
+    }
+
+    #[test]
+    fn metrics_change_the_neighbors() {
+        let xs = vec![vec![1.4, 1.4], vec![0.0, 1.9]];
+        let labels = [0, 1];
+        let query = [0.0, 0.0];
+        // the first point is nearer in Chebyshev distance, the second one in
+        // Manhattan distance
+        assert_eq!(
+            k_nearest_neighbors(&xs, &labels, &query, 1, Metric::Chebyshev),
+            Some(0)
+        );
+        assert_eq!(
+            k_nearest_neighbors(&xs, &labels, &query, 1, Metric::Manhattan),
+            Some(1)
+        );
+    }
+
+    #[test]
+    fn classifies_blobs() {
+        let centers = vec![vec![0.0, 0.0], vec![6.0, 0.0], vec![3.0, 5.0]];
+        let (xs, truth) = make_blobs(300, &centers, 0.8, 274);
+        // the even points are labelled, the odd ones are classified
+        let train: Vec<Vec<f64>> = xs.iter().step_by(2).cloned().collect();
+        let train_labels: Vec<usize> = truth.iter().step_by(2).copied().collect();
+        for metric in [Metric::Euclidean, Metric::Manhattan, Metric::Chebyshev] {
+            let correct = (1..xs.len())
+                .step_by(2)
+                .filter(|&i| {
+                    k_nearest_neighbors(&train, &train_labels, &xs[i], 5, metric) == Some(truth[i])
+                })
+                .count();
+            assert!(correct >= 145, "{metric:?}: {correct}");
+        }
+    }
+
+    #[test]
+    fn nothing_to_vote() {
+        assert_eq!(
+            k_nearest_neighbors(&[], &[], &[1.0], 3, Metric::Euclidean),
+            None
+        );
+        assert_eq!(
+            k_nearest_neighbors(&[vec![1.0]], &[4], &[1.0], 0, Metric::Euclidean),
+            None
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn missing_labels() {
+        k_nearest_neighbors(&[vec![1.0], vec![2.0]], &[0], &[1.0], 1, Metric::Euclidean);
+    }
+}
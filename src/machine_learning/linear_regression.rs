@@ -1,3 +1,5 @@
+use crate::general::Xorshift64;
+
 /// Returns the parameters of the line after performing simple linear regression on the input data.
 pub fn linear_regression(data_points: Vec<(f64, f64)>) -> Option<(f64, f64)> {
     if data_points.is_empty() {
@@ -29,6 +31,57 @@ pub fn linear_regression(data_points: Vec<(f64, f64)>) -> Option<(f64, f64)> {
     Some((a, b))
 }
 
+/// Fits a multiple linear regression `y = b + w_1 * x_1 + ... + w_d * x_d` to
+/// the samples `xs` and targets `ys` by mini-batch stochastic gradient descent,
+/// and returns the coefficients `[b, w_1, ..., w_d]`.
+///
+/// Each epoch shuffles the samples (with a generator seeded by `seed`, so the
+/// fit is reproducible), splits them into batches of `batch_size`, and makes a
+/// step of `lr` against the gradient of the mean squared error of each batch.
+/// Unlike the closed form, which solves a d x d linear system, an epoch only
+/// costs O(n * d), and the samples may be streamed batch by batch.
+pub fn sgd_linear(
+    xs: &[Vec<f64>],
+    ys: &[f64],
+    lr: f64,
+    epochs: usize,
+    batch_size: usize,
+    seed: u64,
+) -> Vec<f64> {
+    assert_eq!(xs.len(), ys.len(), "there must be one target per sample");
+    assert!(batch_size > 0, "the batches cannot be empty");
+    let dimensions = xs.first().map_or(0, Vec::len);
+    let mut coefficients = vec![0.0; dimensions + 1];
+    let mut order: Vec<usize> = (0..xs.len()).collect();
+    let mut rng = Xorshift64::new(seed);
+
+    for _ in 0..epochs {
+        rng.shuffle(&mut order);
+        for batch in order.chunks(batch_size) {
+            let mut gradient = vec![0.0; dimensions + 1];
+            for &i in batch {
+                let prediction = coefficients[0]
+                    + xs[i]
+                        .iter()
+                        .zip(&coefficients[1..])
+                        .map(|(x, w)| x * w)
+                        .sum::<f64>();
+                let error = prediction - ys[i];
+                gradient[0] += error;
+                for (g, x) in gradient[1..].iter_mut().zip(&xs[i]) {
+                    *g += error * x;
+                }
+            }
+            // the gradient of half the mean squared error of the batch
+            let scale = lr / batch.len() as f64;
+            for (c, g) in coefficients.iter_mut().zip(&gradient) {
+                *c -= scale * g;
+            }
+        }
+    }
+    coefficients
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -45,4 +98,88 @@ mod test {
     fn test_empty_list_linear_regression() {
         assert_eq!(linear_regression(vec![]), None);
     }
+
+    /// Solves the normal equations (X^T X) c = X^T y, with a column of ones
+    /// in X for the intercept, by Gaussian elimination
+    fn normal_equations(xs: &[Vec<f64>], ys: &[f64]) -> Vec<f64> {
+        let d = xs[0].len() + 1;
+        let rows: Vec<Vec<f64>> = xs
+            .iter()
+            .map(|x| std::iter::once(1.0).chain(x.iter().copied()).collect())
+            .collect();
+        let mut a = vec![vec![0.0; d + 1]; d];
+        for (row, &y) in rows.iter().zip(ys) {
+            for i in 0..d {
+                for j in 0..d {
+                    a[i][j] += row[i] * row[j];
+                }
+                a[i][d] += row[i] * y;
+            }
+        }
+        for col in 0..d {
+            let pivot = (col..d)
+                .max_by(|&i, &j| a[i][col].abs().total_cmp(&a[j][col].abs()))
+                .unwrap();
+            a.swap(col, pivot);
+            for i in 0..d {
+                if i != col {
+                    let factor = a[i][col] / a[col][col];
+                    let pivot_row = a[col].clone();
+                    for (value, p) in a[i].iter_mut().zip(&pivot_row) {
+                        *value -= factor * p;
+                    }
+                }
+            }
+        }
+        (0..d).map(|i| a[i][d] / a[i][i]).collect()
+    }
+
+    fn synthetic_data(n: usize, seed: u64) -> (Vec<Vec<f64>>, Vec<f64>) {
+        let mut rng = Xorshift64::new(seed);
+        let xs: Vec<Vec<f64>> = (0..n)
+            .map(|_| (0..3).map(|_| rng.next_f64() * 2.0 - 1.0).collect())
+            .collect();
+        let ys = xs
+            .iter()
+            .map(|x| 0.5 + 2.0 * x[0] - 3.0 * x[1] + 1.5 * x[2] + rng.next_gaussian(0.0, 0.1))
+            .collect();
+        (xs, ys)
+    }
+
+    #[test]
+    fn test_sgd_linear_matches_normal_equations() {
+        let (xs, ys) = synthetic_data(1000, 251);
+        let expected = normal_equations(&xs, &ys);
+        // close to the true coefficients, up to the noise
+        for (c, truth) in expected.iter().zip([0.5, 2.0, -3.0, 1.5]) {
+            assert!((c - truth).abs() < 0.05);
+        }
+        let coefficients = sgd_linear(&xs, &ys, 0.05, 200, 16, 7);
+        for (c, e) in coefficients.iter().zip(&expected) {
+            assert!((c - e).abs() < 0.02, "{coefficients:?} != {expected:?}");
+        }
+    }
+
+    #[test]
+    fn test_sgd_linear_batch_sizes() {
+        let (xs, ys) = synthetic_data(200, 5);
+        let expected = normal_equations(&xs, &ys);
+        // from single samples to the full batch gradient descent
+        for batch_size in [1, 10, 200, 1000] {
+            let lr = if batch_size == 1 { 0.01 } else { 0.1 };
+            let coefficients = sgd_linear(&xs, &ys, lr, 500, batch_size, 1);
+            for (c, e) in coefficients.iter().zip(&expected) {
+                assert!((c - e).abs() < 0.02, "{batch_size}: {coefficients:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_sgd_linear_is_reproducible() {
+        let (xs, ys) = synthetic_data(100, 3);
+        let a = sgd_linear(&xs, &ys, 0.05, 5, 8, 42);
+        assert_eq!(a, sgd_linear(&xs, &ys, 0.05, 5, 8, 42));
+        assert_ne!(a, sgd_linear(&xs, &ys, 0.05, 5, 8, 43));
+        assert_eq!(sgd_linear(&xs, &ys, 0.05, 0, 8, 42), vec![0.0; 4]);
+    }
 }
@@ -1,16 +1,45 @@
+mod affinity_propagation;
 mod cholesky;
+mod clustering_metrics;
+mod cross_entropy;
+mod dbscan;
+mod early_stopping;
+mod grid_search;
 mod k_means;
+mod k_nearest_neighbors;
 mod linear_regression;
 mod loss_function;
+mod neural_net;
+mod optics;
 mod optimization;
+mod tsne;
+mod whitening;
 
+pub use self::affinity_propagation::affinity_propagation;
 pub use self::cholesky::cholesky;
+pub use self::clustering_metrics::{
+    adjusted_rand_index, best_label_permutation, calinski_harabasz, canonicalize_labels,
+    cluster_variances, contingency_table, davies_bouldin_index, normalized_mutual_info,
+};
+pub use self::cross_entropy::cross_entropy;
+pub use self::dbscan::{dbscan, dbscan_kdtree, dbscan_with_metric};
+pub use self::early_stopping::EarlyStopper;
+pub use self::grid_search::{grid_search_kmeans, ClusteringMetric};
 pub use self::k_means::k_means;
-pub use self::linear_regression::linear_regression;
+pub use self::k_nearest_neighbors::k_nearest_neighbors;
+pub use self::linear_regression::{linear_regression, sgd_linear};
 pub use self::loss_function::hng_loss;
 pub use self::loss_function::huber_loss;
 pub use self::loss_function::kld_loss;
 pub use self::loss_function::mae_loss;
 pub use self::loss_function::mse_loss;
+pub use self::neural_net::{Activation, NeuralNet};
+pub use self::optics::{extract_dbscan_clustering, optics};
 pub use self::optimization::gradient_descent;
 pub use self::optimization::Adam;
+pub use self::optimization::{
+    minimize_adam, minimize_adam_with_params, minimize_gradient_descent,
+    minimize_gradient_descent_early_stopping, minimize_momentum, AdamParams,
+};
+pub use self::tsne::tsne;
+pub use self::whitening::{whiten, WhiteningParams};
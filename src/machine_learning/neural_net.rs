@@ -0,0 +1,324 @@
+//! # Feed-forward neural network
+//!
+//! A multilayer perceptron: each layer multiplies the outputs of the previous
+//! one by a matrix of weights, adds biases, and applies an activation
+//! function. The hidden layers use a configurable activation, the output layer
+//! always uses the sigmoid, so that the outputs are in (0, 1).
+//!
+//! It is trained by stochastic gradient descent on the squared error, the
+//! gradients being computed by backpropagation: the error of the outputs is
+//! propagated back through the layers with the chain rule.
+//!
+//! ## Resources:
+//!   - [https://en.wikipedia.org/wiki/Multilayer_perceptron]
+//!   - [https://en.wikipedia.org/wiki/Backpropagation]
+
+use crate::general::Xorshift64;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Activation {
+    Sigmoid,
+    Relu,
+}
+
+impl Activation {
+    fn apply(self, z: f64) -> f64 {
+        match self {
+            Activation::Sigmoid => 1.0 / (1.0 + (-z).exp()),
+            Activation::Relu => z.max(0.0),
+        }
+    }
+
+    /// The derivative, given the output `a = apply(z)` of the activation
+    fn derivative(self, a: f64) -> f64 {
+        match self {
+            Activation::Sigmoid => a * (1.0 - a),
+            Activation::Relu => {
+                if a > 0.0 {
+                    1.0
+                } else {
+                    0.0
+                }
+            }
+        }
+    }
+}
+
+pub struct NeuralNet {
+    // weights[l][j][i] connects the input i of layer l to its neuron j
+    weights: Vec<Vec<Vec<f64>>>,
+    biases: Vec<Vec<f64>>,
+    hidden_activation: Activation,
+}
+
+/// The gradients of the squared error with respect to the parameters
+struct Gradients {
+    weights: Vec<Vec<Vec<f64>>>,
+    biases: Vec<Vec<f64>>,
+}
+
+impl NeuralNet {
+    /// Creates a network with the sizes of its layers, from the inputs to the
+    /// outputs, using sigmoid hidden layers and a fixed random initialization.
+    pub fn new(layers: &[usize]) -> Self {
+        NeuralNet::with_options(layers, Activation::Sigmoid, 0)
+    }
+
+    /// Creates a network with the activation of its hidden layers, and the
+    /// seed of the random initialization of its weights.
+    ///
+    /// Panics if there are less than two layers (the inputs and the outputs),
+    /// or if a layer is empty.
+    pub fn with_options(layers: &[usize], hidden_activation: Activation, seed: u64) -> Self {
+        assert!(layers.len() >= 2, "a network needs inputs and outputs");
+        assert!(layers.iter().all(|&size| size > 0), "a layer is empty");
+        let mut rng = Xorshift64::new(seed);
+        // uniform in [-1 / sqrt(n), 1 / sqrt(n)], for the n inputs of the layer
+        let weights = layers
+            .windows(2)
+            .map(|pair| {
+                let limit = 1.0 / (pair[0] as f64).sqrt();
+                (0..pair[1])
+                    .map(|_| {
+                        (0..pair[0])
+                            .map(|_| (2.0 * rng.next_f64() - 1.0) * limit)
+                            .collect()
+                    })
+                    .collect()
+            })
+            .collect();
+        let biases = layers[1..].iter().map(|&size| vec![0.0; size]).collect();
+        NeuralNet {
+            weights,
+            biases,
+            hidden_activation,
+        }
+    }
+
+    /// Returns the sizes of the layers, from the inputs to the outputs
+    pub fn layers(&self) -> Vec<usize> {
+        std::iter::once(self.weights[0][0].len())
+            .chain(self.biases.iter().map(Vec::len))
+            .collect()
+    }
+
+    fn activation(&self, layer: usize) -> Activation {
+        if layer + 1 == self.weights.len() {
+            Activation::Sigmoid
+        } else {
+            self.hidden_activation
+        }
+    }
+
+    /// Returns the outputs of all the layers, starting with the inputs `x`
+    fn forward(&self, x: &[f64]) -> Vec<Vec<f64>> {
+        assert_eq!(x.len(), self.weights[0][0].len(), "wrong number of inputs");
+        let mut outputs = vec![x.to_vec()];
+        for (layer, (weights, biases)) in self.weights.iter().zip(&self.biases).enumerate() {
+            let input = outputs.last().unwrap();
+            let activation = self.activation(layer);
+            let output = weights
+                .iter()
+                .zip(biases)
+                .map(|(row, bias)| {
+                    let z = bias + row.iter().zip(input).map(|(w, a)| w * a).sum::<f64>();
+                    activation.apply(z)
+                })
+                .collect();
+            outputs.push(output);
+        }
+        outputs
+    }
+
+    /// Returns the gradients of half the squared error of the outputs for `x`
+    /// against the targets `y`, given the outputs of `forward(x)`
+    fn backward(&self, outputs: &[Vec<f64>], y: &[f64]) -> Gradients {
+        let last = outputs.last().unwrap();
+        assert_eq!(y.len(), last.len(), "wrong number of targets");
+        // delta[j] is the derivative of the error by the pre-activation of
+        // neuron j of the current layer
+        let mut delta: Vec<f64> = last
+            .iter()
+            .zip(y)
+            .map(|(a, t)| (a - t) * Activation::Sigmoid.derivative(*a))
+            .collect();
+        let mut weights = vec![vec![]; self.weights.len()];
+        let mut biases = vec![vec![]; self.biases.len()];
+
+        for layer in (0..self.weights.len()).rev() {
+            let input = &outputs[layer];
+            weights[layer] = delta
+                .iter()
+                .map(|d| input.iter().map(|a| d * a).collect())
+                .collect();
+            biases[layer] = delta.clone();
+            if layer > 0 {
+                let activation = self.activation(layer - 1);
+                delta = (0..input.len())
+                    .map(|i| {
+                        let back: f64 = self.weights[layer]
+                            .iter()
+                            .zip(&delta)
+                            .map(|(row, d)| row[i] * d)
+                            .sum();
+                        back * activation.derivative(input[i])
+                    })
+                    .collect();
+            }
+        }
+        Gradients { weights, biases }
+    }
+
+    /// Returns the outputs of the network for the inputs `x`
+    pub fn predict(&self, x: &[f64]) -> Vec<f64> {
+        self.forward(x).pop().unwrap()
+    }
+
+    /// Trains the network on the samples `xs` with targets `ys` (in [0, 1],
+    /// as the outputs), for `epochs` passes of stochastic gradient descent of
+    /// learning rate `lr`. Returns the mean squared error after training.
+    pub fn fit(&mut self, xs: &[Vec<f64>], ys: &[Vec<f64>], lr: f64, epochs: usize) -> f64 {
+        assert_eq!(xs.len(), ys.len(), "there must be one target per sample");
+        for _ in 0..epochs {
+            for (x, y) in xs.iter().zip(ys) {
+                let outputs = self.forward(x);
+                let gradients = self.backward(&outputs, y);
+                for (layer, gradient) in self.weights.iter_mut().zip(&gradients.weights) {
+                    for (row, gradient_row) in layer.iter_mut().zip(gradient) {
+                        for (w, g) in row.iter_mut().zip(gradient_row) {
+                            *w -= lr * g;
+                        }
+                    }
+                }
+                for (layer, gradient) in self.biases.iter_mut().zip(&gradients.biases) {
+                    for (b, g) in layer.iter_mut().zip(gradient) {
+                        *b -= lr * g;
+                    }
+                }
+            }
+        }
+        self.mean_squared_error(xs, ys)
+    }
+
+    fn mean_squared_error(&self, xs: &[Vec<f64>], ys: &[Vec<f64>]) -> f64 {
+        let total: f64 = xs
+            .iter()
+            .zip(ys)
+            .map(|(x, y)| {
+                self.predict(x)
+                    .iter()
+                    .zip(y)
+                    .map(|(p, t)| (p - t).powi(2))
+                    .sum::<f64>()
+            })
+            .sum();
+        total / xs.len() as f64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn xor_data() -> (Vec<Vec<f64>>, Vec<Vec<f64>>) {
+        let xs = vec![
+            vec![0.0, 0.0],
+            vec![0.0, 1.0],
+            vec![1.0, 0.0],
+            vec![1.0, 1.0],
+        ];
+        let ys = vec![vec![0.0], vec![1.0], vec![1.0], vec![0.0]];
+        (xs, ys)
+    }
+
+    #[test]
+    fn learns_xor() {
+        let (xs, ys) = xor_data();
+        let mut net = NeuralNet::new(&[2, 4, 1]);
+        let error = net.fit(&xs, &ys, 0.5, 10_000);
+        assert!(error < 1e-2, "error {error}");
+        for (x, y) in xs.iter().zip(&ys) {
+            assert!((net.predict(x)[0] - y[0]).abs() < 0.2);
+        }
+    }
+
+    #[test]
+    fn learns_xor_with_relu() {
+        let (xs, ys) = xor_data();
+        let mut net = NeuralNet::with_options(&[2, 8, 1], Activation::Relu, 7);
+        let error = net.fit(&xs, &ys, 0.1, 5_000);
+        assert!(error < 1e-2, "error {error}");
+    }
+
+    #[test]
+    fn shapes_are_consistent() {
+        let net = NeuralNet::with_options(&[3, 5, 4, 2], Activation::Relu, 1);
+        assert_eq!(net.layers(), vec![3, 5, 4, 2]);
+        let outputs = net.forward(&[0.1, -0.2, 0.3]);
+        let sizes: Vec<usize> = outputs.iter().map(Vec::len).collect();
+        assert_eq!(sizes, vec![3, 5, 4, 2]);
+
+        let gradients = net.backward(&outputs, &[1.0, 0.0]);
+        for (gradient, weights) in gradients.weights.iter().zip(&net.weights) {
+            assert_eq!(gradient.len(), weights.len());
+            for (g, w) in gradient.iter().zip(weights) {
+                assert_eq!(g.len(), w.len());
+            }
+        }
+        let bias_sizes: Vec<usize> = gradients.biases.iter().map(Vec::len).collect();
+        assert_eq!(bias_sizes, vec![5, 4, 2]);
+        assert_eq!(net.predict(&[0.1, -0.2, 0.3]).len(), 2);
+    }
+
+    #[test]
+    fn backpropagation_matches_numerical_gradients() {
+        let x = [0.5, -1.0, 2.0];
+        let y = [0.2, 0.9];
+        let half_squared_error = |net: &NeuralNet| {
+            net.predict(&x)
+                .iter()
+                .zip(&y)
+                .map(|(p, t)| (p - t).powi(2) / 2.0)
+                .sum::<f64>()
+        };
+        for activation in [Activation::Sigmoid, Activation::Relu] {
+            let mut net = NeuralNet::with_options(&[3, 4, 2], activation, 9);
+            let gradients = net.backward(&net.forward(&x), &y);
+            let h = 1e-6;
+            for layer in 0..net.weights.len() {
+                for j in 0..net.weights[layer].len() {
+                    for i in 0..net.weights[layer][j].len() {
+                        let original = net.weights[layer][j][i];
+                        net.weights[layer][j][i] = original + h;
+                        let plus = half_squared_error(&net);
+                        net.weights[layer][j][i] = original - h;
+                        let minus = half_squared_error(&net);
+                        net.weights[layer][j][i] = original;
+                        let numerical = (plus - minus) / (2.0 * h);
+                        assert!((numerical - gradients.weights[layer][j][i]).abs() < 1e-6);
+                    }
+                    let original = net.biases[layer][j];
+                    net.biases[layer][j] = original + h;
+                    let plus = half_squared_error(&net);
+                    net.biases[layer][j] = original - h;
+                    let minus = half_squared_error(&net);
+                    net.biases[layer][j] = original;
+                    let numerical = (plus - minus) / (2.0 * h);
+                    assert!((numerical - gradients.biases[layer][j]).abs() < 1e-6);
+                }
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn wrong_number_of_inputs() {
+        NeuralNet::new(&[2, 3, 1]).predict(&[1.0]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn empty_layer() {
+        NeuralNet::new(&[2, 0, 1]);
+    }
+}
@@ -0,0 +1,203 @@
+//! # OPTICS
+//!
+//! Ordering points to identify the clustering structure generalizes DBSCAN
+//! to clusters of different densities. Instead of labelling the points for a
+//! single radius, it orders them so that the points of a cluster follow each
+//! other, and gives each one its reachability distance: how far it lies from
+//! the dense region of the points before it.
+//!
+//! The core distance of a point is the distance to its `min_pts`-th nearest
+//! neighbor (itself included), if it is at most `eps`. The reachability of `q`
+//! from a core point `p` is `max(core(p), d(p, q))`. The ordering always moves
+//! on to the point of smallest reachability from the points already ordered,
+//! so that a cluster shows as a valley in the plot of the reachabilities
+//! along the ordering, deeper for denser clusters. `extract_dbscan_clustering`
+//! cuts that plot at a given height, which clusters the points as DBSCAN
+//! would with that radius, without running it again.
+//!
+//! ## Resources:
+//!   - [https://en.wikipedia.org/wiki/OPTICS_algorithm]
+
+use crate::data_structures::KdTree;
+use crate::math::Metric;
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+/// Orders the points `xs` with OPTICS, and returns each of them in order with
+/// its reachability distance, infinite for the first point of each group of
+/// points within `eps` of each other.
+pub fn optics(xs: &[Vec<f64>], eps: f64, min_pts: usize) -> Vec<(usize, f64)> {
+    let n = xs.len();
+    let tree = KdTree::new(xs);
+    let mut reachability = vec![f64::INFINITY; n];
+    let mut processed = vec![false; n];
+    let mut ordering = Vec::with_capacity(n);
+
+    for start in 0..n {
+        if processed[start] {
+            continue;
+        }
+        // the seeds by increasing reachability, then index; the bits of
+        // non-negative floats are ordered as the floats. Outdated entries
+        // are skipped when popped.
+        let mut seeds = BinaryHeap::new();
+        seeds.push(Reverse((f64::INFINITY.to_bits(), start)));
+        while let Some(Reverse((_, point))) = seeds.pop() {
+            if processed[point] {
+                continue;
+            }
+            processed[point] = true;
+            ordering.push((point, reachability[point]));
+
+            let neighbors = tree.within_radius(&xs[point], eps);
+            if neighbors.len() < min_pts {
+                continue;
+            }
+            let distances: Vec<f64> = neighbors
+                .iter()
+                .map(|&other| Metric::Euclidean.distance(&xs[point], &xs[other]))
+                .collect();
+            let core_distance = *distances
+                .clone()
+                .select_nth_unstable_by(min_pts.max(1) - 1, f64::total_cmp)
+                .1;
+            for (&other, &d) in neighbors.iter().zip(&distances) {
+                let reach = core_distance.max(d);
+                if !processed[other] && reach < reachability[other] {
+                    reachability[other] = reach;
+                    seeds.push(Reverse((reach.to_bits(), other)));
+                }
+            }
+        }
+    }
+    ordering
+}
+
+/// Clusters the points from their OPTICS `ordering` as DBSCAN with the radius
+/// `eps_prime`, at most the `eps` of the ordering. Returns the cluster of each
+/// point, numbered from 0 along the ordering, or None for noise.
+///
+/// A point farther than `eps_prime` from the points before it starts a new
+/// cluster if the next point is within reach, and is noise otherwise. Since
+/// the core distances are not kept, this tells core points from noise by
+/// their successor, so that a few border points may differ from DBSCAN.
+pub fn extract_dbscan_clustering(ordering: &[(usize, f64)], eps_prime: f64) -> Vec<Option<usize>> {
+    let mut labels = vec![None; ordering.len()];
+    let mut cluster = None;
+    let mut clusters = 0;
+    for (position, &(point, reach)) in ordering.iter().enumerate() {
+        if reach > eps_prime {
+            let next_in_reach = ordering
+                .get(position + 1)
+                .is_some_and(|&(_, next)| next <= eps_prime);
+            cluster = next_in_reach.then(|| {
+                clusters += 1;
+                clusters - 1
+            });
+        }
+        labels[point] = cluster;
+    }
+    labels
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::general::make_blobs;
+    use crate::machine_learning::{adjusted_rand_index, dbscan};
+
+    /// Two dense blobs close to each other, and a sparse one far away
+    fn varying_densities() -> (Vec<Vec<f64>>, Vec<usize>) {
+        let (mut xs, mut truth) = make_blobs(100, &[vec![0.0, 0.0], vec![2.0, 0.0]], 0.15, 257);
+        let (sparse, _) = make_blobs(50, &[vec![20.0, 20.0]], 2.5, 2570);
+        xs.extend(sparse);
+        truth.extend([2; 50]);
+        (xs, truth)
+    }
+
+    #[test]
+    fn ordering_is_a_permutation() {
+        let (xs, _) = varying_densities();
+        let ordering = optics(&xs, 100.0, 5);
+        let mut points: Vec<usize> = ordering.iter().map(|&(p, _)| p).collect();
+        points.sort_unstable();
+        assert_eq!(points, (0..xs.len()).collect::<Vec<_>>());
+        // a single group: only the first point is unreachable
+        assert!(ordering[0].1.is_infinite());
+        assert!(ordering[1..].iter().all(|&(_, r)| r.is_finite()));
+    }
+
+    #[test]
+    fn reachabilities_of_a_line() {
+        // the core distance with 2 points is the distance to the nearest one
+        let xs = vec![vec![0.0], vec![1.0], vec![3.0], vec![10.0]];
+        let ordering = optics(&xs, 5.0, 2);
+        assert_eq!(
+            ordering,
+            vec![(0, f64::INFINITY), (1, 1.0), (2, 2.0), (3, f64::INFINITY)]
+        );
+        assert_eq!(optics(&[], 1.0, 2), vec![]);
+    }
+
+    #[test]
+    fn separates_clusters_of_varying_densities() {
+        let (xs, truth) = varying_densities();
+        let ordering = optics(&xs, 100.0, 5);
+
+        // each blob is visited in one go, as a valley of the reachabilities
+        // below the jump into it
+        let mut blocks: Vec<(usize, Vec<f64>)> = vec![];
+        for &(point, reach) in &ordering {
+            match blocks.last_mut() {
+                Some((label, reaches)) if *label == truth[point] => reaches.push(reach),
+                _ => blocks.push((truth[point], vec![reach])),
+            }
+        }
+        assert_eq!(blocks.len(), 3);
+        for (_, reaches) in &blocks {
+            let deepest = reaches[1..].iter().copied().fold(0.0, f64::max);
+            assert!(deepest < reaches[0], "{reaches:?}");
+        }
+
+        // while no single radius separates the three blobs: those splitting
+        // the dense blobs leave the sparse one as noise, at least in part
+        for eps in [0.1, 0.2, 0.3, 0.5, 0.75, 1.0, 1.5, 2.0, 3.0, 5.0] {
+            // each noise point on its own
+            let labels: Vec<usize> = dbscan(&xs, eps, 5)
+                .iter()
+                .enumerate()
+                .map(|(i, l)| l.unwrap_or(xs.len() + i))
+                .collect();
+            assert!(adjusted_rand_index(&truth, &labels) < 0.99, "{eps}");
+        }
+    }
+
+    #[test]
+    fn extraction_matches_dbscan() {
+        let (xs, _) = varying_densities();
+        let ordering = optics(&xs, 100.0, 5);
+        // the first radius splits the dense blobs and leaves out the sparse
+        // one, the second one merges the dense blobs and finds the sparse one
+        for eps_prime in [0.5, 5.0] {
+            let extracted = extract_dbscan_clustering(&ordering, eps_prime);
+            let expected = dbscan(&xs, eps_prime, 5);
+            let clusters =
+                |labels: &[Option<usize>]| labels.iter().flatten().max().map_or(0, |&c| c + 1);
+            assert_eq!(clusters(&extracted), 2);
+            assert_eq!(clusters(&expected), 2);
+            // the same clusters, but for a few border points
+            let both: Vec<usize> = (0..xs.len())
+                .filter(|&i| extracted[i].is_some() && expected[i].is_some())
+                .collect();
+            assert!(both.len() + 5 >= expected.iter().flatten().count());
+            let a: Vec<usize> = both.iter().map(|&i| extracted[i].unwrap()).collect();
+            let b: Vec<usize> = both.iter().map(|&i| expected[i].unwrap()).collect();
+            assert_eq!(adjusted_rand_index(&a, &b), 1.0);
+        }
+        let small = extract_dbscan_clustering(&ordering, 0.5);
+        assert!(small[..100].iter().all(Option::is_some));
+        assert!(small[100..].iter().filter(|l| l.is_some()).count() < 10);
+        let large = extract_dbscan_clustering(&ordering, 5.0);
+        assert!(large.iter().all(Option::is_some));
+    }
+}
@@ -0,0 +1,416 @@
+//! # Minimization of differentiable functions
+//!
+//! Iterative optimizers which minimize a function `f` of several variables
+//! given its gradient `grad`, starting from `x0`. They stop once the norm of
+//! the gradient is below `tol` or after `max_iter` iterations, and return the
+//! point with the lowest value of `f` among those visited.
+//!
+//! Unlike `machine_learning::gradient_descent` and `machine_learning::Adam`,
+//! which only run updates, these functions look after the whole minimization.
+
+use super::Adam;
+use crate::machine_learning::EarlyStopper;
+
+fn norm(v: &[f64]) -> f64 {
+    v.iter().map(|x| x * x).sum::<f64>().sqrt()
+}
+
+/// Minimizes `f` by plain gradient descent: x_{k+1} = x_k - lr * grad(x_k).
+pub fn minimize_gradient_descent(
+    f: impl Fn(&[f64]) -> f64,
+    grad: impl Fn(&[f64]) -> Vec<f64>,
+    x0: &[f64],
+    lr: f64,
+    max_iter: usize,
+    tol: f64,
+) -> Vec<f64> {
+    minimize_momentum(f, grad, x0, lr, 0.0, max_iter, tol)
+}
+
+/// Minimizes `f` by plain gradient descent as `minimize_gradient_descent`, also
+/// stopping once `stopper`, updated with the value of `f` after each step,
+/// tells that the descent no longer makes progress.
+pub fn minimize_gradient_descent_early_stopping(
+    f: impl Fn(&[f64]) -> f64,
+    grad: impl Fn(&[f64]) -> Vec<f64>,
+    x0: &[f64],
+    lr: f64,
+    max_iter: usize,
+    stopper: &mut EarlyStopper,
+) -> Vec<f64> {
+    let steps = Steps {
+        lr,
+        momentum: 0.0,
+        max_iter,
+        tol: 0.0,
+    };
+    descend(f, grad, x0, steps, Some(stopper))
+}
+
+/// Minimizes `f` by gradient descent with (heavy ball) momentum:
+///
+/// v_{k+1} = momentum * v_k - lr * grad(x_k)
+/// x_{k+1} = x_k + v_{k+1}
+///
+/// The velocity `v` accumulates the past gradients, which speeds up the descent
+/// along long shallow valleys and damps the oscillations across them. A
+/// `momentum` of 0 gives the plain gradient descent; it is usually around 0.9.
+pub fn minimize_momentum(
+    f: impl Fn(&[f64]) -> f64,
+    grad: impl Fn(&[f64]) -> Vec<f64>,
+    x0: &[f64],
+    lr: f64,
+    momentum: f64,
+    max_iter: usize,
+    tol: f64,
+) -> Vec<f64> {
+    let steps = Steps {
+        lr,
+        momentum,
+        max_iter,
+        tol,
+    };
+    descend(f, grad, x0, steps, None)
+}
+
+/// The settings of a gradient descent with momentum
+struct Steps {
+    lr: f64,
+    momentum: f64,
+    max_iter: usize,
+    tol: f64,
+}
+
+fn descend(
+    f: impl Fn(&[f64]) -> f64,
+    grad: impl Fn(&[f64]) -> Vec<f64>,
+    x0: &[f64],
+    steps: Steps,
+    mut stopper: Option<&mut EarlyStopper>,
+) -> Vec<f64> {
+    let Steps {
+        lr,
+        momentum,
+        max_iter,
+        tol,
+    } = steps;
+    let mut x = x0.to_vec();
+    let mut velocity = vec![0.0; x.len()];
+    let mut best = (f(&x), x.clone());
+
+    for _ in 0..max_iter {
+        let gradient = grad(&x);
+        if norm(&gradient) < tol {
+            break;
+        }
+        for ((x_k, v_k), g_k) in x.iter_mut().zip(&mut velocity).zip(&gradient) {
+            *v_k = momentum * *v_k - lr * g_k;
+            *x_k += *v_k;
+        }
+        let value = f(&x);
+        if value < best.0 {
+            best = (value, x.clone());
+        }
+        if stopper
+            .as_mut()
+            .is_some_and(|stopper| stopper.update(value))
+        {
+            break;
+        }
+    }
+    best.1
+}
+
+/// The hyperparameters of `minimize_adam_with_params`
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct AdamParams {
+    /// Exponential decay rate of the first moment (mean) estimates
+    pub beta1: f64,
+    /// Exponential decay rate of the second moment (uncentered variance) estimates
+    pub beta2: f64,
+    /// Added to the square root of the second moment, to prevent divisions by zero
+    pub epsilon: f64,
+}
+
+impl Default for AdamParams {
+    /// The values recommended in the Adam paper
+    fn default() -> Self {
+        AdamParams {
+            beta1: 0.9,
+            beta2: 0.999,
+            epsilon: 1e-8,
+        }
+    }
+}
+
+/// Minimizes `f` with the Adam optimizer and the default `AdamParams`.
+///
+/// Adam divides each step by a running estimate of the magnitude of the
+/// gradient along each coordinate, so that all the coordinates move at a
+/// similar pace `lr`, which makes it much faster than plain gradient descent
+/// on ill-conditioned problems. See `machine_learning::Adam` for the update.
+pub fn minimize_adam(
+    f: impl Fn(&[f64]) -> f64,
+    grad: impl Fn(&[f64]) -> Vec<f64>,
+    x0: &[f64],
+    lr: f64,
+    max_iter: usize,
+    tol: f64,
+) -> Vec<f64> {
+    minimize_adam_with_params(f, grad, x0, lr, AdamParams::default(), max_iter, tol)
+}
+
+/// Minimizes `f` with the Adam optimizer and the given hyperparameters.
+pub fn minimize_adam_with_params(
+    f: impl Fn(&[f64]) -> f64,
+    grad: impl Fn(&[f64]) -> Vec<f64>,
+    x0: &[f64],
+    lr: f64,
+    params: AdamParams,
+    max_iter: usize,
+    tol: f64,
+) -> Vec<f64> {
+    let mut optimizer = Adam::new(
+        Some(lr),
+        Some((params.beta1, params.beta2)),
+        Some(params.epsilon),
+        x0.len(),
+    );
+    let mut x = x0.to_vec();
+    let mut best = (f(&x), x.clone());
+
+    for _ in 0..max_iter {
+        let gradient = grad(&x);
+        if norm(&gradient) < tol {
+            break;
+        }
+        // the optimizer returns the update of the parameters
+        for (x_k, step) in x.iter_mut().zip(optimizer.step(&gradient)) {
+            *x_k += step;
+        }
+        let value = f(&x);
+        if value < best.0 {
+            best = (value, x.clone());
+        }
+    }
+    best.1
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn quadratic_bowl(x: &[f64]) -> f64 {
+        (x[0] - 3.0).powi(2) + 2.0 * (x[1] + 1.0).powi(2)
+    }
+
+    fn quadratic_bowl_gradient(x: &[f64]) -> Vec<f64> {
+        vec![2.0 * (x[0] - 3.0), 4.0 * (x[1] + 1.0)]
+    }
+
+    fn rosenbrock(x: &[f64]) -> f64 {
+        (1.0 - x[0]).powi(2) + 100.0 * (x[1] - x[0] * x[0]).powi(2)
+    }
+
+    fn rosenbrock_gradient(x: &[f64]) -> Vec<f64> {
+        vec![
+            -2.0 * (1.0 - x[0]) - 400.0 * x[0] * (x[1] - x[0] * x[0]),
+            200.0 * (x[1] - x[0] * x[0]),
+        ]
+    }
+
+    fn distance(a: &[f64], b: &[f64]) -> f64 {
+        a.iter()
+            .zip(b)
+            .map(|(x, y)| (x - y).powi(2))
+            .sum::<f64>()
+            .sqrt()
+    }
+
+    #[test]
+    fn quadratic_bowl_minimum() {
+        let x = minimize_gradient_descent(
+            quadratic_bowl,
+            quadratic_bowl_gradient,
+            &[-4.0, 5.0],
+            0.1,
+            1000,
+            1e-10,
+        );
+        assert!(distance(&x, &[3.0, -1.0]) < 1e-9);
+    }
+
+    #[test]
+    fn stops_on_small_gradient() {
+        // the gradient is small enough from the start
+        let x = minimize_gradient_descent(
+            quadratic_bowl,
+            quadratic_bowl_gradient,
+            &[3.0, -1.0 + 1e-9],
+            0.1,
+            1000,
+            1e-6,
+        );
+        assert_eq!(x, vec![3.0, -1.0 + 1e-9]);
+        // no iteration at all
+        assert_eq!(
+            minimize_gradient_descent(
+                quadratic_bowl,
+                quadratic_bowl_gradient,
+                &[0.0, 0.0],
+                0.1,
+                0,
+                0.0
+            ),
+            vec![0.0, 0.0]
+        );
+    }
+
+    #[test]
+    fn early_stopping_ends_the_descent() {
+        let steps = std::cell::Cell::new(0);
+        let counted = |x: &[f64]| {
+            steps.set(steps.get() + 1);
+            quadratic_bowl(x)
+        };
+        let mut stopper = EarlyStopper::new(5, 1e-8);
+        let x = minimize_gradient_descent_early_stopping(
+            counted,
+            quadratic_bowl_gradient,
+            &[-4.0, 5.0],
+            0.1,
+            100_000,
+            &mut stopper,
+        );
+        // near the minimum, long before the iterations run out
+        assert!(distance(&x, &[3.0, -1.0]) < 1e-3);
+        assert!(steps.get() < 200, "{}", steps.get());
+        assert!(stopper.best().unwrap() < 1e-6);
+        // the same as the plain descent, for as many steps
+        let plain = minimize_gradient_descent(
+            quadratic_bowl,
+            quadratic_bowl_gradient,
+            &[-4.0, 5.0],
+            0.1,
+            steps.get() - 1,
+            0.0,
+        );
+        assert_eq!(x, plain);
+    }
+
+    #[test]
+    fn rosenbrock_minimum() {
+        let x = minimize_gradient_descent(
+            rosenbrock,
+            rosenbrock_gradient,
+            &[-1.2, 1.0],
+            1e-3,
+            50_000,
+            1e-8,
+        );
+        assert!(distance(&x, &[1.0, 1.0]) < 1e-2, "{x:?}");
+    }
+
+    #[test]
+    fn momentum_is_faster_on_rosenbrock() {
+        let iterations = 5_000;
+        let plain = minimize_gradient_descent(
+            rosenbrock,
+            rosenbrock_gradient,
+            &[-1.2, 1.0],
+            1e-3,
+            iterations,
+            1e-8,
+        );
+        let with_momentum = minimize_momentum(
+            rosenbrock,
+            rosenbrock_gradient,
+            &[-1.2, 1.0],
+            1e-3,
+            0.9,
+            iterations,
+            1e-8,
+        );
+        assert!(
+            distance(&with_momentum, &[1.0, 1.0]) < 1e-3,
+            "{with_momentum:?}"
+        );
+        assert!(distance(&with_momentum, &[1.0, 1.0]) < distance(&plain, &[1.0, 1.0]));
+    }
+
+    #[test]
+    fn diverging_steps_keep_the_best_point() {
+        // a learning rate above 1 overshoots more and more on x^2
+        let x =
+            minimize_gradient_descent(|x| x[0] * x[0], |x| vec![2.0 * x[0]], &[1.0], 1.5, 20, 0.0);
+        assert_eq!(x, vec![1.0]);
+    }
+
+    #[test]
+    fn adam_rosenbrock_minimum() {
+        let x = minimize_adam(
+            rosenbrock,
+            rosenbrock_gradient,
+            &[-1.2, 1.0],
+            0.02,
+            20_000,
+            1e-8,
+        );
+        assert!(distance(&x, &[1.0, 1.0]) < 1e-3, "{x:?}");
+    }
+
+    /// Counts the iterations needed to reach the minimum of a quadratic whose
+    /// curvatures are a thousand times apart
+    fn iterations_on_poorly_scaled_quadratic(
+        minimize: impl Fn(&dyn Fn(&[f64]) -> Vec<f64>) -> Vec<f64>,
+    ) -> usize {
+        let calls = std::cell::Cell::new(0);
+        let gradient = |x: &[f64]| {
+            calls.set(calls.get() + 1);
+            vec![2.0 * x[0], 2000.0 * x[1]]
+        };
+        let x = minimize(&gradient);
+        assert!(distance(&x, &[0.0, 0.0]) < 1e-3, "{x:?}");
+        calls.get()
+    }
+
+    #[test]
+    fn adam_is_faster_on_poorly_scaled_quadratic() {
+        let f = |x: &[f64]| x[0] * x[0] + 1000.0 * x[1] * x[1];
+        let start = [5.0, 5.0];
+        let with_adam = iterations_on_poorly_scaled_quadratic(|grad| {
+            minimize_adam(f, grad, &start, 0.1, 100_000, 1e-3)
+        });
+        // beyond 1e-3, that is 2 / 2000, gradient descent diverges along y
+        let with_gradient_descent = iterations_on_poorly_scaled_quadratic(|grad| {
+            minimize_gradient_descent(f, grad, &start, 9e-4, 100_000, 1e-3)
+        });
+        assert!(
+            with_adam * 5 < with_gradient_descent,
+            "{with_adam} vs {with_gradient_descent}"
+        );
+    }
+
+    #[test]
+    fn adam_params_defaults() {
+        let params = AdamParams::default();
+        assert_eq!(
+            (params.beta1, params.beta2, params.epsilon),
+            (0.9, 0.999, 1e-8)
+        );
+        let custom = AdamParams {
+            beta1: 0.8,
+            ..AdamParams::default()
+        };
+        let x = minimize_adam_with_params(
+            quadratic_bowl,
+            quadratic_bowl_gradient,
+            &[-4.0, 5.0],
+            0.05,
+            custom,
+            20_000,
+            1e-6,
+        );
+        assert!(distance(&x, &[3.0, -1.0]) < 1e-3, "{x:?}");
+    }
+}
@@ -1,5 +1,10 @@
 mod adam;
 mod gradient_descent;
+mod minimize;
 
 pub use self::adam::Adam;
 pub use self::gradient_descent::gradient_descent;
+pub use self::minimize::{
+    minimize_adam, minimize_adam_with_params, minimize_gradient_descent,
+    minimize_gradient_descent_early_stopping, minimize_momentum, AdamParams,
+};
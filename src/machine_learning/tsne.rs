@@ -0,0 +1,275 @@
+//! # t-SNE
+//!
+//! t-distributed stochastic neighbor embedding maps high-dimensional points
+//! to the plane for visualization, keeping the neighbors of each point close.
+//!
+//! The similarity of `x_j` to `x_i` is a gaussian `p_j|i ∝ exp(-β_i |x_i - x_j|^2)`,
+//! whose precision `β_i` is tuned so that the perplexity `e^H(p_.|i)` (a
+//! smooth count of neighbors) is the given one, and `p_ij = (p_j|i + p_i|j) / 2n`.
+//! In the plane, the similarities follow a heavier tailed Student
+//! t-distribution, `q_ij ∝ 1 / (1 + |y_i - y_j|^2)`. The embedding `y` minimizes
+//! the Kullback-Leibler divergence of `q` from `p` by gradient descent:
+//!
+//! `∂C/∂y_i = 4 Σ_j (p_ij - q_ij) (y_i - y_j) / (1 + |y_i - y_j|^2)`
+//!
+//! This is the exact algorithm, computing all the pairs at each iteration in
+//! O(n^2), which is fine for small datasets (Barnes-Hut approximations scale
+//! to larger ones). It follows the optimization of the original paper: the
+//! `p_ij` are exaggerated during the first iterations to form well separated
+//! clusters, with momentum and adaptive gains on the steps.
+//!
+//! ## Resources:
+//!   - [https://en.wikipedia.org/wiki/T-distributed_stochastic_neighbor_embedding]
+//!   - [https://lvdmaaten.github.io/tsne/]
+
+use crate::general::Xorshift64;
+
+const LEARNING_RATE: f64 = 200.0;
+const EXAGGERATION: f64 = 12.0;
+
+fn squared_distances(xs: &[impl AsRef<[f64]>]) -> Vec<Vec<f64>> {
+    xs.iter()
+        .map(|a| {
+            xs.iter()
+                .map(|b| {
+                    a.as_ref()
+                        .iter()
+                        .zip(b.as_ref())
+                        .map(|(u, v)| (u - v).powi(2))
+                        .sum()
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// Returns the conditional probabilities p_j|i of the row `distances` of
+/// point `i`, with the precision matching the perplexity
+fn conditional_probabilities(distances: &[f64], i: usize, perplexity: f64) -> Vec<f64> {
+    let target_entropy = perplexity.ln();
+    let (mut beta, mut lo, mut hi) = (1.0, 0.0, f64::INFINITY);
+    let mut probs = vec![0.0; distances.len()];
+    // bisection on the precision, the entropy decreasing as it grows
+    for _ in 0..100 {
+        let min = distances
+            .iter()
+            .enumerate()
+            .filter(|&(j, _)| j != i)
+            .map(|(_, &d)| d)
+            .fold(f64::INFINITY, f64::min);
+        for (j, p) in probs.iter_mut().enumerate() {
+            // shifted by the nearest distance, so that not all vanish
+            *p = if j == i {
+                0.0
+            } else {
+                (-beta * (distances[j] - min)).exp()
+            };
+        }
+        let sum: f64 = probs.iter().sum();
+        // H = ln(sum) + β Σ p_j d_j (with the shifted distances)
+        let entropy = sum.ln()
+            + beta
+                * probs
+                    .iter()
+                    .zip(distances)
+                    .map(|(p, d)| p * (d - min))
+                    .sum::<f64>()
+                / sum;
+        probs.iter_mut().for_each(|p| *p /= sum);
+        if (entropy - target_entropy).abs() < 1e-5 {
+            break;
+        }
+        if entropy > target_entropy {
+            lo = beta;
+            beta = if hi.is_infinite() {
+                beta * 2.0
+            } else {
+                (beta + hi) / 2.0
+            };
+        } else {
+            hi = beta;
+            beta = (beta + lo) / 2.0;
+        }
+    }
+    probs
+}
+
+/// Embeds the points `xs` in the plane with the exact t-SNE, running `iters`
+/// iterations of gradient descent from a random start drawn with `seed`.
+///
+/// The `perplexity` is roughly the number of neighbors each point should keep
+/// close; it is usually between 5 and 50, and must be below the number of
+/// points.
+pub fn tsne(xs: &[Vec<f64>], perplexity: f64, iters: usize, seed: u64) -> Vec<[f64; 2]> {
+    let n = xs.len();
+    if n <= 1 {
+        return vec![[0.0, 0.0]; n];
+    }
+    assert!(
+        perplexity > 0.0 && perplexity < n as f64,
+        "the perplexity must be positive and below the number of points"
+    );
+
+    let distances = squared_distances(xs);
+    let conditional: Vec<Vec<f64>> = (0..n)
+        .map(|i| conditional_probabilities(&distances[i], i, perplexity))
+        .collect();
+    let p: Vec<Vec<f64>> = (0..n)
+        .map(|i| {
+            (0..n)
+                .map(|j| ((conditional[i][j] + conditional[j][i]) / (2.0 * n as f64)).max(1e-12))
+                .collect()
+        })
+        .collect();
+
+    let mut rng = Xorshift64::new(seed);
+    let mut y: Vec<[f64; 2]> = (0..n)
+        .map(|_| [rng.next_gaussian(0.0, 1e-4), rng.next_gaussian(0.0, 1e-4)])
+        .collect();
+    let mut velocity = vec![[0.0; 2]; n];
+    let mut gains = vec![[1.0f64; 2]; n];
+    let exaggerated_iters = (iters / 4).min(250);
+
+    for iter in 0..iters {
+        let exaggeration = if iter < exaggerated_iters {
+            EXAGGERATION
+        } else {
+            1.0
+        };
+        let momentum = if iter < exaggerated_iters { 0.5 } else { 0.8 };
+
+        // the unnormalized q_ij, that is the Student kernel
+        let kernel: Vec<Vec<f64>> = squared_distances(&y)
+            .into_iter()
+            .map(|row| row.into_iter().map(|d| 1.0 / (1.0 + d)).collect())
+            .collect();
+        let total: f64 = (0..n)
+            .flat_map(|i| (0..n).filter(move |&j| j != i).map(move |j| (i, j)))
+            .map(|(i, j)| kernel[i][j])
+            .sum();
+
+        for i in 0..n {
+            let mut gradient = [0.0; 2];
+            for j in (0..n).filter(|&j| j != i) {
+                let q = (kernel[i][j] / total).max(1e-12);
+                let force = 4.0 * (exaggeration * p[i][j] - q) * kernel[i][j];
+                for (g, axis) in gradient.iter_mut().zip(0..2) {
+                    *g += force * (y[i][axis] - y[j][axis]);
+                }
+            }
+            for ((gain, step), g) in gains[i].iter_mut().zip(&mut velocity[i]).zip(gradient) {
+                // the gain grows while the steps keep going down the gradient
+                *gain = if (g > 0.0) != (*step > 0.0) {
+                    *gain + 0.2
+                } else {
+                    (*gain * 0.8).max(0.01)
+                };
+                *step = momentum * *step - LEARNING_RATE * *gain * g;
+            }
+        }
+        for (point, step) in y.iter_mut().zip(&velocity) {
+            point[0] += step[0];
+            point[1] += step[1];
+        }
+        // the cost is invariant to translations, keep the embedding centered
+        let mean = y
+            .iter()
+            .fold([0.0; 2], |acc, p| [acc[0] + p[0], acc[1] + p[1]]);
+        for point in y.iter_mut() {
+            point[0] -= mean[0] / n as f64;
+            point[1] -= mean[1] / n as f64;
+        }
+    }
+    y
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::general::make_blobs;
+
+    fn distance(a: &[f64; 2], b: &[f64; 2]) -> f64 {
+        (a[0] - b[0]).hypot(a[1] - b[1])
+    }
+
+    /// Returns the mean distance between the points of the same blob, and the
+    /// mean distance between the points of different blobs
+    fn mean_distances(embedding: &[[f64; 2]], labels: &[usize]) -> (f64, f64) {
+        let (mut within, mut across) = ((0.0, 0), (0.0, 0));
+        for i in 0..embedding.len() {
+            for j in i + 1..embedding.len() {
+                let d = distance(&embedding[i], &embedding[j]);
+                let sum = if labels[i] == labels[j] {
+                    &mut within
+                } else {
+                    &mut across
+                };
+                sum.0 += d;
+                sum.1 += 1;
+            }
+        }
+        (within.0 / within.1 as f64, across.0 / across.1 as f64)
+    }
+
+    #[test]
+    fn separates_blobs() {
+        let centers = vec![vec![0.0; 10], vec![10.0; 10], {
+            let mut c = vec![0.0; 10];
+            c[0] = 20.0;
+            c
+        }];
+        let (xs, labels) = make_blobs(60, &centers, 1.0, 255);
+        let embedding = tsne(&xs, 10.0, 1000, 1);
+        assert_eq!(embedding.len(), 60);
+        assert!(embedding.iter().flatten().all(|v| v.is_finite()));
+        let (within, across) = mean_distances(&embedding, &labels);
+        assert!(within * 3.0 < across, "{within} vs {across}");
+
+        // and every point is nearer to the points of its blob on average
+        for i in 0..xs.len() {
+            let mean_to = |same: bool| {
+                let others: Vec<f64> = (0..xs.len())
+                    .filter(|&j| j != i && (labels[j] == labels[i]) == same)
+                    .map(|j| distance(&embedding[i], &embedding[j]))
+                    .collect();
+                others.iter().sum::<f64>() / others.len() as f64
+            };
+            assert!(mean_to(true) < mean_to(false));
+        }
+    }
+
+    #[test]
+    fn reproducible() {
+        let (xs, _) = make_blobs(20, &[vec![0.0; 4], vec![5.0; 4]], 1.0, 1);
+        assert_eq!(tsne(&xs, 5.0, 50, 7), tsne(&xs, 5.0, 50, 7));
+        assert_ne!(tsne(&xs, 5.0, 50, 7), tsne(&xs, 5.0, 50, 8));
+    }
+
+    #[test]
+    fn perplexity_is_matched() {
+        let (xs, _) = make_blobs(30, &[vec![0.0; 3]], 1.0, 2);
+        let distances = squared_distances(&xs);
+        for i in [0, 11, 29] {
+            let probs = conditional_probabilities(&distances[i], i, 8.0);
+            assert!((probs.iter().sum::<f64>() - 1.0).abs() < 1e-12);
+            let entropy: f64 = probs
+                .iter()
+                .filter(|&&p| p > 0.0)
+                .map(|p| -p * p.ln())
+                .sum();
+            assert!((entropy.exp() - 8.0).abs() < 1e-3);
+        }
+    }
+
+    #[test]
+    fn tiny_inputs() {
+        assert!(tsne(&[], 5.0, 10, 0).is_empty());
+        assert_eq!(tsne(&[vec![1.0, 2.0]], 5.0, 10, 0), vec![[0.0, 0.0]]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn perplexity_too_large() {
+        tsne(&[vec![0.0], vec![1.0], vec![2.0]], 3.0, 10, 0);
+    }
+}
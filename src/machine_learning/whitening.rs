@@ -0,0 +1,266 @@
+//! # ZCA whitening
+//!
+//! Whitening transforms the features of a dataset so that they are centered,
+//! uncorrelated and of unit variance, that is, so that their covariance matrix
+//! is the identity. It makes PCA, gradient descent and neural networks
+//! insensitive to the scales and correlations of the raw features.
+//!
+//! With `C = U Λ U^T` the eigendecomposition of the covariance matrix, ZCA
+//! whitening maps `x` to `W (x - mean)` with `W = U Λ^(-1/2) U^T = C^(-1/2)`.
+//! Among all the whitening transforms, it is the one keeping the whitened data
+//! closest to the original data.
+//!
+//! The covariance is normalized by the number of samples. The directions of
+//! (numerically) zero variance are projected out rather than blown up, so that
+//! the covariance of the whitened data is the identity on the other
+//! directions and zero on them.
+//!
+//! ## Resources:
+//!   - [https://en.wikipedia.org/wiki/Whitening_transformation]
+
+/// The fitted transform of `whiten`, to apply to new data
+#[derive(Clone, Debug, PartialEq)]
+pub struct WhiteningParams {
+    /// The mean of each feature
+    pub mean: Vec<f64>,
+    /// The matrix `C^(-1/2)`, by rows
+    pub transform: Vec<Vec<f64>>,
+}
+
+impl WhiteningParams {
+    /// Whitens the sample `x` with the transform fitted by `whiten`
+    pub fn apply(&self, x: &[f64]) -> Vec<f64> {
+        assert_eq!(x.len(), self.mean.len(), "the sample has a wrong dimension");
+        let centered: Vec<f64> = x.iter().zip(&self.mean).map(|(v, m)| v - m).collect();
+        self.transform
+            .iter()
+            .map(|row| row.iter().zip(&centered).map(|(w, c)| w * c).sum())
+            .collect()
+    }
+}
+
+/// Returns the covariance matrix of `xs`, normalized by their number
+fn covariance(xs: &[Vec<f64>], mean: &[f64]) -> Vec<Vec<f64>> {
+    let d = mean.len();
+    (0..d)
+        .map(|i| {
+            (0..d)
+                .map(|j| {
+                    xs.iter()
+                        .map(|x| (x[i] - mean[i]) * (x[j] - mean[j]))
+                        .sum::<f64>()
+                        / xs.len() as f64
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// Returns the eigenvalues of the symmetric matrix `a`, and its eigenvectors
+/// as the columns of a matrix, with the cyclic Jacobi eigenvalue algorithm:
+/// rotations zero the off-diagonal entries one at a time until they vanish.
+fn symmetric_eigen(mut a: Vec<Vec<f64>>) -> (Vec<f64>, Vec<Vec<f64>>) {
+    let n = a.len();
+    let mut v: Vec<Vec<f64>> = (0..n)
+        .map(|i| (0..n).map(|j| if i == j { 1.0 } else { 0.0 }).collect())
+        .collect();
+    for _ in 0..100 {
+        let off_diagonal: f64 = (0..n)
+            .flat_map(|i| (0..n).filter(move |&j| j != i).map(move |j| (i, j)))
+            .map(|(i, j)| a[i][j] * a[i][j])
+            .sum();
+        let scale: f64 = (0..n).map(|i| a[i][i] * a[i][i]).sum();
+        if off_diagonal <= 1e-30 * scale || off_diagonal == 0.0 {
+            break;
+        }
+        for p in 0..n {
+            for q in p + 1..n {
+                if a[p][q] == 0.0 {
+                    continue;
+                }
+                // the rotation by the angle zeroing a[p][q]
+                let theta = (a[q][q] - a[p][p]) / (2.0 * a[p][q]);
+                let t = theta.signum() / (theta.abs() + (theta * theta + 1.0).sqrt());
+                let t = if theta == 0.0 { 1.0 } else { t };
+                let c = 1.0 / (t * t + 1.0).sqrt();
+                let s = t * c;
+                for row in a.iter_mut() {
+                    let (akp, akq) = (row[p], row[q]);
+                    row[p] = c * akp - s * akq;
+                    row[q] = s * akp + c * akq;
+                }
+                let (head, tail) = a.split_at_mut(q);
+                for (apk, aqk) in head[p].iter_mut().zip(tail[0].iter_mut()) {
+                    let (x, y) = (*apk, *aqk);
+                    *apk = c * x - s * y;
+                    *aqk = s * x + c * y;
+                }
+                for row in v.iter_mut() {
+                    let (vkp, vkq) = (row[p], row[q]);
+                    row[p] = c * vkp - s * vkq;
+                    row[q] = s * vkp + c * vkq;
+                }
+            }
+        }
+    }
+    ((0..n).map(|i| a[i][i]).collect(), v)
+}
+
+/// Returns the ZCA whitening of the samples `xs`, which must all have the same
+/// dimension, with the fitted transform.
+pub fn whiten(xs: &[Vec<f64>]) -> (Vec<Vec<f64>>, WhiteningParams) {
+    assert!(!xs.is_empty(), "there is nothing to whiten");
+    let d = xs[0].len();
+    assert!(
+        xs.iter().all(|x| x.len() == d),
+        "the samples have different dimensions"
+    );
+    let mean: Vec<f64> = (0..d)
+        .map(|j| xs.iter().map(|x| x[j]).sum::<f64>() / xs.len() as f64)
+        .collect();
+    let (eigenvalues, eigenvectors) = symmetric_eigen(covariance(xs, &mean));
+
+    let largest = eigenvalues.iter().copied().fold(0.0, f64::max);
+    let inverse_sqrt: Vec<f64> = eigenvalues
+        .iter()
+        .map(|&l| {
+            if l > 1e-12 * largest {
+                1.0 / l.sqrt()
+            } else {
+                0.0
+            }
+        })
+        .collect();
+    // W = U Λ^(-1/2) U^T
+    let transform = (0..d)
+        .map(|i| {
+            (0..d)
+                .map(|j| {
+                    (0..d)
+                        .map(|k| eigenvectors[i][k] * inverse_sqrt[k] * eigenvectors[j][k])
+                        .sum()
+                })
+                .collect()
+        })
+        .collect();
+
+    let params = WhiteningParams { mean, transform };
+    let whitened = xs.iter().map(|x| params.apply(x)).collect();
+    (whitened, params)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::general::Xorshift64;
+
+    fn mean_and_covariance(xs: &[Vec<f64>]) -> (Vec<f64>, Vec<Vec<f64>>) {
+        let d = xs[0].len();
+        let mean: Vec<f64> = (0..d)
+            .map(|j| xs.iter().map(|x| x[j]).sum::<f64>() / xs.len() as f64)
+            .collect();
+        let cov = covariance(xs, &mean);
+        (mean, cov)
+    }
+
+    /// Samples correlated features, mixing independent gaussians
+    fn correlated_samples(rng: &mut Xorshift64, n: usize, mixing: &[Vec<f64>]) -> Vec<Vec<f64>> {
+        (0..n)
+            .map(|_| {
+                let z: Vec<f64> = (0..mixing[0].len())
+                    .map(|_| rng.next_gaussian(0.0, 1.0))
+                    .collect();
+                mixing
+                    .iter()
+                    .enumerate()
+                    .map(|(i, row)| {
+                        10.0 * i as f64 + row.iter().zip(&z).map(|(m, z)| m * z).sum::<f64>()
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn whitened_covariance_is_identity() {
+        let mut rng = Xorshift64::new(254);
+        let mixing = vec![
+            vec![3.0, 0.0, 0.0],
+            vec![2.0, 0.5, 0.0],
+            vec![-1.0, 4.0, 0.1],
+        ];
+        let xs = correlated_samples(&mut rng, 500, &mixing);
+        let (whitened, params) = whiten(&xs);
+        let (mean, cov) = mean_and_covariance(&whitened);
+        for i in 0..3 {
+            assert!(mean[i].abs() < 1e-9);
+            for j in 0..3 {
+                let expected = if i == j { 1.0 } else { 0.0 };
+                assert!((cov[i][j] - expected).abs() < 1e-9, "{cov:?}");
+            }
+        }
+        // the transform is symmetric, as for any ZCA whitening
+        for i in 0..3 {
+            for j in 0..3 {
+                assert!((params.transform[i][j] - params.transform[j][i]).abs() < 1e-9);
+            }
+        }
+    }
+
+    #[test]
+    fn applies_to_new_data() {
+        let mut rng = Xorshift64::new(2540);
+        let mixing = vec![vec![1.0, 2.0], vec![0.0, 0.5]];
+        let xs = correlated_samples(&mut rng, 20_000, &mixing);
+        let (whitened, params) = whiten(&xs);
+        assert_eq!(params.apply(&xs[7]), whitened[7]);
+        // samples of the same distribution are nearly white too
+        let new = correlated_samples(&mut rng, 20_000, &mixing);
+        let transformed: Vec<Vec<f64>> = new.iter().map(|x| params.apply(x)).collect();
+        let (mean, cov) = mean_and_covariance(&transformed);
+        for i in 0..2 {
+            assert!(mean[i].abs() < 0.05);
+            for j in 0..2 {
+                let expected = if i == j { 1.0 } else { 0.0 };
+                assert!((cov[i][j] - expected).abs() < 0.05, "{cov:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn zero_variance_directions_are_dropped() {
+        // the second feature is twice the first one, the third is constant
+        let xs: Vec<Vec<f64>> = (0..10)
+            .map(|i| vec![i as f64, 2.0 * i as f64, 5.0])
+            .collect();
+        let (whitened, _) = whiten(&xs);
+        let (_, cov) = mean_and_covariance(&whitened);
+        assert!(whitened.iter().flatten().all(|v| v.is_finite()));
+        // a single direction is left, of unit variance
+        let trace: f64 = (0..3).map(|i| cov[i][i]).sum();
+        assert!((trace - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn eigendecomposition() {
+        let a = vec![
+            vec![4.0, 1.0, -2.0],
+            vec![1.0, 2.0, 0.0],
+            vec![-2.0, 0.0, 3.0],
+        ];
+        let (values, vectors) = symmetric_eigen(a.clone());
+        for k in 0..3 {
+            for i in 0..3 {
+                let av: f64 = (0..3).map(|j| a[i][j] * vectors[j][k]).sum();
+                assert!((av - values[k] * vectors[i][k]).abs() < 1e-12);
+            }
+        }
+        assert!((values.iter().sum::<f64>() - 9.0).abs() < 1e-12);
+    }
+
+    #[test]
+    #[should_panic]
+    fn empty_data() {
+        whiten(&[]);
+    }
+}
@@ -0,0 +1,129 @@
+//! Discrete convolution and cross-correlation, in full mode: the output has a
+//! value for each overlap of the signal and the kernel, so `n + k - 1` values
+//! for a signal of length `n` and a kernel of length `k`, or none if either is
+//! empty. Both take O(nk).
+//!
+//! Wikipedia: <https://en.wikipedia.org/wiki/Convolution#Discrete_convolution>,
+//! <https://en.wikipedia.org/wiki/Cross-correlation>
+
+/// Returns the convolution of `signal` by `kernel`:
+///
+/// `out[j] = Σ_i signal[i] kernel[j - i]`
+///
+/// It is commutative, and the kernel `[1.0]` is its identity. Filters such
+/// as the moving averages and Savitzky-Golay are convolutions by their
+/// weights.
+pub fn convolve(signal: &[f64], kernel: &[f64]) -> Vec<f64> {
+    if signal.is_empty() || kernel.is_empty() {
+        return vec![];
+    }
+    let mut out = vec![0.0; signal.len() + kernel.len() - 1];
+    for (i, s) in signal.iter().enumerate() {
+        for (value, k) in out[i..].iter_mut().zip(kernel) {
+            *value += s * k;
+        }
+    }
+    out
+}
+
+/// Returns the cross-correlation of `signal` with `kernel`, for the shifts of
+/// the kernel from `1 - k` to `n - 1`:
+///
+/// `out[j] = Σ_i signal[i + j - (k - 1)] kernel[i]`
+///
+/// which is the convolution by the reversed kernel. It peaks where the signal
+/// looks the most like the kernel.
+pub fn cross_correlate(signal: &[f64], kernel: &[f64]) -> Vec<f64> {
+    let reversed: Vec<f64> = kernel.iter().rev().copied().collect();
+    convolve(signal, &reversed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::simple_moving_average;
+
+    fn assert_close(a: &[f64], b: &[f64]) {
+        assert_eq!(a.len(), b.len());
+        for (x, y) in a.iter().zip(b) {
+            assert!((x - y).abs() < 1e-12, "{a:?} vs {b:?}");
+        }
+    }
+
+    /// The definition, over the whole range of shifts
+    fn brute_force_convolve(signal: &[f64], kernel: &[f64]) -> Vec<f64> {
+        let (n, k) = (signal.len() as i64, kernel.len() as i64);
+        (0..n + k - 1)
+            .map(|j| {
+                (0..n)
+                    .filter(|i| (0..k).contains(&(j - i)))
+                    .map(|i| signal[i as usize] * kernel[(j - i) as usize])
+                    .sum()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn known_convolutions() {
+        // [0, 1, 2, 3] + [0, 0, 0.5, 1, 1.5]
+        assert_close(
+            &convolve(&[1.0, 2.0, 3.0], &[0.0, 1.0, 0.5]),
+            &[0.0, 1.0, 2.5, 4.0, 1.5],
+        );
+        assert_close(&convolve(&[1.0, 1.0], &[1.0, 1.0]), &[1.0, 2.0, 1.0]);
+        assert!(convolve(&[], &[1.0]).is_empty());
+        assert!(convolve(&[1.0], &[]).is_empty());
+    }
+
+    #[test]
+    fn identity_kernel() {
+        let signal = [3.0, -1.0, 4.0, 1.0, -5.0];
+        assert_close(&convolve(&signal, &[1.0]), &signal);
+        assert_close(&cross_correlate(&signal, &[1.0]), &signal);
+        // a shifted impulse shifts the signal
+        assert_close(
+            &convolve(&signal, &[0.0, 1.0]),
+            &[0.0, 3.0, -1.0, 4.0, 1.0, -5.0],
+        );
+    }
+
+    #[test]
+    fn known_cross_correlations() {
+        assert_close(
+            &cross_correlate(&[1.0, 2.0, 3.0], &[0.0, 1.0, 0.5]),
+            &[0.5, 2.0, 3.5, 3.0, 0.0],
+        );
+        // peaks at the shift matching the pattern
+        let signal = [0.0, 0.0, 1.0, 2.0, 1.0, 0.0, 0.0];
+        let correlation = cross_correlate(&signal, &[1.0, 2.0, 1.0]);
+        let peak = (0..correlation.len())
+            .max_by(|&i, &j| correlation[i].total_cmp(&correlation[j]))
+            .unwrap();
+        // the kernel starts at signal[2], a shift of 2 + k - 1
+        assert_eq!(peak, 4);
+    }
+
+    #[test]
+    fn against_brute_force() {
+        let signal = [0.5, -2.0, 1.5, 3.0, 0.0, -1.0, 2.5];
+        let kernel = [1.0, -0.5, 0.25];
+        assert_close(
+            &convolve(&signal, &kernel),
+            &brute_force_convolve(&signal, &kernel),
+        );
+        // commutative
+        assert_close(&convolve(&signal, &kernel), &convolve(&kernel, &signal));
+    }
+
+    #[test]
+    fn moving_average_is_a_convolution() {
+        let data = [2.0, 4.0, 6.0, 8.0, 10.0, 3.0];
+        let window = 3;
+        let convolved = convolve(&data, &[1.0 / window as f64; 3]);
+        // once the window is full
+        assert_close(
+            &convolved[window - 1..data.len()],
+            &simple_moving_average(&data, window)[window - 1..],
+        );
+    }
+}
@@ -0,0 +1,165 @@
+use super::thomas_solve;
+
+/// A natural cubic spline, see `cubic_spline`
+#[derive(Clone, Debug, PartialEq)]
+pub struct CubicSpline {
+    xs: Vec<f64>,
+    ys: Vec<f64>,
+    // the second derivatives at the knots
+    second_derivatives: Vec<f64>,
+}
+
+/// Returns the natural cubic spline through `points`, whose x must be
+/// strictly increasing.
+///
+/// Between two consecutive knots, the spline is a cubic polynomial, and the
+/// pieces join with continuous first and second derivatives. The natural
+/// spline has no curvature at both ends, which gives a tridiagonal system for
+/// the second derivatives at the knots, solved in O(n) by `thomas_solve`.
+/// Among all the twice differentiable interpolants, it is the one which bends
+/// the least (of smallest integral of the squared second derivative).
+///
+/// Panics with less than two points, or if the x are not strictly increasing.
+///
+/// Wikipedia: <https://en.wikipedia.org/wiki/Spline_interpolation>
+pub fn cubic_spline(points: &[(f64, f64)]) -> CubicSpline {
+    assert!(points.len() >= 2, "at least two points are needed");
+    assert!(
+        points.windows(2).all(|pair| pair[0].0 < pair[1].0),
+        "the x must be strictly increasing"
+    );
+    let xs: Vec<f64> = points.iter().map(|p| p.0).collect();
+    let ys: Vec<f64> = points.iter().map(|p| p.1).collect();
+    let n = points.len();
+    let h: Vec<f64> = xs.windows(2).map(|pair| pair[1] - pair[0]).collect();
+    let slopes: Vec<f64> = (0..n - 1).map(|i| (ys[i + 1] - ys[i]) / h[i]).collect();
+
+    // h[i - 1] M[i - 1] + 2 (h[i - 1] + h[i]) M[i] + h[i] M[i + 1]
+    //   = 6 (slopes[i] - slopes[i - 1])
+    // for the inner knots, with M[0] = M[n - 1] = 0
+    let inner: Vec<f64> = if n > 2 {
+        thomas_solve(
+            &h[1..n - 2],
+            &(1..n - 1)
+                .map(|i| 2.0 * (h[i - 1] + h[i]))
+                .collect::<Vec<_>>(),
+            &h[1..n - 2],
+            &(1..n - 1)
+                .map(|i| 6.0 * (slopes[i] - slopes[i - 1]))
+                .collect::<Vec<_>>(),
+        )
+    } else {
+        vec![]
+    };
+    let mut second_derivatives = vec![0.0];
+    second_derivatives.extend(inner);
+    second_derivatives.push(0.0);
+
+    CubicSpline {
+        xs,
+        ys,
+        second_derivatives,
+    }
+}
+
+impl CubicSpline {
+    /// Returns the value of the spline at `x`; beyond the knots, the first or
+    /// last cubic piece is extended.
+    pub fn evaluate(&self, x: f64) -> f64 {
+        let last = self.xs.len() - 2;
+        // the piece [xs[i], xs[i + 1]] containing x
+        let i = self
+            .xs
+            .partition_point(|&knot| knot <= x)
+            .clamp(1, last + 1)
+            - 1;
+        let (x0, x1) = (self.xs[i], self.xs[i + 1]);
+        let (y0, y1) = (self.ys[i], self.ys[i + 1]);
+        let (m0, m1) = (self.second_derivatives[i], self.second_derivatives[i + 1]);
+        let h = x1 - x0;
+        let (left, right) = (x - x0, x1 - x);
+        m0 * right.powi(3) / (6.0 * h)
+            + m1 * left.powi(3) / (6.0 * h)
+            + (y0 / h - m0 * h / 6.0) * right
+            + (y1 / h - m1 * h / 6.0) * left
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(a: f64, b: f64, tol: f64) {
+        assert!((a - b).abs() < tol, "{a} vs {b}");
+    }
+
+    #[test]
+    fn passes_through_the_knots() {
+        let points = [(-1.0, 2.0), (0.0, 0.5), (0.5, 1.5), (2.0, -1.0), (3.5, 0.0)];
+        let spline = cubic_spline(&points);
+        for &(x, y) in &points {
+            assert_close(spline.evaluate(x), y, 1e-12);
+        }
+    }
+
+    #[test]
+    fn is_smooth_at_the_knots() {
+        let points = [(0.0, 1.0), (1.0, 3.0), (1.5, 2.0), (3.0, 2.5), (4.0, 0.0)];
+        let spline = cubic_spline(&points);
+        let h = 1e-4;
+        for &(x, y) in &points[1..points.len() - 1] {
+            // the value, slope and curvature match on both sides
+            let (before, after) = (spline.evaluate(x - h), spline.evaluate(x + h));
+            assert_close(before, y, 1e-3);
+            assert_close(after, y, 1e-3);
+            let slope_before = (y - spline.evaluate(x - 2.0 * h)) / (2.0 * h);
+            let slope_after = (spline.evaluate(x + 2.0 * h) - y) / (2.0 * h);
+            assert_close(slope_before, slope_after, 1e-2);
+            let curvature_before = (y - 2.0 * before + spline.evaluate(x - 2.0 * h)) / (h * h);
+            let curvature_after = (spline.evaluate(x + 2.0 * h) - 2.0 * after + y) / (h * h);
+            assert_close(curvature_before, curvature_after, 1e-2);
+        }
+        // no curvature at the ends
+        for x in [0.0, 4.0] {
+            let curvature = (spline.evaluate(x + h) - 2.0 * spline.evaluate(x)
+                + spline.evaluate(x - h))
+                / (h * h);
+            assert_close(curvature, 0.0, 1e-2);
+        }
+    }
+
+    #[test]
+    fn approximates_sine() {
+        let points: Vec<(f64, f64)> = (0..=8)
+            .map(|i| {
+                let x = i as f64 * std::f64::consts::PI / 4.0;
+                (x, x.sin())
+            })
+            .collect();
+        let spline = cubic_spline(&points);
+        for i in 0..=200 {
+            let x = i as f64 * 2.0 * std::f64::consts::PI / 200.0;
+            assert_close(spline.evaluate(x), x.sin(), 1e-2);
+        }
+    }
+
+    #[test]
+    fn straight_lines() {
+        // two points give their line, extended on both sides
+        let spline = cubic_spline(&[(1.0, 1.0), (3.0, 5.0)]);
+        for x in [-1.0, 1.0, 2.0, 3.0, 10.0] {
+            assert_close(spline.evaluate(x), 2.0 * x - 1.0, 1e-12);
+        }
+        // as do collinear points
+        let spline = cubic_spline(&[(0.0, 0.0), (1.0, -1.0), (4.0, -4.0), (5.0, -5.0)]);
+        for x in [0.5, 2.0, 4.5] {
+            assert_close(spline.evaluate(x), -x, 1e-12);
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn x_not_increasing() {
+        cubic_spline(&[(0.0, 0.0), (2.0, 1.0), (2.0, 3.0)]);
+    }
+}
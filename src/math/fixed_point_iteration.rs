@@ -0,0 +1,81 @@
+/// Finds a fixed point `x = f(x)` by the relaxed iteration
+/// `x_{k+1} = (1 - ω) x_k + ω f(x_k)` from `x0`, with `ω` the `relaxation`.
+///
+/// Returns the first iterate within `tol` of the previous one, or None if
+/// there is none within `max_iter` iterations, or if the iterates stop being
+/// finite numbers.
+///
+/// The plain iteration (`ω = 1`) converges near a fixed point where
+/// `|f'| < 1`. The relaxed one has the slope `1 - ω + ω f'` there, so that
+/// under-relaxation (`0 < ω < 1`) also tames fixed points where `f' < -1`,
+/// around which the plain iteration oscillates away, and over-relaxation
+/// (`ω > 1`) speeds up slow monotonic convergences.
+///
+/// Wikipedia: <https://en.wikipedia.org/wiki/Fixed-point_iteration>
+pub fn fixed_point(
+    f: impl Fn(f64) -> f64,
+    x0: f64,
+    tol: f64,
+    max_iter: usize,
+    relaxation: f64,
+) -> Option<f64> {
+    let mut x = x0;
+    for _ in 0..max_iter {
+        let next = (1.0 - relaxation) * x + relaxation * f(x);
+        if !next.is_finite() {
+            return None;
+        }
+        if (next - x).abs() < tol {
+            return Some(next);
+        }
+        x = next;
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cosine() {
+        // the Dottie number
+        let x = fixed_point(f64::cos, 1.0, 1e-12, 1000, 1.0).unwrap();
+        assert!((x - 0.739_085_133_215_160_6).abs() < 1e-10);
+        assert!((x.cos() - x).abs() < 1e-10);
+        // with a relaxation close to 1 / (1 - f'(x)), it converges much faster
+        let count = |relaxation| {
+            (1..1000)
+                .find(|&n| fixed_point(f64::cos, 1.0, 1e-12, n, relaxation).is_some())
+                .unwrap()
+        };
+        assert!(count(0.6) * 4 < count(1.0));
+    }
+
+    #[test]
+    fn under_relaxation_tames_divergence() {
+        // the fixed point 1 of 3 - 2x repels the plain iteration
+        let f = |x: f64| 3.0 - 2.0 * x;
+        assert_eq!(fixed_point(f, 0.9, 1e-10, 1000, 1.0), None);
+        let x = fixed_point(f, 0.9, 1e-10, 1000, 0.5).unwrap();
+        assert!((x - 1.0).abs() < 1e-9);
+        // while the plain iteration runs around 4 - x forever
+        assert_eq!(fixed_point(|x| 4.0 - x, 0.0, 1e-10, 1000, 1.0), None);
+        assert_eq!(fixed_point(|x| 4.0 - x, 0.0, 1e-10, 1000, 0.5), Some(2.0));
+    }
+
+    #[test]
+    fn square_root_as_a_fixed_point() {
+        // Heron's method, x = (x + a / x) / 2
+        let x = fixed_point(|x| (x + 2.0 / x) / 2.0, 1.0, 1e-15, 100, 1.0).unwrap();
+        assert!((x - 2f64.sqrt()).abs() < 1e-15);
+    }
+
+    #[test]
+    fn no_convergence() {
+        // no fixed point at all
+        assert_eq!(fixed_point(|x| x + 1.0, 0.0, 1e-6, 1000, 1.0), None);
+        assert_eq!(fixed_point(|x| x * x, 2.0, 1e-6, 1000, 1.0), None);
+        assert_eq!(fixed_point(f64::cos, 1.0, 1e-12, 0, 1.0), None);
+    }
+}
@@ -0,0 +1,146 @@
+//! Histograms, counting the values falling in each of a sequence of bins
+//!
+//! Wikipedia: <https://en.wikipedia.org/wiki/Histogram>
+
+/// Returns the counts of `data` in `bins` bins of equal width over
+/// `[min, max]` of the data, with the `bins + 1` edges of the bins.
+///
+/// Each bin holds the values from its left edge, included, to its right
+/// edge, excluded, but for the last bin, which also holds the maximum. If all
+/// the values are equal, the range is widened to `[v - 0.5, v + 0.5]`.
+///
+/// Panics if `bins` is 0, or if `data` is empty or has a NaN.
+pub fn histogram(data: &[f64], bins: usize) -> (Vec<u64>, Vec<f64>) {
+    assert!(bins > 0, "at least one bin is needed");
+    assert!(!data.is_empty(), "the data is empty");
+    assert!(data.iter().all(|v| !v.is_nan()), "the data has a NaN");
+    let mut min = data.iter().copied().fold(f64::INFINITY, f64::min);
+    let mut max = data.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+    if min == max {
+        min -= 0.5;
+        max += 0.5;
+    }
+    let width = (max - min) / bins as f64;
+    let mut edges: Vec<f64> = (0..bins).map(|i| min + i as f64 * width).collect();
+    edges.push(max);
+
+    let mut counts = vec![0; bins];
+    for &value in data {
+        // the rounding may put a value a bin too far, which the edges settle
+        let mut bin = (((value - min) / width) as usize).min(bins - 1);
+        if value < edges[bin] {
+            bin -= 1;
+        } else if bin + 1 < bins && value >= edges[bin + 1] {
+            bin += 1;
+        }
+        counts[bin] += 1;
+    }
+    (counts, edges)
+}
+
+/// Returns the counts of `data` in the bins between consecutive `edges`,
+/// which must be strictly increasing.
+///
+/// As with `histogram`, the bins hold their left edge but not their right
+/// one, but for the last bin, which holds both. The values out of the edges
+/// are not counted.
+///
+/// Panics with less than two edges, or if they are not strictly increasing.
+pub fn histogram_fixed_edges(data: &[f64], edges: &[f64]) -> Vec<u64> {
+    assert!(edges.len() >= 2, "at least two edges are needed");
+    assert!(
+        edges.windows(2).all(|pair| pair[0] < pair[1]),
+        "the edges must be strictly increasing"
+    );
+    let bins = edges.len() - 1;
+    let mut counts = vec![0; bins];
+    for &value in data {
+        if value == edges[bins] {
+            counts[bins - 1] += 1;
+        } else {
+            // the number of edges not above the value
+            let bin = edges.partition_point(|&edge| edge <= value);
+            if (1..=bins).contains(&bin) {
+                counts[bin - 1] += 1;
+            }
+        }
+    }
+    counts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::general::Xorshift64;
+
+    #[test]
+    fn known_histogram() {
+        let data = [0.0, 1.0, 1.5, 2.0, 2.5, 3.9, 4.0];
+        let (counts, edges) = histogram(&data, 4);
+        assert_eq!(edges, vec![0.0, 1.0, 2.0, 3.0, 4.0]);
+        // the maximum lands in the last bin
+        assert_eq!(counts, vec![1, 2, 2, 2]);
+        assert_eq!(counts.iter().sum::<u64>(), data.len() as u64);
+    }
+
+    #[test]
+    fn edge_values() {
+        // on an inner edge, in the bin on its right
+        let (counts, _) = histogram(&[0.0, 0.5, 0.5, 1.0], 2);
+        assert_eq!(counts, vec![1, 3]);
+        // a single value or equal values
+        let (counts, edges) = histogram(&[3.0, 3.0, 3.0], 2);
+        assert_eq!(edges, vec![2.5, 3.0, 3.5]);
+        assert_eq!(counts, vec![0, 3]);
+        let (counts, _) = histogram(&[-7.0], 1);
+        assert_eq!(counts, vec![1]);
+    }
+
+    #[test]
+    fn counts_sum_to_the_length() {
+        let mut rng = Xorshift64::new(269);
+        let data: Vec<f64> = (0..1000).map(|_| rng.next_gaussian(3.0, 10.0)).collect();
+        for bins in [1, 2, 7, 64, 1000] {
+            let (counts, edges) = histogram(&data, bins);
+            assert_eq!(counts.len(), bins);
+            assert_eq!(edges.len(), bins + 1);
+            assert_eq!(counts.iter().sum::<u64>(), data.len() as u64);
+            assert_eq!(histogram_fixed_edges(&data, &edges), counts);
+        }
+    }
+
+    #[test]
+    fn uniform_data_is_even() {
+        let mut rng = Xorshift64::new(2690);
+        let data: Vec<f64> = (0..100_000).map(|_| rng.next_f64()).collect();
+        let (counts, _) = histogram(&data, 10);
+        for count in counts {
+            // about 10000 each, with a standard deviation of 95
+            assert!((9_500..10_500).contains(&count), "{count}");
+        }
+    }
+
+    #[test]
+    fn fixed_edges() {
+        let data = [-1.0, 0.0, 0.5, 1.0, 2.5, 3.0, 3.5, 10.0];
+        // uneven bins; -1, 3.5 and 10 are out
+        assert_eq!(
+            histogram_fixed_edges(&data, &[0.0, 1.0, 2.0, 3.0]),
+            vec![2, 1, 2]
+        );
+        assert_eq!(histogram_fixed_edges(&data, &[0.0, 10.0]), vec![7]);
+        assert_eq!(histogram_fixed_edges(&[], &[0.0, 1.0]), vec![0]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn no_bins() {
+        histogram(&[1.0, 2.0], 0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn edges_not_increasing() {
+        histogram_fixed_edges(&[1.0], &[0.0, 2.0, 2.0]);
+    }
+}
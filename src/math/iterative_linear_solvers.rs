@@ -0,0 +1,238 @@
+//! Iterative solvers of linear systems `Ax = b`
+//!
+//! Instead of factoring `A` as a direct solver does, they refine a guess of
+//! the solution, each iteration costing one product by `A`. It pays off for
+//! large sparse systems, whose factors would not be sparse. Both converge
+//! when `A` is strictly diagonally dominant, and Gauss-Seidel also when it is
+//! symmetric positive definite.
+//!
+//! - Jacobi solves each equation `i` for `x_i`, from the previous iterate:
+//!   `x_i' = (b_i - Σ_{j != i} a_ij x_j) / a_ii`
+//! - Gauss-Seidel does the same, but uses the new values of the unknowns as
+//!   soon as they are computed, which usually halves the iterations and needs
+//!   no copy of the iterate.
+//!
+//! Both start from zero and stop once no unknown changes by `tol` or more.
+//!
+//! Wikipedia: <https://en.wikipedia.org/wiki/Jacobi_method>,
+//! <https://en.wikipedia.org/wiki/Gauss%E2%80%93Seidel_method>
+
+fn check_system(a: &[Vec<f64>], b: &[f64]) {
+    assert!(
+        a.iter().all(|row| row.len() == b.len()) && a.len() == b.len(),
+        "the system is not square"
+    );
+    assert!(
+        (0..a.len()).all(|i| a[i][i] != 0.0),
+        "the diagonal has a zero"
+    );
+}
+
+/// Returns the sum of `a_ij x_j` over `j != i`
+fn off_diagonal_product(row: &[f64], i: usize, x: &[f64]) -> f64 {
+    row.iter()
+        .zip(x)
+        .enumerate()
+        .filter(|&(j, _)| j != i)
+        .map(|(_, (a, x))| a * x)
+        .sum()
+}
+
+/// Returns the solution with the number of iterations run
+fn jacobi_counting(
+    a: &[Vec<f64>],
+    b: &[f64],
+    max_iter: usize,
+    tol: f64,
+) -> Option<(Vec<f64>, usize)> {
+    check_system(a, b);
+    let mut x = vec![0.0; b.len()];
+    for iteration in 1..=max_iter {
+        let next: Vec<f64> = (0..b.len())
+            .map(|i| (b[i] - off_diagonal_product(&a[i], i, &x)) / a[i][i])
+            .collect();
+        let change = next
+            .iter()
+            .zip(&x)
+            .map(|(u, v)| (u - v).abs())
+            .fold(0.0, f64::max);
+        x = next;
+        if !change.is_finite() {
+            return None;
+        }
+        if change < tol {
+            return Some((x, iteration));
+        }
+    }
+    None
+}
+
+/// Returns the solution with the number of iterations run
+fn gauss_seidel_counting(
+    a: &[Vec<f64>],
+    b: &[f64],
+    max_iter: usize,
+    tol: f64,
+) -> Option<(Vec<f64>, usize)> {
+    check_system(a, b);
+    let mut x = vec![0.0; b.len()];
+    for iteration in 1..=max_iter {
+        let mut change: f64 = 0.0;
+        for i in 0..b.len() {
+            let updated = (b[i] - off_diagonal_product(&a[i], i, &x)) / a[i][i];
+            change = change.max((updated - x[i]).abs());
+            x[i] = updated;
+        }
+        if !change.is_finite() {
+            return None;
+        }
+        if change < tol {
+            return Some((x, iteration));
+        }
+    }
+    None
+}
+
+/// Solves `ax = b` with the Jacobi method, in at most `max_iter` iterations,
+/// or returns None if it does not converge by then.
+///
+/// Panics if `a` is not square of the size of `b`, or has zeros on its
+/// diagonal.
+pub fn jacobi_solve(a: &[Vec<f64>], b: &[f64], max_iter: usize, tol: f64) -> Option<Vec<f64>> {
+    jacobi_counting(a, b, max_iter, tol).map(|(x, _)| x)
+}
+
+/// Solves `ax = b` with the Gauss-Seidel method, in at most `max_iter`
+/// iterations, or returns None if it does not converge by then.
+///
+/// Panics if `a` is not square of the size of `b`, or has zeros on its
+/// diagonal.
+pub fn gauss_seidel_solve(
+    a: &[Vec<f64>],
+    b: &[f64],
+    max_iter: usize,
+    tol: f64,
+) -> Option<Vec<f64>> {
+    gauss_seidel_counting(a, b, max_iter, tol).map(|(x, _)| x)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::general::Xorshift64;
+    use crate::math::gaussian_elimination;
+
+    fn direct_solve(a: &[Vec<f64>], b: &[f64]) -> Vec<f64> {
+        let mut augmented: Vec<Vec<f32>> = a
+            .iter()
+            .zip(b)
+            .map(|(row, &b)| row.iter().chain([b].iter()).map(|&v| v as f32).collect())
+            .collect();
+        gaussian_elimination(&mut augmented)
+            .into_iter()
+            .map(f64::from)
+            .collect()
+    }
+
+    fn assert_close(x: &[f64], y: &[f64], tol: f64) {
+        for (u, v) in x.iter().zip(y) {
+            assert!((u - v).abs() < tol, "{x:?} vs {y:?}");
+        }
+    }
+
+    /// A random strictly diagonally dominant system
+    fn dominant_system(rng: &mut Xorshift64, n: usize) -> (Vec<Vec<f64>>, Vec<f64>) {
+        let mut a: Vec<Vec<f64>> = (0..n)
+            .map(|_| (0..n).map(|_| rng.next_f64() * 2.0 - 1.0).collect())
+            .collect();
+        for (i, row) in a.iter_mut().enumerate() {
+            let off_diagonal: f64 = row.iter().map(|v| v.abs()).sum();
+            row[i] = off_diagonal + 1.0 + rng.next_f64();
+        }
+        let b = (0..n).map(|_| rng.next_f64() * 20.0 - 10.0).collect();
+        (a, b)
+    }
+
+    #[test]
+    fn small_system() {
+        let a = vec![
+            vec![10.0, -1.0, 2.0, 0.0],
+            vec![-1.0, 11.0, -1.0, 3.0],
+            vec![2.0, -1.0, 10.0, -1.0],
+            vec![0.0, 3.0, -1.0, 8.0],
+        ];
+        let b = [6.0, 25.0, -11.0, 15.0];
+        let expected = [1.0, 2.0, -1.0, 1.0];
+        assert_close(&jacobi_solve(&a, &b, 100, 1e-12).unwrap(), &expected, 1e-10);
+        assert_close(
+            &gauss_seidel_solve(&a, &b, 100, 1e-12).unwrap(),
+            &expected,
+            1e-10,
+        );
+    }
+
+    #[test]
+    fn agree_with_the_direct_solver() {
+        let mut rng = Xorshift64::new(263);
+        for n in [1, 2, 5, 20, 50] {
+            let (a, b) = dominant_system(&mut rng, n);
+            let expected = direct_solve(&a, &b);
+            let (jacobi, jacobi_iterations) = jacobi_counting(&a, &b, 1000, 1e-12).unwrap();
+            let (gauss_seidel, gauss_seidel_iterations) =
+                gauss_seidel_counting(&a, &b, 1000, 1e-12).unwrap();
+            // the direct solver works in f32
+            assert_close(&jacobi, &expected, 1e-4);
+            assert_close(&gauss_seidel, &expected, 1e-4);
+            assert_close(&jacobi, &gauss_seidel, 1e-10);
+            assert!(gauss_seidel_iterations <= jacobi_iterations);
+        }
+    }
+
+    #[test]
+    fn gauss_seidel_is_faster() {
+        // the 1D Poisson equation, weakly diagonally dominant
+        let n: usize = 20;
+        let a: Vec<Vec<f64>> = (0..n)
+            .map(|i| {
+                (0..n)
+                    .map(|j| match i.abs_diff(j) {
+                        0 => 2.0,
+                        1 => -1.0,
+                        _ => 0.0,
+                    })
+                    .collect()
+            })
+            .collect();
+        let b = vec![1.0; n];
+        let (jacobi, jacobi_iterations) = jacobi_counting(&a, &b, 10_000, 1e-10).unwrap();
+        let (gauss_seidel, gauss_seidel_iterations) =
+            gauss_seidel_counting(&a, &b, 10_000, 1e-10).unwrap();
+        // x_i = (i + 1) (n - i) / 2
+        let expected: Vec<f64> = (0..n).map(|i| ((i + 1) * (n - i)) as f64 / 2.0).collect();
+        assert_close(&jacobi, &expected, 1e-6);
+        assert_close(&gauss_seidel, &expected, 1e-6);
+        // about twice as fast
+        assert!(
+            gauss_seidel_iterations * 3 < jacobi_iterations * 2,
+            "{gauss_seidel_iterations} vs {jacobi_iterations}"
+        );
+    }
+
+    #[test]
+    fn no_convergence() {
+        // not diagonally dominant, and both iterations diverge
+        let a = vec![vec![1.0, 3.0], vec![2.0, 1.0]];
+        let b = [4.0, 3.0];
+        assert_eq!(jacobi_solve(&a, &b, 1000, 1e-10), None);
+        assert_eq!(gauss_seidel_solve(&a, &b, 1000, 1e-10), None);
+        // too few iterations
+        let (a, b) = dominant_system(&mut Xorshift64::new(2630), 10);
+        assert_eq!(jacobi_solve(&a, &b, 1, 1e-10), None);
+    }
+
+    #[test]
+    #[should_panic]
+    fn zero_on_the_diagonal() {
+        jacobi_solve(&[vec![0.0, 1.0], vec![1.0, 0.0]], &[1.0, 1.0], 10, 1e-6);
+    }
+}
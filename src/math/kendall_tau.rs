@@ -0,0 +1,194 @@
+/// Sorts `values` by merge sort, and returns the number of inversions, the
+/// pairs `i < j` with `values[i] > values[j]`; equal values are not
+/// inversions
+fn sort_counting_inversions(values: &mut [f64]) -> u64 {
+    if values.len() < 2 {
+        return 0;
+    }
+    let mid = values.len() / 2;
+    let mut inversions =
+        sort_counting_inversions(&mut values[..mid]) + sort_counting_inversions(&mut values[mid..]);
+    let mut merged = Vec::with_capacity(values.len());
+    let (left, right) = values.split_at(mid);
+    let (mut i, mut j) = (0, 0);
+    while i < left.len() && j < right.len() {
+        if right[j] < left[i] {
+            // right[j] comes before all the rest of the left half
+            inversions += (left.len() - i) as u64;
+            merged.push(right[j]);
+            j += 1;
+        } else {
+            merged.push(left[i]);
+            i += 1;
+        }
+    }
+    merged.extend_from_slice(&left[i..]);
+    merged.extend_from_slice(&right[j..]);
+    values.copy_from_slice(&merged);
+    inversions
+}
+
+/// Returns the number of pairs in the runs of equal values of the sorted
+/// `values`, under the equality `same`
+fn tied_pairs<T>(sorted: &[T], same: impl Fn(&T, &T) -> bool) -> u64 {
+    let mut pairs = 0;
+    let mut run = 1;
+    for pair in sorted.windows(2) {
+        if same(&pair[0], &pair[1]) {
+            // the new value ties with the run before it
+            pairs += run;
+            run += 1;
+        } else {
+            run = 1;
+        }
+    }
+    pairs
+}
+
+/// Returns Kendall's tau-b rank correlation of `x` and `y`, in [-1, 1]:
+///
+/// `(concordant - discordant) / sqrt((n0 - x_ties) (n0 - y_ties))`
+///
+/// with `n0 = n (n - 1) / 2` the number of pairs, and `x_ties` and `y_ties`
+/// the pairs tied in `x` and in `y`. A pair `(i, j)` is concordant if `x` and
+/// `y` order it the same way, and discordant if they order it oppositely.
+/// Without ties, it is the tau-a, `(concordant - discordant) / n0`.
+///
+/// Knight's algorithm computes it in O(n log n): once the pairs are sorted by
+/// `x`, then `y`, the discordant pairs are the inversions of the `y`, which a
+/// merge sort counts.
+///
+/// Returns None if the lengths differ, with less than two pairs, on a NaN, or
+/// if `x` or `y` is constant, for which the coefficient is not defined.
+///
+/// Wikipedia: <https://en.wikipedia.org/wiki/Kendall_rank_correlation_coefficient>
+pub fn kendall_tau(x: &[f64], y: &[f64]) -> Option<f64> {
+    if x.len() != y.len() || x.len() < 2 || x.iter().chain(y).any(|v| v.is_nan()) {
+        return None;
+    }
+    let n = x.len() as u64;
+    let mut pairs: Vec<(f64, f64)> = x.iter().copied().zip(y.iter().copied()).collect();
+    // not `total_cmp`, which orders -0.0 before 0.0, where the ties are
+    // counted with `==`; there are no NaNs to compare
+    pairs.sort_by(|a, b| a.partial_cmp(b).unwrap());
+
+    let all_pairs = n * (n - 1) / 2;
+    let x_ties = tied_pairs(&pairs, |a, b| a.0 == b.0);
+    let joint_ties = tied_pairs(&pairs, |a, b| a == b);
+    let mut ys: Vec<f64> = pairs.iter().map(|p| p.1).collect();
+    let discordant = sort_counting_inversions(&mut ys);
+    let y_ties = tied_pairs(&ys, |a, b| a == b);
+
+    // the pairs not tied in x nor y, less the discordant ones twice
+    let difference = all_pairs as i64 - x_ties as i64 - y_ties as i64 + joint_ties as i64
+        - 2 * discordant as i64;
+    let denominator = ((all_pairs - x_ties) as f64 * (all_pairs - y_ties) as f64).sqrt();
+    (denominator > 0.0).then(|| difference as f64 / denominator)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::general::Xorshift64;
+
+    fn assert_close(a: f64, b: f64) {
+        assert!((a - b).abs() < 1e-12, "{a} vs {b}");
+    }
+
+    /// Counts the pairs one by one
+    fn brute_force(x: &[f64], y: &[f64]) -> Option<f64> {
+        let n = x.len();
+        let (mut concordant, mut discordant, mut x_ties, mut y_ties) = (0, 0, 0, 0);
+        for i in 0..n {
+            for j in i + 1..n {
+                let sign = (x[i] - x[j]).signum() * (y[i] - y[j]).signum();
+                if x[i] == x[j] {
+                    x_ties += 1;
+                }
+                if y[i] == y[j] {
+                    y_ties += 1;
+                }
+                if x[i] != x[j] && y[i] != y[j] {
+                    if sign > 0.0 {
+                        concordant += 1;
+                    } else {
+                        discordant += 1;
+                    }
+                }
+            }
+        }
+        let all = n * (n - 1) / 2;
+        let denominator = (((all - x_ties) * (all - y_ties)) as f64).sqrt();
+        (denominator > 0.0).then(|| (concordant as f64 - discordant as f64) / denominator)
+    }
+
+    #[test]
+    fn perfect_agreement() {
+        let x = [1.0, 2.0, 3.0, 4.0, 5.0];
+        // any increasing function of x
+        let y: Vec<f64> = x.iter().map(|v: &f64| v.exp()).collect();
+        assert_close(kendall_tau(&x, &y).unwrap(), 1.0);
+        let reversed: Vec<f64> = x.iter().map(|v| -v * v).collect();
+        assert_close(kendall_tau(&x, &reversed).unwrap(), -1.0);
+    }
+
+    #[test]
+    fn tie_correction() {
+        // 4 concordant pairs, one tied in x and one in y: the tau-a would be
+        // 4 / 6, the tau-b is 4 / sqrt(5 * 5)
+        let x = [1.0, 1.0, 2.0, 3.0];
+        let y = [1.0, 2.0, 2.0, 3.0];
+        assert_close(kendall_tau(&x, &y).unwrap(), 0.8);
+        // ties in both, and a pair tied in both
+        let x = [1.0, 1.0, 1.0, 2.0, 3.0, 3.0];
+        let y = [2.0, 2.0, 1.0, 3.0, 1.0, 4.0];
+        assert_close(kendall_tau(&x, &y).unwrap(), brute_force(&x, &y).unwrap());
+    }
+
+    #[test]
+    fn against_brute_force() {
+        let mut rng = Xorshift64::new(271);
+        for n in [2, 3, 10, 57, 200] {
+            // few distinct values, for many ties
+            let x: Vec<f64> = (0..n).map(|_| rng.gen_range(0..6) as f64).collect();
+            let y: Vec<f64> = x
+                .iter()
+                .map(|v| v + rng.gen_range(0..4) as f64 - 1.5)
+                .collect();
+            match (kendall_tau(&x, &y), brute_force(&x, &y)) {
+                (Some(fast), Some(slow)) => assert_close(fast, slow),
+                (fast, slow) => assert_eq!(fast, slow),
+            }
+            // and without ties
+            let x: Vec<f64> = (0..n).map(|_| rng.next_f64()).collect();
+            let y: Vec<f64> = (0..n).map(|_| rng.next_f64()).collect();
+            assert_close(kendall_tau(&x, &y).unwrap(), brute_force(&x, &y).unwrap());
+        }
+    }
+
+    #[test]
+    fn signed_zeros_tie() {
+        let x = [-0.0, 0.0, 1.0];
+        let y = [5.0, 1.0, 2.0];
+        assert_close(kendall_tau(&x, &y).unwrap(), brute_force(&x, &y).unwrap());
+        assert_close(kendall_tau(&x, &y).unwrap(), 0.0);
+        let y = [0.0, 1.0, -0.0];
+        assert_close(kendall_tau(&y, &x).unwrap(), brute_force(&y, &x).unwrap());
+    }
+
+    #[test]
+    fn undefined() {
+        assert_eq!(kendall_tau(&[1.0, 2.0], &[1.0]), None);
+        assert_eq!(kendall_tau(&[1.0], &[1.0]), None);
+        assert_eq!(kendall_tau(&[1.0, 2.0, 3.0], &[5.0; 3]), None);
+        assert_eq!(kendall_tau(&[1.0, f64::NAN], &[1.0, 2.0]), None);
+    }
+
+    #[test]
+    fn counts_inversions() {
+        let mut values = [3.0, 1.0, 2.0, 2.0, 0.0];
+        // (3, 1), (3, 2), (3, 2), (3, 0), (1, 0), (2, 0), (2, 0)
+        assert_eq!(sort_counting_inversions(&mut values), 7);
+        assert_eq!(values, [0.0, 1.0, 2.0, 2.0, 3.0]);
+    }
+}
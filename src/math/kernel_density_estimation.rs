@@ -0,0 +1,139 @@
+//! # Kernel density estimation
+//!
+//! Estimates the probability density from which `samples` were drawn as the
+//! average of a Gaussian bump centered on each sample:
+//!
+//! `f(x) = 1 / (n h) Σ_i φ((x - x_i) / h)`
+//!
+//! with `φ` the density of the standard normal distribution. The bandwidth
+//! `h` sets the width of the bumps: too small, and the estimate is spiky;
+//! too large, and it blurs the modes together.
+//!
+//! Wikipedia: <https://en.wikipedia.org/wiki/Kernel_density_estimation>
+
+use std::f64::consts::PI;
+
+/// Returns the Gaussian kernel density estimate of `samples` with the
+/// bandwidth `bandwidth`, which takes O(n) to evaluate at a point.
+///
+/// Panics if `samples` is empty, or if `bandwidth` is not positive.
+pub fn kde(samples: &[f64], bandwidth: f64) -> impl Fn(f64) -> f64 {
+    assert!(!samples.is_empty(), "the estimate needs samples");
+    assert!(bandwidth > 0.0, "the bandwidth must be positive");
+    let samples = samples.to_vec();
+    let normalization = 1.0 / (samples.len() as f64 * bandwidth * (2.0 * PI).sqrt());
+    move |x| {
+        samples
+            .iter()
+            .map(|sample| (-0.5 * ((x - sample) / bandwidth).powi(2)).exp())
+            .sum::<f64>()
+            * normalization
+    }
+}
+
+/// Returns the bandwidth of Silverman's rule of thumb for `samples`,
+/// `0.9 min(σ, IQR / 1.34) n^(-1/5)` with `σ` their sample standard deviation
+/// and `IQR` their interquartile range. It is optimal for normal samples,
+/// and the `IQR` keeps it from oversmoothing the samples with outliers or
+/// several modes.
+///
+/// Panics if there are less than two samples, or if they are all equal.
+pub fn kde_silverman(samples: &[f64]) -> f64 {
+    assert!(samples.len() >= 2, "the spread needs two samples");
+    let n = samples.len() as f64;
+    let mean = samples.iter().sum::<f64>() / n;
+    let std = (samples.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (n - 1.0)).sqrt();
+
+    let mut sorted = samples.to_vec();
+    sorted.sort_by(f64::total_cmp);
+    let iqr = quantile(&sorted, 0.75) - quantile(&sorted, 0.25);
+    // half the samples may be equal, leaving only the standard deviation
+    let spread = if iqr > 0.0 { std.min(iqr / 1.34) } else { std };
+    assert!(spread > 0.0, "the samples are all equal");
+    0.9 * spread * n.powf(-0.2)
+}
+
+/// Returns the quantile `q` of `sorted`, interpolated between its values
+fn quantile(sorted: &[f64], q: f64) -> f64 {
+    let position = q * (sorted.len() - 1) as f64;
+    let below = position.floor() as usize;
+    let above = position.ceil() as usize;
+    sorted[below] + (sorted[above] - sorted[below]) * (position - below as f64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::general::Xorshift64;
+    use crate::math::trapezoidal_integral;
+
+    /// 700 samples around -3, and 300 around 3
+    fn bimodal_samples() -> Vec<f64> {
+        let mut rng = Xorshift64::new(290);
+        (0..1000)
+            .map(|i| {
+                let center = if i < 700 { -3.0 } else { 3.0 };
+                rng.next_gaussian(center, 1.0)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn single_sample() {
+        // the density of N(2, 0.5²)
+        let density = kde(&[2.0], 0.5);
+        let peak = 1.0 / (0.5 * (2.0 * PI).sqrt());
+        assert!((density(2.0) - peak).abs() < 1e-12);
+        assert!((density(2.5) - peak * (-0.5f64).exp()).abs() < 1e-12);
+        assert_eq!(density(1.0), density(3.0));
+    }
+
+    #[test]
+    fn integrates_to_one() {
+        let samples = bimodal_samples();
+        for bandwidth in [0.1, kde_silverman(&samples), 2.0] {
+            let density = kde(&samples, bandwidth);
+            let integral = trapezoidal_integral(-20.0, 20.0, &density, 4000);
+            assert!((integral - 1.0).abs() < 1e-3, "{bandwidth}: {integral}");
+        }
+    }
+
+    #[test]
+    fn peaks_at_the_mode() {
+        let samples = bimodal_samples();
+        let density = kde(&samples, kde_silverman(&samples));
+        let grid: Vec<f64> = (-800..=800).map(|i| i as f64 / 100.0).collect();
+        let mode = grid
+            .iter()
+            .copied()
+            .max_by(|&a, &b| density(a).total_cmp(&density(b)))
+            .unwrap();
+        assert!((mode + 3.0).abs() < 0.3, "{mode}");
+        // and the smaller mode stands out from the valley between them
+        assert!(density(3.0) > 2.0 * density(0.0));
+        assert!(density(-3.0) > density(3.0));
+    }
+
+    #[test]
+    fn silverman_bandwidth() {
+        // a standard normal sample gets about 0.9 * 1000^(-1/5) = 0.226
+        let mut rng = Xorshift64::new(2900);
+        let samples: Vec<f64> = (0..1000).map(|_| rng.next_gaussian(0.0, 1.0)).collect();
+        assert!((kde_silverman(&samples) - 0.226).abs() < 0.02);
+        // the interquartile range of 1, 2, 3, 4 is 1.5, against σ = 1.29
+        let expected = 0.9 * (1.5 / 1.34f64).min(1.6667f64.sqrt()) * 4f64.powf(-0.2);
+        assert!((kde_silverman(&[4.0, 1.0, 3.0, 2.0]) - expected).abs() < 1e-4);
+    }
+
+    #[test]
+    #[should_panic]
+    fn constant_samples() {
+        kde_silverman(&[1.0, 1.0, 1.0]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn no_bandwidth() {
+        let _ = kde(&[1.0], 0.0);
+    }
+}
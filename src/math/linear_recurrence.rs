@@ -0,0 +1,200 @@
+// A k-order linear recurrence is a sequence where every term is a fixed linear
+// combination of the k previous ones:
+//
+//     a(n) = c[0] * a(n - 1) + c[1] * a(n - 2) + ... + c[k - 1] * a(n - k)
+//
+// Fibonacci numbers are the case k = 2 and c = [1, 1].
+//
+// Writing the k last terms as a vector, going one step further is a
+// multiplication by the k x k companion matrix
+//
+//     | c[0] c[1] ... c[k-2] c[k-1] |
+//     |  1    0   ...   0      0    |
+//     |  0    1   ...   0      0    |
+//     |  .    .         .      .    |
+//     |  0    0   ...   1      0    |
+//
+// so the n-th term is obtained from the (n - k + 1)-th power of this matrix,
+// computed by binary exponentiation with O(log n) products of O(k^3) each.
+
+type Matrix = Vec<Vec<u64>>;
+
+fn multiply(a: &Matrix, b: &Matrix, modulus: u64) -> Matrix {
+    let k = a.len();
+    let modulus = modulus as u128;
+    let mut result = vec![vec![0; k]; k];
+    for i in 0..k {
+        for l in 0..k {
+            if a[i][l] == 0 {
+                continue;
+            }
+            let a_il = a[i][l] as u128;
+            for j in 0..k {
+                result[i][j] = ((result[i][j] as u128 + a_il * b[l][j] as u128) % modulus) as u64;
+            }
+        }
+    }
+    result
+}
+
+fn power(mut base: Matrix, mut exponent: u64, modulus: u64) -> Matrix {
+    let k = base.len();
+    let mut result: Matrix = (0..k)
+        .map(|i| (0..k).map(|j| u64::from(i == j)).collect())
+        .collect();
+    while exponent > 0 {
+        if exponent & 1 == 1 {
+            result = multiply(&result, &base, modulus);
+        }
+        base = multiply(&base, &base, modulus);
+        exponent >>= 1;
+    }
+    result
+}
+
+/// Computes the `n`-th term (0-indexed), modulo `modulus`, of the linear
+/// recurrence `a(n) = coeffs[0] * a(n - 1) + ... + coeffs[k - 1] * a(n - k)`
+/// whose first terms are `initial = [a(0), ..., a(k - 1)]`.
+///
+/// Runs in O(k³ log n) time using the companion matrix of the recurrence.
+///
+/// # Panics
+///
+/// Panics if `coeffs` is empty, if `coeffs` and `initial` do not have the
+/// same length, or if `modulus` is zero.
+pub fn linear_recurrence(coeffs: &[u64], initial: &[u64], n: u64, modulus: u64) -> u64 {
+    assert!(
+        !coeffs.is_empty(),
+        "the recurrence must have an order of at least 1"
+    );
+    assert_eq!(
+        coeffs.len(),
+        initial.len(),
+        "one initial term is needed per coefficient"
+    );
+    assert!(modulus > 0, "modulus must be positive");
+
+    let k = coeffs.len();
+    if n < k as u64 {
+        return initial[n as usize] % modulus;
+    }
+
+    let mut companion = vec![vec![0; k]; k];
+    for (j, &c) in coeffs.iter().enumerate() {
+        companion[0][j] = c % modulus;
+    }
+    for i in 1..k {
+        companion[i][i - 1] = 1 % modulus;
+    }
+
+    // the state vector (a(k - 1), ..., a(0)) becomes (a(n), ..., a(n - k + 1))
+    let m = power(companion, n - k as u64 + 1, modulus);
+    m[0].iter()
+        .zip(initial.iter().rev())
+        .fold(0u128, |acc, (&m_0j, &a_j)| {
+            (acc + m_0j as u128 * (a_j % modulus) as u128) % modulus as u128
+        }) as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dynamic_programming::nth_fibonacci_number_modulo_m;
+
+    /// Computes the terms of the recurrence one by one
+    fn brute_force(coeffs: &[u64], initial: &[u64], n: u64, modulus: u64) -> u64 {
+        let mut terms: Vec<u64> = initial.iter().map(|&a| a % modulus).collect();
+        while terms.len() as u64 <= n {
+            let next = coeffs
+                .iter()
+                .zip(terms.iter().rev())
+                .fold(0u128, |acc, (&c, &a)| {
+                    (acc + c as u128 * a as u128) % modulus as u128
+                });
+            terms.push(next as u64);
+        }
+        terms[n as usize]
+    }
+
+    #[test]
+    fn fibonacci() {
+        let expected = [0, 1, 1, 2, 3, 5, 8, 13, 21, 34, 55, 89];
+        for (n, &fib) in expected.iter().enumerate() {
+            assert_eq!(linear_recurrence(&[1, 1], &[0, 1], n as u64, u64::MAX), fib);
+        }
+        for n in 0..200 {
+            assert_eq!(
+                linear_recurrence(&[1, 1], &[0, 1], n, 1_000_000_007),
+                brute_force(&[1, 1], &[0, 1], n, 1_000_000_007)
+            );
+        }
+    }
+
+    #[test]
+    fn tribonacci() {
+        let expected = [0, 0, 1, 1, 2, 4, 7, 13, 24, 44, 81, 149];
+        for (n, &trib) in expected.iter().enumerate() {
+            assert_eq!(
+                linear_recurrence(&[1, 1, 1], &[0, 0, 1], n as u64, 1000),
+                trib
+            );
+        }
+        for n in 0..200 {
+            assert_eq!(
+                linear_recurrence(&[1, 1, 1], &[0, 0, 1], n, 998_244_353),
+                brute_force(&[1, 1, 1], &[0, 0, 1], n, 998_244_353)
+            );
+        }
+    }
+
+    #[test]
+    fn arbitrary_recurrence() {
+        // a(n) = 3 a(n-1) + 0 a(n-2) + 5 a(n-3) + 7 a(n-4)
+        let coeffs = [3, 0, 5, 7];
+        let initial = [2, 7, 1, 8];
+        for n in 0..100 {
+            assert_eq!(
+                linear_recurrence(&coeffs, &initial, n, 1_000_003),
+                brute_force(&coeffs, &initial, n, 1_000_003)
+            );
+        }
+    }
+
+    #[test]
+    fn first_order_is_geometric() {
+        // a(n) = 2 a(n - 1), a(0) = 3
+        assert_eq!(linear_recurrence(&[2], &[3], 10, u64::MAX), 3 * 1024);
+    }
+
+    #[test]
+    fn huge_index() {
+        let n = 1_000_000_000_000_000_000;
+        assert_eq!(
+            linear_recurrence(&[1, 1], &[0, 1], n, 1000) as i128,
+            nth_fibonacci_number_modulo_m(n as i64, 1000)
+        );
+    }
+
+    #[test]
+    fn large_values_do_not_overflow() {
+        let modulus = u64::MAX - 58; // largest prime below 2^64
+        for n in [0, 1, 50, 93, 94, 500] {
+            assert_eq!(
+                linear_recurrence(&[u64::MAX, 1], &[u64::MAX - 1, 12], n, modulus),
+                brute_force(&[u64::MAX, 1], &[u64::MAX - 1, 12], n, modulus)
+            );
+        }
+    }
+
+    #[test]
+    fn modulus_one() {
+        assert_eq!(linear_recurrence(&[1, 1], &[0, 1], 10, 1), 0);
+        assert_eq!(linear_recurrence(&[1, 1], &[0, 1], 1, 1), 0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn mismatched_lengths() {
+        linear_recurrence(&[1, 1], &[0], 5, 10);
+    }
+}
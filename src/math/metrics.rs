@@ -0,0 +1,165 @@
+//! Distances between points, for the algorithms comparing them (k-means,
+//! k-nearest neighbors, DBSCAN, ...) to take the one fitting their data.
+//!
+//! Wikipedia: <https://en.wikipedia.org/wiki/Metric_space>
+
+/// A way to measure the distance between two points of the same dimension
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Metric {
+    /// The length of the segment between the points, `sqrt(Σ (a_i - b_i)²)`
+    Euclidean,
+    /// The square of the Euclidean distance, which orders the points the
+    /// same way without a square root, but is not a metric: it breaks the
+    /// triangle inequality
+    SquaredEuclidean,
+    /// The taxicab distance, `Σ |a_i - b_i|`
+    Manhattan,
+    /// The largest difference on a coordinate, `max |a_i - b_i|`
+    Chebyshev,
+    /// One minus the cosine of the angle between the points seen as vectors,
+    /// in [0, 2], which ignores their lengths: `1 - a·b / (|a| |b|)`. It is
+    /// not a metric either, and is NaN for a zero vector.
+    Cosine,
+    /// The `p`-norm of the difference, `(Σ |a_i - b_i|^p)^(1/p)`, with
+    /// `p >= 1`: Manhattan for 1, Euclidean for 2, and Chebyshev as `p` goes
+    /// to infinity
+    Minkowski(f64),
+}
+
+impl Metric {
+    /// Returns the distance between `a` and `b`, in f64 whatever their type.
+    ///
+    /// Panics if their lengths differ, or for a Minkowski distance with
+    /// `p < 1`, which is not a metric.
+    pub fn distance<T: Copy + Into<f64>>(&self, a: &[T], b: &[T]) -> f64 {
+        assert_eq!(a.len(), b.len(), "the points have different dimensions");
+        let differences = a.iter().zip(b).map(|(&x, &y)| (x.into() - y.into()).abs());
+        match *self {
+            Metric::Euclidean => Metric::SquaredEuclidean.distance(a, b).sqrt(),
+            Metric::SquaredEuclidean => differences.map(|d| d * d).sum(),
+            Metric::Manhattan => differences.sum(),
+            Metric::Chebyshev => differences.fold(0.0, f64::max),
+            Metric::Cosine => {
+                let dot = |u: &[T], v: &[T]| -> f64 {
+                    u.iter().zip(v).map(|(&x, &y)| x.into() * y.into()).sum()
+                };
+                1.0 - dot(a, b) / (dot(a, a) * dot(b, b)).sqrt()
+            }
+            Metric::Minkowski(p) => {
+                assert!(p >= 1.0, "the Minkowski distance needs p >= 1");
+                if p.is_infinite() {
+                    Metric::Chebyshev.distance(a, b)
+                } else {
+                    differences.map(|d| d.powf(p)).sum::<f64>().powf(1.0 / p)
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::general::Xorshift64;
+
+    fn assert_close(a: f64, b: f64) {
+        assert!((a - b).abs() < 1e-12, "{a} vs {b}");
+    }
+
+    #[test]
+    fn known_distances() {
+        let (a, b) = ([1.0, 2.0, 3.0], [4.0, 6.0, 3.0]);
+        assert_close(Metric::Euclidean.distance(&a, &b), 5.0);
+        assert_close(Metric::SquaredEuclidean.distance(&a, &b), 25.0);
+        assert_close(Metric::Manhattan.distance(&a, &b), 7.0);
+        assert_close(Metric::Chebyshev.distance(&a, &b), 4.0);
+        assert_close(
+            Metric::Minkowski(3.0).distance(&a, &b),
+            91f64.powf(1.0 / 3.0),
+        );
+        // 1 - 25 / sqrt(14 * 61)
+        assert_close(
+            Metric::Cosine.distance(&a, &b),
+            1.0 - 25.0 / (14.0f64 * 61.0).sqrt(),
+        );
+        for metric in [Metric::Euclidean, Metric::Manhattan, Metric::Cosine] {
+            assert_close(metric.distance(&a, &a), 0.0);
+        }
+    }
+
+    #[test]
+    fn cosine_ignores_lengths() {
+        assert_close(Metric::Cosine.distance(&[1.0, 1.0], &[3.0, 3.0]), 0.0);
+        assert_close(Metric::Cosine.distance(&[1.0, 0.0], &[0.0, 2.0]), 1.0);
+        assert_close(Metric::Cosine.distance(&[1.0, 2.0], &[-2.0, -4.0]), 2.0);
+        assert!(Metric::Cosine.distance(&[0.0, 0.0], &[1.0, 2.0]).is_nan());
+    }
+
+    #[test]
+    fn minkowski_generalizes() {
+        let (a, b) = ([0.5, -1.5, 2.0, 0.0], [-1.0, 1.0, 2.5, 3.0]);
+        assert_close(
+            Metric::Minkowski(1.0).distance(&a, &b),
+            Metric::Manhattan.distance(&a, &b),
+        );
+        assert_close(
+            Metric::Minkowski(2.0).distance(&a, &b),
+            Metric::Euclidean.distance(&a, &b),
+        );
+        assert_close(
+            Metric::Minkowski(f64::INFINITY).distance(&a, &b),
+            Metric::Chebyshev.distance(&a, &b),
+        );
+        assert!(
+            (Metric::Minkowski(60.0).distance(&a, &b) - Metric::Chebyshev.distance(&a, &b)).abs()
+                < 0.1
+        );
+    }
+
+    #[test]
+    fn works_on_f32() {
+        let (a, b) = ([0.0f32, 3.0], [4.0f32, 0.0]);
+        assert_close(Metric::Euclidean.distance(&a, &b), 5.0);
+    }
+
+    #[test]
+    fn triangle_inequality() {
+        let mut rng = Xorshift64::new(274);
+        let mut point = || -> Vec<f64> { (0..4).map(|_| rng.next_gaussian(0.0, 3.0)).collect() };
+        let metrics = [
+            Metric::Euclidean,
+            Metric::Manhattan,
+            Metric::Chebyshev,
+            Metric::Minkowski(1.5),
+            Metric::Minkowski(4.0),
+        ];
+        for _ in 0..200 {
+            let (a, b, c) = (point(), point(), point());
+            for metric in metrics {
+                let (ab, bc, ac) = (
+                    metric.distance(&a, &b),
+                    metric.distance(&b, &c),
+                    metric.distance(&a, &c),
+                );
+                assert!(ac <= ab + bc + 1e-9, "{metric:?}");
+                // and symmetry
+                assert_close(ab, metric.distance(&b, &a));
+            }
+        }
+        // which the squared distance breaks
+        let squared = |a: &[f64], b: &[f64]| Metric::SquaredEuclidean.distance(a, b);
+        assert!(squared(&[0.0], &[2.0]) > squared(&[0.0], &[1.0]) + squared(&[1.0], &[2.0]));
+    }
+
+    #[test]
+    #[should_panic]
+    fn mismatched_dimensions() {
+        Metric::Euclidean.distance(&[1.0, 2.0], &[1.0]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn minkowski_below_one() {
+        Metric::Minkowski(0.5).distance(&[1.0], &[2.0]);
+    }
+}
@@ -14,7 +14,9 @@ mod ceil;
 mod chinese_remainder_theorem;
 mod collatz_sequence;
 mod combinations;
+mod convolution;
 mod cross_entropy_loss;
+mod cubic_spline;
 mod decimal_to_fraction;
 mod doomsday;
 mod elliptic_curve;
@@ -27,27 +29,35 @@ mod fast_fourier_transform;
 mod fast_power;
 mod faster_perfect_numbers;
 mod field;
+mod fixed_point_iteration;
 mod frizzy_number;
 mod gaussian_elimination;
 mod gaussian_error_linear_unit;
 mod gcd_of_n_numbers;
 mod geometric_series;
 mod greatest_common_divisor;
+mod histogram;
 mod huber_loss;
 mod interest;
 mod interpolation;
 mod interquartile_range;
+mod iterative_linear_solvers;
 mod karatsuba_multiplication;
+mod kendall_tau;
+mod kernel_density_estimation;
 mod lcm_of_n_numbers;
 mod leaky_relu;
 mod least_square_approx;
+mod linear_recurrence;
 mod linear_sieve;
 mod logarithm;
 mod lucas_series;
 mod matrix_ops;
 mod mersenne_primes;
+mod metrics;
 mod miller_rabin;
 mod modular_exponential;
+mod moving_average;
 mod newton_raphson;
 mod nthprime;
 mod pascal_triangle;
@@ -60,20 +70,25 @@ mod prime_factors;
 mod prime_numbers;
 mod quadratic_residue;
 mod random;
+mod ranking;
 mod relu;
+mod savitzky_golay;
 mod sieve_of_eratosthenes;
 mod sigmoid;
 mod signum;
 mod simpsons_integration;
 mod softmax;
+mod sparse;
 mod sprague_grundy_theorem;
 mod square_pyramidal_numbers;
 mod square_root;
+mod statistics;
 mod sum_of_digits;
 mod sum_of_geometric_progression;
 mod sum_of_harmonic_series;
 mod sylvester_sequence;
 mod tanh;
+mod thomas_algorithm;
 mod trapezoidal_integration;
 mod trial_division;
 mod trig_functions;
@@ -96,7 +111,9 @@ pub use self::ceil::ceil;
 pub use self::chinese_remainder_theorem::chinese_remainder_theorem;
 pub use self::collatz_sequence::sequence;
 pub use self::combinations::combinations;
+pub use self::convolution::{convolve, cross_correlate};
 pub use self::cross_entropy_loss::cross_entropy_loss;
+pub use self::cubic_spline::{cubic_spline, CubicSpline};
 pub use self::decimal_to_fraction::decimal_to_fraction;
 pub use self::doomsday::get_week_day;
 pub use self::elliptic_curve::EllipticCurve;
@@ -112,6 +129,7 @@ pub use self::fast_fourier_transform::{
 pub use self::fast_power::fast_power;
 pub use self::faster_perfect_numbers::generate_perfect_numbers;
 pub use self::field::{Field, PrimeField};
+pub use self::fixed_point_iteration::fixed_point;
 pub use self::frizzy_number::get_nth_frizzy;
 pub use self::gaussian_elimination::gaussian_elimination;
 pub use self::gaussian_error_linear_unit::gaussian_error_linear_unit;
@@ -121,22 +139,29 @@ pub use self::greatest_common_divisor::{
     greatest_common_divisor_iterative, greatest_common_divisor_recursive,
     greatest_common_divisor_stein,
 };
+pub use self::histogram::{histogram, histogram_fixed_edges};
 pub use self::huber_loss::huber_loss;
 pub use self::interest::{compound_interest, simple_interest};
 pub use self::interpolation::{lagrange_polynomial_interpolation, linear_interpolation};
 pub use self::interquartile_range::interquartile_range;
+pub use self::iterative_linear_solvers::{gauss_seidel_solve, jacobi_solve};
 pub use self::karatsuba_multiplication::multiply;
+pub use self::kendall_tau::kendall_tau;
+pub use self::kernel_density_estimation::{kde, kde_silverman};
 pub use self::lcm_of_n_numbers::lcm;
 pub use self::leaky_relu::leaky_relu;
 pub use self::least_square_approx::least_square_approx;
+pub use self::linear_recurrence::linear_recurrence;
 pub use self::linear_sieve::LinearSieve;
 pub use self::logarithm::log;
 pub use self::lucas_series::dynamic_lucas_number;
 pub use self::lucas_series::recursive_lucas_number;
 pub use self::matrix_ops::Matrix;
 pub use self::mersenne_primes::{get_mersenne_primes, is_mersenne_prime};
+pub use self::metrics::Metric;
 pub use self::miller_rabin::{big_miller_rabin, miller_rabin};
 pub use self::modular_exponential::{mod_inverse, modular_exponential};
+pub use self::moving_average::{exponential_moving_average, simple_moving_average};
 pub use self::newton_raphson::find_root;
 pub use self::nthprime::nthprime;
 pub use self::pascal_triangle::pascal_triangle;
@@ -150,20 +175,25 @@ pub use self::prime_factors::prime_factors;
 pub use self::prime_numbers::prime_numbers;
 pub use self::quadratic_residue::{cipolla, tonelli_shanks};
 pub use self::random::PCG32;
+pub use self::ranking::{argsort, rankdata};
 pub use self::relu::relu;
+pub use self::savitzky_golay::savitzky_golay;
 pub use self::sieve_of_eratosthenes::sieve_of_eratosthenes;
 pub use self::sigmoid::sigmoid;
 pub use self::signum::signum;
 pub use self::simpsons_integration::simpsons_integration;
-pub use self::softmax::softmax;
+pub use self::softmax::{softmax, softmax_f64};
+pub use self::sparse::SparseMatrix;
 pub use self::sprague_grundy_theorem::calculate_grundy_number;
 pub use self::square_pyramidal_numbers::square_pyramidal_number;
 pub use self::square_root::{fast_inv_sqrt, square_root};
+pub use self::statistics::{one_sample_ttest, two_sample_ttest, OnlineCovariance};
 pub use self::sum_of_digits::{sum_digits_iterative, sum_digits_recursive};
 pub use self::sum_of_geometric_progression::sum_of_geometric_progression;
 pub use self::sum_of_harmonic_series::sum_of_harmonic_progression;
 pub use self::sylvester_sequence::sylvester;
 pub use self::tanh::tanh;
+pub use self::thomas_algorithm::thomas_solve;
 pub use self::trapezoidal_integration::trapezoidal_integral;
 pub use self::trial_division::trial_division;
 pub use self::trig_functions::cosine;
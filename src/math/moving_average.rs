@@ -0,0 +1,124 @@
+//! Moving averages, which smooth a series by averaging each value with the
+//! values before it.
+//!
+//! Wikipedia: <https://en.wikipedia.org/wiki/Moving_average>
+
+/// Returns the simple moving average of `data`: the mean of each value and
+/// the `window - 1` values before it, in O(n).
+///
+/// The output has the length of the input. The first `window - 1` values do
+/// not have enough values before them, so they are the means of the shorter
+/// windows starting at the first value: the output starts with `data[0]`,
+/// then `(data[0] + data[1]) / 2`, and so on.
+///
+/// Panics if `window` is 0.
+pub fn simple_moving_average(data: &[f64], window: usize) -> Vec<f64> {
+    assert!(window > 0, "the window must not be empty");
+    let mut sum = 0.0;
+    data.iter()
+        .enumerate()
+        .map(|(i, &value)| {
+            sum += value;
+            if i >= window {
+                sum -= data[i - window];
+            }
+            sum / (i + 1).min(window) as f64
+        })
+        .collect()
+}
+
+/// Returns the exponential moving average of `data`, of smoothing factor
+/// `alpha` in (0, 1]: it starts with `data[0]`, and then
+///
+/// `ema[i] = alpha * data[i] + (1 - alpha) * ema[i - 1]`
+///
+/// so that the weights of the past values decrease geometrically. The larger
+/// `alpha`, the faster it follows the data; with 1, it is the data.
+///
+/// Panics if `alpha` is not in (0, 1].
+pub fn exponential_moving_average(data: &[f64], alpha: f64) -> Vec<f64> {
+    assert!(
+        alpha > 0.0 && alpha <= 1.0,
+        "the smoothing factor must be in (0, 1]"
+    );
+    let mut average = None;
+    data.iter()
+        .map(|&value| {
+            let next = match average {
+                None => value,
+                Some(previous) => alpha * value + (1.0 - alpha) * previous,
+            };
+            average = Some(next);
+            next
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(a: &[f64], b: &[f64]) {
+        assert_eq!(a.len(), b.len());
+        for (x, y) in a.iter().zip(b) {
+            assert!((x - y).abs() < 1e-12, "{a:?} vs {b:?}");
+        }
+    }
+
+    #[test]
+    fn simple_known_series() {
+        let data = [2.0, 4.0, 6.0, 8.0, 10.0, 3.0];
+        // the leading windows are shorter
+        assert_close(
+            &simple_moving_average(&data, 3),
+            &[2.0, 3.0, 4.0, 6.0, 8.0, 7.0],
+        );
+        assert_close(&simple_moving_average(&data, 1), &data);
+        // a window longer than the data averages all the values so far
+        assert_close(
+            &simple_moving_average(&data, 10),
+            &[2.0, 3.0, 4.0, 5.0, 6.0, 5.5],
+        );
+        assert!(simple_moving_average(&[], 3).is_empty());
+    }
+
+    #[test]
+    fn exponential_known_series() {
+        let data = [10.0, 20.0, 30.0, 20.0];
+        // 10, 0.5 * 20 + 0.5 * 10, 0.5 * 30 + 0.5 * 15, 0.5 * 20 + 0.5 * 22.5
+        assert_close(
+            &exponential_moving_average(&data, 0.5),
+            &[10.0, 15.0, 22.5, 21.25],
+        );
+        assert_close(
+            &exponential_moving_average(&data, 0.25),
+            &[10.0, 12.5, 16.875, 17.65625],
+        );
+        assert!(exponential_moving_average(&[], 0.5).is_empty());
+    }
+
+    #[test]
+    fn exponential_with_alpha_one_is_the_input() {
+        let data = [3.0, -1.0, 4.0, 1.0, -5.0, 9.0];
+        assert_close(&exponential_moving_average(&data, 1.0), &data);
+    }
+
+    #[test]
+    fn constant_series() {
+        let data = [7.0; 20];
+        assert_close(&simple_moving_average(&data, 5), &data);
+        assert_close(&exponential_moving_average(&data, 0.3), &data);
+    }
+
+    #[test]
+    #[should_panic]
+    fn empty_window() {
+        simple_moving_average(&[1.0], 0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn alpha_out_of_range() {
+        exponential_moving_average(&[1.0], 0.0);
+    }
+}
@@ -0,0 +1,94 @@
+use std::cmp::Ordering;
+
+/// Returns the indices which sort `data`: `data[result[0]]` is the smallest
+/// element, and so on. The sort is stable, so equal elements keep their
+/// order.
+pub fn argsort<T: Ord>(data: &[T]) -> Vec<usize> {
+    let mut indices: Vec<usize> = (0..data.len()).collect();
+    indices.sort_by_key(|&i| &data[i]);
+    indices
+}
+
+/// Returns the rank of each element of `data`, from 1 for the smallest, the
+/// tied elements sharing the average of their ranks: `[10, 10, 20]` has the
+/// ranks `[1.5, 1.5, 3]`. The ranks always sum to `n (n + 1) / 2`.
+///
+/// Rank correlations, such as Spearman's, are computed on such ranks.
+///
+/// Panics if two elements do not compare, as a NaN.
+///
+/// Wikipedia: <https://en.wikipedia.org/wiki/Ranking#Fractional_ranking_(%221_2.5_2.5_4%22_ranking)>
+pub fn rankdata<T: PartialOrd>(data: &[T]) -> Vec<f64> {
+    let compare = |a: &T, b: &T| a.partial_cmp(b).expect("the elements must compare");
+    let mut order: Vec<usize> = (0..data.len()).collect();
+    order.sort_by(|&i, &j| compare(&data[i], &data[j]));
+
+    let mut ranks = vec![0.0; data.len()];
+    let mut start = 0;
+    while start < order.len() {
+        // the run of ties in [start, end)
+        let mut end = start + 1;
+        while end < order.len()
+            && compare(&data[order[start]], &data[order[end]]) == Ordering::Equal
+        {
+            end += 1;
+        }
+        // the average of the ranks start + 1 to end
+        let rank = (start + end + 1) as f64 / 2.0;
+        for &i in &order[start..end] {
+            ranks[i] = rank;
+        }
+        start = end;
+    }
+    ranks
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn argsort_sorts() {
+        let data = [5, 3, 9, 1, 3, 7];
+        let order = argsort(&data);
+        let permuted: Vec<i32> = order.iter().map(|&i| data[i]).collect();
+        let mut sorted = data.to_vec();
+        sorted.sort();
+        assert_eq!(permuted, sorted);
+        // stable: the first 3 comes first
+        assert_eq!(order, vec![3, 1, 4, 0, 5, 2]);
+        assert!(argsort::<u8>(&[]).is_empty());
+    }
+
+    #[test]
+    fn argsort_strings() {
+        let words = ["pear", "apple", "fig", "apple"];
+        assert_eq!(argsort(&words), vec![1, 3, 2, 0]);
+    }
+
+    #[test]
+    fn ranks_without_ties() {
+        assert_eq!(rankdata(&[40, 10, 30, 20]), vec![4.0, 1.0, 3.0, 2.0]);
+        assert_eq!(rankdata(&[0.5, -1.0]), vec![2.0, 1.0]);
+        assert!(rankdata::<f64>(&[]).is_empty());
+    }
+
+    #[test]
+    fn ranks_with_ties() {
+        // the tied smallest pair shares ranks 1 and 2
+        assert_eq!(rankdata(&[1.0, 1.0, 2.0]), vec![1.5, 1.5, 3.0]);
+        assert_eq!(
+            rankdata(&[3, 1, 4, 1, 5, 9, 2, 6, 5, 3, 5]),
+            vec![4.5, 1.5, 6.0, 1.5, 8.0, 11.0, 3.0, 10.0, 8.0, 4.5, 8.0]
+        );
+        assert_eq!(rankdata(&["b"; 4]), vec![2.5; 4]);
+        let ranks = rankdata(&[2, 7, 2, 2, 0, 7]);
+        assert_eq!(ranks.iter().sum::<f64>(), 21.0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn rank_nan() {
+        rankdata(&[1.0, f64::NAN]);
+    }
+}
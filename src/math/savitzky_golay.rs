@@ -0,0 +1,192 @@
+/// Solves the small dense system `ax = b` by Gaussian elimination with
+/// partial pivoting
+fn solve(mut a: Vec<Vec<f64>>, mut b: Vec<f64>) -> Vec<f64> {
+    let n = b.len();
+    for col in 0..n {
+        let pivot = (col..n)
+            .max_by(|&i, &j| a[i][col].abs().total_cmp(&a[j][col].abs()))
+            .unwrap();
+        a.swap(col, pivot);
+        b.swap(col, pivot);
+        let pivot_row = a[col].clone();
+        for row in col + 1..n {
+            let factor = a[row][col] / pivot_row[col];
+            for (value, pivot_value) in a[row][col..].iter_mut().zip(&pivot_row[col..]) {
+                *value -= factor * pivot_value;
+            }
+            b[row] -= factor * b[col];
+        }
+    }
+    let mut x = vec![0.0; n];
+    for row in (0..n).rev() {
+        let rest: f64 = (row + 1..n).map(|k| a[row][k] * x[k]).sum();
+        x[row] = (b[row] - rest) / a[row][row];
+    }
+    x
+}
+
+/// Returns, for each position `o` in a window, the weights of the window
+/// samples giving the value at `o` of their least squares polynomial
+fn window_weights(window: usize, poly_order: usize) -> Vec<Vec<f64>> {
+    let half = (window / 2) as f64;
+    // the Vandermonde matrix of the centered positions, for conditioning
+    let vandermonde: Vec<Vec<f64>> = (0..window)
+        .map(|j| {
+            (0..=poly_order)
+                .map(|p| (j as f64 - half).powi(p as i32))
+                .collect()
+        })
+        .collect();
+    let gram: Vec<Vec<f64>> = (0..=poly_order)
+        .map(|p| {
+            (0..=poly_order)
+                .map(|q| vandermonde.iter().map(|row| row[p] * row[q]).sum())
+                .collect()
+        })
+        .collect();
+    // the value at o of the fit is v_o^T (V^T V)^-1 V^T y
+    (0..window)
+        .map(|o| {
+            let c = solve(gram.clone(), vandermonde[o].clone());
+            vandermonde
+                .iter()
+                .map(|row| row.iter().zip(&c).map(|(v, c)| v * c).sum())
+                .collect()
+        })
+        .collect()
+}
+
+/// Smooths `data` with a Savitzky-Golay filter: each sample is replaced by
+/// the value of the polynomial of degree `poly_order` fitting, by least
+/// squares, the `window` samples centered on it.
+///
+/// It amounts to a convolution with fixed weights. Unlike a moving average,
+/// which flattens peaks, it keeps the polynomials of degree up to
+/// `poly_order` intact, and so the height and width of the peaks wider than
+/// the window. Near the ends, the polynomial of the first or last full window
+/// gives the values. A window longer than the data is cut down to the longest
+/// odd window they hold, and `poly_order` to at most its length minus one,
+/// rather than panicking, as `simple_moving_average` does with such windows.
+///
+/// Panics if the window is even, or not longer than `poly_order`.
+///
+/// Wikipedia: <https://en.wikipedia.org/wiki/Savitzky%E2%80%93Golay_filter>
+pub fn savitzky_golay(data: &[f64], window: usize, poly_order: usize) -> Vec<f64> {
+    assert!(!window.is_multiple_of(2), "the window must be odd");
+    assert!(
+        window > poly_order,
+        "the window must be longer than the polynomial order"
+    );
+    if data.is_empty() {
+        return vec![];
+    }
+    let window = window.min(data.len() - (data.len() + 1) % 2);
+    let poly_order = poly_order.min(window - 1);
+    let weights = window_weights(window, poly_order);
+    let half = window / 2;
+    (0..data.len())
+        .map(|i| {
+            let start = i.saturating_sub(half).min(data.len() - window);
+            weights[i - start]
+                .iter()
+                .zip(&data[start..start + window])
+                .map(|(w, y)| w * y)
+                .sum()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::general::Xorshift64;
+    use std::f64::consts::PI;
+
+    fn rms(a: &[f64], b: &[f64]) -> f64 {
+        (a.iter().zip(b).map(|(x, y)| (x - y).powi(2)).sum::<f64>() / a.len() as f64).sqrt()
+    }
+
+    #[test]
+    fn classic_coefficients() {
+        // the quadratic smoothing over 5 points is (-3, 12, 17, 12, -3) / 35
+        let weights = window_weights(5, 2);
+        for (w, expected) in weights[2].iter().zip([-3.0, 12.0, 17.0, 12.0, -3.0]) {
+            assert!((w - expected / 35.0).abs() < 1e-12);
+        }
+        // with degree 0, it is the moving average
+        let impulse = [0.0, 0.0, 0.0, 3.0, 0.0, 0.0, 0.0];
+        let smoothed = savitzky_golay(&impulse, 3, 0);
+        for (s, e) in smoothed.iter().zip([0.0, 0.0, 1.0, 1.0, 1.0, 0.0, 0.0]) {
+            assert!((s - e).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn keeps_polynomials() {
+        let cubic: Vec<f64> = (0..30)
+            .map(|i| {
+                let x = i as f64 / 3.0;
+                x * x * x - 4.0 * x * x + x - 2.0
+            })
+            .collect();
+        for (s, c) in savitzky_golay(&cubic, 9, 3).iter().zip(&cubic) {
+            assert!((s - c).abs() < 1e-8, "{s} vs {c}");
+        }
+    }
+
+    #[test]
+    fn smooths_a_noisy_sine() {
+        let mut rng = Xorshift64::new(266);
+        let clean: Vec<f64> = (0..400)
+            .map(|i| (2.0 * PI * i as f64 / 100.0).sin())
+            .collect();
+        let noisy: Vec<f64> = clean
+            .iter()
+            .map(|y| y + rng.next_gaussian(0.0, 0.2))
+            .collect();
+        let smoothed = savitzky_golay(&noisy, 21, 3);
+        assert_eq!(smoothed.len(), noisy.len());
+        // much closer to the clean signal
+        let (before, after) = (rms(&noisy, &clean), rms(&smoothed, &clean));
+        assert!(after * 2.0 < before, "{after} vs {before}");
+        // with its amplitude nearly intact, projecting it on the sine
+        let amplitude =
+            2.0 / clean.len() as f64 * smoothed.iter().zip(&clean).map(|(s, c)| s * c).sum::<f64>();
+        assert!((amplitude - 1.0).abs() < 0.05, "{amplitude}");
+    }
+
+    #[test]
+    fn keeps_peaks_better_than_a_moving_average() {
+        let peak: Vec<f64> = (0..61)
+            .map(|i| (-((i as f64 - 30.0) / 4.0).powi(2)).exp())
+            .collect();
+        let polynomial = savitzky_golay(&peak, 11, 4);
+        let average = savitzky_golay(&peak, 11, 0);
+        assert!((polynomial[30] - 1.0).abs() < 0.05);
+        assert!(average[30] < 0.8);
+    }
+
+    #[test]
+    fn data_shorter_than_the_window() {
+        assert!(savitzky_golay(&[], 5, 2).is_empty());
+        // fitted exactly by the quadratic of the longest window, of 3 values
+        let data = [1.0, 2.0, 4.0, 8.0];
+        for (smoothed, value) in savitzky_golay(&data, 7, 2).iter().zip(data) {
+            assert!((smoothed - value).abs() < 1e-12);
+        }
+        assert_eq!(savitzky_golay(&[1.5, -2.0], 5, 2), vec![1.5, -2.0]);
+        assert_eq!(savitzky_golay(&[3.0], 11, 4), vec![3.0]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn even_window() {
+        savitzky_golay(&[1.0; 10], 4, 2);
+    }
+
+    #[test]
+    #[should_panic]
+    fn order_too_high() {
+        savitzky_golay(&[1.0; 10], 3, 3);
+    }
+}
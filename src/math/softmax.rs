@@ -17,6 +17,10 @@
 //! This implementation uses the `std::f32::consts::E` constant for the base of the exponential function. and
 //! f32 vectors to compute the values. The function creates a new vector and not altering the input vector.
 //!
+//! `softmax_f64` computes it on f64 numbers, subtracting the maximum of the array from all its values
+//! before exponentiating them. That does not change the result, as it divides both the numerator and the
+//! sum by `e^max`, but keeps `e^x` from overflowing for large values.
+//!
 use std::f32::consts::E;
 
 pub fn softmax(array: Vec<f32>) -> Vec<f32> {
@@ -35,6 +39,15 @@ pub fn softmax(array: Vec<f32>) -> Vec<f32> {
     softmax_array
 }
 
+pub fn softmax_f64(array: &[f64]) -> Vec<f64> {
+    let max = array.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+
+    let exps: Vec<f64> = array.iter().map(|value| (value - max).exp()).collect();
+    let sum: f64 = exps.iter().sum();
+
+    exps.into_iter().map(|value| value / sum).collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -53,4 +66,55 @@ mod tests {
             ]
         );
     }
+
+    fn assert_close(a: &[f64], b: &[f64]) {
+        assert_eq!(a.len(), b.len());
+        for (x, y) in a.iter().zip(b) {
+            assert!((x - y).abs() < 1e-12, "{a:?} vs {b:?}");
+        }
+    }
+
+    #[test]
+    fn known_values() {
+        let probs = softmax_f64(&[1.0, 2.0, 3.0]);
+        let sum = 1f64.exp() + 2f64.exp() + 3f64.exp();
+        assert_close(
+            &probs,
+            &[1f64.exp() / sum, 2f64.exp() / sum, 3f64.exp() / sum],
+        );
+        assert_close(&softmax_f64(&[0.0, 0.0]), &[0.5, 0.5]);
+        assert_close(&softmax_f64(&[-7.0]), &[1.0]);
+        assert!(softmax_f64(&[]).is_empty());
+    }
+
+    #[test]
+    fn sums_to_one() {
+        for logits in [
+            vec![9.0, 0.5, -3.0, 0.0, 3.0],
+            vec![-100.0, 100.0],
+            vec![1e-3; 10],
+        ] {
+            let probs = softmax_f64(&logits);
+            assert!((probs.iter().sum::<f64>() - 1.0).abs() < 1e-12);
+            assert!(probs.iter().all(|&p| (0.0..=1.0).contains(&p)));
+        }
+    }
+
+    #[test]
+    fn invariant_to_shifts() {
+        let logits = [0.3, -1.2, 2.5, 0.0];
+        let probs = softmax_f64(&logits);
+        for shift in [-50.0, 1.0, 1000.0] {
+            let shifted: Vec<f64> = logits.iter().map(|z| z + shift).collect();
+            assert_close(&softmax_f64(&shifted), &probs);
+        }
+    }
+
+    #[test]
+    fn large_values_do_not_overflow() {
+        // e^1000 is infinite in f64
+        assert_close(&softmax_f64(&[1000.0, 1000.0]), &[0.5, 0.5]);
+        let probs = softmax_f64(&[1000.0, 0.0]);
+        assert_close(&probs, &[1.0, (-1000f64).exp()]);
+    }
 }
@@ -0,0 +1,244 @@
+/// A sparse matrix in compressed sparse row (CSR) format.
+///
+/// The non-zero entries are stored row after row: `values[k]` is the value in
+/// column `col_indices[k]`, and the entries of row `i` are those at indices
+/// `row_offsets[i]..row_offsets[i + 1]`. This takes O(rows + nnz) memory, and
+/// a matrix-vector product costs O(rows + nnz) instead of O(rows * cols),
+/// which suits large sparse matrices like graph adjacency matrices, graph
+/// Laplacians or PageRank transition matrices.
+///
+/// See <https://en.wikipedia.org/wiki/Sparse_matrix#Compressed_sparse_row_(CSR,_CRS_or_Yale_format)>
+#[derive(Clone, Debug, PartialEq)]
+pub struct SparseMatrix {
+    rows: usize,
+    cols: usize,
+    row_offsets: Vec<usize>,
+    col_indices: Vec<usize>,
+    values: Vec<f64>,
+}
+
+impl SparseMatrix {
+    /// Builds a `rows` x `cols` matrix from `(row, col, value)` triples given
+    /// in any order. The values of duplicate positions are added together.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a position is outside the matrix.
+    pub fn from_triplets(rows: usize, cols: usize, triplets: &[(usize, usize, f64)]) -> Self {
+        let mut sorted = triplets.to_vec();
+        for &(row, col, _) in &sorted {
+            assert!(
+                row < rows && col < cols,
+                "position ({row}, {col}) out of bounds"
+            );
+        }
+        sorted.sort_by_key(|&(row, col, _)| (row, col));
+
+        let mut row_offsets = vec![0; rows + 1];
+        let mut col_indices: Vec<usize> = Vec::with_capacity(sorted.len());
+        let mut values: Vec<f64> = Vec::with_capacity(sorted.len());
+        let mut last = None;
+        for (row, col, value) in sorted {
+            if last == Some((row, col)) {
+                *values.last_mut().unwrap() += value;
+                continue;
+            }
+            last = Some((row, col));
+            row_offsets[row + 1] += 1;
+            col_indices.push(col);
+            values.push(value);
+        }
+        for i in 0..rows {
+            row_offsets[i + 1] += row_offsets[i];
+        }
+
+        SparseMatrix {
+            rows,
+            cols,
+            row_offsets,
+            col_indices,
+            values,
+        }
+    }
+
+    /// Builds the `n` x `n` identity matrix
+    pub fn identity(n: usize) -> Self {
+        let triplets: Vec<(usize, usize, f64)> = (0..n).map(|i| (i, i, 1.0)).collect();
+        Self::from_triplets(n, n, &triplets)
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    /// Number of stored entries
+    pub fn nnz(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Returns the entry at `(row, col)`, zero if it is not stored
+    pub fn get(&self, row: usize, col: usize) -> f64 {
+        let range = self.row_offsets[row]..self.row_offsets[row + 1];
+        match self.col_indices[range.clone()].binary_search(&col) {
+            Ok(k) => self.values[range.start + k],
+            Err(_) => 0.0,
+        }
+    }
+
+    /// Iterates over the stored `(col, value)` entries of `row`
+    pub fn row(&self, row: usize) -> impl Iterator<Item = (usize, f64)> + '_ {
+        let range = self.row_offsets[row]..self.row_offsets[row + 1];
+        self.col_indices[range.clone()]
+            .iter()
+            .copied()
+            .zip(self.values[range].iter().copied())
+    }
+
+    /// Computes the product `A x`
+    ///
+    /// # Panics
+    ///
+    /// Panics if the length of `x` is not the number of columns.
+    pub fn mul_vec(&self, x: &[f64]) -> Vec<f64> {
+        assert_eq!(x.len(), self.cols, "dimension mismatch");
+        (0..self.rows)
+            .map(|row| self.row(row).map(|(col, value)| value * x[col]).sum())
+            .collect()
+    }
+
+    /// Returns the transposed matrix, in O(rows + cols + nnz)
+    pub fn transpose(&self) -> Self {
+        // counting sort of the entries by column
+        let mut row_offsets = vec![0; self.cols + 1];
+        for &col in &self.col_indices {
+            row_offsets[col + 1] += 1;
+        }
+        for i in 0..self.cols {
+            row_offsets[i + 1] += row_offsets[i];
+        }
+
+        let mut next = row_offsets.clone();
+        let mut col_indices = vec![0; self.nnz()];
+        let mut values = vec![0.0; self.nnz()];
+        // rows are visited in order, so each transposed row stays sorted
+        for row in 0..self.rows {
+            for (col, value) in self.row(row) {
+                col_indices[next[col]] = row;
+                values[next[col]] = value;
+                next[col] += 1;
+            }
+        }
+
+        SparseMatrix {
+            rows: self.cols,
+            cols: self.rows,
+            row_offsets,
+            col_indices,
+            values,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::general::Xorshift64;
+
+    fn random_triplets(
+        rows: usize,
+        cols: usize,
+        count: usize,
+        rng: &mut Xorshift64,
+    ) -> Vec<(usize, usize, f64)> {
+        (0..count)
+            .map(|_| {
+                (
+                    rng.gen_range(0..rows as u64) as usize,
+                    rng.gen_range(0..cols as u64) as usize,
+                    rng.next_f64() * 2.0 - 1.0,
+                )
+            })
+            .collect()
+    }
+
+    fn to_dense(rows: usize, cols: usize, triplets: &[(usize, usize, f64)]) -> Vec<Vec<f64>> {
+        let mut dense = vec![vec![0.0; cols]; rows];
+        for &(row, col, value) in triplets {
+            dense[row][col] += value;
+        }
+        dense
+    }
+
+    #[test]
+    fn matrix_vector_product_against_dense() {
+        let mut rng = Xorshift64::new(18);
+        for (rows, cols, count) in [(1, 1, 1), (5, 3, 6), (40, 60, 150), (100, 100, 500)] {
+            let triplets = random_triplets(rows, cols, count, &mut rng);
+            let sparse = SparseMatrix::from_triplets(rows, cols, &triplets);
+            let dense = to_dense(rows, cols, &triplets);
+            let x: Vec<f64> = (0..cols).map(|_| rng.next_f64() * 10.0 - 5.0).collect();
+
+            let result = sparse.mul_vec(&x);
+            assert_eq!(result.len(), rows);
+            for (row, value) in dense.iter().zip(result) {
+                let expected: f64 = row.iter().zip(&x).map(|(a, b)| a * b).sum();
+                assert!((value - expected).abs() < 1e-9);
+            }
+        }
+    }
+
+    #[test]
+    fn identity() {
+        let identity = SparseMatrix::identity(4);
+        assert_eq!(identity.nnz(), 4);
+        let x = [1.5, -2.0, 0.0, 7.25];
+        assert_eq!(identity.mul_vec(&x), x);
+        assert_eq!(identity.transpose(), identity);
+    }
+
+    #[test]
+    fn transpose_against_dense() {
+        let mut rng = Xorshift64::new(19);
+        let (rows, cols) = (30, 17);
+        let triplets = random_triplets(rows, cols, 80, &mut rng);
+        let sparse = SparseMatrix::from_triplets(rows, cols, &triplets);
+        let transposed = sparse.transpose();
+        assert_eq!((transposed.rows(), transposed.cols()), (cols, rows));
+        assert_eq!(transposed.nnz(), sparse.nnz());
+        let dense = to_dense(rows, cols, &triplets);
+        for (i, row) in dense.iter().enumerate() {
+            for (j, &value) in row.iter().enumerate() {
+                assert_eq!(sparse.get(i, j), value);
+                assert_eq!(transposed.get(j, i), value);
+            }
+        }
+        assert_eq!(transposed.transpose(), sparse);
+    }
+
+    #[test]
+    fn duplicates_are_summed() {
+        let matrix = SparseMatrix::from_triplets(2, 2, &[(0, 1, 1.0), (1, 0, 3.0), (0, 1, 2.5)]);
+        assert_eq!(matrix.nnz(), 2);
+        assert_eq!(matrix.get(0, 1), 3.5);
+        assert_eq!(matrix.get(0, 0), 0.0);
+        assert_eq!(matrix.mul_vec(&[1.0, 2.0]), vec![7.0, 3.0]);
+    }
+
+    #[test]
+    fn empty_rows_and_matrix() {
+        let matrix = SparseMatrix::from_triplets(3, 2, &[(2, 1, 4.0)]);
+        assert_eq!(matrix.mul_vec(&[1.0, 1.0]), vec![0.0, 0.0, 4.0]);
+        let empty = SparseMatrix::from_triplets(0, 0, &[]);
+        assert!(empty.mul_vec(&[]).is_empty());
+    }
+
+    #[test]
+    #[should_panic]
+    fn wrong_vector_length() {
+        SparseMatrix::identity(3).mul_vec(&[1.0, 2.0]);
+    }
+}
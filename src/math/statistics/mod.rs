@@ -0,0 +1,5 @@
+mod online_covariance;
+mod ttest;
+
+pub use self::online_covariance::OnlineCovariance;
+pub use self::ttest::{one_sample_ttest, two_sample_ttest};
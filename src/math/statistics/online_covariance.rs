@@ -0,0 +1,163 @@
+/// Computes the mean and the covariance matrix of a stream of points in a
+/// single pass, with Welford's algorithm extended to several dimensions.
+///
+/// With `δ = x - m` the deviation of a new point from the previous mean, the
+/// mean moves by `δ / n`, and the co-moment `M[i][j]`, the sum of the products
+/// of the deviations from the mean, grows by `δ[i] δ[j] (n - 1) / n`.
+/// Unlike summing the products of the raw values, it does not lose the
+/// precision of small covariances to large means.
+///
+/// See <https://en.wikipedia.org/wiki/Algorithms_for_calculating_variance#Online>
+#[derive(Clone, Debug, PartialEq)]
+pub struct OnlineCovariance {
+    count: usize,
+    mean: Vec<f64>,
+    comoment: Vec<Vec<f64>>,
+}
+
+impl OnlineCovariance {
+    /// Creates an empty estimate for points of `dimensions` coordinates
+    pub fn new(dimensions: usize) -> Self {
+        OnlineCovariance {
+            count: 0,
+            mean: vec![0.0; dimensions],
+            comoment: vec![vec![0.0; dimensions]; dimensions],
+        }
+    }
+
+    /// Adds `point` to the estimates in O(d²).
+    ///
+    /// Panics if `point` does not have the dimensions of the estimate.
+    pub fn push(&mut self, point: &[f64]) {
+        assert_eq!(
+            point.len(),
+            self.mean.len(),
+            "the point has other dimensions"
+        );
+        self.count += 1;
+        let delta: Vec<f64> = point.iter().zip(&self.mean).map(|(x, m)| x - m).collect();
+        let n = self.count as f64;
+        for (m, d) in self.mean.iter_mut().zip(&delta) {
+            *m += d / n;
+        }
+        // the same product for M[i][j] and M[j][i], which stays symmetric
+        for (row, di) in self.comoment.iter_mut().zip(&delta) {
+            for (c, dj) in row.iter_mut().zip(&delta) {
+                *c += di * dj * (n - 1.0) / n;
+            }
+        }
+    }
+
+    /// Returns the number of points pushed
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    /// Returns the mean of the points, 0 in every dimension if there is none
+    pub fn mean(&self) -> &[f64] {
+        &self.mean
+    }
+
+    /// Returns the sample covariance matrix of the points, their co-moment
+    /// over `n - 1`. With less than two points, the entries are NaN.
+    pub fn covariance(&self) -> Vec<Vec<f64>> {
+        let denominator = if self.count < 2 {
+            f64::NAN
+        } else {
+            (self.count - 1) as f64
+        };
+        self.comoment
+            .iter()
+            .map(|row| row.iter().map(|c| c / denominator).collect())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::general::Xorshift64;
+
+    /// The sample covariance matrix of `xs`, computed from their mean
+    fn batch_covariance(xs: &[Vec<f64>]) -> Vec<Vec<f64>> {
+        let n = xs.len() as f64;
+        let d = xs[0].len();
+        let mean: Vec<f64> = (0..d)
+            .map(|i| xs.iter().map(|x| x[i]).sum::<f64>() / n)
+            .collect();
+        (0..d)
+            .map(|i| {
+                (0..d)
+                    .map(|j| {
+                        xs.iter()
+                            .map(|x| (x[i] - mean[i]) * (x[j] - mean[j]))
+                            .sum::<f64>()
+                            / (n - 1.0)
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn small_example() {
+        let mut online = OnlineCovariance::new(2);
+        for point in [[1.0, 2.0], [3.0, 1.0], [5.0, 0.0]] {
+            online.push(&point);
+        }
+        assert_eq!(online.count(), 3);
+        assert_eq!(online.mean(), &[3.0, 1.0]);
+        // the second coordinate goes down by half the first
+        assert_eq!(online.covariance(), vec![vec![4.0, -2.0], vec![-2.0, 1.0]]);
+    }
+
+    #[test]
+    fn matches_the_batch_covariance() {
+        let mut rng = Xorshift64::new(288);
+        // correlated coordinates: y = 2x + e, z = -x + 0.5y + e, offset far
+        // from the origin
+        let xs: Vec<Vec<f64>> = (0..5_000)
+            .map(|_| {
+                let x = rng.next_gaussian(0.0, 1.0);
+                let y = 2.0 * x + rng.next_gaussian(0.0, 1.0);
+                let z = -x + 0.5 * y + rng.next_gaussian(0.0, 0.5);
+                vec![x + 1e4, y - 3e3, z]
+            })
+            .collect();
+        let mut online = OnlineCovariance::new(3);
+        for x in &xs {
+            online.push(x);
+        }
+        let expected = batch_covariance(&xs);
+        let covariance = online.covariance();
+        for i in 0..3 {
+            for j in 0..3 {
+                assert!(
+                    (covariance[i][j] - expected[i][j]).abs() < 1e-9,
+                    "{covariance:?} vs {expected:?}"
+                );
+                assert_eq!(covariance[i][j], covariance[j][i]);
+            }
+        }
+        // close to the covariances of the distribution: cov(x, y) = 2,
+        // cov(x, z) = -1 + 1 = 0, var(y) = 5
+        assert!((covariance[0][1] - 2.0).abs() < 0.1, "{covariance:?}");
+        assert!(covariance[0][2].abs() < 0.1, "{covariance:?}");
+        assert!((covariance[1][1] - 5.0).abs() < 0.25, "{covariance:?}");
+    }
+
+    #[test]
+    fn too_few_points() {
+        let mut online = OnlineCovariance::new(2);
+        assert_eq!(online.mean(), &[0.0, 0.0]);
+        online.push(&[1.0, 2.0]);
+        assert_eq!(online.mean(), &[1.0, 2.0]);
+        assert!(online.covariance()[0][1].is_nan());
+    }
+
+    #[test]
+    #[should_panic]
+    fn other_dimensions() {
+        OnlineCovariance::new(2).push(&[1.0, 2.0, 3.0]);
+    }
+}
@@ -0,0 +1,114 @@
+//! Student's t-tests, which compare means when the variances are unknown.
+//!
+//! They return the t-statistic with its degrees of freedom; its p-value is
+//! then read from the Student's t-distribution of those degrees of freedom.
+//! Large statistics, of either sign, go against the hypothesis of equal
+//! means.
+//!
+//! Wikipedia: <https://en.wikipedia.org/wiki/Student%27s_t-test>
+
+/// Returns the mean and unbiased variance of `data`
+fn mean_and_variance(data: &[f64]) -> (f64, f64) {
+    assert!(data.len() >= 2, "at least two values are needed");
+    let n = data.len() as f64;
+    let mean = data.iter().sum::<f64>() / n;
+    let variance = data.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (n - 1.0);
+    (mean, variance)
+}
+
+/// Tests whether `data` comes from a population of mean `mu0`, returning
+///
+/// `t = (mean - mu0) / (s / sqrt(n))`
+///
+/// with `s` the sample standard deviation, and its `n - 1` degrees of freedom.
+///
+/// Panics with less than two values.
+pub fn one_sample_ttest(data: &[f64], mu0: f64) -> (f64, f64) {
+    let (mean, variance) = mean_and_variance(data);
+    let n = data.len() as f64;
+    ((mean - mu0) / (variance / n).sqrt(), n - 1.0)
+}
+
+/// Tests whether `a` and `b` come from populations of the same mean, with
+/// Welch's t-test, which does not assume equal variances. It returns
+///
+/// `t = (mean_a - mean_b) / sqrt(s_a² / n_a + s_b² / n_b)`
+///
+/// with the Welch-Satterthwaite degrees of freedom
+///
+/// `(s_a² / n_a + s_b² / n_b)² / ((s_a² / n_a)² / (n_a - 1) + (s_b² / n_b)² / (n_b - 1))`
+///
+/// which are not integers in general.
+///
+/// Panics if a sample has less than two values.
+///
+/// Wikipedia: <https://en.wikipedia.org/wiki/Welch%27s_t-test>
+pub fn two_sample_ttest(a: &[f64], b: &[f64]) -> (f64, f64) {
+    let (mean_a, variance_a) = mean_and_variance(a);
+    let (mean_b, variance_b) = mean_and_variance(b);
+    let (n_a, n_b) = (a.len() as f64, b.len() as f64);
+    let (error_a, error_b) = (variance_a / n_a, variance_b / n_b);
+    let t = (mean_a - mean_b) / (error_a + error_b).sqrt();
+    let df = (error_a + error_b).powi(2)
+        / (error_a.powi(2) / (n_a - 1.0) + error_b.powi(2) / (n_b - 1.0));
+    (t, df)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(a: f64, b: f64) {
+        assert!((a - b).abs() < 1e-12, "{a} vs {b}");
+    }
+
+    #[test]
+    fn one_sample() {
+        // a mean of 3 and a variance of 2.5, so a standard error of sqrt(1 / 2)
+        let data = [1.0, 2.0, 3.0, 4.0, 5.0];
+        let (t, df) = one_sample_ttest(&data, 2.0);
+        assert_close(t, 2f64.sqrt());
+        assert_close(df, 4.0);
+        let (t, _) = one_sample_ttest(&data, 3.0);
+        assert_close(t, 0.0);
+        let (t, _) = one_sample_ttest(&data, 4.0);
+        assert_close(t, -(2f64.sqrt()));
+    }
+
+    #[test]
+    fn two_samples() {
+        // means 3 and 5, standard errors 2.5 / 5 and (20 / 3) / 4
+        let a = [1.0, 2.0, 3.0, 4.0, 5.0];
+        let b = [2.0, 4.0, 6.0, 8.0];
+        let (t, df) = two_sample_ttest(&a, &b);
+        assert_close(t, -2.0 / (13.0f64 / 6.0).sqrt());
+        assert_close(df, 2028.0 / 427.0);
+        // antisymmetric
+        let (t_reversed, df_reversed) = two_sample_ttest(&b, &a);
+        assert_close(t_reversed, -t);
+        assert_close(df_reversed, df);
+    }
+
+    #[test]
+    fn identical_samples() {
+        let a = [2.5, 3.0, 1.5, 4.0, 2.0, 3.5];
+        let (t, df) = two_sample_ttest(&a, &a);
+        assert_close(t, 0.0);
+        // with equal variances and sizes, the pooled 2 (n - 1)
+        assert_close(df, 10.0);
+    }
+
+    #[test]
+    fn far_means() {
+        let a = [10.1, 9.8, 10.3, 9.9, 10.0];
+        let b = [0.2, -0.1, 0.0, 0.3, -0.2];
+        let (t, _) = two_sample_ttest(&a, &b);
+        assert!(t > 50.0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn too_few_values() {
+        one_sample_ttest(&[1.0], 0.0);
+    }
+}
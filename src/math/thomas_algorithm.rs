@@ -0,0 +1,131 @@
+/// Solves the tridiagonal system of `n` equations
+///
+/// `a[i - 1] x[i - 1] + b[i] x[i] + c[i] x[i + 1] = d[i]`
+///
+/// with the Thomas algorithm, with `a` the `n - 1` entries of the subdiagonal,
+/// `b` the `n` entries of the main diagonal, and `c` the `n - 1` entries of
+/// the superdiagonal.
+///
+/// It is Gaussian elimination without pivoting, which only has to update the
+/// next row at each step: a forward sweep eliminates the subdiagonal, and a
+/// back substitution gives the unknowns, in O(n) rather than O(n^3). Without
+/// pivoting, it is stable for diagonally dominant or symmetric positive
+/// definite matrices, as those of spline fitting or of finite differences.
+///
+/// Panics if the lengths do not match.
+///
+/// Wikipedia: <https://en.wikipedia.org/wiki/Tridiagonal_matrix_algorithm>
+pub fn thomas_solve(a: &[f64], b: &[f64], c: &[f64], d: &[f64]) -> Vec<f64> {
+    let n = b.len();
+    assert_eq!(d.len(), n, "the right-hand side has a wrong length");
+    if n == 0 {
+        return vec![];
+    }
+    assert!(
+        a.len() == n - 1 && c.len() == n - 1,
+        "the off-diagonals must have one entry less than the diagonal"
+    );
+
+    // the superdiagonal and right-hand side once the subdiagonal is eliminated,
+    // and the diagonal divided out
+    let mut c_prime = Vec::with_capacity(n - 1);
+    let mut d_prime = Vec::with_capacity(n);
+    c_prime.extend(c.first().map(|&c0| c0 / b[0]));
+    d_prime.push(d[0] / b[0]);
+    for i in 1..n {
+        let pivot = b[i] - a[i - 1] * c_prime[i - 1];
+        if i < n - 1 {
+            c_prime.push(c[i] / pivot);
+        }
+        d_prime.push((d[i] - a[i - 1] * d_prime[i - 1]) / pivot);
+    }
+
+    let mut x = d_prime;
+    for i in (0..n - 1).rev() {
+        x[i] -= c_prime[i] * x[i + 1];
+    }
+    x
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::general::Xorshift64;
+    use crate::math::gaussian_elimination;
+
+    fn general_solve(a: &[f64], b: &[f64], c: &[f64], d: &[f64]) -> Vec<f64> {
+        let n = b.len();
+        let mut augmented = vec![vec![0f32; n + 1]; n];
+        for i in 0..n {
+            augmented[i][i] = b[i] as f32;
+            if i > 0 {
+                augmented[i][i - 1] = a[i - 1] as f32;
+            }
+            if i + 1 < n {
+                augmented[i][i + 1] = c[i] as f32;
+            }
+            augmented[i][n] = d[i] as f32;
+        }
+        gaussian_elimination(&mut augmented)
+            .into_iter()
+            .map(f64::from)
+            .collect()
+    }
+
+    #[test]
+    fn small_system() {
+        // 2x - y = 1, -x + 2y - z = 0, -y + 2z = 1
+        let x = thomas_solve(
+            &[-1.0, -1.0],
+            &[2.0, 2.0, 2.0],
+            &[-1.0, -1.0],
+            &[1.0, 0.0, 1.0],
+        );
+        for (u, v) in x.iter().zip([1.0, 1.0, 1.0]) {
+            assert!((u - v).abs() < 1e-12);
+        }
+        assert_eq!(thomas_solve(&[], &[4.0], &[], &[2.0]), vec![0.5]);
+        assert!(thomas_solve(&[], &[], &[], &[]).is_empty());
+    }
+
+    #[test]
+    fn random_dominant_systems() {
+        let mut rng = Xorshift64::new(264);
+        let mut random = move || rng.next_f64() * 2.0 - 1.0;
+        for n in [2, 3, 10, 50] {
+            let a: Vec<f64> = (1..n).map(|_| random()).collect();
+            let c: Vec<f64> = (1..n).map(|_| random()).collect();
+            let b: Vec<f64> = (0..n)
+                .map(|i| {
+                    let off = if i > 0 { a[i - 1].abs() } else { 0.0 }
+                        + if i + 1 < n { c[i].abs() } else { 0.0 };
+                    (off + 0.5) * random().signum()
+                })
+                .collect();
+            let d: Vec<f64> = (0..n).map(|_| random() * 10.0).collect();
+
+            let x = thomas_solve(&a, &b, &c, &d);
+            // the general solver works in f32
+            for (u, v) in x.iter().zip(general_solve(&a, &b, &c, &d)) {
+                assert!((u - v).abs() < 1e-3, "{u} vs {v}");
+            }
+            // and the residuals vanish
+            for i in 0..n {
+                let mut lhs = b[i] * x[i];
+                if i > 0 {
+                    lhs += a[i - 1] * x[i - 1];
+                }
+                if i + 1 < n {
+                    lhs += c[i] * x[i + 1];
+                }
+                assert!((lhs - d[i]).abs() < 1e-9);
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn mismatched_lengths() {
+        thomas_solve(&[1.0, 1.0], &[2.0, 2.0], &[1.0], &[0.0, 0.0]);
+    }
+}
@@ -0,0 +1,205 @@
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+
+// Polynomial rolling hash modulo the Mersenne prime 2^61 - 1. The base is larger
+// than any char, so that distinct single characters never collide.
+const MODULUS: u64 = (1 << 61) - 1;
+const BASE: u64 = 1_234_567_891;
+
+fn mul_mod(a: u64, b: u64) -> u64 {
+    (a as u128 * b as u128 % MODULUS as u128) as u64
+}
+
+/// Prefix hashes and powers of the base, giving the hash of any substring in O(1)
+struct RollingHash {
+    prefix: Vec<u64>,
+    powers: Vec<u64>,
+}
+
+impl RollingHash {
+    fn new(chars: &[char]) -> Self {
+        let mut prefix = vec![0; chars.len() + 1];
+        let mut powers = vec![1; chars.len() + 1];
+        for (i, &c) in chars.iter().enumerate() {
+            prefix[i + 1] = (mul_mod(prefix[i], BASE) + c as u64) % MODULUS;
+            powers[i + 1] = mul_mod(powers[i], BASE);
+        }
+        RollingHash { prefix, powers }
+    }
+
+    /// Hash of the substring `[start, start + len)`
+    fn hash(&self, start: usize, len: usize) -> u64 {
+        let removed = mul_mod(self.prefix[start], self.powers[len]);
+        (self.prefix[start + len] + MODULUS - removed) % MODULUS
+    }
+}
+
+/// Returns the first position where a substring of length `len` that occurs
+/// at least twice starts, if any
+///
+/// The substrings are only compared once, for the pair of equal hashes with
+/// the earliest start: if they are equal, no repeat can start before it, as
+/// it would have given an earlier pair. Only if a collision made them differ
+/// are all the candidates compared.
+fn find_repeat(chars: &[char], hashes: &RollingHash, len: usize) -> Option<usize> {
+    let mut first_with_hash: HashMap<u64, usize> = HashMap::new();
+    // the earliest start sharing its hash with a later one, and that one
+    let mut earliest: Option<(usize, usize)> = None;
+    for start in 0..=chars.len() - len {
+        match first_with_hash.entry(hashes.hash(start, len)) {
+            Entry::Occupied(entry) => {
+                let first = *entry.get();
+                if earliest.is_none_or(|(earliest, _)| first < earliest) {
+                    earliest = Some((first, start));
+                }
+            }
+            Entry::Vacant(entry) => {
+                entry.insert(start);
+            }
+        }
+    }
+    // no equal hashes means no equal substrings
+    let (first, other) = earliest?;
+    if chars[first..first + len] == chars[other..other + len] {
+        Some(first)
+    } else {
+        find_repeat_verified(chars, hashes, len)
+    }
+}
+
+/// Same as `find_repeat`, comparing every pair of substrings with equal hashes
+fn find_repeat_verified(chars: &[char], hashes: &RollingHash, len: usize) -> Option<usize> {
+    let mut seen: HashMap<u64, Vec<usize>> = HashMap::new();
+    let mut first_repeat: Option<usize> = None;
+    for start in 0..=chars.len() - len {
+        let candidates = seen.entry(hashes.hash(start, len)).or_default();
+        // equal hashes may come from different substrings, so every candidate
+        // is verified
+        let substring = &chars[start..start + len];
+        match candidates
+            .iter()
+            .find(|&&other| &chars[other..other + len] == substring)
+        {
+            Some(&first) => first_repeat = Some(first_repeat.map_or(first, |f| f.min(first))),
+            None => candidates.push(start),
+        }
+    }
+    first_repeat
+}
+
+/// Returns the longest substring of `s` occurring at least twice (the
+/// occurrences may overlap), or an empty string if no character repeats.
+/// Among several longest ones, the one whose first occurrence comes first
+/// is returned.
+///
+/// The length of the answer is binary searched, as a repeated substring of
+/// length `l` contains repeated substrings of every smaller length, and the
+/// existence of a repeat of a given length is tested by hashing all the
+/// substrings of this length with a rolling hash in O(n).
+/// The full algorithm runs in O(n log n) expected time.
+///
+/// Two different substrings may collide, i.e. have the same hash, with a
+/// probability of about n / 2^61 for each pair. Rather than returning a
+/// wrong answer in this case, a single pair of substrings with equal hashes
+/// is compared for each length, and all of them only after a collision: the
+/// worst case, with collisions at every length, is O(n² log n).
+pub fn longest_repeated_substring(s: &str) -> String {
+    let chars: Vec<char> = s.chars().collect();
+    let hashes = RollingHash::new(&chars);
+
+    // a repeat of length `low` exists, none of length `high` does
+    let (mut low, mut high) = (0, chars.len());
+    let mut best_start = 0;
+    while low + 1 < high {
+        let middle = (low + high) / 2;
+        match find_repeat(&chars, &hashes, middle) {
+            Some(start) => {
+                low = middle;
+                best_start = start;
+            }
+            None => high = middle,
+        }
+    }
+    chars[best_start..best_start + low].iter().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::general::Xorshift64;
+
+    fn brute_force(s: &str) -> String {
+        let chars: Vec<char> = s.chars().collect();
+        for len in (1..chars.len()).rev() {
+            for start in 0..=chars.len() - len {
+                let substring = &chars[start..start + len];
+                let repeated = (start + 1..=chars.len() - len)
+                    .any(|other| &chars[other..other + len] == substring);
+                if repeated {
+                    return substring.iter().collect();
+                }
+            }
+        }
+        String::new()
+    }
+
+    macro_rules! test_longest_repeated_substring {
+        ($($name:ident: $inputs:expr,)*) => {
+        $(
+            #[test]
+            fn $name() {
+                let (s, expected) = $inputs;
+                assert_eq!(longest_repeated_substring(s), expected);
+            }
+        )*
+        }
+    }
+
+    test_longest_repeated_substring! {
+        banana: ("banana", "ana"),
+        no_repeats: ("abcdefg", ""),
+        identical_characters: ("aaaaaa", "aaaaa"),
+        empty: ("", ""),
+        single_character: ("x", ""),
+        two_characters: ("zz", "z"),
+        first_occurrence_first: ("abcXbcYab", "ab"),
+        longer_text: ("the quick fox and the quick dog", "the quick "),
+        unicode: ("日本語と日本語", "日本語"),
+    }
+
+    #[test]
+    fn long_runs() {
+        // every substring repeats, which compared them all at each length
+        let s = "a".repeat(200_000);
+        assert_eq!(longest_repeated_substring(&s).len(), 199_999);
+        let s = "ab".repeat(50_000) + "c";
+        assert_eq!(longest_repeated_substring(&s).len(), 99_998);
+    }
+
+    #[test]
+    fn collisions_are_verified() {
+        let chars: Vec<char> = "abcdbcxab".chars().collect();
+        // every substring hashes to 0
+        let colliding = RollingHash {
+            prefix: vec![0; chars.len() + 1],
+            powers: vec![1; chars.len() + 1],
+        };
+        let expected = [Some(0), Some(0), None, None];
+        for (len, expected) in (1..).zip(expected) {
+            assert_eq!(find_repeat(&chars, &colliding, len), expected, "{len}");
+        }
+    }
+
+    #[test]
+    fn random_strings_against_brute_force() {
+        let mut rng = Xorshift64::new(16);
+        for len in 0..60 {
+            for alphabet in [2, 4, 26] {
+                let s: String = (0..len)
+                    .map(|_| (b'a' + rng.gen_range(0..alphabet) as u8) as char)
+                    .collect();
+                assert_eq!(longest_repeated_substring(&s), brute_force(&s), "{s}");
+            }
+        }
+    }
+}
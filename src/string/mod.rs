@@ -10,6 +10,7 @@ mod jaro_winkler_distance;
 mod knuth_morris_pratt;
 mod levenshtein_distance;
 mod lipogram;
+mod longest_repeated_substring;
 mod manacher;
 mod palindrome;
 mod pangram;
@@ -17,8 +18,10 @@ mod rabin_karp;
 mod reverse;
 mod run_length_encoding;
 mod shortest_palindrome;
+mod soundex;
 mod suffix_array;
 mod suffix_array_manber_myers;
+mod suffix_automaton;
 mod suffix_tree;
 mod z_algorithm;
 
@@ -36,6 +39,7 @@ pub use self::jaro_winkler_distance::jaro_winkler_distance;
 pub use self::knuth_morris_pratt::knuth_morris_pratt;
 pub use self::levenshtein_distance::{naive_levenshtein_distance, optimized_levenshtein_distance};
 pub use self::lipogram::is_lipogram;
+pub use self::longest_repeated_substring::longest_repeated_substring;
 pub use self::manacher::manacher;
 pub use self::palindrome::is_palindrome;
 pub use self::pangram::is_pangram;
@@ -44,8 +48,10 @@ pub use self::rabin_karp::rabin_karp;
 pub use self::reverse::reverse;
 pub use self::run_length_encoding::{run_length_decoding, run_length_encoding};
 pub use self::shortest_palindrome::shortest_palindrome;
+pub use self::soundex::soundex;
 pub use self::suffix_array::generate_suffix_array;
 pub use self::suffix_array_manber_myers::generate_suffix_array_manber_myers;
+pub use self::suffix_automaton::SuffixAutomaton;
 pub use self::suffix_tree::{Node, SuffixTree};
 pub use self::z_algorithm::match_pattern;
 pub use self::z_algorithm::z_array;
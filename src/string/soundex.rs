@@ -0,0 +1,103 @@
+// Soundex is a phonetic algorithm for indexing names by sound, as pronounced
+// in English. Names that sound alike are encoded to the same 4-character code
+// (a letter followed by three digits), so that they can be matched despite
+// minor differences in spelling.
+//
+// More information: https://en.wikipedia.org/wiki/Soundex
+
+/// Maps an uppercase ASCII letter to its Soundex digit.
+/// Vowels (and `Y`) map to `'0'`, which is never emitted but separates
+/// consonants sharing the same digit.
+fn soundex_digit(c: char) -> char {
+    match c {
+        'B' | 'F' | 'P' | 'V' => '1',
+        'C' | 'G' | 'J' | 'K' | 'Q' | 'S' | 'X' | 'Z' => '2',
+        'D' | 'T' => '3',
+        'L' => '4',
+        'M' | 'N' => '5',
+        'R' => '6',
+        _ => '0',
+    }
+}
+
+/// Computes the American Soundex code of `name`.
+///
+/// Characters which are not ASCII letters are ignored. If `name` contains no
+/// letter at all, an empty string is returned.
+pub fn soundex(name: &str) -> String {
+    let mut letters = name
+        .chars()
+        .filter(|c| c.is_ascii_alphabetic())
+        .map(|c| c.to_ascii_uppercase());
+
+    let first = match letters.next() {
+        Some(c) => c,
+        None => return String::new(),
+    };
+
+    let mut code = String::with_capacity(4);
+    code.push(first);
+    // the first letter takes part in the collapsing of adjacent digits
+    let mut last_digit = soundex_digit(first);
+
+    for c in letters {
+        if code.len() == 4 {
+            break;
+        }
+        // `H` and `W` neither produce a digit nor separate identical digits
+        if c == 'H' || c == 'W' {
+            continue;
+        }
+        let digit = soundex_digit(c);
+        if digit != '0' && digit != last_digit {
+            code.push(digit);
+        }
+        last_digit = digit;
+    }
+
+    while code.len() < 4 {
+        code.push('0');
+    }
+    code
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    macro_rules! test_soundex {
+        ($($name:ident: $tc:expr,)*) => {
+        $(
+            #[test]
+            fn $name() {
+                let (input, expected) = $tc;
+                assert_eq!(soundex(input), expected);
+            }
+        )*
+        }
+    }
+
+    test_soundex! {
+        robert: ("Robert", "R163"),
+        rupert: ("Rupert", "R163"),
+        rubin: ("Rubin", "R150"),
+        tymczak: ("Tymczak", "T522"),
+        ashcraft: ("Ashcraft", "A261"),
+        pfister: ("Pfister", "P236"),
+        honeyman: ("Honeyman", "H555"),
+        short_name_is_padded: ("Lee", "L000"),
+        single_letter: ("a", "A000"),
+        lowercase_input: ("robert", "R163"),
+        punctuation_is_ignored: ("O'Hara", "O600"),
+        surrounding_non_letters: ("  #Robert42 ", "R163"),
+        empty_input: ("", ""),
+        no_letters: ("1234 !?", ""),
+    }
+
+    #[test]
+    fn similar_names_collide() {
+        assert_eq!(soundex("Robert"), soundex("Rupert"));
+        assert_eq!(soundex("Smith"), soundex("Smyth"));
+        assert_ne!(soundex("Robert"), soundex("Rubin"));
+    }
+}
@@ -0,0 +1,204 @@
+// A suffix automaton is the smallest deterministic automaton accepting all the
+// suffixes of a string. Every path from the initial state spells a substring,
+// and every substring is spelled by exactly one path, so substring queries run
+// in O(m) for a pattern of length m.
+//
+// Each state gathers the substrings that end at the same set of positions
+// ("endpos" equivalence class). They are suffixes of each other, the longest
+// one having `len` characters, and the state's suffix link leads to the class
+// of the next shorter suffix. The automaton has at most 2n - 1 states and 3n - 4
+// transitions, and is built online in O(n) (times the cost of a map access).
+//
+// https://en.wikipedia.org/wiki/Suffix_automaton
+
+use std::collections::HashMap;
+
+struct State {
+    len: usize,
+    link: Option<usize>,
+    next: HashMap<char, usize>,
+}
+
+pub struct SuffixAutomaton {
+    states: Vec<State>,
+    last: usize,
+}
+
+impl SuffixAutomaton {
+    pub fn new(text: &str) -> Self {
+        let mut automaton = SuffixAutomaton {
+            states: vec![State {
+                len: 0,
+                link: None,
+                next: HashMap::new(),
+            }],
+            last: 0,
+        };
+        for c in text.chars() {
+            automaton.extend(c);
+        }
+        automaton
+    }
+
+    /// Appends `c` to the string recognized by the automaton
+    fn extend(&mut self, c: char) {
+        let current = self.states.len();
+        self.states.push(State {
+            len: self.states[self.last].len + 1,
+            link: None,
+            next: HashMap::new(),
+        });
+
+        // every suffix of the previous string without a `c` transition can now
+        // be followed by `c`
+        let mut p = Some(self.last);
+        while let Some(state) = p {
+            if self.states[state].next.contains_key(&c) {
+                break;
+            }
+            self.states[state].next.insert(c, current);
+            p = self.states[state].link;
+        }
+
+        self.states[current].link = Some(match p {
+            None => 0,
+            Some(p) => {
+                let q = self.states[p].next[&c];
+                if self.states[p].len + 1 == self.states[q].len {
+                    q
+                } else {
+                    // `q` holds strings too long to end at the new position as
+                    // well: its shortest ones are split into a clone
+                    let clone = self.states.len();
+                    self.states.push(State {
+                        len: self.states[p].len + 1,
+                        link: self.states[q].link,
+                        next: self.states[q].next.clone(),
+                    });
+                    let mut p = Some(p);
+                    while let Some(state) = p {
+                        if self.states[state].next.get(&c) != Some(&q) {
+                            break;
+                        }
+                        self.states[state].next.insert(c, clone);
+                        p = self.states[state].link;
+                    }
+                    self.states[q].link = Some(clone);
+                    clone
+                }
+            }
+        });
+        self.last = current;
+    }
+
+    /// Returns `true` if `pattern` is a substring of the text (the empty
+    /// string always is)
+    pub fn contains_substring(&self, pattern: &str) -> bool {
+        let mut state = 0;
+        for c in pattern.chars() {
+            match self.states[state].next.get(&c) {
+                Some(&next) => state = next,
+                None => return false,
+            }
+        }
+        true
+    }
+
+    /// Returns the number of distinct non-empty substrings of the text
+    pub fn distinct_substring_count(&self) -> u64 {
+        // a state holds the substrings whose lengths are in
+        // (len(link), len], one per length
+        self.states
+            .iter()
+            .skip(1)
+            .map(|state| (state.len - self.states[state.link.unwrap()].len) as u64)
+            .sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::general::Xorshift64;
+    use std::collections::HashSet;
+
+    fn brute_force_count(text: &str) -> u64 {
+        let chars: Vec<char> = text.chars().collect();
+        let mut substrings = HashSet::new();
+        for i in 0..chars.len() {
+            for j in i + 1..=chars.len() {
+                substrings.insert(chars[i..j].to_vec());
+            }
+        }
+        substrings.len() as u64
+    }
+
+    fn random_string(len: usize, alphabet: &[char], rng: &mut Xorshift64) -> String {
+        (0..len)
+            .map(|_| alphabet[rng.gen_range(0..alphabet.len() as u64) as usize])
+            .collect()
+    }
+
+    #[test]
+    fn known_counts() {
+        assert_eq!(SuffixAutomaton::new("").distinct_substring_count(), 0);
+        assert_eq!(SuffixAutomaton::new("a").distinct_substring_count(), 1);
+        assert_eq!(SuffixAutomaton::new("aaaa").distinct_substring_count(), 4);
+        assert_eq!(SuffixAutomaton::new("abab").distinct_substring_count(), 7);
+        assert_eq!(
+            SuffixAutomaton::new("banana").distinct_substring_count(),
+            15
+        );
+        assert_eq!(SuffixAutomaton::new("abc").distinct_substring_count(), 6);
+    }
+
+    #[test]
+    fn count_against_brute_force() {
+        let mut rng = Xorshift64::new(14);
+        for len in 0..40 {
+            for alphabet in [&['a', 'b'][..], &['x', 'y', 'z'], &['é', 'ö', 'a', '中']] {
+                let text = random_string(len, alphabet, &mut rng);
+                assert_eq!(
+                    SuffixAutomaton::new(&text).distinct_substring_count(),
+                    brute_force_count(&text),
+                    "{text}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn membership_matches_str_contains() {
+        let mut rng = Xorshift64::new(15);
+        let alphabet = ['a', 'b', 'c'];
+        for _ in 0..20 {
+            let text = random_string(60, &alphabet, &mut rng);
+            let automaton = SuffixAutomaton::new(&text);
+            for _ in 0..200 {
+                let len = rng.gen_range(0..12) as usize;
+                let pattern = random_string(len, &alphabet, &mut rng);
+                assert_eq!(
+                    automaton.contains_substring(&pattern),
+                    text.contains(&pattern)
+                );
+            }
+            // every actual substring is found
+            for i in 0..text.len() {
+                assert!(automaton.contains_substring(&text[i..]));
+                assert!(automaton.contains_substring(&text[..i]));
+            }
+        }
+    }
+
+    #[test]
+    fn simple_membership() {
+        let automaton = SuffixAutomaton::new("abracadabra");
+        assert!(automaton.contains_substring("abra"));
+        assert!(automaton.contains_substring("cad"));
+        assert!(automaton.contains_substring(""));
+        assert!(automaton.contains_substring("abracadabra"));
+        assert!(!automaton.contains_substring("abracadabrab"));
+        assert!(!automaton.contains_substring("dab r"));
+        assert!(!SuffixAutomaton::new("").contains_substring("a"));
+    }
+}